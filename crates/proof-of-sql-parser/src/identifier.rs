@@ -26,6 +26,26 @@ impl Identifier {
         Self::from_str(string.as_ref())
     }
 
+    /// The [Identifier] that `name` is when it is written in double quotes, as in `"first name"`.
+    ///
+    /// A quoted identifier keeps its case and may be a keyword or contain any character,
+    /// so `name` is taken as is, without the quotes or escaping. It must not be empty,
+    /// and must be 64 bytes or less.
+    pub fn try_new_quoted<S: AsRef<str>>(name: S) -> ParseResult<Self> {
+        let name = name.as_ref();
+        if name.is_empty() {
+            return Err(ParseError::IdentifierParseError(
+                "Quoted identifier cannot be empty".to_string(),
+            ));
+        }
+        let name = ArrayString::from(name).map_err(|_| {
+            ParseError::IdentifierParseError(
+                "Identifier is too long, must be 64 bytes or less".to_string(),
+            )
+        })?;
+        Ok(Self { name })
+    }
+
     /// The name of this [Identifier]
     /// It already implements [Deref] to [str], so this method is not necessary for most use cases.
     pub fn name(&self) -> &str {
@@ -36,20 +56,49 @@ impl Identifier {
     pub fn as_str(&self) -> &str {
         self.name()
     }
+
+    /// The [Identifier] as it is written in SQL.
+    ///
+    /// This is the name itself if it parses back to this identifier, and otherwise the name in double
+    /// quotes with each double quote in it doubled, as for a keyword, a name with uppercase letters
+    /// or a name with characters such as spaces.
+    pub fn to_sql_string(&self) -> String {
+        if IdentifierParser::new().parse(self.name()).ok().as_ref() == Some(self) {
+            self.name().to_string()
+        } else {
+            format!("\"{}\"", self.name().replace('"', "\"\""))
+        }
+    }
 }
 
 impl FromStr for Identifier {
     type Err = ParseError;
 
     fn from_str(string: &str) -> ParseResult<Self> {
-        let name = IdentifierParser::new()
+        IdentifierParser::new()
             .parse(string)
-            .map_err(|e| ParseError::IdentifierParseError(format!("{:?}", e)))?;
+            .map_err(|e| ParseError::IdentifierParseError(format!("{:?}", e)))
+    }
+}
 
-        Ok(Identifier::new(name))
+// Identifiers are serialized as they are written in SQL, so that quoted identifiers can be deserialized.
+impl serde::Serialize for Identifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_sql_string())
+    }
+}
+impl<'d> serde::Deserialize<'d> for Identifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'d>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Identifier::from_str(&string).map_err(serde::de::Error::custom)
     }
 }
-crate::impl_serde_from_str!(Identifier);
 
 impl fmt::Display for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -154,6 +203,71 @@ mod tests {
         assert!(Identifier::from_str(&"LONG_IDENTIFIER_OVER_64_CHARACTERS".repeat(12)).is_err());
     }
 
+    #[test]
+    fn we_can_parse_quoted_identifiers_that_keep_their_case_and_may_be_keywords() {
+        assert_eq!(
+            Identifier::from_str(r#""select""#).unwrap().name(),
+            "select"
+        );
+        assert_eq!(
+            Identifier::from_str(r#""First Name""#).unwrap().name(),
+            "First Name"
+        );
+        assert_eq!(
+            Identifier::from_str(r#""say ""hi""""#).unwrap().name(),
+            r#"say "hi""#
+        );
+        assert_eq!(
+            Identifier::from_str(r#""good_identifier""#).unwrap(),
+            Identifier::from_str("GOOD_IDENTIFIER").unwrap()
+        );
+        assert_ne!(
+            Identifier::from_str(r#""GOOD_IDENTIFIER""#).unwrap(),
+            Identifier::from_str("GOOD_IDENTIFIER").unwrap()
+        );
+        assert_eq!(
+            Identifier::try_new_quoted("first name").unwrap(),
+            Identifier::from_str(r#""first name""#).unwrap()
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_empty_long_or_unterminated_quoted_identifiers() {
+        assert!(Identifier::from_str(r#""""#).is_err());
+        assert!(Identifier::from_str(r#""unterminated"#).is_err());
+        assert!(Identifier::from_str(r#""a"b""#).is_err());
+        assert!(Identifier::from_str(&format!(r#""{}""#, "a".repeat(65))).is_err());
+        assert!(Identifier::try_new_quoted("").is_err());
+        assert!(Identifier::try_new_quoted("a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn identifiers_are_quoted_in_sql_only_if_they_need_to_be() {
+        for (sql, expected) in [
+            ("GOOD_IDENTIFIER", "good_identifier"),
+            (r#""good_identifier""#, "good_identifier"),
+            (r#""select""#, r#""select""#),
+            (r#""First Name""#, r#""First Name""#),
+            (r#""say ""hi""""#, r#""say ""hi""""#),
+        ] {
+            let identifier = Identifier::from_str(sql).unwrap();
+            assert_eq!(identifier.to_sql_string(), expected);
+            assert_eq!(
+                Identifier::from_str(&identifier.to_sql_string()).unwrap(),
+                identifier
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_round_trip_a_quoted_identifier_through_serde() {
+        let identifier = Identifier::from_str(r#""First Name""#).unwrap();
+        let serialized = serde_json::to_string(&identifier).unwrap();
+        assert_eq!(serialized, r#""\"First Name\"""#);
+        let deserialized: Identifier = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, identifier);
+    }
+
     #[test]
     fn serialize_works() {
         let identifier = Identifier::from_str("GOOD_IDENTIFIER").unwrap();
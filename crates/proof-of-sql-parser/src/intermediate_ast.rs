@@ -70,6 +70,8 @@ pub enum TableExpression {
         table: Identifier,
         /// Namespace / schema for the table
         schema: Option<Identifier>,
+        /// Catalog containing the schema, which can only be given together with the schema
+        catalog: Option<Identifier>,
     },
 }
 
@@ -88,6 +90,9 @@ pub enum BinaryOperator {
     /// Numeric division
     Division,
 
+    /// Integer division truncated towards zero, such as `a DIV b`
+    IntegerDivision,
+
     /// Logical And
     And,
 
@@ -109,6 +114,16 @@ pub enum BinaryOperator {
 pub enum UnaryOperator {
     /// Logical inversion
     Not,
+    /// Signum, i.e. -1, 0, or 1 for negative, zero, or positive numbers
+    Sign,
+    /// The length of a string in bytes
+    Length,
+    /// `IS TRUE`, which holds if the expression is true, i.e. neither false nor NULL
+    IsTrue,
+    /// `IS FALSE`, which holds if the expression is false, i.e. neither true nor NULL
+    IsFalse,
+    /// `IS UNKNOWN`, which holds if the expression is NULL
+    IsUnknown,
 }
 
 // Aggregation operators
@@ -125,6 +140,10 @@ pub enum AggregationOperator {
     Count,
     /// Return the first value
     First,
+    /// Whether all values are true, which is true for no values
+    BoolAnd,
+    /// Whether any value is true, which is false for no values
+    BoolOr,
 }
 
 impl std::fmt::Display for AggregationOperator {
@@ -135,6 +154,26 @@ impl std::fmt::Display for AggregationOperator {
             AggregationOperator::Sum => write!(f, "sum"),
             AggregationOperator::Count => write!(f, "count"),
             AggregationOperator::First => write!(f, "first"),
+            AggregationOperator::BoolAnd => write!(f, "bool_and"),
+            AggregationOperator::BoolOr => write!(f, "bool_or"),
+        }
+    }
+}
+
+/// Aggregation operators of window aggregations
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum WindowAggregationOperator {
+    /// Sum
+    Sum,
+    /// Average, truncated toward zero at the scale of the aggregated expression
+    Avg,
+}
+
+impl std::fmt::Display for WindowAggregationOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowAggregationOperator::Sum => write!(f, "sum"),
+            WindowAggregationOperator::Avg => write!(f, "avg"),
         }
     }
 }
@@ -175,6 +214,47 @@ pub enum Expression {
         op: AggregationOperator,
         /// The expression to aggregate
         expr: Box<Expression>,
+        /// The condition that a row must satisfy to be aggregated, as in `SUM(x) FILTER (WHERE x > 0)`
+        filter: Option<Box<Expression>>,
+    },
+
+    /// Row value, such as `(a, b)`, which may only be compared for equality with another row value
+    Tuple(Vec<Box<Expression>>),
+
+    /// Membership test against a list of literals, such as `a IN (1, 2.5, 3)`
+    ///
+    /// `a NOT IN (1, 2.5, 3)` is parsed as the `NOT` of this expression.
+    InList {
+        /// The expression to look up in the list
+        expr: Box<Expression>,
+        /// The literals that the expression is compared with
+        list: Vec<Literal>,
+    },
+
+    /// The selection indicator of the where clause, `SELECTED()`, which is true for the rows that match it
+    Selected,
+
+    /// Aggregation over the rows that match the where clause, whose value is repeated for
+    /// each of them, as in `AVG(x) OVER ()`
+    ///
+    /// With `PARTITION BY`, as in `SUM(x) OVER (PARTITION BY region)`, each row gets the
+    /// aggregation over only the matching rows with the same values of the partition columns.
+    WindowAggregation {
+        /// The aggregation operator
+        op: WindowAggregationOperator,
+        /// The expression to aggregate
+        expr: Box<Expression>,
+        /// The columns that partition the rows, which is empty for `OVER ()`
+        partition_by: Vec<Identifier>,
+    },
+
+    /// The position, from 1, of each row that matches the where clause when they are sorted,
+    /// as in `ROW_NUMBER() OVER (ORDER BY x DESC)`
+    ///
+    /// Rows that tie on every order by column keep the order that they have in the table.
+    RowNumber {
+        /// The columns that the rows are sorted by
+        order_by: Vec<OrderBy>,
     },
 }
 
@@ -184,6 +264,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Sum,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -192,6 +273,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Max,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -200,6 +282,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Min,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -208,6 +291,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Count,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -216,8 +300,42 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::First,
             expr: Box::new(self),
+            filter: None,
         })
     }
+
+    /// Create a new BOOL_AND()
+    pub fn bool_and(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::BoolAnd,
+            expr: Box::new(self),
+            filter: None,
+        })
+    }
+
+    /// Create a new BOOL_OR()
+    pub fn bool_or(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::BoolOr,
+            expr: Box::new(self),
+            filter: None,
+        })
+    }
+
+    /// Restrict an aggregation to the rows that satisfy `condition`, i.e. `<agg> FILTER (WHERE <condition>)`
+    ///
+    /// Panics if this is not an aggregation.
+    pub fn filter(self, condition: Box<Expression>) -> Box<Self> {
+        match self {
+            Expression::Aggregation { op, expr, .. } => Box::new(Expression::Aggregation {
+                op,
+                expr,
+                filter: Some(condition),
+            }),
+            _ => panic!("only an aggregation can be filtered"),
+        }
+    }
+
     /// Create an `AliasedResultExpr` from an `Expression` using the provided alias.
     pub fn alias(self, alias: &str) -> AliasedResultExpr {
         AliasedResultExpr {
@@ -328,6 +446,8 @@ pub enum Literal {
     VarChar(String),
     /// Decimal Literal
     Decimal(IntermediateDecimal),
+    /// The `NULL` literal, whose value is unknown
+    Null,
 }
 
 impl From<bool> for Literal {
@@ -1,5 +1,9 @@
 use crate::{
-    intermediate_ast::OrderByDirection::{Asc, Desc},
+    intermediate_ast::{
+        Literal,
+        OrderByDirection::{Asc, Desc},
+        WindowAggregationOperator,
+    },
     intermediate_decimal::IntermediateDecimal,
     sql::*,
     test_utility::*,
@@ -205,6 +209,74 @@ fn we_can_parse_a_query_with_a_column_equals_a_string() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_hex_and_binary_integer_literals() {
+    for (literal, expected) in [
+        ("0x0F", 15),
+        ("0XfF", 255),
+        ("0x00", 0),
+        ("-0x10", -16),
+        ("+0x10", 16),
+        ("0b1010", 10),
+        ("0B0", 0),
+        ("-0b11", -3),
+        ("0x7FFFFFFFFFFFFFFF", i64::MAX),
+    ] {
+        let ast = format!("SELECT A FROM SXT_TAB WHERE A = {literal}")
+            .parse::<SelectStatement>()
+            .unwrap();
+        let expected_ast = select(
+            query(
+                cols_res(&["a"]),
+                tab(None, "sxt_tab"),
+                equal(col("a"), lit(expected)),
+                vec![],
+            ),
+            vec![],
+            None,
+        );
+        assert_eq!(ast, expected_ast, "{literal}");
+    }
+}
+
+#[test]
+fn hex_literals_outside_of_i64_range_are_int128_literals() {
+    let ast = "SELECT A FROM SXT_TAB WHERE A = 0xFFFFFFFFFFFFFFFF"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(col("a"), lit(u64::MAX as i128)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_hex_or_binary_literals_outside_of_i128_range() {
+    assert!("select 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF from tab"
+        .parse::<SelectStatement>()
+        .is_ok());
+    for sql in [
+        "select 0x80000000000000000000000000000000 from tab",
+        "select a from tab where a = 0x123456789ABCDEF0123456789ABCDEF01",
+        "select a from tab where a = 0b11111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111",
+    ] {
+        assert_eq!(
+            sql.parse::<SelectStatement>(),
+            Err(super::error::ParseError::QueryParseError(
+                "i128 out of range".to_string()
+            )),
+            "{sql}"
+        );
+    }
+}
+
 #[test]
 fn we_can_parse_a_query_with_a_column_equals_a_decimal() {
     let ast = "SELECT A FROM SXT_TAB WHERE A = -0.32"
@@ -386,6 +458,327 @@ fn we_can_parse_a_query_with_one_logical_not_filter_expression() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_query_with_a_length_function() {
+    let ast = "select length(a) as l, b from sxt_tab where LENGTH(b) = 3"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(length(col("a")), "l"), col_res(col("b"), "b")],
+            tab(None, "sxt_tab"),
+            equal(length(col("b")), lit(3)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_sign_function() {
+    let ast = "select sign(a) as s, b from sxt_tab where SIGN(b - 1) = -1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(sign(col("a")), "s"), col_res(col("b"), "b")],
+            tab(None, "sxt_tab"),
+            equal(sign(col("b") - lit(1)), lit(-1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_the_selected_pseudo_column() {
+    let ast = "select a, SELECTED(), selected() as s from sxt_tab where a >= 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(selected(), "__selected__"),
+                col_res(selected(), "s"),
+            ],
+            tab(None, "sxt_tab"),
+            ge(col("a"), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select selected(a) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_window_aggregates_over_the_whole_selection() {
+    let ast =
+        "select x, x - AVG(x) OVER () as deviation, sum(x + 1) over() from sxt_tab where x >= 1"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(col("x"), "x"),
+                col_res(
+                    col("x") - window_agg(WindowAggregationOperator::Avg, col("x")),
+                    "deviation",
+                ),
+                col_res(
+                    window_agg(WindowAggregationOperator::Sum, col("x") + lit(1)),
+                    "__expr__",
+                ),
+            ],
+            tab(None, "sxt_tab"),
+            ge(col("x"), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select avg(x) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select max(x) over () from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(x) over (x) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_window_aggregates_over_partitions() {
+    let ast = "select region, SUM(x) OVER (PARTITION BY region) as total, \
+        x - avg(x) over (partition by region, year) from sxt_tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("region"), "region"),
+                col_res(
+                    window_agg_partitioned(WindowAggregationOperator::Sum, col("x"), &["region"]),
+                    "total",
+                ),
+                col_res(
+                    col("x")
+                        - window_agg_partitioned(
+                            WindowAggregationOperator::Avg,
+                            col("x"),
+                            &["region", "year"],
+                        ),
+                    "__expr__",
+                ),
+            ],
+            tab(None, "sxt_tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select sum(x) over (partition by) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(x) over (partition by x + 1) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(x) over partition by x from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_row_numbers() {
+    let ast = "select x, ROW_NUMBER() OVER (ORDER BY x) as rn, \
+        row_number() over (order by y desc, x asc) - 1 from sxt_tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("x"), "x"),
+                col_res(row_number(order("x", Asc)), "rn"),
+                col_res(
+                    row_number(orders(&["y", "x"], &[Desc, Asc])) - lit(1),
+                    "__expr__",
+                ),
+            ],
+            tab(None, "sxt_tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select row_number() over () from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select row_number(x) over (order by x) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select row_number() from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select row_number() over (order by x + 1) from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_an_in_list_of_mixed_literals() {
+    let ast = "select a from sxt_tab where b IN (1, -2.5, 'x') and not c in (true)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                in_list(
+                    col("b"),
+                    [
+                        1.into(),
+                        IntermediateDecimal::try_from("-2.5").unwrap().into(),
+                        "x".into(),
+                    ],
+                ),
+                not(in_list(col("c"), [true.into()])),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_not_in_list() {
+    let ast = "select a from sxt_tab where b NOT IN (1, 'x') or c not in (true)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(
+                not(in_list(col("b"), [1.into(), "x".into()])),
+                not(in_list(col("c"), [true.into()])),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select a from sxt_tab where b not in ()"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_is_true_is_false_and_is_unknown() {
+    let ast = "select a is unknown as u from sxt_tab where b = 1 IS TRUE and not c is false"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(is_unknown(col("a")), "u")],
+            tab(None, "sxt_tab"),
+            and(is_true(equal(col("b"), lit(1))), not(is_false(col("c")))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select a from sxt_tab where b is 1"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_null_literals() {
+    let ast = "select a from sxt_tab where b = NULL or c in (1, null)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(col("a"), "a")],
+            tab(None, "sxt_tab"),
+            or(
+                equal(col("b"), lit(Literal::Null)),
+                in_list(col("c"), [Literal::BigInt(1), Literal::Null]),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_an_empty_in_list_or_an_in_list_of_columns() {
+    assert!("select a from sxt_tab where b in ()"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b in (c, 1)"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_tuple_equality_filter_expression() {
+    let ast = "select a from sxt_tab where (b, c) = (1, 'x')"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(tuple([col("b"), col("c")]), tuple([lit(1), lit("x")])),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_three_element_tuple_inequality_filter_expression() {
+    let ast = "select a from sxt_tab where (b, c + 1, (d)) != (1, e, 2)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            not(equal(
+                tuple([col("b"), col("c") + lit(1), col("d")]),
+                tuple([lit(1), col("e"), lit(2)]),
+            )),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_query_with_one_logical_and_filter_expression() {
     let ast = "select a from sxt_tab where (b = 3) and c"
@@ -681,6 +1074,24 @@ fn we_can_parse_a_query_with_one_schema_followed_by_a_table_name() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_query_with_a_catalog_and_a_schema_followed_by_a_table_name() {
+    let ast = "select a from Cat.eth.sxt_tab where b <= 4"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            catalog_tab("cat", "eth", "sxt_tab"),
+            le(col("b"), lit(4)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_query_without_a_filter() {
     let ast = "select a from tab".parse::<SelectStatement>().unwrap();
@@ -804,8 +1215,8 @@ fn we_cannot_parse_invalid_order_by_expressions() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_two_schemas_followed_by_a_table_name() {
-    assert!("select a from schema.Identifier.tab"
+fn we_cannot_parse_a_query_with_more_than_a_catalog_and_a_schema_followed_by_a_table_name() {
+    assert!("select a from cat.schema.Identifier.tab"
         .parse::<SelectStatement>()
         .is_err());
 }
@@ -1193,6 +1604,58 @@ fn we_can_parse_a_group_by_clause_containing_multiple_aggregations_where_clause_
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_boolean_aggregations() {
+    let ast = "select batch, BOOL_AND(is_valid) as all_valid, bool_or(is_valid) any_valid from tab group by batch"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("batch"), "batch"),
+                col_res(col("is_valid").bool_and(), "all_valid"),
+                col_res(col("is_valid").bool_or(), "any_valid"),
+            ],
+            tab(None, "tab"),
+            group_by(&["batch"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_aggregations_with_a_filter_clause() {
+    let ast = "select a, sum(x) filter (where x > 0) as pos, COUNT(*) FILTER (WHERE b = 'US') us from tab group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                filter_res(sum_res(col("x"), "pos"), not(le(col("x"), lit(0)))),
+                filter_res(count_all_res("us"), equal(col("b"), lit("US"))),
+            ],
+            tab(None, "tab"),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_filter_clause_without_an_aggregation() {
+    assert!("select a filter (where a > 0) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(a) filter (a > 0) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_cannot_parse_a_group_by_clause_after_order_by() {
     assert!("select a from tab order by a group by a"
@@ -1280,6 +1743,27 @@ fn mul_and_div_operators_have_the_same_precedence_and_left_expressions_are_alway
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_an_integer_division_with_the_precedence_of_a_division() {
+    let ast = "select a DIV b, c div 2 * d, e + f Div g from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(int_div(col("a"), col("b")), "__expr__"),
+                col_res(int_div(col("c"), lit(2)) * col("d"), "__expr__"),
+                col_res(col("e") + int_div(col("f"), col("g")), "__expr__"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_single_literal_in_the_result_expr() {
     let ast = "select -123 from tab".parse::<SelectStatement>().unwrap();
@@ -5,9 +5,10 @@ use std::{
     str::FromStr,
 };
 
-/// Unique resource identifier, like `schema.object_name`.
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Copy)]
+/// Unique resource identifier, like `schema.object_name` or `catalog.schema.object_name`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Copy)]
 pub struct ResourceId {
+    catalog: Option<Identifier>,
     schema: Identifier,
     object_name: Identifier,
 }
@@ -16,6 +17,20 @@ impl ResourceId {
     /// Constructor for [ResourceId]s.
     pub fn new(schema: Identifier, object_name: Identifier) -> Self {
         Self {
+            catalog: None,
+            schema,
+            object_name,
+        }
+    }
+
+    /// Constructor for [ResourceId]s whose schema belongs to a catalog.
+    pub fn new_with_catalog(
+        catalog: Identifier,
+        schema: Identifier,
+        object_name: Identifier,
+    ) -> Self {
+        Self {
+            catalog: Some(catalog),
             schema,
             object_name,
         }
@@ -33,11 +48,17 @@ impl ResourceId {
         let object_name = Identifier::try_new(object_name)?;
 
         Ok(ResourceId {
+            catalog: None,
             schema,
             object_name,
         })
     }
 
+    /// The catalog identifier of this [ResourceId], if it has one.
+    pub fn catalog(&self) -> Option<Identifier> {
+        self.catalog
+    }
+
     /// The schema identifier of this [ResourceId].
     pub fn schema(&self) -> Identifier {
         self.schema
@@ -57,10 +78,12 @@ impl ResourceId {
     /// Furthermore, while space and time APIs accept lowercase resource identifiers,
     /// all resource identifiers are stored internally in uppercase.
     /// This method performs that transformation as well.
+    /// The catalog, if there is one, is the first part of the key.
     /// For more information, see
     /// <https://space-and-time.atlassian.net/wiki/spaces/SE/pages/4947974/Gateway+Storage+Overview#Database-Resources>.
     pub fn storage_format(&self) -> String {
         let ResourceId {
+            catalog,
             schema,
             object_name,
         } = self;
@@ -68,18 +91,34 @@ impl ResourceId {
         let schema = schema.name().to_string().to_uppercase();
         let object_name = object_name.name().to_string().to_uppercase();
 
-        format!("{schema}:{object_name}")
+        match catalog {
+            Some(catalog) => {
+                let catalog = catalog.name().to_string().to_uppercase();
+                format!("{catalog}:{schema}:{object_name}")
+            }
+            None => format!("{schema}:{object_name}"),
+        }
     }
 }
 
 impl Display for ResourceId {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ResourceId {
+            catalog,
             schema,
             object_name,
         } = self;
 
-        formatter.write_str(format!("{schema}.{object_name}").as_str())
+        // Quote the identifiers that need it, so that the resource id can be parsed back.
+        if let Some(catalog) = catalog {
+            write!(formatter, "{}.", catalog.to_sql_string())?;
+        }
+        write!(
+            formatter,
+            "{}.{}",
+            schema.to_sql_string(),
+            object_name.to_sql_string()
+        )
     }
 }
 
@@ -87,14 +126,14 @@ impl FromStr for ResourceId {
     type Err = ParseError;
 
     fn from_str(string: &str) -> ParseResult<Self> {
-        let (schema, object_name) = ResourceIdParser::new()
+        let (catalog, schema, object_name) = ResourceIdParser::new()
             .parse(string)
             .map_err(|e| ParseError::ResourceIdParseError(format!("{:?}", e)))?;
 
-        // use unsafe `Identifier::new` to prevent double parsing the ids
         Ok(ResourceId {
-            schema: Identifier::new(schema),
-            object_name: Identifier::new(object_name),
+            catalog,
+            schema,
+            object_name,
         })
     }
 }
@@ -199,7 +238,31 @@ mod tests {
         assert!(ResourceId::from_str(".GOOD_IDENTIFIER").is_err());
         assert!(ResourceId::from_str(".").is_err());
         assert!(ResourceId::from_str("GOOD_IDENTIFIER").is_err());
-        assert!(ResourceId::from_str("GOOD_IDENTIFIER.GOOD_IDENTIFIER.GOOD_IDENTIFIER").is_err());
+        assert!(ResourceId::from_str(
+            "GOOD_IDENTIFIER.GOOD_IDENTIFIER.GOOD_IDENTIFIER.GOOD_IDENTIFIER"
+        )
+        .is_err());
+        assert!(ResourceId::from_str("GOOD_IDENTIFIER..GOOD_IDENTIFIER").is_err());
+    }
+
+    #[test]
+    fn we_can_parse_display_and_store_a_resource_id_with_a_catalog() {
+        let resource_id = ResourceId::from_str("Cat.sch._tab").unwrap();
+        assert_eq!(
+            resource_id,
+            ResourceId::new_with_catalog(
+                Identifier::try_new("cat").unwrap(),
+                Identifier::try_new("sch").unwrap(),
+                Identifier::try_new("_tab").unwrap()
+            )
+        );
+        assert_eq!(resource_id.catalog().unwrap().name(), "cat");
+        assert_eq!(resource_id.schema().name(), "sch");
+        assert_eq!(resource_id.object_name().name(), "_tab");
+        assert_eq!(resource_id.to_string(), "cat.sch._tab");
+        assert_eq!(resource_id.storage_format(), "CAT:SCH:_TAB");
+        assert_eq!(ResourceId::from_str("sch._tab").unwrap().catalog(), None);
+        assert_ne!(resource_id, ResourceId::from_str("sch._tab").unwrap());
     }
 
     #[test]
@@ -75,13 +75,17 @@ fn convert_table_expr_to_resource_id_vector(
         let table_ref: &TableExpression = table_expression.deref();
 
         match table_ref {
-            TableExpression::Named { table, schema } => {
-                let schema = schema
-                    .as_ref()
-                    .map(|schema| schema.as_str())
-                    .unwrap_or_else(|| default_schema.name());
-
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+            TableExpression::Named {
+                table,
+                schema,
+                catalog,
+            } => {
+                let schema = schema.unwrap_or(default_schema);
+
+                tables.push(match catalog {
+                    Some(catalog) => ResourceId::new_with_catalog(*catalog, schema, *table),
+                    None => ResourceId::new(schema, *table),
+                });
             }
         }
     }
@@ -129,4 +133,18 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_in_case_the_table_has_a_catalog() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM CAT.SCHEMA.TAB WHERE C = 3")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            ["cat.schema.tab".parse::<ResourceId>().unwrap()]
+        );
+    }
 }
@@ -31,6 +31,41 @@ pub fn not(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+pub fn sign(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::Sign,
+        expr,
+    })
+}
+
+pub fn length(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::Length,
+        expr,
+    })
+}
+
+pub fn is_true(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsTrue,
+        expr,
+    })
+}
+
+pub fn is_false(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsFalse,
+        expr,
+    })
+}
+
+pub fn is_unknown(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsUnknown,
+        expr,
+    })
+}
+
 pub fn and(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Binary {
         op: BinaryOperator::And,
@@ -47,10 +82,27 @@ pub fn or(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+pub fn int_div(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::IntegerDivision,
+        left,
+        right,
+    })
+}
+
 pub fn tab(schema: Option<&str>, name: &str) -> Box<TableExpression> {
     Box::new(TableExpression::Named {
         table: name.parse().unwrap(),
         schema: schema.map(|schema| schema.parse().unwrap()),
+        catalog: None,
+    })
+}
+
+pub fn catalog_tab(catalog: &str, schema: &str, name: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Named {
+        table: name.parse().unwrap(),
+        schema: Some(schema.parse().unwrap()),
+        catalog: Some(catalog.parse().unwrap()),
     })
 }
 
@@ -58,6 +110,41 @@ pub fn col(name: &str) -> Box<Expression> {
     Box::new(Expression::Column(name.parse().unwrap()))
 }
 
+pub fn tuple(exprs: impl IntoIterator<Item = Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::Tuple(exprs.into_iter().collect()))
+}
+
+pub fn in_list(expr: Box<Expression>, list: impl IntoIterator<Item = Literal>) -> Box<Expression> {
+    Box::new(Expression::InList {
+        expr,
+        list: list.into_iter().collect(),
+    })
+}
+
+pub fn selected() -> Box<Expression> {
+    Box::new(Expression::Selected)
+}
+
+pub fn window_agg(op: WindowAggregationOperator, expr: Box<Expression>) -> Box<Expression> {
+    window_agg_partitioned(op, expr, &[])
+}
+
+pub fn window_agg_partitioned(
+    op: WindowAggregationOperator,
+    expr: Box<Expression>,
+    partition_by: &[&str],
+) -> Box<Expression> {
+    Box::new(Expression::WindowAggregation {
+        op,
+        expr,
+        partition_by: group_by(partition_by),
+    })
+}
+
+pub fn row_number(order_by: Vec<OrderBy>) -> Box<Expression> {
+    Box::new(Expression::RowNumber { order_by })
+}
+
 pub fn lit<L: Into<Literal>>(literal: L) -> Box<Expression> {
     Box::new(Expression::Literal(literal.into()))
 }
@@ -82,6 +169,7 @@ pub fn min_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
         expr: Box::new(Expression::Aggregation {
             op: AggregationOperator::Min,
             expr,
+            filter: None,
         }),
         alias: alias.parse().unwrap(),
     })
@@ -92,6 +180,7 @@ pub fn max_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
         expr: Expression::Aggregation {
             op: AggregationOperator::Max,
             expr,
+            filter: None,
         }
         .into(),
         alias: alias.parse().unwrap(),
@@ -103,6 +192,7 @@ pub fn sum_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
         expr: Expression::Aggregation {
             op: AggregationOperator::Sum,
             expr,
+            filter: None,
         }
         .into(),
         alias: alias.parse().unwrap(),
@@ -114,6 +204,7 @@ pub fn count_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
         expr: Expression::Aggregation {
             op: AggregationOperator::Count,
             expr,
+            filter: None,
         }
         .into(),
         alias: alias.parse().unwrap(),
@@ -125,12 +216,25 @@ pub fn count_all_res(alias: &str) -> SelectResultExpr {
         expr: Expression::Aggregation {
             op: AggregationOperator::Count,
             expr: Box::new(Expression::Wildcard),
+            filter: None,
         }
         .into(),
         alias: alias.parse().unwrap(),
     })
 }
 
+pub fn filter_res(res: SelectResultExpr, filter: Box<Expression>) -> SelectResultExpr {
+    match res {
+        SelectResultExpr::AliasedResultExpr(AliasedResultExpr { expr, alias }) => {
+            SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+                expr: expr.filter(filter),
+                alias,
+            })
+        }
+        SelectResultExpr::ALL => panic!("only an aggregation can be filtered"),
+    }
+}
+
 pub fn query(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
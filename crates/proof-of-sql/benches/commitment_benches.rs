@@ -0,0 +1,42 @@
+//! Benchmarking of the commitment of a wide table using the `criterion` crate.
+//! To compare the sequential and the parallel commitment of the columns, execute the following commands:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench commitment_benches --no-default-features --features test
+//! cargo bench -p proof-of-sql --bench commitment_benches --no-default-features --features test,parallel-commitments
+//! ```
+#![allow(missing_docs)]
+use criterion::{criterion_group, criterion_main, Criterion};
+use proof_of_sql::{
+    base::commitment::VecCommitmentExt,
+    proof_primitive::dory::{test_rng, DoryCommitment, DoryProverPublicSetup},
+};
+use rand::Rng;
+
+const NUM_COLUMNS: usize = 50;
+const NUM_ROWS: usize = 10_000;
+
+fn commitment_benches(c: &mut Criterion) {
+    let setup = DoryProverPublicSetup::rand(7, 7, &mut test_rng());
+    let mut rng = rand::thread_rng();
+    let data = Vec::from_iter(
+        (0..NUM_COLUMNS).map(|_| Vec::from_iter((0..NUM_ROWS).map(|_| rng.gen::<i64>()))),
+    );
+
+    let mut group = c.benchmark_group(format!("Commit {NUM_COLUMNS} Columns"));
+    group.sample_size(10);
+    group.throughput(criterion::Throughput::Elements(
+        (NUM_COLUMNS * NUM_ROWS) as u64,
+    ));
+    group.bench_function("Dory", |b| {
+        b.iter(|| {
+            Vec::<DoryCommitment>::from_columns_with_offset(
+                data.iter().map(Vec::as_slice),
+                0,
+                &setup,
+            )
+        })
+    });
+}
+
+criterion_group!(benches, commitment_benches);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+//! Benchmarking of `COUNT(*)` at different selectivities using the `criterion` crate.
+//! To run the benchmark, execute the following command:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench count_benches
+//! ```
+//! Only the count is sent and nothing is committed besides what the `WHERE` clause needs,
+//! so the size of the proof, which is printed for each selectivity, does not depend on the number of selected rows.
+#![allow(missing_docs)]
+use blitzar::proof::InnerProductProof;
+use criterion::{criterion_group, criterion_main, Criterion};
+use proof_of_sql::base::database::ColumnType;
+
+#[allow(dead_code)]
+mod scaffold;
+use scaffold::{criterion_scaffold, proof_size, OptionalRandBound};
+
+const SIZES: &[usize] = &[1_000_000];
+
+const COUNT_SQL: &str = "SELECT COUNT(*) FROM table WHERE a = 0";
+const FILTER_SQL: &str = "SELECT b FROM table WHERE a = 0";
+
+// `a` is uniform in [-bound, bound], so `a = 0` selects roughly 1 in `2 * bound + 1` rows.
+const SELECTIVITIES: &[(&str, OptionalRandBound)] = &[
+    ("1% Selected", Some(|_| 49)),
+    ("33% Selected", Some(|_| 1)),
+    ("100% Selected", Some(|_| 0)),
+];
+
+fn count_benches(c: &mut Criterion) {
+    for &(title, bound) in SELECTIVITIES {
+        let columns = [
+            ("a", ColumnType::BigInt, bound),
+            ("b", ColumnType::BigInt, None),
+        ];
+        for query in [COUNT_SQL, FILTER_SQL] {
+            println!(
+                "{title} - {query}: proof size of {} bytes",
+                proof_size::<InnerProductProof>(query, &columns, SIZES[0], &())
+            );
+        }
+        criterion_scaffold::<InnerProductProof>(c, title, COUNT_SQL, &columns, SIZES, &(), &());
+    }
+}
+
+criterion_group!(benches, count_benches);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+//! Benchmarking of an equality filter on a large table using the `criterion` crate.
+//! To compare the scalar and the vectorized equality paths, execute the following commands:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench equality_benches
+//! cargo bench -p proof-of-sql --bench equality_benches --features vectorized-equality
+//! ```
+//! Each run proves both `a = 0`, which takes the fast path for an equality between a column and a
+//! literal, and `a = c` for a column `c` of zeros, which takes the general path.
+#![allow(missing_docs)]
+use blitzar::proof::InnerProductProof;
+use criterion::{criterion_group, criterion_main, Criterion};
+use proof_of_sql::base::database::ColumnType;
+
+#[allow(dead_code)]
+mod scaffold;
+use scaffold::{criterion_scaffold, OptionalRandBound};
+
+const SIZES: &[usize] = &[50_000_000];
+
+const EQUALITY_FILTER_TITLE: &str = "Equality Filter";
+const EQUALITY_FILTER_SQL: &str = "SELECT b FROM table WHERE a = 0";
+const EQUALITY_FILTER_COLUMNS: &[(&str, ColumnType, OptionalRandBound)] = &[
+    (
+        "a",
+        ColumnType::BigInt,
+        Some(|size| (size / 10).max(10) as i64),
+    ),
+    ("b", ColumnType::BigInt, None),
+];
+
+const GENERAL_EQUALITY_FILTER_TITLE: &str = "Equality Filter Between Columns";
+const GENERAL_EQUALITY_FILTER_SQL: &str = "SELECT b FROM table WHERE a = c";
+const GENERAL_EQUALITY_FILTER_COLUMNS: &[(&str, ColumnType, OptionalRandBound)] = &[
+    (
+        "a",
+        ColumnType::BigInt,
+        Some(|size| (size / 10).max(10) as i64),
+    ),
+    ("b", ColumnType::BigInt, None),
+    ("c", ColumnType::BigInt, Some(|_| 0)),
+];
+
+fn equality_benches(c: &mut Criterion) {
+    criterion_scaffold::<InnerProductProof>(
+        c,
+        EQUALITY_FILTER_TITLE,
+        EQUALITY_FILTER_SQL,
+        EQUALITY_FILTER_COLUMNS,
+        SIZES,
+        &(),
+        &(),
+    );
+    criterion_scaffold::<InnerProductProof>(
+        c,
+        GENERAL_EQUALITY_FILTER_TITLE,
+        GENERAL_EQUALITY_FILTER_SQL,
+        GENERAL_EQUALITY_FILTER_COLUMNS,
+        SIZES,
+        &(),
+        &(),
+    );
+}
+
+criterion_group!(benches, equality_benches);
+criterion_main!(benches);
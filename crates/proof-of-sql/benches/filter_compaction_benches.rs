@@ -0,0 +1,45 @@
+//! Benchmarking of a 1%-selectivity filter with and without filter compaction using the `criterion` crate.
+//! To run the benchmark, execute the following command:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench filter_compaction_benches
+//! ```
+#![allow(missing_docs)]
+use blitzar::proof::InnerProductProof;
+use criterion::{criterion_group, criterion_main, Criterion};
+use proof_of_sql::{base::database::ColumnType, sql::proof::ProverConfig};
+
+#[allow(dead_code)]
+mod scaffold;
+use scaffold::{criterion_scaffold_with_config, OptionalRandBound};
+
+const SIZES: &[usize] = &[1_000_000, 10_000_000];
+
+const SELECTIVE_FILTER_SQL: &str = "SELECT b FROM table WHERE a = 0";
+const SELECTIVE_FILTER_COLUMNS: &[(&str, ColumnType, OptionalRandBound)] = &[
+    // `a` is uniform in [-49, 49], so `a = 0` selects roughly 1% of the rows.
+    ("a", ColumnType::BigInt, Some(|_| 49)),
+    ("b", ColumnType::BigInt, None),
+];
+
+fn filter_compaction_benches(c: &mut Criterion) {
+    for (title, disable_filter_compaction) in
+        [("Compacted Filter", false), ("Full Length Filter", true)]
+    {
+        criterion_scaffold_with_config::<InnerProductProof>(
+            c,
+            title,
+            SELECTIVE_FILTER_SQL,
+            SELECTIVE_FILTER_COLUMNS,
+            SIZES,
+            &(),
+            &(),
+            &ProverConfig {
+                disable_filter_compaction,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+criterion_group!(benches, filter_compaction_benches);
+criterion_main!(benches);
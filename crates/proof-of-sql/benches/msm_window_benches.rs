@@ -0,0 +1,45 @@
+//! Benchmarking of the window of the multi-scalar multiplications of Dory commitments using the `criterion` crate.
+//! The windows only apply to the pure Rust backend, so execute the following command:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench msm_window_benches --no-default-features --features test
+//! ```
+#![allow(missing_docs)]
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use proof_of_sql::{
+    base::commitment::VecCommitmentExt,
+    proof_primitive::dory::{test_rng, DoryCommitment, DoryProverPublicSetup},
+};
+use rand::Rng;
+
+const NUM_ROWS: usize = 1_000_000;
+const WINDOW_BITS: [usize; 8] = [2, 4, 6, 8, 10, 12, 14, 16];
+
+fn msm_window_benches(c: &mut Criterion) {
+    let setup = DoryProverPublicSetup::rand(10, 10, &mut test_rng());
+    let mut rng = rand::thread_rng();
+    let data = Vec::from_iter((0..NUM_ROWS).map(|_| rng.gen::<i64>()));
+    let columns = [data.as_slice().into()];
+
+    let mut group = c.benchmark_group(format!("Commit {NUM_ROWS} Rows"));
+    group.sample_size(10);
+    group.throughput(criterion::Throughput::Elements(NUM_ROWS as u64));
+    let mut commit = |id, msm_window_bits| {
+        group.bench_with_input(id, &msm_window_bits, |b, &msm_window_bits| {
+            b.iter(|| {
+                Vec::<DoryCommitment>::from_commitable_columns_with_offset_and_msm_window(
+                    &columns,
+                    0,
+                    &setup,
+                    msm_window_bits,
+                )
+            })
+        });
+    };
+    commit(BenchmarkId::new("Dory", "auto"), None);
+    for window_bits in WINDOW_BITS {
+        commit(BenchmarkId::new("Dory", window_bits), Some(window_bits));
+    }
+}
+
+criterion_group!(benches, msm_window_benches);
+criterion_main!(benches);
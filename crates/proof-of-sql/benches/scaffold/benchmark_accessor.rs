@@ -66,6 +66,9 @@ impl<C: Commitment> DataAccessor<C::Scalar> for BenchmarkAccessor<'_, C> {
     fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
         self.columns.get(&column).unwrap().clone()
     }
+    fn column_type(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.lookup_column(table_ref, column_id)
+    }
 }
 impl<C: Commitment> MetadataAccessor for BenchmarkAccessor<'_, C> {
     fn get_length(&self, table_ref: TableRef) -> usize {
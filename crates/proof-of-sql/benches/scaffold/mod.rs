@@ -3,14 +3,19 @@ use bumpalo::Bump;
 use criterion::{AxisScale, Criterion, PlotConfiguration};
 use proof_of_sql::{
     base::{commitment::CommitmentEvaluationProof, database::ColumnType},
-    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+    sql::{
+        parse::QueryExpr,
+        proof::{ProverConfig, VerifiableQueryResult},
+    },
 };
 use rand::prelude::Rng;
+use serde::Serialize;
 mod benchmark_accessor;
 use benchmark_accessor::BenchmarkAccessor;
 pub mod querys;
 mod random_util;
-use random_util::{generate_random_columns, OptionalRandBound};
+use random_util::generate_random_columns;
+pub use random_util::OptionalRandBound;
 
 fn scaffold<'a, CP: CommitmentEvaluationProof>(
     query: &str,
@@ -28,7 +33,7 @@ fn scaffold<'a, CP: CommitmentEvaluationProof>(
     );
     let query =
         QueryExpr::try_new(query.parse().unwrap(), "bench".parse().unwrap(), accessor).unwrap();
-    let result = VerifiableQueryResult::new(query.proof_expr(), accessor, prover_setup);
+    let result = VerifiableQueryResult::new(query.proof_expr(), accessor, prover_setup).unwrap();
     (query, result)
 }
 
@@ -61,6 +66,32 @@ pub fn jaeger_scaffold<CP: CommitmentEvaluationProof>(
         .unwrap();
 }
 
+/// Returns the size in bytes of the serialized proof and result of `query`.
+#[allow(dead_code)]
+pub fn proof_size<CP: CommitmentEvaluationProof + Serialize>(
+    query: &str,
+    columns: &[(&str, ColumnType, OptionalRandBound)],
+    size: usize,
+    prover_setup: &CP::ProverPublicSetup,
+) -> usize {
+    init_backend();
+    let mut accessor = BenchmarkAccessor::default();
+    let mut rng = rand::thread_rng();
+    let alloc = Bump::new();
+    let (_query, result) = scaffold::<CP>(
+        query,
+        columns,
+        size,
+        prover_setup,
+        &alloc,
+        &mut accessor,
+        &mut rng,
+    );
+    postcard::to_allocvec(&result)
+        .expect("a result can always be serialized")
+        .len()
+}
+
 #[allow(dead_code)]
 pub fn criterion_scaffold<CP: CommitmentEvaluationProof>(
     c: &mut Criterion,
@@ -70,6 +101,29 @@ pub fn criterion_scaffold<CP: CommitmentEvaluationProof>(
     sizes: &[usize],
     prover_setup: &CP::ProverPublicSetup,
     verifier_setup: &CP::VerifierPublicSetup,
+) {
+    criterion_scaffold_with_config::<CP>(
+        c,
+        title,
+        query,
+        columns,
+        sizes,
+        prover_setup,
+        verifier_setup,
+        &ProverConfig::default(),
+    );
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+pub fn criterion_scaffold_with_config<CP: CommitmentEvaluationProof>(
+    c: &mut Criterion,
+    title: &str,
+    query: &str,
+    columns: &[(&str, ColumnType, OptionalRandBound)],
+    sizes: &[usize],
+    prover_setup: &CP::ProverPublicSetup,
+    verifier_setup: &CP::VerifierPublicSetup,
+    config: &ProverConfig,
 ) {
     let mut group = c.benchmark_group(format!("{} - {}", title, query));
     group.sample_size(10);
@@ -90,7 +144,14 @@ pub fn criterion_scaffold<CP: CommitmentEvaluationProof>(
             &mut rng,
         );
         group.bench_function("Generate Proof", |b| {
-            b.iter(|| VerifiableQueryResult::<CP>::new(query.proof_expr(), &accessor, prover_setup))
+            b.iter(|| {
+                VerifiableQueryResult::<CP>::new_with_config(
+                    query.proof_expr(),
+                    &accessor,
+                    prover_setup,
+                    config,
+                )
+            })
         });
         group.bench_function("Verify Proof", |b| {
             b.iter(|| result.verify(query.proof_expr(), &accessor, verifier_setup))
@@ -0,0 +1,44 @@
+//! Benchmarking of a query whose sumcheck polynomial has many subpolynomials using the `criterion` crate.
+//! To compare the sequential and the parallel evaluation of the sumcheck rounds, execute the following commands:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench sumcheck_benches
+//! cargo bench -p proof-of-sql --bench sumcheck_benches --features parallel-sumcheck
+//! ```
+//! Each equality of the filter adds its own subpolynomials, so the sumcheck dominates the proof.
+#![allow(missing_docs)]
+use blitzar::proof::InnerProductProof;
+use criterion::{criterion_group, criterion_main, Criterion};
+use proof_of_sql::base::database::ColumnType;
+
+#[allow(dead_code)]
+mod scaffold;
+use scaffold::{criterion_scaffold, OptionalRandBound};
+
+const SIZES: &[usize] = &[1_000_000];
+
+const NUM_EQUALITIES: i64 = 32;
+
+const SUMCHECK_COLUMNS: &[(&str, ColumnType, OptionalRandBound)] = &[
+    ("a", ColumnType::BigInt, Some(|_| 2 * NUM_EQUALITIES)),
+    ("b", ColumnType::BigInt, None),
+];
+
+fn sumcheck_benches(c: &mut Criterion) {
+    let where_expr = (0..NUM_EQUALITIES)
+        .map(|i| format!("a = {i}"))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let query = format!("SELECT b FROM table WHERE {where_expr}");
+    criterion_scaffold::<InnerProductProof>(
+        c,
+        "Many Subpolynomials",
+        &query,
+        SUMCHECK_COLUMNS,
+        SIZES,
+        &(),
+        &(),
+    );
+}
+
+criterion_group!(benches, sumcheck_benches);
+criterion_main!(benches);
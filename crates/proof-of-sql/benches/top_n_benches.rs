@@ -0,0 +1,32 @@
+//! Benchmarking of a top-10 query over 1M rows against the same query without a limit using the `criterion` crate.
+//! To run the benchmark, execute the following command:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench top_n_benches
+//! ```
+#![allow(missing_docs)]
+use blitzar::proof::InnerProductProof;
+use criterion::{criterion_group, criterion_main, Criterion};
+use proof_of_sql::base::database::ColumnType;
+
+#[allow(dead_code)]
+mod scaffold;
+use scaffold::{criterion_scaffold, OptionalRandBound};
+
+const SIZES: &[usize] = &[1_000_000];
+
+const TOP_N_COLUMNS: &[(&str, ColumnType, OptionalRandBound)] = &[
+    ("a", ColumnType::BigInt, None),
+    ("b", ColumnType::BigInt, None),
+];
+
+fn top_n_benches(c: &mut Criterion) {
+    for (title, query) in [
+        ("Top 10", "SELECT a, b FROM table ORDER BY a DESC LIMIT 10"),
+        ("Full Sort", "SELECT a, b FROM table ORDER BY a DESC"),
+    ] {
+        criterion_scaffold::<InnerProductProof>(c, title, query, TOP_N_COLUMNS, SIZES, &(), &());
+    }
+}
+
+criterion_group!(benches, top_n_benches);
+criterion_main!(benches);
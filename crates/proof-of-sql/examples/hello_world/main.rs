@@ -44,7 +44,7 @@ fn main() {
     end_timer(timer);
     let timer = start_timer("Generating Proof");
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     end_timer(timer);
     let timer = start_timer("Verifying Proof");
     let result = proof.verify(query.proof_expr(), &accessor, &serialized_result, &());
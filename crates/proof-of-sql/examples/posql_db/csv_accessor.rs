@@ -83,6 +83,13 @@ impl<S: Scalar> DataAccessor<S> for CsvDataAccessor {
     fn get_column(&self, column: ColumnRef) -> Column<S> {
         self.inner.get_column(column)
     }
+    fn column_type(
+        &self,
+        table_ref: TableRef,
+        column_id: proof_of_sql_parser::Identifier,
+    ) -> Option<proof_of_sql::base::database::ColumnType> {
+        self.inner.lookup_column(table_ref, column_id)
+    }
 }
 impl MetadataAccessor for CsvDataAccessor {
     fn get_length(&self, table_ref: TableRef) -> usize {
@@ -206,7 +206,8 @@ fn main() {
                 query.proof_expr(),
                 &csv_accessor,
                 &(),
-            );
+            )
+            .expect("Failed to prove query");
             end_timer(timer);
             fs::write(
                 file,
@@ -43,6 +43,10 @@ impl<S: Scalar> DataAccessor<S> for RecordBatchAccessor {
         );
         result
     }
+
+    fn column_type(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.lookup_column(table_ref, column_id)
+    }
 }
 impl MetadataAccessor for RecordBatchAccessor {
     fn get_length(&self, table_ref: TableRef) -> usize {
@@ -43,6 +43,7 @@ impl ColumnCommitmentMetadata {
             | (
                 ColumnType::Boolean
                 | ColumnType::VarChar
+                | ColumnType::Binary(_)
                 | ColumnType::Scalar
                 | ColumnType::Decimal75(..),
                 ColumnBounds::NoOrder,
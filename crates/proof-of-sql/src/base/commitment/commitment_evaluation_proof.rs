@@ -92,7 +92,12 @@ impl CommitmentEvaluationProof for InnerProductProof {
         generators_offset: u64,
         _setup: &Self::ProverPublicSetup,
     ) -> Self {
-        assert!(!a.is_empty());
+        // An empty table is proven as a single row of zero, which has the same identity commitment.
+        let a = if a.is_empty() {
+            &[Self::Scalar::ZERO]
+        } else {
+            a
+        };
         let b = &mut vec![Default::default(); a.len()];
         if b_point.is_empty() {
             assert_eq!(b.len(), 1);
@@ -117,8 +122,7 @@ impl CommitmentEvaluationProof for InnerProductProof {
         table_length: usize,
         _setup: &Self::VerifierPublicSetup,
     ) -> Result<(), Self::Error> {
-        assert!(table_length > 0);
-        let b = &mut vec![Default::default(); table_length];
+        let b = &mut vec![Default::default(); table_length.max(1)];
         if b_point.is_empty() {
             assert_eq!(b.len(), 1);
             b[0] = Self::Scalar::ONE;
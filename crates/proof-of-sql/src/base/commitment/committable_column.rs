@@ -2,7 +2,7 @@ use crate::base::{
     database::{Column, ColumnType, OwnedColumn},
     math::decimal::Precision,
     ref_into::RefInto,
-    scalar::Scalar,
+    scalar::{Scalar, VarCharHash},
 };
 #[cfg(feature = "blitzar")]
 use blitzar::sequence::Sequence;
@@ -37,6 +37,8 @@ pub enum CommittableColumn<'a> {
     Scalar(Vec<[u64; 4]>),
     /// Column of limbs for committing to scalars, hashed from a VarChar column.
     VarChar(Vec<[u64; 4]>),
+    /// Column of limbs for committing to scalars, hashed from a Binary column of the given length.
+    Binary(usize, Vec<[u64; 4]>),
 }
 
 impl<'a> CommittableColumn<'a> {
@@ -50,6 +52,7 @@ impl<'a> CommittableColumn<'a> {
             CommittableColumn::Decimal75(_, _, col) => col.len(),
             CommittableColumn::Scalar(col) => col.len(),
             CommittableColumn::VarChar(col) => col.len(),
+            CommittableColumn::Binary(_, col) => col.len(),
             CommittableColumn::Boolean(col) => col.len(),
         }
     }
@@ -77,6 +80,7 @@ impl<'a> From<&CommittableColumn<'a>> for ColumnType {
             }
             CommittableColumn::Scalar(_) => ColumnType::Scalar,
             CommittableColumn::VarChar(_) => ColumnType::VarChar,
+            CommittableColumn::Binary(length, _) => ColumnType::Binary(*length),
             CommittableColumn::Boolean(_) => ColumnType::Boolean,
         }
     }
@@ -99,6 +103,10 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for CommittableColumn<'a> {
                 let as_limbs: Vec<_> = scalars.iter().map(RefInto::<[u64; 4]>::ref_into).collect();
                 CommittableColumn::VarChar(as_limbs)
             }
+            Column::Binary(length, (_, scalars)) => {
+                let as_limbs: Vec<_> = scalars.iter().map(RefInto::<[u64; 4]>::ref_into).collect();
+                CommittableColumn::Binary(*length, as_limbs)
+            }
         }
     }
 }
@@ -121,13 +129,38 @@ impl<'a, S: Scalar> From<&'a OwnedColumn<S>> for CommittableColumn<'a> {
                     .collect(),
             ),
             OwnedColumn::Scalar(scalars) => (scalars as &[_]).into(),
+            OwnedColumn::VarChar(_) | OwnedColumn::Binary(_, _) => {
+                Self::from_owned_column_with_varchar_hash(value, VarCharHash::default())
+            }
+        }
+    }
+}
+
+impl<'a> CommittableColumn<'a> {
+    /// Converts an [`OwnedColumn`], hashing the values of a `VarChar` or `Binary` column with `varchar_hash`.
+    ///
+    /// `From<&OwnedColumn>` is the same conversion with the default [`VarCharHash`].
+    pub fn from_owned_column_with_varchar_hash<S: Scalar>(
+        column: &'a OwnedColumn<S>,
+        varchar_hash: VarCharHash,
+    ) -> Self {
+        match column {
             OwnedColumn::VarChar(strings) => CommittableColumn::VarChar(
                 strings
                     .iter()
-                    .map(Into::<S>::into)
+                    .map(|string| varchar_hash.hash_to_scalar::<S>(string))
                     .map(Into::<[u64; 4]>::into)
                     .collect(),
             ),
+            OwnedColumn::Binary(length, values) => CommittableColumn::Binary(
+                *length,
+                values
+                    .iter()
+                    .map(|bytes| varchar_hash.hash_bytes_to_scalar::<S>(bytes))
+                    .map(Into::<[u64; 4]>::into)
+                    .collect(),
+            ),
+            _ => column.into(),
         }
     }
 }
@@ -173,7 +206,9 @@ impl<'a, 'b> From<&'a CommittableColumn<'b>> for Sequence<'a> {
             CommittableColumn::Int128(ints) => Sequence::from(*ints),
             CommittableColumn::Decimal75(_, _, limbs) => Sequence::from(limbs),
             CommittableColumn::Scalar(limbs) => Sequence::from(limbs),
-            CommittableColumn::VarChar(limbs) => Sequence::from(limbs),
+            CommittableColumn::VarChar(limbs) | CommittableColumn::Binary(_, limbs) => {
+                Sequence::from(limbs)
+            }
             CommittableColumn::Boolean(bools) => Sequence::from(*bools),
         }
     }
@@ -730,6 +765,29 @@ mod tests {
         assert_eq!(commitment_buffer[0], commitment_buffer[1]);
     }
 
+    #[test]
+    fn different_varchar_hashes_result_in_different_commitments() {
+        let values = ["12", "34", "56"].map(String::from);
+        let owned_column = OwnedColumn::<Curve25519Scalar>::VarChar(values.to_vec());
+        let plain = CommittableColumn::from_owned_column_with_varchar_hash(
+            &owned_column,
+            VarCharHash::Blake3,
+        );
+        assert_eq!(plain, CommittableColumn::from(&owned_column));
+        let separated = CommittableColumn::from_owned_column_with_varchar_hash(
+            &owned_column,
+            VarCharHash::DomainSeparatedBlake3,
+        );
+
+        let mut commitment_buffer = [CompressedRistretto::default(); 2];
+        compute_curve25519_commitments(
+            &mut commitment_buffer,
+            &[Sequence::from(&plain), Sequence::from(&separated)],
+            0,
+        );
+        assert_ne!(commitment_buffer[0], commitment_buffer[1]);
+    }
+
     #[test]
     fn we_can_commit_to_scalar_column_through_committable_column() {
         // empty case
@@ -34,12 +34,17 @@ pub use column_commitments::{
 mod table_commitment;
 pub use table_commitment::{
     AppendTableCommitmentError, MixedLengthColumns, NegativeRange, TableCommitment,
-    TableCommitmentArithmeticError, TableCommitmentFromColumnsError,
+    TableCommitmentArithmeticError, TableCommitmentFromColumnsError, TABLE_COMMITMENT_ROOT_CONTEXT,
 };
 
 mod query_commitments;
 pub use query_commitments::{QueryCommitments, QueryCommitmentsExt};
 
+mod sparse_column;
+pub use sparse_column::{
+    InvalidSparseEntries, SparseColumn, SparseColumnLengthMismatch, SparseCommitmentAccessor,
+};
+
 /// A trait for using commitment schemes generically.
 pub trait Commitment:
     AddAssign
@@ -53,6 +58,7 @@ pub trait Commitment:
     + core::fmt::Debug
     + std::marker::Sync
     + std::marker::Send
+    + 'static
 {
     /// The associated scalar that the commitment is for.
     /// There are multiple possible commitment schemes for a scalar, but only one scalar for any commitment.
@@ -73,6 +79,21 @@ pub trait Commitment:
         setup: &Self::PublicSetup,
     );
 
+    /// Compute the commitments for the given columns like [`Self::compute_commitments`],
+    /// with multi-scalar multiplications over windows of `msm_window_bits` bits.
+    ///
+    /// The commitments are the same for any window. `None` lets the scheme choose the window,
+    /// and schemes that always choose their own window ignore it, which is the default.
+    fn compute_commitments_with_msm_window(
+        commitments: &mut [Self],
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup,
+        _msm_window_bits: Option<usize>,
+    ) {
+        Self::compute_commitments(commitments, committable_columns, offset, setup);
+    }
+
     /// Compute a linear combination of the given commitments: `sum commitment[i] * multiplier[i]`.
     fn fold_commitments(commitments: &[Self], multipliers: &[Self::Scalar]) -> Self;
 }
@@ -110,7 +131,7 @@ impl Commitment for RistrettoPoint {
         _offset: usize,
         _setup: &Self::PublicSetup,
     ) {
-        unimplemented!()
+        unimplemented!("Curve25519 commitments require the blitzar feature; use Dory instead")
     }
 
     fn fold_commitments(commitments: &[Self], multipliers: &[Self::Scalar]) -> Self {
@@ -1,4 +1,4 @@
-use super::{TableCommitment, VecCommitmentExt};
+use super::{ColumnBounds, TableCommitment, TableCommitmentArithmeticError, VecCommitmentExt};
 use crate::base::database::{
     ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor, SchemaAccessor,
     TableRef,
@@ -25,6 +25,26 @@ where
         columns: impl IntoIterator<Item = ColumnRef>,
         accessor: &impl CommitmentAccessor<Decompressed<C>>,
     ) -> Self;
+
+    /// Add `delta`, the commitment to rows appended to the table `table_ref`, to the commitment to the rows before them.
+    ///
+    /// This lets a verifier keep up with an append-only table without recommitting the rows it already has a commitment to.
+    /// `delta` must be committed with an offset of the end of the existing commitment's range,
+    /// so that the sum is the commitment to the whole table.
+    /// Queries are then proven and verified over the whole table as one contiguous range,
+    /// so a selection that spans both sides of the boundary needs no special treatment.
+    /// If there is no commitment to `table_ref` yet, `delta` becomes its commitment.
+    ///
+    /// Errors with [`TableCommitmentArithmeticError::NonContiguous`] if `delta` does not begin where the existing commitment ends,
+    /// or with [`TableCommitmentArithmeticError::ColumnMismatch`] if their columns differ,
+    /// in which case the existing commitment is left unchanged.
+    fn try_append_delta(
+        &mut self,
+        table_ref: TableRef,
+        delta: TableCommitment<C>,
+    ) -> Result<(), TableCommitmentArithmeticError>
+    where
+        C: Clone;
 }
 
 impl<C> QueryCommitmentsExt<C> for QueryCommitments<C>
@@ -54,6 +74,25 @@ where
             })
             .collect()
     }
+
+    fn try_append_delta(
+        &mut self,
+        table_ref: TableRef,
+        delta: TableCommitment<C>,
+    ) -> Result<(), TableCommitmentArithmeticError>
+    where
+        C: Clone,
+    {
+        let table_commitment = match self.get(&table_ref) {
+            Some(base) if base.range().end != delta.range().start => {
+                Err(TableCommitmentArithmeticError::NonContiguous)?
+            }
+            Some(base) => base.clone().try_add(delta)?,
+            None => delta,
+        };
+        self.insert(table_ref, table_commitment);
+        Ok(())
+    }
 }
 
 impl<C> MetadataAccessor for QueryCommitments<C>
@@ -88,6 +127,15 @@ where
             .get_commitment(&column.column_id())
             .unwrap()
     }
+
+    fn get_column_bounds(&self, column: ColumnRef) -> Option<ColumnBounds> {
+        let table_commitment = self.get(&column.table_ref())?;
+
+        table_commitment
+            .column_commitments()
+            .get_metadata(&column.column_id())
+            .map(|metadata| *metadata.bounds())
+    }
 }
 
 impl<C> SchemaAccessor for QueryCommitments<C>
@@ -194,6 +242,65 @@ mod tests {
         assert_eq!(query_commitments.get_length(no_rows_id), 0);
     }
 
+    #[test]
+    fn we_can_append_a_delta_to_the_commitment_of_a_table() {
+        let table_ref = "sxt.table".parse().unwrap();
+        let base: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint("column_a", [1, 2, 3]),
+            varchar("column_b", ["Lorem", "ipsum", "dolor"]),
+        ]);
+        let delta: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint("column_a", [4, -5]),
+            varchar("column_b", ["sit", "amet"]),
+        ]);
+        let mut merged = base.clone();
+        merged.append(&delta).unwrap();
+
+        let mut query_commitments = QueryCommitments::new();
+        query_commitments
+            .try_append_delta(
+                table_ref,
+                TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(&base, 2, &()),
+            )
+            .unwrap();
+        query_commitments
+            .try_append_delta(
+                table_ref,
+                TableCommitment::from_owned_table_with_offset(&delta, 5, &()),
+            )
+            .unwrap();
+        assert_eq!(
+            query_commitments[&table_ref],
+            TableCommitment::from_owned_table_with_offset(&merged, 2, &())
+        );
+
+        // the delta must begin where the existing commitment ends
+        for offset in [0, 6] {
+            assert!(matches!(
+                query_commitments.try_append_delta(
+                    table_ref,
+                    TableCommitment::from_owned_table_with_offset(&delta, offset, &()),
+                ),
+                Err(TableCommitmentArithmeticError::NonContiguous)
+            ));
+        }
+        assert!(matches!(
+            query_commitments.try_append_delta(
+                table_ref,
+                TableCommitment::from_owned_table_with_offset(
+                    &owned_table::<Curve25519Scalar>([bigint("column_a", [6])]),
+                    7,
+                    &()
+                ),
+            ),
+            Err(TableCommitmentArithmeticError::ColumnMismatch(_))
+        ));
+        assert_eq!(
+            query_commitments[&table_ref],
+            TableCommitment::from_owned_table_with_offset(&merged, 2, &())
+        );
+    }
+
     #[test]
     fn we_can_get_commitment_of_a_column() {
         let column_a_id: Identifier = "column_a".parse().unwrap();
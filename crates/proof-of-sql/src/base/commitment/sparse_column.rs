@@ -0,0 +1,436 @@
+use super::{committable_column::CommittableColumn, ColumnBounds, Commitment};
+use crate::base::{
+    database::{
+        ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor, SchemaAccessor, TableRef,
+    },
+    scalar::{Scalar, VarCharHash},
+};
+use proof_of_sql_parser::Identifier;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// The number of rows of the chunks of ones that the commitment to a nonzero default is computed from.
+///
+/// Tests use short chunks, so that their small columns span several of them.
+const ONES_CHUNK_LEN: usize = if cfg!(test) { 4 } else { 1 << 16 };
+
+/// Cannot create a [`SparseColumn`] whose entries are not at strictly increasing rows of the column.
+#[derive(Debug, Error)]
+#[error(
+    "cannot create a SparseColumn whose entries are not at strictly increasing rows of the column"
+)]
+pub struct InvalidSparseEntries;
+
+/// A column whose rows are all `default` except for a few entries, such as a column of flags.
+///
+/// The commitment to the column is computed from the entries and their rows, and is the same as
+/// the commitment to the dense column. So it can be used wherever the dense commitment is,
+/// and proofs and their verification are unchanged.
+///
+/// # When sparsity helps
+/// Committing to the dense column costs a multi-scalar multiplication over every row. Here each
+/// run of entries at consecutive rows is committed separately, so the cost is proportional to the
+/// number of entries plus a fixed overhead per run. A nonzero `default` adds a commitment to a
+/// column of ones, which only adds up generators. It is committed in chunks of a fixed number of rows,
+/// so the dense column is never allocated. This is cheaper when the entries are a small
+/// fraction of the rows, for instance a few percent, and cheapest when they are clustered.
+/// When the entries are scattered and numerous, the overhead per run dominates and the dense
+/// commitment is faster.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseColumn<S: Scalar> {
+    length: usize,
+    default: S,
+    entries: Vec<(usize, S)>,
+}
+
+impl<S: Scalar> SparseColumn<S> {
+    /// Create a column of `length` rows that are `default` except for the given entries,
+    /// which are `(row, value)` pairs.
+    ///
+    /// Errors with [`InvalidSparseEntries`] unless the rows of the entries are strictly increasing
+    /// and less than `length`.
+    pub fn try_new(
+        length: usize,
+        default: S,
+        entries: Vec<(usize, S)>,
+    ) -> Result<Self, InvalidSparseEntries> {
+        let rows_are_increasing = entries.windows(2).all(|pair| pair[0].0 < pair[1].0);
+        let rows_are_in_range = entries.last().map_or(true, |&(row, _)| row < length);
+        if !(rows_are_increasing && rows_are_in_range) {
+            return Err(InvalidSparseEntries);
+        }
+        Ok(Self {
+            length,
+            default,
+            entries,
+        })
+    }
+
+    /// Create the sparse representation of a dense column, keeping the rows that are not `default`.
+    pub fn from_dense<T: Copy + PartialEq + Into<S>>(values: &[T], default: T) -> Self {
+        Self {
+            length: values.len(),
+            default: default.into(),
+            entries: values
+                .iter()
+                .enumerate()
+                .filter(|(_, &value)| value != default)
+                .map(|(row, &value)| (row, value.into()))
+                .collect(),
+        }
+    }
+
+    /// The number of rows of the column.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Whether the column has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// The value of the rows without an entry.
+    pub fn default_value(&self) -> S {
+        self.default
+    }
+
+    /// The `(row, value)` pairs of the rows that are not necessarily `default`.
+    pub fn entries(&self) -> &[(usize, S)] {
+        &self.entries
+    }
+
+    /// The dense column.
+    pub fn to_dense(&self) -> Vec<S> {
+        let mut values = vec![self.default; self.length];
+        for &(row, value) in &self.entries {
+            values[row] = value;
+        }
+        values
+    }
+
+    /// Compute the commitment to the dense column with the given generator offset.
+    pub fn commitment<C: Commitment<Scalar = S>>(
+        &self,
+        offset: usize,
+        setup: &C::PublicSetup,
+    ) -> C {
+        let mut commitments = Vec::new();
+        let mut multipliers = Vec::new();
+        if self.default != S::ZERO {
+            let ones = vec![true; self.length.min(ONES_CHUNK_LEN)];
+            for start in (0..self.length).step_by(ONES_CHUNK_LEN) {
+                let chunk_len = ONES_CHUNK_LEN.min(self.length - start);
+                commitments.push(commit(&ones[..chunk_len], offset + start, setup));
+                multipliers.push(self.default);
+            }
+        }
+        // The differences from `default`, in runs of consecutive rows.
+        let mut runs: Vec<(usize, Vec<S>)> = Vec::new();
+        for &(row, value) in &self.entries {
+            match runs.last_mut() {
+                Some((start, run)) if *start + run.len() == row => run.push(value - self.default),
+                _ => runs.push((row, vec![value - self.default])),
+            }
+        }
+        for (start, run) in &runs {
+            commitments.push(commit(run.as_slice(), offset + start, setup));
+            multipliers.push(S::ONE);
+        }
+        C::fold_commitments(&commitments, &multipliers)
+    }
+}
+
+/// Cannot add a [`SparseColumn`] to a [`SparseCommitmentAccessor`] unless it has as many rows as its table.
+#[derive(Debug, Error)]
+#[error("cannot add a SparseColumn with a different number of rows than its table")]
+pub struct SparseColumnLengthMismatch;
+
+/// A [`CommitmentAccessor`] that commits to some columns from their [`SparseColumn`]s,
+/// and gets everything else, such as the commitments to the other columns, from `inner`.
+///
+/// This lets whoever keeps the commitments store a sparse column, e.g. a column of flags,
+/// without its dense form. The verifier uses the commitment like any other,
+/// so the proof is unchanged.
+pub struct SparseCommitmentAccessor<'a, C: Commitment, A> {
+    inner: &'a A,
+    commitments: HashMap<ColumnRef, C>,
+}
+
+impl<'a, C: Commitment, A: CommitmentAccessor<C>> SparseCommitmentAccessor<'a, C, A> {
+    /// Create an accessor with no sparse columns, which gives the commitments of `inner`.
+    pub fn new(inner: &'a A) -> Self {
+        Self {
+            inner,
+            commitments: HashMap::new(),
+        }
+    }
+
+    /// Commit to `column` from `sparse` with the offset of its table, in place of the commitment of `inner`.
+    ///
+    /// Errors with [`SparseColumnLengthMismatch`] unless `sparse` has as many rows as the table has in `inner`.
+    pub fn try_add_column(
+        &mut self,
+        column: ColumnRef,
+        sparse: &SparseColumn<C::Scalar>,
+        setup: &C::PublicSetup,
+    ) -> Result<(), SparseColumnLengthMismatch> {
+        let table_ref = column.table_ref();
+        if sparse.len() != self.inner.get_length(table_ref) {
+            return Err(SparseColumnLengthMismatch);
+        }
+        let commitment = sparse.commitment(self.inner.get_offset(table_ref), setup);
+        self.commitments.insert(column, commitment);
+        Ok(())
+    }
+}
+
+impl<C: Commitment, A: CommitmentAccessor<C>> MetadataAccessor
+    for SparseCommitmentAccessor<'_, C, A>
+{
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.inner.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.inner.get_offset(table_ref)
+    }
+
+    fn varchar_hash(&self) -> VarCharHash {
+        self.inner.varchar_hash()
+    }
+}
+
+impl<C: Commitment, A: CommitmentAccessor<C>> CommitmentAccessor<C>
+    for SparseCommitmentAccessor<'_, C, A>
+{
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        match self.commitments.get(&column) {
+            Some(commitment) => *commitment,
+            None => self.inner.get_commitment(column),
+        }
+    }
+
+    /// The bounds of `inner` for the columns that are not sparse. No bounds are recorded for a sparse column.
+    fn get_column_bounds(&self, column: ColumnRef) -> Option<ColumnBounds> {
+        if self.commitments.contains_key(&column) {
+            None
+        } else {
+            self.inner.get_column_bounds(column)
+        }
+    }
+}
+
+impl<C: Commitment, A: CommitmentAccessor<C> + SchemaAccessor> SchemaAccessor
+    for SparseCommitmentAccessor<'_, C, A>
+{
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.inner.lookup_column(table_ref, column_id)
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.inner.lookup_schema(table_ref)
+    }
+}
+
+fn commit<'a, C: Commitment>(
+    column: impl Into<CommittableColumn<'a>>,
+    offset: usize,
+    setup: &C::PublicSetup,
+) -> C {
+    let mut commitment = [C::default()];
+    C::compute_commitments(&mut commitment, &[column.into()], offset, setup);
+    let [commitment] = commitment;
+    commitment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::database::{
+            owned_table_utility::*, ColumnRef, ColumnType, CommitmentAccessor,
+            OwnedTableTestAccessor, TestAccessor,
+        },
+        proof_primitive::dory::{
+            DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+        },
+        sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+    };
+    use ark_std::test_rng;
+
+    fn dense_commitment(
+        values: &[DoryScalar],
+        offset: usize,
+        setup: &DoryProverPublicSetup,
+    ) -> DoryCommitment {
+        commit(values, offset, setup)
+    }
+
+    #[test]
+    fn we_can_commit_to_a_sparse_column_like_to_the_dense_column() {
+        let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+        let sparse = SparseColumn::<DoryScalar>::try_new(
+            10,
+            DoryScalar::ZERO,
+            vec![
+                (0, 5.into()),
+                (3, (-2).into()),
+                (4, 7.into()),
+                (9, 1.into()),
+            ],
+        )
+        .unwrap();
+        let dense = sparse.to_dense();
+        assert_eq!(dense, [5, 0, 0, -2, 7, 0, 0, 0, 0, 1].map(DoryScalar::from));
+        for offset in [0, 3] {
+            assert_eq!(
+                sparse.commitment::<DoryCommitment>(offset, &setup),
+                dense_commitment(&dense, offset, &setup)
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_commit_to_a_sparse_column_with_a_nonzero_default() {
+        let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+        let values = [3i64, 3, -1, 3, 3, 8, 3];
+        let sparse = SparseColumn::<DoryScalar>::from_dense(&values, 3);
+        assert_eq!(sparse.entries(), [(2, (-1).into()), (5, 8.into())]);
+        assert_eq!(sparse.default_value(), 3.into());
+        assert_eq!(sparse.len(), 7);
+        assert_eq!(
+            sparse.commitment::<DoryCommitment>(2, &setup),
+            dense_commitment(&sparse.to_dense(), 2, &setup)
+        );
+
+        let empty = SparseColumn::<DoryScalar>::from_dense::<i64>(&[], 3);
+        assert!(empty.is_empty());
+        assert_eq!(
+            empty.commitment::<DoryCommitment>(0, &setup),
+            DoryCommitment::default()
+        );
+    }
+
+    #[test]
+    fn we_cannot_create_a_sparse_column_with_unordered_or_out_of_range_entries() {
+        let entry = |row: usize| (row, DoryScalar::ONE);
+        assert!(SparseColumn::try_new(4, DoryScalar::ZERO, vec![entry(1), entry(1)]).is_err());
+        assert!(SparseColumn::try_new(4, DoryScalar::ZERO, vec![entry(2), entry(1)]).is_err());
+        assert!(SparseColumn::try_new(4, DoryScalar::ZERO, vec![entry(4)]).is_err());
+        assert!(SparseColumn::try_new(4, DoryScalar::ZERO, vec![entry(0), entry(3)]).is_ok());
+        assert!(SparseColumn::try_new(0, DoryScalar::ZERO, vec![]).is_ok());
+    }
+
+    #[test]
+    fn we_can_verify_a_query_against_the_commitment_to_a_sparse_column() {
+        let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+        let flags: Vec<i64> = (0..100).map(|row| i64::from(row == 17)).collect();
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor =
+            OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+        accessor.add_table(
+            t,
+            owned_table([bigint("a", 0..100), bigint("flag", flags.iter().copied())]),
+            0,
+        );
+        let sparse = SparseColumn::<DoryScalar>::from_dense(&flags, 0);
+        assert_eq!(sparse.entries().len(), 1);
+        let flag = ColumnRef::new(t, "flag".parse().unwrap(), ColumnType::BigInt);
+        assert_eq!(
+            sparse.commitment::<DoryCommitment>(0, &setup),
+            accessor.get_commitment(flag)
+        );
+
+        let query = QueryExpr::<DoryCommitment>::try_new(
+            "SELECT a FROM t WHERE flag = 1".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let res = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .unwrap()
+        .table;
+        assert_eq!(res, owned_table([bigint("a", [17])]));
+    }
+
+    #[test]
+    fn we_can_verify_a_query_with_the_commitment_of_a_sparse_column_from_an_accessor() {
+        let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+        let flags: Vec<i64> = (0..10).map(|row| if row == 6 { 2 } else { 1 }).collect();
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor =
+            OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+        accessor.add_table(
+            t,
+            owned_table([bigint("a", 0..10), bigint("flag", flags.iter().copied())]),
+            0,
+        );
+        let flag = ColumnRef::new(t, "flag".parse().unwrap(), ColumnType::BigInt);
+        let query = QueryExpr::<DoryCommitment>::try_new(
+            "SELECT a FROM t WHERE flag = 2".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let res = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &setup,
+        )
+        .unwrap();
+
+        let mut sparse_accessor = SparseCommitmentAccessor::new(&accessor);
+        sparse_accessor
+            .try_add_column(flag, &SparseColumn::from_dense(&flags, 1), &setup)
+            .unwrap();
+        assert_eq!(
+            sparse_accessor.get_commitment(flag),
+            accessor.get_commitment(flag)
+        );
+        assert_eq!(
+            res.verify(query.proof_expr(), &sparse_accessor, &(&setup).into())
+                .unwrap()
+                .table,
+            owned_table([bigint("a", [6])])
+        );
+
+        // the flag is committed at a different row than the one the proof is over
+        let mut other_flags = flags.clone();
+        other_flags.swap(6, 7);
+        let mut sparse_accessor = SparseCommitmentAccessor::new(&accessor);
+        sparse_accessor
+            .try_add_column(flag, &SparseColumn::from_dense(&other_flags, 1), &setup)
+            .unwrap();
+        assert!(res
+            .verify(query.proof_expr(), &sparse_accessor, &(&setup).into())
+            .is_err());
+
+        assert!(SparseCommitmentAccessor::new(&accessor)
+            .try_add_column(flag, &SparseColumn::from_dense(&flags[1..], 1), &setup)
+            .is_err());
+
+        // the column is committed at the offset of its table
+        let mut offset_accessor =
+            OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+        offset_accessor.add_table(
+            t,
+            owned_table([bigint("a", 0..10), bigint("flag", flags.iter().copied())]),
+            3,
+        );
+        let mut sparse_accessor = SparseCommitmentAccessor::new(&offset_accessor);
+        sparse_accessor
+            .try_add_column(flag, &SparseColumn::from_dense(&flags, 1), &setup)
+            .unwrap();
+        assert_eq!(
+            sparse_accessor.get_commitment(flag),
+            offset_accessor.get_commitment(flag)
+        );
+    }
+}
@@ -84,6 +84,11 @@ pub enum AppendRecordBatchTableCommitmentError {
     ArrowBatchToColumnError(#[from] RecordBatchToColumnsError),
 }
 
+/// The blake3 key derivation context used by [`TableCommitment::root`].
+///
+/// This should only change if the way the root is computed changes.
+pub const TABLE_COMMITMENT_ROOT_CONTEXT: &str = "proof-of-sql 2024 table commitment root v1";
+
 /// Commitment for an entire table, with column and table metadata.
 ///
 /// Unlike [`ColumnCommitments`], all columns in this commitment must have the same length.
@@ -164,6 +169,33 @@ where
         self.range.len()
     }
 
+    /// Returns a single 32-byte root that binds this table commitment, so that clients can publish one value per table.
+    ///
+    /// The root is a blake3 hash in key derivation mode with the context string
+    /// [`TABLE_COMMITMENT_ROOT_CONTEXT`], which separates it from any other use of blake3 in this crate.
+    /// The hashed message is the postcard encoding of the row range followed by, for every column in order,
+    /// the identifier, column type, and column commitment.
+    ///
+    /// Column bounds are not included, since they are not needed to verify a proof.
+    /// So, changing the data of any column, the column order, or the row range changes the root.
+    pub fn root(&self) -> [u8; 32]
+    where
+        C: Serialize,
+    {
+        let mut hasher = blake3::Hasher::new_derive_key(TABLE_COMMITMENT_ROOT_CONTEXT);
+        hasher.update(
+            &postcard::to_allocvec(&(self.range.start as u64, self.range.end as u64))
+                .expect("a range should always be serializable"),
+        );
+        for (identifier, metadata, commitment) in self.column_commitments.iter() {
+            hasher.update(
+                &postcard::to_allocvec(&(identifier, metadata.column_type(), commitment))
+                    .expect("column commitments should always be serializable"),
+            );
+        }
+        hasher.finalize().into()
+    }
+
     /// Returns a [`TableCommitment`] to the provided columns with the given row offset.
     ///
     /// Provided columns must have the same length and no duplicate identifiers.
@@ -1269,4 +1301,75 @@ mod tests {
 
         assert_eq!(commitment, expected_commitment);
     }
+
+    #[test]
+    fn changing_any_single_column_changes_the_table_commitment_root() {
+        let bigint_id: Identifier = "bigint_column".parse().unwrap();
+        let varchar_id: Identifier = "varchar_column".parse().unwrap();
+        let scalar_id: Identifier = "scalar_column".parse().unwrap();
+
+        let table = |bigint_data: [i64; 3], varchar_data: [&str; 3], scalar_data: [i64; 3]| {
+            TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+                &owned_table::<Curve25519Scalar>([
+                    bigint(bigint_id, bigint_data),
+                    varchar(varchar_id, varchar_data),
+                    scalar(scalar_id, scalar_data),
+                ]),
+                0,
+                &(),
+            )
+        };
+
+        let root = table([1, 2, 3], ["a", "b", "c"], [4, 5, 6]).root();
+        assert_eq!(root, table([1, 2, 3], ["a", "b", "c"], [4, 5, 6]).root());
+
+        assert_ne!(root, table([1, 2, 4], ["a", "b", "c"], [4, 5, 6]).root());
+        assert_ne!(root, table([1, 2, 3], ["a", "x", "c"], [4, 5, 6]).root());
+        assert_ne!(root, table([1, 2, 3], ["a", "b", "c"], [0, 5, 6]).root());
+
+        // renaming or reordering columns also changes the root
+        let renamed = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &owned_table::<Curve25519Scalar>([
+                bigint("other_column", [1, 2, 3]),
+                varchar(varchar_id, ["a", "b", "c"]),
+                scalar(scalar_id, [4, 5, 6]),
+            ]),
+            0,
+            &(),
+        );
+        assert_ne!(root, renamed.root());
+        let reordered = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &owned_table::<Curve25519Scalar>([
+                varchar(varchar_id, ["a", "b", "c"]),
+                bigint(bigint_id, [1, 2, 3]),
+                scalar(scalar_id, [4, 5, 6]),
+            ]),
+            0,
+            &(),
+        );
+        assert_ne!(root, reordered.root());
+    }
+
+    #[test]
+    fn a_stale_root_does_not_match_an_updated_table_commitment() {
+        let mut table_commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &owned_table::<Curve25519Scalar>([bigint("a", [1, 2, 3])]),
+            0,
+            &(),
+        );
+        let stale_root = table_commitment.root();
+
+        table_commitment
+            .append_owned_table(&owned_table::<Curve25519Scalar>([bigint("a", [0])]), &())
+            .unwrap();
+        assert_ne!(table_commitment.root(), stale_root);
+
+        // a table commitment to the same data at a different offset also has a different root
+        let offset_commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &owned_table::<Curve25519Scalar>([bigint("a", [1, 2, 3])]),
+            1,
+            &(),
+        );
+        assert_ne!(offset_commitment.root(), stale_root);
+    }
 }
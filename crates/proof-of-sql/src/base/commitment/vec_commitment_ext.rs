@@ -31,6 +31,17 @@ pub trait VecCommitmentExt {
         setup: &Self::CommitmentPublicSetup,
     ) -> Self;
 
+    /// Returns a collection of commitments to the provided slice of `CommittableColumn`s using the given generator offset,
+    /// with multi-scalar multiplications over windows of `msm_window_bits` bits.
+    ///
+    /// See [`Commitment::compute_commitments_with_msm_window`].
+    fn from_commitable_columns_with_offset_and_msm_window(
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::CommitmentPublicSetup,
+        msm_window_bits: Option<usize>,
+    ) -> Self;
+
     /// Append rows of data from the provided columns to the existing commitments.
     ///
     /// The given generator offset will be used for committing to the new rows.
@@ -118,6 +129,24 @@ impl<C: Commitment> VecCommitmentExt for Vec<C> {
         commitments
     }
 
+    fn from_commitable_columns_with_offset_and_msm_window(
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::CommitmentPublicSetup,
+        msm_window_bits: Option<usize>,
+    ) -> Self {
+        let mut commitments = vec![C::default(); committable_columns.len()];
+        C::compute_commitments_with_msm_window(
+            &mut commitments,
+            committable_columns,
+            offset,
+            setup,
+            msm_window_bits,
+        );
+
+        commitments
+    }
+
     fn try_append_rows_with_offset<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = COL>,
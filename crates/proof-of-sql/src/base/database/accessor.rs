@@ -1,7 +1,7 @@
 use crate::base::{
-    commitment::Commitment,
+    commitment::{ColumnBounds, Commitment},
     database::{Column, ColumnRef, ColumnType, TableRef},
-    scalar::Scalar,
+    scalar::{Scalar, VarCharHash},
 };
 use proof_of_sql_parser::Identifier;
 
@@ -20,6 +20,13 @@ pub trait MetadataAccessor {
     /// If the data span has its first row starting at the ith table row,
     /// this `get_offset` should then return `i`.
     fn get_offset(&self, table_ref: TableRef) -> usize;
+
+    /// Return the hash that the strings of `VarChar` columns are mapped to scalars with
+    ///
+    /// The prover and the verifier must use the same hash as the one that the commitments were computed with.
+    fn varchar_hash(&self) -> VarCharHash {
+        VarCharHash::default()
+    }
 }
 
 /// Access commitments of database columns.
@@ -50,6 +57,15 @@ pub trait MetadataAccessor {
 pub trait CommitmentAccessor<C: Commitment>: MetadataAccessor {
     /// Return the full table column commitment
     fn get_commitment(&self, column: ColumnRef) -> C;
+
+    /// Return the bounds of the values of a column, as recorded alongside its commitment
+    ///
+    /// The bounds are trusted like the commitment itself, so [`Bounds::Sharp`](crate::base::commitment::Bounds::Sharp)
+    /// bounds let the minimum and maximum of the column be verified without proving them from its values.
+    /// Returns `None` if no bounds were recorded for the column.
+    fn get_column_bounds(&self, _column: ColumnRef) -> Option<ColumnBounds> {
+        None
+    }
 }
 
 /// Access database columns of an in-memory table span.
@@ -81,9 +97,30 @@ pub trait CommitmentAccessor<C: Commitment>: MetadataAccessor {
 ///
 /// Note: we assume that the query has already been validated so that we
 /// will only be accessing information about columns that exist in the database.
+/// [`DataAccessor::column_type`] allows this validation to be done without fetching any data.
 pub trait DataAccessor<S: Scalar>: MetadataAccessor {
     /// Return the data span in the table (not the full-table data)
     fn get_column(&self, column: ColumnRef) -> Column<S>;
+
+    /// Return the column's data type without fetching its data
+    ///
+    /// Return:
+    ///   - Some(type) if the column exists, where `type` is the column's data type
+    ///   - None in case the table or the column does not exist
+    fn column_type(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType>;
+
+    /// Return the number of rows of the table span, without fetching any of its columns
+    ///
+    /// This is the length of the span that was registered for the table, which starts at
+    /// [`MetadataAccessor::get_offset`], so the offset is not included in the count.
+    fn row_count(&self, table_ref: TableRef) -> usize {
+        self.get_length(table_ref)
+    }
+
+    /// Return whether the column exists in the table, without fetching its data
+    fn has_column(&self, table_ref: TableRef, column_id: Identifier) -> bool {
+        self.column_type(table_ref, column_id).is_some()
+    }
 }
 
 /// Access tables and their schemas in a database.
@@ -104,8 +141,11 @@ pub trait SchemaAccessor {
 
     /// Lookup all the column names and their data types in the specified table
     ///
+    /// This lets a client discover the schema of a table before writing a query against it.
+    /// Implementations must only read the table's metadata, never its column data.
+    ///
     /// Return:
-    ///   - The list of column names with their data types
+    ///   - The list of column names with their data types, in the order of the table's columns
     ///
     /// Precondition 1: the table must exist and be tamperproof.
     /// Precondition 2: `table_name` must be lowercase.
@@ -9,8 +9,8 @@ use crate::{
 };
 use arrow::{
     array::{
-        Array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, StringArray,
+        Array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, FixedSizeBinaryArray,
+        Int16Array, Int32Array, Int64Array, StringArray,
     },
     datatypes::{i256, DataType},
 };
@@ -148,7 +148,7 @@ impl ArrayRefExt for ArrayRef {
     /// - `alloc`: Reference to a `Bump` allocator used for memory allocation during the conversion.
     /// - `range`: Reference to a `Range<usize>` specifying the slice of the array to convert.
     /// - `precomputed_scals`: Optional reference to a slice of `Curve25519Scalar` values.
-    ///    VarChar columns store hashes to their values as scalars, which can be provided here.
+    ///    VarChar and Binary columns store hashes to their values as scalars, which can be provided here.
     ///
     /// # Supported types
     /// - For `DataType::Int64` and `DataType::Decimal128(38, 0)`, it slices the array
@@ -156,6 +156,7 @@ impl ArrayRefExt for ArrayRef {
     /// - Decimal256, converts arrow i256 columns into Decimal75(precision, scale) columns.
     /// - For `DataType::Utf8`, it extracts string values and scalar values (if `precomputed_scals`
     ///   is provided) for the specified range and returns a `VarChar` column.
+    /// - For `DataType::FixedSizeBinary`, it does the same with the byte values and returns a `Binary` column.
     ///
     /// # Panics
     /// - When any range is OOB, i.e. indexing 3..6 or 5..5 on array of size 2.
@@ -271,6 +272,26 @@ impl ArrayRefExt for ArrayRef {
                     ))
                 }
             }
+            DataType::FixedSizeBinary(length) if *length >= 0 => {
+                if let Some(array) = self.as_any().downcast_ref::<FixedSizeBinaryArray>() {
+                    let vals = alloc
+                        .alloc_slice_fill_with(range.end - range.start, |i| -> &'a [u8] {
+                            array.value(range.start + i)
+                        });
+
+                    let scals = if let Some(scals) = precomputed_scals {
+                        &scals[range.start..range.end]
+                    } else {
+                        alloc.alloc_slice_fill_with(vals.len(), |i| -> S { vals[i].into() })
+                    };
+
+                    Ok(Column::Binary(*length as usize, (vals, scals)))
+                } else {
+                    Err(ArrowArrayToColumnConversionError::UnsupportedType(
+                        self.data_type().clone(),
+                    ))
+                }
+            }
             data_type => Err(ArrowArrayToColumnConversionError::UnsupportedType(
                 data_type.clone(),
             )),
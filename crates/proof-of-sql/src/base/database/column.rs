@@ -1,13 +1,15 @@
-use super::{LiteralValue, TableRef};
+use super::{LiteralValue, OwnedColumn, TableRef};
 use crate::base::{
     math::decimal::{scale_scalar, Precision},
-    scalar::Scalar,
+    scalar::{Scalar, VarCharHash},
 };
 use arrow::datatypes::{DataType, Field};
 use bumpalo::Bump;
 use proof_of_sql_parser::Identifier;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
 
 /// Represents a read-only view of a column in an in-memory,
 /// column-oriented database.
@@ -37,6 +39,10 @@ pub enum Column<'a, S: Scalar> {
     ///  - the first element maps to the str values.
     ///  - the second element maps to the str hashes (see [crate::base::scalar::Scalar]).
     VarChar((&'a [&'a str], &'a [S])),
+    /// Fixed-length binary columns, such as hashes or addresses
+    ///  - the first element is the length of every value in bytes.
+    ///  - the second element maps to the byte values and their hashes, as for `VarChar`.
+    Binary(usize, (&'a [&'a [u8]], &'a [S])),
 }
 impl<'a, S: Scalar> Column<'a, S> {
     /// Provides the column type associated with the column
@@ -47,6 +53,7 @@ impl<'a, S: Scalar> Column<'a, S> {
             Self::Int(_) => ColumnType::Int,
             Self::BigInt(_) => ColumnType::BigInt,
             Self::VarChar(_) => ColumnType::VarChar,
+            Self::Binary(length, _) => ColumnType::Binary(*length),
             Self::Int128(_) => ColumnType::Int128,
             Self::Scalar(_) => ColumnType::Scalar,
             Self::Decimal75(precision, scale, _) => ColumnType::Decimal75(*precision, *scale),
@@ -63,6 +70,10 @@ impl<'a, S: Scalar> Column<'a, S> {
                 assert_eq!(col.len(), scals.len());
                 col.len()
             }
+            Self::Binary(_, (col, scals)) => {
+                assert_eq!(col.len(), scals.len());
+                col.len()
+            }
             Self::Int128(col) => col.len(),
             Self::Scalar(col) => col.len(),
             Self::Decimal75(_, _, col) => col.len(),
@@ -105,6 +116,91 @@ impl<'a, S: Scalar> Column<'a, S> {
                 alloc.alloc_slice_fill_with(length, |_| alloc.alloc_str(string) as &str),
                 alloc.alloc_slice_fill_copy(length, *scalar),
             )),
+            LiteralValue::Binary((bytes, scalar)) => Column::Binary(
+                bytes.len(),
+                (
+                    alloc.alloc_slice_fill_with(length, |_| {
+                        alloc.alloc_slice_copy(bytes) as &[u8]
+                    }),
+                    alloc.alloc_slice_fill_copy(length, *scalar),
+                ),
+            ),
+        }
+    }
+
+    /// Borrow the data of an [`OwnedColumn`], hashing the values of a `VarChar` or `Binary` column with `varchar_hash`
+    pub fn from_owned_column_with_varchar_hash(
+        owned_column: &'a OwnedColumn<S>,
+        varchar_hash: VarCharHash,
+        alloc: &'a Bump,
+    ) -> Self {
+        match owned_column {
+            OwnedColumn::Boolean(col) => Column::Boolean(col),
+            OwnedColumn::SmallInt(col) => Column::SmallInt(col),
+            OwnedColumn::Int(col) => Column::Int(col),
+            OwnedColumn::BigInt(col) => Column::BigInt(col),
+            OwnedColumn::Int128(col) => Column::Int128(col),
+            OwnedColumn::Decimal75(precision, scale, col) => {
+                Column::Decimal75(*precision, *scale, col)
+            }
+            OwnedColumn::Scalar(col) => Column::Scalar(col),
+            OwnedColumn::VarChar(col) => {
+                let col: &mut [&str] = alloc.alloc_slice_fill_iter(col.iter().map(|s| s.as_str()));
+                let scals: &mut [_] =
+                    alloc.alloc_slice_fill_iter(col.iter().map(|s| varchar_hash.hash_to_scalar(s)));
+                Column::VarChar((col, scals))
+            }
+            OwnedColumn::Binary(length, col) => {
+                let col: &mut [&[u8]] =
+                    alloc.alloc_slice_fill_iter(col.iter().map(|bytes| bytes.as_slice()));
+                let scals: &mut [_] = alloc.alloc_slice_fill_iter(
+                    col.iter()
+                        .map(|bytes| varchar_hash.hash_bytes_to_scalar(bytes)),
+                );
+                Column::Binary(*length, (col, scals))
+            }
+        }
+    }
+
+    /// Generate a column of the given type and length where every entry is the default (zero) value
+    ///
+    /// The default of a `Binary` column is all zero bytes, which are hashed with `varchar_hash`
+    /// like any other bytes. The default of a `VarChar` column is the empty string, which always hashes to zero.
+    pub(crate) fn default_with_length(
+        column_type: ColumnType,
+        length: usize,
+        varchar_hash: VarCharHash,
+        alloc: &'a Bump,
+    ) -> Self {
+        match column_type {
+            ColumnType::Boolean => Column::Boolean(alloc.alloc_slice_fill_copy(length, false)),
+            ColumnType::SmallInt => Column::SmallInt(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::Int => Column::Int(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::BigInt => Column::BigInt(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::Int128 => Column::Int128(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::Scalar => Column::Scalar(alloc.alloc_slice_fill_copy(length, S::zero())),
+            ColumnType::Decimal75(precision, scale) => Column::Decimal75(
+                precision,
+                scale,
+                alloc.alloc_slice_fill_copy(length, S::zero()),
+            ),
+            ColumnType::VarChar => Column::VarChar((
+                alloc.alloc_slice_fill_copy(length, ""),
+                alloc.alloc_slice_fill_copy(length, S::zero()),
+            )),
+            ColumnType::Binary(byte_length) => {
+                let zeros: &[u8] = alloc.alloc_slice_fill_copy(byte_length, 0);
+                Column::Binary(
+                    byte_length,
+                    (
+                        alloc.alloc_slice_fill_copy(length, zeros),
+                        alloc.alloc_slice_fill_copy(
+                            length,
+                            varchar_hash.hash_bytes_to_scalar(zeros),
+                        ),
+                    ),
+                )
+            }
         }
     }
 
@@ -128,7 +224,7 @@ impl<'a, S: Scalar> Column<'a, S> {
                 .par_iter()
                 .map(|s| *s * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::VarChar((_, scals)) => scals
+            Self::VarChar((_, scals)) | Self::Binary(_, (_, scals)) => scals
                 .par_iter()
                 .map(|s| *s * scale_factor)
                 .collect::<Vec<_>>(),
@@ -188,6 +284,9 @@ pub enum ColumnType {
     /// Mapped to String
     #[serde(alias = "VARCHAR", alias = "varchar")]
     VarChar,
+    /// Mapped to byte arrays of the given length
+    #[serde(alias = "BINARY", alias = "binary")]
+    Binary(usize),
     /// Mapped to Curve25519Scalar
     #[serde(alias = "SCALAR", alias = "scalar")]
     Scalar,
@@ -236,7 +335,7 @@ impl ColumnType {
     pub fn scale(&self) -> Option<i8> {
         match self {
             Self::Decimal75(_, scale) => Some(*scale),
-            Self::BigInt | Self::Int128 | Self::Scalar => Some(0),
+            Self::SmallInt | Self::Int | Self::BigInt | Self::Int128 | Self::Scalar => Some(0),
             _ => None,
         }
     }
@@ -255,6 +354,9 @@ impl From<&ColumnType> for DataType {
                 DataType::Decimal256(precision.value(), *scale)
             }
             ColumnType::VarChar => DataType::Utf8,
+            ColumnType::Binary(length) => {
+                DataType::FixedSizeBinary(i32::try_from(*length).expect("binary length overflow"))
+            }
             ColumnType::Scalar => unimplemented!("Cannot convert Scalar type to arrow type"),
         }
     }
@@ -275,12 +377,18 @@ impl TryFrom<DataType> for ColumnType {
                 Ok(ColumnType::Decimal75(Precision::new(precision)?, scale))
             }
             DataType::Utf8 => Ok(ColumnType::VarChar),
+            DataType::FixedSizeBinary(length) if length >= 0 => {
+                Ok(ColumnType::Binary(length as usize))
+            }
             _ => Err(format!("Unsupported arrow data type {:?}", data_type)),
         }
     }
 }
 
 /// Display the column type as a str name (in all caps)
+///
+/// Decimals include their precision and scale, as in `DECIMAL75(12,2)`, and binaries their length, as in `BINARY(20)`.
+/// The output can be parsed back with [`str::parse`].
 impl std::fmt::Display for ColumnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -290,18 +398,76 @@ impl std::fmt::Display for ColumnType {
             ColumnType::BigInt => write!(f, "BIGINT"),
             ColumnType::Int128 => write!(f, "DECIMAL"),
             ColumnType::Decimal75(precision, scale) => {
-                write!(
-                    f,
-                    "DECIMAL75(PRECISION: {:?}, SCALE: {scale})",
-                    precision.value()
-                )
+                write!(f, "DECIMAL75({},{scale})", precision.value())
             }
             ColumnType::VarChar => write!(f, "VARCHAR"),
+            ColumnType::Binary(length) => write!(f, "BINARY({length})"),
             ColumnType::Scalar => write!(f, "SCALAR"),
         }
     }
 }
 
+/// Errors from parsing a [`ColumnType`] from a string
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ColumnTypeParseError {
+    /// The string does not name a column type.
+    #[error("unknown column type {0}")]
+    UnknownColumnType(String),
+    /// The precision or scale of a decimal type is missing or out of range.
+    #[error("invalid decimal precision or scale in {0}")]
+    InvalidDecimalParameters(String),
+    /// The length of a binary type is missing or out of range.
+    #[error("invalid binary length in {0}")]
+    InvalidBinaryLength(String),
+}
+
+/// Parse a column type from the names written by its [`Display`](std::fmt::Display) implementation
+///
+/// Names are case insensitive and may be surrounded by whitespace, e.g. `bigint`, `Decimal75(12, 2)` or `binary(20)`.
+impl FromStr for ColumnType {
+    type Err = ColumnTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_uppercase();
+        match name.as_str() {
+            "BOOLEAN" => return Ok(ColumnType::Boolean),
+            "SMALLINT" => return Ok(ColumnType::SmallInt),
+            "INT" => return Ok(ColumnType::Int),
+            "BIGINT" => return Ok(ColumnType::BigInt),
+            "DECIMAL" => return Ok(ColumnType::Int128),
+            "VARCHAR" => return Ok(ColumnType::VarChar),
+            "SCALAR" => return Ok(ColumnType::Scalar),
+            _ => {}
+        }
+        if let Some(length) = name
+            .strip_prefix("BINARY")
+            .and_then(|rest| rest.trim_start().strip_prefix('('))
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return length
+                .trim()
+                .parse()
+                .map(ColumnType::Binary)
+                .map_err(|_| ColumnTypeParseError::InvalidBinaryLength(s.to_string()));
+        }
+        let parameters = name
+            .strip_prefix("DECIMAL75")
+            .and_then(|rest| rest.trim_start().strip_prefix('('))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| ColumnTypeParseError::UnknownColumnType(s.to_string()))?;
+        let invalid_parameters = || ColumnTypeParseError::InvalidDecimalParameters(s.to_string());
+        let (precision, scale) = parameters.split_once(',').ok_or_else(invalid_parameters)?;
+        let precision = precision
+            .trim()
+            .parse()
+            .ok()
+            .and_then(|precision| Precision::new(precision).ok())
+            .ok_or_else(invalid_parameters)?;
+        let scale = scale.trim().parse().map_err(|_| invalid_parameters())?;
+        Ok(ColumnType::Decimal75(precision, scale))
+    }
+}
+
 /// Reference of a SQL column
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Copy, Serialize, Deserialize)]
 pub struct ColumnRef {
@@ -379,6 +545,84 @@ mod tests {
     use super::*;
     use crate::{base::scalar::Curve25519Scalar, proof_primitive::dory::DoryScalar};
 
+    #[test]
+    fn we_can_display_and_parse_column_types() {
+        assert_eq!(ColumnType::BigInt.to_string(), "BIGINT");
+        assert_eq!(
+            ColumnType::Decimal75(Precision::new(12).unwrap(), 2).to_string(),
+            "DECIMAL75(12,2)"
+        );
+        assert_eq!("bigint".parse(), Ok(ColumnType::BigInt));
+        assert_eq!(" Decimal ".parse(), Ok(ColumnType::Int128));
+        assert_eq!(ColumnType::Binary(20).to_string(), "BINARY(20)");
+        assert_eq!("binary( 32 )".parse(), Ok(ColumnType::Binary(32)));
+        assert_eq!(
+            "decimal75( 12 , -2 )".parse(),
+            Ok(ColumnType::Decimal75(Precision::new(12).unwrap(), -2))
+        );
+    }
+
+    #[test]
+    fn column_types_round_trip_through_display_and_parse() {
+        let decimals = (1..=75).flat_map(|precision| {
+            (i8::MIN..=i8::MAX)
+                .map(move |scale| ColumnType::Decimal75(Precision::new(precision).unwrap(), scale))
+        });
+        let column_types = [
+            ColumnType::Boolean,
+            ColumnType::SmallInt,
+            ColumnType::Int,
+            ColumnType::BigInt,
+            ColumnType::Int128,
+            ColumnType::VarChar,
+            ColumnType::Binary(20),
+            ColumnType::Scalar,
+        ]
+        .into_iter()
+        .chain(decimals);
+        for column_type in column_types {
+            assert_eq!(column_type.to_string().parse(), Ok(column_type));
+        }
+    }
+
+    #[test]
+    fn we_cannot_parse_invalid_column_types() {
+        for s in [
+            "",
+            "TEXT",
+            "BIGINT(1)",
+            "DECIMAL75",
+            "DECIMAL75(12,2",
+            "BINARY",
+        ] {
+            assert_eq!(
+                s.parse::<ColumnType>(),
+                Err(ColumnTypeParseError::UnknownColumnType(s.to_string()))
+            );
+        }
+        for s in [
+            "DECIMAL75()",
+            "DECIMAL75(12)",
+            "DECIMAL75(0,0)",
+            "DECIMAL75(76,0)",
+            "DECIMAL75(12,128)",
+            "DECIMAL75(12,2,1)",
+        ] {
+            assert_eq!(
+                s.parse::<ColumnType>(),
+                Err(ColumnTypeParseError::InvalidDecimalParameters(
+                    s.to_string()
+                ))
+            );
+        }
+        for s in ["BINARY()", "BINARY(-1)", "BINARY(20,2)"] {
+            assert_eq!(
+                s.parse::<ColumnType>(),
+                Err(ColumnTypeParseError::InvalidBinaryLength(s.to_string()))
+            );
+        }
+    }
+
     #[test]
     fn column_type_serializes_to_string() {
         let column_type = ColumnType::Boolean;
@@ -722,4 +966,60 @@ mod tests {
         assert_eq!(column.len(), 0);
         assert!(column.is_empty());
     }
+
+    #[test]
+    fn we_can_create_a_default_column_of_any_type() {
+        let alloc = Bump::new();
+        let precision = Precision::new(10).unwrap();
+        for column_type in [
+            ColumnType::Boolean,
+            ColumnType::SmallInt,
+            ColumnType::Int,
+            ColumnType::BigInt,
+            ColumnType::Int128,
+            ColumnType::VarChar,
+            ColumnType::Binary(20),
+            ColumnType::Scalar,
+            ColumnType::Decimal75(precision, 2),
+        ] {
+            let column = Column::<Curve25519Scalar>::default_with_length(
+                column_type,
+                3,
+                VarCharHash::default(),
+                &alloc,
+            );
+            assert_eq!(column.column_type(), column_type);
+            assert_eq!(column.len(), 3);
+        }
+        let column = Column::<Curve25519Scalar>::default_with_length(
+            ColumnType::VarChar,
+            2,
+            VarCharHash::default(),
+            &alloc,
+        );
+        assert_eq!(
+            column,
+            Column::VarChar((&["", ""], &[Curve25519Scalar::ZERO; 2]))
+        );
+    }
+
+    #[test]
+    fn the_default_of_a_binary_column_is_hashed_like_its_bytes() {
+        let alloc = Bump::new();
+        let zeros: &[u8] = &[0; 4];
+        for varchar_hash in [VarCharHash::Blake3, VarCharHash::DomainSeparatedBlake3] {
+            let column = Column::<Curve25519Scalar>::default_with_length(
+                ColumnType::Binary(4),
+                2,
+                varchar_hash,
+                &alloc,
+            );
+            let hash: Curve25519Scalar = varchar_hash.hash_bytes_to_scalar(zeros);
+            assert_eq!(column, Column::Binary(4, (&[zeros; 2], &[hash; 2])));
+        }
+        assert_ne!(
+            VarCharHash::Blake3.hash_bytes_to_scalar::<Curve25519Scalar>(zeros),
+            VarCharHash::DomainSeparatedBlake3.hash_bytes_to_scalar(zeros)
+        );
+    }
 }
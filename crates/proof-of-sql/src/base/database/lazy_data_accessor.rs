@@ -0,0 +1,135 @@
+use super::{
+    Column, ColumnRef, ColumnType, DataAccessor, MetadataAccessor, OwnedColumn, SchemaAccessor,
+    TableRef,
+};
+use crate::base::scalar::{Scalar, VarCharHash};
+use bumpalo::Bump;
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+use std::cell::OnceCell;
+
+/// A [`DataAccessor`] that fetches each column from a backing store the first time it is read.
+///
+/// Only the schemas, lengths and offsets of the tables are held up front. A proof only reads
+/// the columns that its query references, so a query over a few columns of a wide table
+/// only materializes those columns. A fetched column is kept until the accessor is dropped,
+/// so no column is fetched more than once.
+pub struct LazyDataAccessor<S: Scalar, F> {
+    tables: IndexMap<TableRef, LazyTable<S>>,
+    fetch: F,
+    alloc: Bump,
+    varchar_hash: VarCharHash,
+}
+
+struct LazyTable<S: Scalar> {
+    length: usize,
+    offset: usize,
+    columns: IndexMap<Identifier, (ColumnType, OnceCell<OwnedColumn<S>>)>,
+}
+
+impl<S: Scalar, F: Fn(ColumnRef) -> OwnedColumn<S>> LazyDataAccessor<S, F> {
+    /// Create an accessor without tables that fetches the data of a column with `fetch`.
+    ///
+    /// `fetch` must return the span of the column that the accessor was given when its table
+    /// was added, with the declared type and length.
+    pub fn new(fetch: F) -> Self {
+        Self {
+            tables: IndexMap::new(),
+            fetch,
+            alloc: Bump::new(),
+            varchar_hash: VarCharHash::default(),
+        }
+    }
+
+    /// Create an accessor without tables that fetches the data of a column with `fetch`,
+    /// and hashes the strings of `VarChar` columns with `varchar_hash`.
+    pub fn new_with_varchar_hash(fetch: F, varchar_hash: VarCharHash) -> Self {
+        Self {
+            varchar_hash,
+            ..Self::new(fetch)
+        }
+    }
+
+    /// Add a table with the given columns, in order, whose span has `length` rows starting at `offset`.
+    ///
+    /// None of the data of the table is fetched.
+    pub fn add_table(
+        &mut self,
+        table_ref: TableRef,
+        schema: impl IntoIterator<Item = (Identifier, ColumnType)>,
+        length: usize,
+        offset: usize,
+    ) {
+        let columns = schema
+            .into_iter()
+            .map(|(column_id, column_type)| (column_id, (column_type, OnceCell::new())))
+            .collect();
+        self.tables.insert(
+            table_ref,
+            LazyTable {
+                length,
+                offset,
+                columns,
+            },
+        );
+    }
+
+    /// Return whether the data of the column has been fetched.
+    pub fn is_fetched(&self, column: ColumnRef) -> bool {
+        self.tables
+            .get(&column.table_ref())
+            .and_then(|table| table.columns.get(&column.column_id()))
+            .is_some_and(|(_, data)| data.get().is_some())
+    }
+}
+
+impl<S: Scalar, F: Fn(ColumnRef) -> OwnedColumn<S>> DataAccessor<S> for LazyDataAccessor<S, F> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        let table = self.tables.get(&column.table_ref()).unwrap();
+        let (column_type, data) = table.columns.get(&column.column_id()).unwrap();
+        let owned_column = data.get_or_init(|| {
+            let owned_column = (self.fetch)(column);
+            assert_eq!(
+                (owned_column.column_type(), owned_column.len()),
+                (*column_type, table.length),
+                "the fetched column must have the declared type and length"
+            );
+            owned_column
+        });
+        Column::from_owned_column_with_varchar_hash(owned_column, self.varchar_hash, &self.alloc)
+    }
+
+    fn column_type(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.lookup_column(table_ref, column_id)
+    }
+}
+
+impl<S: Scalar, F> MetadataAccessor for LazyDataAccessor<S, F> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.tables.get(&table_ref).unwrap().length
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.tables.get(&table_ref).unwrap().offset
+    }
+
+    fn varchar_hash(&self) -> VarCharHash {
+        self.varchar_hash
+    }
+}
+
+impl<S: Scalar, F> SchemaAccessor for LazyDataAccessor<S, F> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        Some(self.tables.get(&table_ref)?.columns.get(&column_id)?.0)
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.tables
+            .get(&table_ref)
+            .unwrap()
+            .columns
+            .iter()
+            .map(|(&column_id, (column_type, _))| (column_id, *column_type))
+            .collect()
+    }
+}
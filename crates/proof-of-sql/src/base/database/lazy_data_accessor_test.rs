@@ -0,0 +1,129 @@
+use super::{
+    owned_table_utility::*, ColumnRef, DataAccessor, LazyDataAccessor, OwnedColumn, OwnedTable,
+    OwnedTableTestAccessor, SchemaAccessor, TestAccessor,
+};
+use crate::{
+    proof_primitive::dory::{DoryEvaluationProof, DoryProverPublicSetup, DoryScalar},
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::Identifier;
+use std::cell::RefCell;
+
+fn wide_table() -> OwnedTable<DoryScalar> {
+    owned_table([
+        bigint("a", [1, 2, 3]),
+        varchar("b", ["x", "yy", "zzz"]),
+        bigint("c", [4, 5, 6]),
+        int("d", [7, 8, 9]),
+        boolean("e", [true, false, true]),
+        bigint("f", [10, 11, 12]),
+        int128("g", [13, 14, 15]),
+        varchar("h", ["p", "q", "r"]),
+        decimal75("i", 10, 2, [16, 17, 18]),
+        smallint("j", [19_i16, 20, 21]),
+    ])
+}
+
+fn lazy_accessor<'a>(
+    table: &'a OwnedTable<DoryScalar>,
+    fetched: &'a RefCell<Vec<Identifier>>,
+) -> LazyDataAccessor<DoryScalar, impl Fn(ColumnRef) -> OwnedColumn<DoryScalar> + 'a> {
+    let mut accessor = LazyDataAccessor::new(move |column: ColumnRef| {
+        fetched.borrow_mut().push(column.column_id());
+        table.inner_table()[&column.column_id()].clone()
+    });
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        table
+            .inner_table()
+            .iter()
+            .map(|(&id, column)| (id, column.column_type())),
+        table.num_rows(),
+        0,
+    );
+    accessor
+}
+
+#[test]
+fn we_only_fetch_the_columns_that_a_query_references() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut verifier_accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+    verifier_accessor.add_table("sxt.t".parse().unwrap(), wide_table(), 0);
+
+    let table = wide_table();
+    let fetched = RefCell::new(Vec::new());
+    let accessor = lazy_accessor(&table, &fetched);
+    let query = QueryExpr::try_new(
+        "SELECT a, b FROM t WHERE a >= 2".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(fetched.borrow().is_empty());
+
+    let res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap()
+            .verify(query.proof_expr(), &verifier_accessor, &(&setup).into())
+            .unwrap()
+            .table;
+    assert_eq!(
+        res,
+        owned_table([bigint("a", [2, 3]), varchar("b", ["yy", "zzz"])])
+    );
+    let mut fetched = fetched.take();
+    fetched.sort();
+    assert_eq!(
+        fetched,
+        [
+            Identifier::try_new("a").unwrap(),
+            Identifier::try_new("b").unwrap()
+        ]
+    );
+}
+
+#[test]
+fn the_schema_of_a_lazy_table_is_known_without_fetching_it() {
+    let table = wide_table();
+    let fetched = RefCell::new(Vec::new());
+    let accessor = lazy_accessor(&table, &fetched);
+    let t = "sxt.t".parse().unwrap();
+    let schema = accessor.lookup_schema(t);
+    assert_eq!(schema.len(), 10);
+    assert_eq!(
+        accessor.column_type(t, "i".parse().unwrap()),
+        Some(table.inner_table()[&Identifier::try_new("i").unwrap()].column_type())
+    );
+    assert_eq!(accessor.column_type(t, "k".parse().unwrap()), None);
+    assert!(fetched.borrow().is_empty());
+
+    let column = ColumnRef::new(t, schema[3].0, schema[3].1);
+    assert!(!accessor.is_fetched(column));
+    accessor.get_column(column);
+    accessor.get_column(column);
+    assert!(accessor.is_fetched(column));
+    assert_eq!(*fetched.borrow(), [schema[3].0]);
+}
+
+#[test]
+fn the_row_count_of_a_table_is_the_length_of_its_registered_span() {
+    let table = wide_table();
+    let fetched = RefCell::new(Vec::new());
+    let accessor = lazy_accessor(&table, &fetched);
+    assert_eq!(accessor.row_count("sxt.t".parse().unwrap()), 3);
+    assert!(fetched.borrow().is_empty());
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    let t = "sxt.t".parse().unwrap();
+    let empty = "sxt.empty".parse().unwrap();
+    accessor.add_table(t, wide_table(), 5);
+    accessor.add_table(empty, owned_table([bigint("a", [0; 0])]), 2);
+    assert_eq!(accessor.row_count(t), 3);
+    assert_eq!(accessor.row_count(empty), 0);
+    accessor.update_offset(t, 0);
+    assert_eq!(accessor.row_count(t), 3);
+}
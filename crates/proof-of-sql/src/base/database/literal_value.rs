@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 /// Note: The types here should correspond to native SQL database types.
 /// See `<https://ignite.apache.org/docs/latest/sql-reference/data-types>` for
 /// a description of the native types used by Apache Ignite.
-#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Hash, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum LiteralValue<S: Scalar> {
     /// Boolean literals
@@ -22,6 +22,10 @@ pub enum LiteralValue<S: Scalar> {
     ///  - the first element maps to the str value.
     ///  - the second element maps to the str hash (see [crate::base::scalar::Scalar]).
     VarChar((String, S)),
+    /// Fixed-length binary literals
+    ///  - the first element maps to the bytes.
+    ///  - the second element maps to the hash of the bytes, as for `VarChar`.
+    Binary((Vec<u8>, S)),
     /// i128 literals
     Int128(i128),
     /// Decimal literals with a max width of 252 bits
@@ -40,6 +44,7 @@ impl<S: Scalar> LiteralValue<S> {
             Self::Int(_) => ColumnType::Int,
             Self::BigInt(_) => ColumnType::BigInt,
             Self::VarChar(_) => ColumnType::VarChar,
+            Self::Binary((bytes, _)) => ColumnType::Binary(bytes.len()),
             Self::Int128(_) => ColumnType::Int128,
             Self::Scalar(_) => ColumnType::Scalar,
             Self::Decimal75(precision, scale, _) => ColumnType::Decimal75(*precision, *scale),
@@ -53,7 +58,7 @@ impl<S: Scalar> LiteralValue<S> {
             Self::SmallInt(i) => i.into(),
             Self::Int(i) => i.into(),
             Self::BigInt(i) => i.into(),
-            Self::VarChar((_, s)) => *s,
+            Self::VarChar((_, s)) | Self::Binary((_, s)) => *s,
             Self::Int128(i) => i.into(),
             Self::Decimal75(_, _, s) => *s,
             Self::Scalar(scalar) => *scalar,
@@ -5,7 +5,7 @@ mod accessor;
 pub use accessor::{CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAccessor};
 
 mod column;
-pub use column::{Column, ColumnField, ColumnRef, ColumnType};
+pub use column::{Column, ColumnField, ColumnRef, ColumnType, ColumnTypeParseError};
 pub(crate) use column::{INT128_PRECISION, INT128_SCALE};
 
 mod literal_value;
@@ -35,20 +35,23 @@ pub use record_batch_test_accessor::RecordBatchTestAccessor;
 #[cfg(all(test, feature = "blitzar"))]
 mod record_batch_test_accessor_test;
 
-#[cfg(any(test, feature = "test"))]
-mod test_accessor_utility;
-#[cfg(any(test, feature = "test"))]
-pub use test_accessor_utility::{make_random_test_accessor_data, RandomTestAccessorDescriptor};
-
 mod owned_column;
-pub use owned_column::OwnedColumn;
+pub use owned_column::{OwnedColumn, OwnedColumnValue};
 mod owned_table;
-pub use owned_table::OwnedTable;
-pub(crate) use owned_table::OwnedTableError;
+pub use owned_table::{OwnedTable, OwnedTableError, OwnedTableRows, OWNED_TABLE_CHECKSUM_CONTEXT};
 #[cfg(test)]
 mod owned_table_test;
+mod schema_validation;
+pub use schema_validation::{validate_against_schema, SchemaError};
+#[cfg(test)]
+mod schema_validation_test;
 pub mod owned_table_utility;
 
+mod lazy_data_accessor;
+pub use lazy_data_accessor::LazyDataAccessor;
+#[cfg(test)]
+mod lazy_data_accessor_test;
+
 mod owned_and_arrow_conversions;
 pub use owned_and_arrow_conversions::OwnedArrowConversionError;
 #[cfg(test)]
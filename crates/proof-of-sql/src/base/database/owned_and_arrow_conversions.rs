@@ -5,8 +5,13 @@
 //! Boolean <-> Boolean
 //! BigInt <-> Int64
 //! VarChar <-> Utf8/String
+//! Binary(n) <-> FixedSizeBinary(n)
 //! Int128 <-> Decimal128(38,0)
 //! Decimal75 <-> S
+//! Decimal75 <- Decimal128(p,s), except for Decimal128(38,0)
+//!
+//! `OwnedTable::try_from_record_batch_with_defaults` fills nulls and absent columns with
+//! per-column defaults, since owned types have no nulls.
 //!
 //! Note: this converts `Int128` values to `Decimal128(38,0)`, which are backed by `i128`.
 //! This is because there is no `Int128` type in Arrow.
@@ -15,16 +20,16 @@
 use super::scalar_and_i256_conversions::convert_scalar_to_i256;
 use crate::base::{
     database::{
-        scalar_and_i256_conversions::convert_i256_to_scalar, OwnedColumn, OwnedTable,
-        OwnedTableError,
+        scalar_and_i256_conversions::convert_i256_to_scalar, ColumnType, LiteralValue, OwnedColumn,
+        OwnedTable, OwnedTableError,
     },
     math::decimal::Precision,
     scalar::Scalar,
 };
 use arrow::{
     array::{
-        ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, StringArray,
+        ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, FixedSizeBinaryArray, Int16Array,
+        Int32Array, Int64Array, StringArray,
     },
     datatypes::{i256, DataType, Schema, SchemaRef},
     error::ArrowError,
@@ -54,6 +59,9 @@ pub enum OwnedArrowConversionError {
     /// This error occurs when trying to convert from an Arrow array with nulls.
     #[error("null values are not supported in OwnedColumn yet")]
     NullNotSupportedYet,
+    /// This error occurs when a default value does not have the type of the column it fills.
+    #[error("a default of type {1} cannot fill a column of arrow type {0}")]
+    DefaultTypeMismatch(DataType, ColumnType),
 }
 
 impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
@@ -79,6 +87,13 @@ impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
             }
             OwnedColumn::Scalar(_) => unimplemented!("Cannot convert Scalar type to arrow type"),
             OwnedColumn::VarChar(col) => Arc::new(StringArray::from(col)),
+            OwnedColumn::Binary(length, col) => Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    col.into_iter().map(Some),
+                    i32::try_from(length).expect("binary length overflow"),
+                )
+                .unwrap(),
+            ),
         }
     }
 }
@@ -99,6 +114,46 @@ impl<S: Scalar> TryFrom<OwnedTable<S>> for RecordBatch {
     }
 }
 
+impl<S: Scalar> OwnedTable<S> {
+    /// Convert the table to an Arrow `RecordBatch`, such as to pass a verified result on to Arrow or DataFusion.
+    ///
+    /// This is the same as converting with `RecordBatch::try_from`, except that a `Decimal75` column
+    /// is a `Decimal128` column when its precision and values fit in one. A precision of 38 with a scale
+    /// of 0 stays a `Decimal256`, since `Decimal128(38,0)` is how an `Int128` column is converted.
+    ///
+    /// # Panics
+    /// Panics if the table has a `Scalar` column, which has no Arrow type.
+    pub fn to_record_batch(&self) -> RecordBatch {
+        if self.is_empty() {
+            return RecordBatch::new_empty(SchemaRef::new(Schema::empty()));
+        }
+        RecordBatch::try_from_iter(self.inner_table().iter().map(|(identifier, column)| {
+            let array = match column {
+                OwnedColumn::Decimal75(precision, scale, col)
+                    if precision.value() <= 38 && (precision.value(), *scale) != (38, 0) =>
+                {
+                    col.iter()
+                        .map(|value| convert_scalar_to_i256(value).to_i128())
+                        .collect::<Option<Vec<_>>>()
+                        .map(|values| -> ArrayRef {
+                            Arc::new(
+                                Decimal128Array::from(values)
+                                    .with_precision_and_scale(precision.value(), *scale)
+                                    .unwrap(),
+                            )
+                        })
+                }
+                _ => None,
+            };
+            (
+                identifier,
+                array.unwrap_or_else(|| ArrayRef::from(column.clone())),
+            )
+        }))
+        .expect("the columns of a table have the same length")
+    }
+}
+
 impl<S: Scalar> TryFrom<ArrayRef> for OwnedColumn<S> {
     type Error = OwnedArrowConversionError;
     fn try_from(value: ArrayRef) -> Result<Self, Self::Error> {
@@ -152,6 +207,18 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .values()
                     .to_vec(),
             )),
+            DataType::Decimal128(precision, scale) => Ok(Self::Decimal75(
+                Precision::new(*precision).expect("precision is less than 39"),
+                *scale,
+                value
+                    .as_any()
+                    .downcast_ref::<Decimal128Array>()
+                    .unwrap()
+                    .values()
+                    .iter()
+                    .map(|&value| S::from(value))
+                    .collect(),
+            )),
             DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(Self::Decimal75(
                 Precision::new(*precision).expect("precision is less than 76"),
                 *scale,
@@ -174,6 +241,17 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .map(|s| s.unwrap().to_string())
                     .collect(),
             )),
+            DataType::FixedSizeBinary(length) if *length >= 0 => Ok(Self::Binary(
+                *length as usize,
+                value
+                    .as_any()
+                    .downcast_ref::<FixedSizeBinaryArray>()
+                    .unwrap()
+                    .iter()
+                    .map(|bytes| bytes.map(<[u8]>::to_vec))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(OwnedArrowConversionError::NullNotSupportedYet)?,
+            )),
             &data_type => Err(OwnedArrowConversionError::UnsupportedType(
                 data_type.clone(),
             )),
@@ -192,7 +270,9 @@ impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
             .zip(value.columns())
             .map(|(field, array_ref)| {
                 let owned_column = OwnedColumn::try_from(array_ref)?;
-                let identifier = Identifier::try_new(field.name())?; //This may always succeed.
+                // A field name that is not an identifier as it is, such as a keyword, is the name of a quoted identifier.
+                let identifier = Identifier::try_new(field.name())
+                    .or_else(|_| Identifier::try_new_quoted(field.name()))?;
                 Ok((identifier, owned_column))
             })
             .collect();
@@ -204,3 +284,197 @@ impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
         }
     }
 }
+
+/// Replaces the nulls of `array` with `default`.
+fn fill_nulls_with_default<S: Scalar>(
+    array: &ArrayRef,
+    default: &LiteralValue<S>,
+) -> Result<ArrayRef, OwnedArrowConversionError> {
+    if array.null_count() == 0 {
+        return Ok(array.clone());
+    }
+    let mismatch = || {
+        OwnedArrowConversionError::DefaultTypeMismatch(
+            array.data_type().clone(),
+            default.column_type(),
+        )
+    };
+    Ok(match (array.data_type(), default) {
+        (DataType::Boolean, LiteralValue::Boolean(default)) => Arc::new(BooleanArray::from(
+            array
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .unwrap()
+                .iter()
+                .map(|value| value.unwrap_or(*default))
+                .collect::<Vec<_>>(),
+        )),
+        (DataType::Int16, LiteralValue::SmallInt(default)) => Arc::new(Int16Array::from(
+            array
+                .as_any()
+                .downcast_ref::<Int16Array>()
+                .unwrap()
+                .iter()
+                .map(|value| value.unwrap_or(*default))
+                .collect::<Vec<_>>(),
+        )),
+        (DataType::Int32, LiteralValue::Int(default)) => Arc::new(Int32Array::from(
+            array
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+                .iter()
+                .map(|value| value.unwrap_or(*default))
+                .collect::<Vec<_>>(),
+        )),
+        (DataType::Int64, LiteralValue::BigInt(default)) => Arc::new(Int64Array::from(
+            array
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .iter()
+                .map(|value| value.unwrap_or(*default))
+                .collect::<Vec<_>>(),
+        )),
+        (DataType::Decimal128(38, 0), LiteralValue::Int128(default)) => Arc::new(
+            Decimal128Array::from(
+                array
+                    .as_any()
+                    .downcast_ref::<Decimal128Array>()
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.unwrap_or(*default))
+                    .collect::<Vec<_>>(),
+            )
+            .with_precision_and_scale(38, 0)
+            .unwrap(),
+        ),
+        (
+            DataType::Decimal128(precision, scale),
+            LiteralValue::Decimal75(default_precision, default_scale, default),
+        ) if *precision == default_precision.value() && scale == default_scale => {
+            let default = convert_scalar_to_i256(default)
+                .to_i128()
+                .ok_or_else(mismatch)?;
+            Arc::new(
+                Decimal128Array::from(
+                    array
+                        .as_any()
+                        .downcast_ref::<Decimal128Array>()
+                        .unwrap()
+                        .iter()
+                        .map(|value| value.unwrap_or(default))
+                        .collect::<Vec<_>>(),
+                )
+                .with_precision_and_scale(*precision, *scale)
+                .unwrap(),
+            )
+        }
+        (
+            DataType::Decimal256(precision, scale),
+            LiteralValue::Decimal75(default_precision, default_scale, default),
+        ) if *precision == default_precision.value() && scale == default_scale => {
+            let default = convert_scalar_to_i256(default);
+            Arc::new(
+                Decimal256Array::from(
+                    array
+                        .as_any()
+                        .downcast_ref::<Decimal256Array>()
+                        .unwrap()
+                        .iter()
+                        .map(|value| value.unwrap_or(default))
+                        .collect::<Vec<_>>(),
+                )
+                .with_precision_and_scale(*precision, *scale)
+                .unwrap(),
+            )
+        }
+        (DataType::Utf8, LiteralValue::VarChar((default, _))) => Arc::new(StringArray::from(
+            array
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .iter()
+                .map(|value| value.unwrap_or(default.as_str()))
+                .collect::<Vec<_>>(),
+        )),
+        (DataType::FixedSizeBinary(length), LiteralValue::Binary((default, _)))
+            if usize::try_from(*length) == Ok(default.len()) =>
+        {
+            Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    array
+                        .as_any()
+                        .downcast_ref::<FixedSizeBinaryArray>()
+                        .unwrap()
+                        .iter()
+                        .map(|value| Some(value.unwrap_or(default.as_slice()))),
+                    *length,
+                )
+                .unwrap(),
+            )
+        }
+        _ => return Err(mismatch()),
+    })
+}
+
+/// Creates a column of `len` copies of `default`.
+fn repeat_default<S: Scalar>(default: &LiteralValue<S>, len: usize) -> OwnedColumn<S> {
+    match default {
+        LiteralValue::Boolean(value) => OwnedColumn::Boolean(vec![*value; len]),
+        LiteralValue::SmallInt(value) => OwnedColumn::SmallInt(vec![*value; len]),
+        LiteralValue::Int(value) => OwnedColumn::Int(vec![*value; len]),
+        LiteralValue::BigInt(value) => OwnedColumn::BigInt(vec![*value; len]),
+        LiteralValue::VarChar((value, _)) => OwnedColumn::VarChar(vec![value.clone(); len]),
+        LiteralValue::Binary((value, _)) => {
+            OwnedColumn::Binary(value.len(), vec![value.clone(); len])
+        }
+        LiteralValue::Int128(value) => OwnedColumn::Int128(vec![*value; len]),
+        LiteralValue::Decimal75(precision, scale, value) => {
+            OwnedColumn::Decimal75(*precision, *scale, vec![*value; len])
+        }
+        LiteralValue::Scalar(value) => OwnedColumn::Scalar(vec![*value; len]),
+    }
+}
+
+impl<S: Scalar> OwnedTable<S> {
+    /// Convert an Arrow `RecordBatch` from a sparse source to a table, filling the gaps with per-column defaults.
+    ///
+    /// A null in a column with a default becomes that default, and a column that has a default but is absent
+    /// from the batch is filled with it entirely. Absent columns follow the batch's columns, in the order of `defaults`.
+    /// Since owned columns have no nulls, a filled value is indistinguishable from an ingested one.
+    ///
+    /// # Errors
+    /// Besides the errors of `OwnedTable::try_from`, this fails with
+    /// `OwnedArrowConversionError::DefaultTypeMismatch` if a default does not have the type of its column,
+    /// and with `OwnedArrowConversionError::NullNotSupportedYet` if a column without a default has nulls.
+    pub fn try_from_record_batch_with_defaults(
+        batch: RecordBatch,
+        defaults: &IndexMap<Identifier, LiteralValue<S>>,
+    ) -> Result<Self, OwnedArrowConversionError> {
+        let mut table = IndexMap::with_capacity(batch.num_columns() + defaults.len());
+        for (field, array_ref) in batch.schema().fields().iter().zip(batch.columns()) {
+            let identifier = Identifier::try_new(field.name())
+                .or_else(|_| Identifier::try_new_quoted(field.name()))?;
+            let filled = match defaults.get(&identifier) {
+                Some(default) => fill_nulls_with_default(array_ref, default)?,
+                None if array_ref.null_count() > 0 => {
+                    return Err(OwnedArrowConversionError::NullNotSupportedYet)
+                }
+                None => array_ref.clone(),
+            };
+            if table
+                .insert(identifier, OwnedColumn::try_from(&filled)?)
+                .is_some()
+            {
+                return Err(OwnedArrowConversionError::DuplicateIdentifiers);
+            }
+        }
+        for (identifier, default) in defaults {
+            if !table.contains_key(identifier) {
+                table.insert(*identifier, repeat_default(default, batch.num_rows()));
+            }
+        }
+        Ok(Self::try_new(table)?)
+    }
+}
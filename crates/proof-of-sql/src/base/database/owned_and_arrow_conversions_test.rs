@@ -1,14 +1,15 @@
-use super::{OwnedColumn, OwnedTable};
+use super::{ColumnType, OwnedColumn, OwnedTable};
 use crate::{
     base::{
-        database::{owned_table_utility::*, OwnedArrowConversionError},
+        database::{owned_table_utility::*, LiteralValue, OwnedArrowConversionError},
+        math::decimal::Precision,
         scalar::Curve25519Scalar,
     },
     record_batch,
 };
 use arrow::{
     array::{ArrayRef, BooleanArray, Decimal128Array, Float32Array, Int64Array, StringArray},
-    datatypes::Schema,
+    datatypes::{DataType, Schema},
     record_batch::RecordBatch,
 };
 use indexmap::IndexMap;
@@ -147,3 +148,139 @@ fn we_panic_when_converting_an_owned_table_with_a_scalar_column() {
     let owned_table = owned_table::<Curve25519Scalar>([scalar("a", [0; 0])]);
     let _ = RecordBatch::try_from(owned_table);
 }
+
+#[test]
+fn we_can_round_trip_a_mixed_owned_table_through_a_record_batch() {
+    let owned_table = owned_table::<Curve25519Scalar>([
+        boolean("boolean", [true, false, true]),
+        smallint("int16", [i16::MIN, 0, i16::MAX]),
+        int("int32", [i32::MIN, 0, i32::MAX]),
+        bigint("int64", [i64::MIN, 0, i64::MAX]),
+        int128("int128", [i128::MIN, 0, i128::MAX]),
+        varchar("string", ["a", "", "ccc"]),
+        decimal75("small_decimal", 10, 2, [-12345, 0, 99_999_999]),
+        decimal75("wide_decimal", 38, 0, [-1, 0, 1]),
+        decimal75("large_decimal", 75, 5, [-1, 0, i128::MAX]),
+    ]);
+    let record_batch = owned_table.to_record_batch();
+    let data_types = Vec::from_iter(
+        record_batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.data_type().clone()),
+    );
+    assert_eq!(
+        data_types,
+        [
+            DataType::Boolean,
+            DataType::Int16,
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Decimal128(38, 0),
+            DataType::Utf8,
+            DataType::Decimal128(10, 2),
+            DataType::Decimal256(38, 0),
+            DataType::Decimal256(75, 5),
+        ]
+    );
+    assert_eq!(OwnedTable::try_from(record_batch).unwrap(), owned_table);
+
+    let empty = OwnedTable::<Curve25519Scalar>::try_new(IndexMap::new()).unwrap();
+    assert_eq!(
+        empty.to_record_batch(),
+        RecordBatch::new_empty(Arc::new(Schema::empty()))
+    );
+}
+
+fn sparse_record_batch() -> RecordBatch {
+    RecordBatch::try_from_iter([
+        (
+            "a",
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+        ),
+        (
+            "b",
+            Arc::new(StringArray::from(vec![Some("x"), None, None])) as ArrayRef,
+        ),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn we_can_fill_nulls_and_absent_columns_with_defaults() {
+    let defaults = IndexMap::from_iter([
+        ("c".parse().unwrap(), LiteralValue::Boolean(true)),
+        (
+            "b".parse().unwrap(),
+            LiteralValue::VarChar(("none".to_string(), "none".into())),
+        ),
+        ("a".parse().unwrap(), LiteralValue::BigInt(-1)),
+    ]);
+    assert_eq!(
+        OwnedTable::<Curve25519Scalar>::try_from_record_batch_with_defaults(
+            sparse_record_batch(),
+            &defaults
+        )
+        .unwrap(),
+        owned_table([
+            bigint("a", [1, -1, 3]),
+            varchar("b", ["x", "none", "none"]),
+            boolean("c", [true, true, true]),
+        ])
+    );
+}
+
+#[test]
+fn we_can_fill_decimal_nulls_with_defaults() {
+    let record_batch = RecordBatch::try_from_iter([(
+        "d",
+        Arc::new(
+            Decimal128Array::from(vec![None, Some(125)])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+        ) as ArrayRef,
+    )])
+    .unwrap();
+    let defaults = IndexMap::from_iter([(
+        "d".parse().unwrap(),
+        LiteralValue::Decimal75(Precision::new(10).unwrap(), 2, 100.into()),
+    )]);
+    assert_eq!(
+        OwnedTable::<Curve25519Scalar>::try_from_record_batch_with_defaults(
+            record_batch,
+            &defaults
+        )
+        .unwrap(),
+        owned_table([decimal75("d", 10, 2, [100, 125])])
+    );
+}
+
+#[test]
+fn we_cannot_fill_nulls_without_a_default_or_with_a_mistyped_default() {
+    let defaults = IndexMap::from_iter([("a".parse().unwrap(), LiteralValue::BigInt(-1))]);
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::try_from_record_batch_with_defaults(
+            sparse_record_batch(),
+            &defaults
+        ),
+        Err(OwnedArrowConversionError::NullNotSupportedYet)
+    ));
+    let defaults = IndexMap::from_iter([
+        ("a".parse().unwrap(), LiteralValue::Int(-1)),
+        (
+            "b".parse().unwrap(),
+            LiteralValue::VarChar(("none".to_string(), "none".into())),
+        ),
+    ]);
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::try_from_record_batch_with_defaults(
+            sparse_record_batch(),
+            &defaults
+        ),
+        Err(OwnedArrowConversionError::DefaultTypeMismatch(
+            DataType::Int64,
+            ColumnType::Int
+        ))
+    ));
+}
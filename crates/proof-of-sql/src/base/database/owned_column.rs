@@ -3,7 +3,14 @@
 /// converting to the final result in either Arrow format or JSON.
 /// This is the analog of an arrow Array.
 use super::ColumnType;
-use crate::base::{math::decimal::Precision, scalar::Scalar};
+use crate::{
+    base::{
+        math::decimal::{check_decimal_precision, Decimal, DecimalRounding, Precision},
+        scalar::Scalar,
+    },
+    sql::parse::ConversionResult,
+};
+use proof_of_sql_parser::intermediate_decimal::IntermediateDecimal;
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[non_exhaustive]
 /// Supported types for OwnedColumn
@@ -18,6 +25,8 @@ pub enum OwnedColumn<S: Scalar> {
     BigInt(Vec<i64>),
     /// String columns
     VarChar(Vec<String>),
+    /// Fixed-length binary columns, with the length of every value in bytes
+    Binary(usize, Vec<Vec<u8>>),
     /// i128 columns
     Int128(Vec<i128>),
     /// Decimal columns
@@ -35,6 +44,7 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Int(col) => col.len(),
             OwnedColumn::BigInt(col) => col.len(),
             OwnedColumn::VarChar(col) => col.len(),
+            OwnedColumn::Binary(_, col) => col.len(),
             OwnedColumn::Int128(col) => col.len(),
             OwnedColumn::Decimal75(_, _, col) => col.len(),
             OwnedColumn::Scalar(col) => col.len(),
@@ -48,11 +58,157 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Int(col) => col.is_empty(),
             OwnedColumn::BigInt(col) => col.is_empty(),
             OwnedColumn::VarChar(col) => col.is_empty(),
+            OwnedColumn::Binary(_, col) => col.is_empty(),
             OwnedColumn::Int128(col) => col.is_empty(),
             OwnedColumn::Scalar(col) => col.is_empty(),
             OwnedColumn::Decimal75(_, _, col) => col.is_empty(),
         }
     }
+    /// Appends the values of `other` to the end of this column.
+    ///
+    /// Panics if the columns do not have the same type.
+    pub(crate) fn append(&mut self, other: &OwnedColumn<S>) {
+        match (self, other) {
+            (OwnedColumn::Boolean(col), OwnedColumn::Boolean(other)) => {
+                col.extend_from_slice(other)
+            }
+            (OwnedColumn::SmallInt(col), OwnedColumn::SmallInt(other)) => {
+                col.extend_from_slice(other)
+            }
+            (OwnedColumn::Int(col), OwnedColumn::Int(other)) => col.extend_from_slice(other),
+            (OwnedColumn::BigInt(col), OwnedColumn::BigInt(other)) => col.extend_from_slice(other),
+            (OwnedColumn::VarChar(col), OwnedColumn::VarChar(other)) => {
+                col.extend_from_slice(other)
+            }
+            (OwnedColumn::Binary(length, col), OwnedColumn::Binary(other_length, other))
+                if length == other_length =>
+            {
+                col.extend_from_slice(other)
+            }
+            (OwnedColumn::Int128(col), OwnedColumn::Int128(other)) => col.extend_from_slice(other),
+            (
+                OwnedColumn::Decimal75(precision, scale, col),
+                OwnedColumn::Decimal75(other_precision, other_scale, other),
+            ) if precision == other_precision && scale == other_scale => {
+                col.extend_from_slice(other)
+            }
+            (OwnedColumn::Scalar(col), OwnedColumn::Scalar(other)) => col.extend_from_slice(other),
+            _ => panic!("cannot append columns of different types"),
+        }
+    }
+    /// Creates a decimal column from unscaled values.
+    ///
+    /// Errors with [`ConversionError::DecimalValueExceedsPrecision`](crate::sql::parse::ConversionError::DecimalValueExceedsPrecision)
+    /// if a value has more digits than `precision`.
+    pub fn try_new_decimal75(
+        precision: Precision,
+        scale: i8,
+        values: Vec<S>,
+    ) -> ConversionResult<Self> {
+        for value in &values {
+            check_decimal_precision(value, precision)?;
+        }
+        Ok(OwnedColumn::Decimal75(precision, scale, values))
+    }
+    /// Creates a decimal column from floats, rounding each one to the nearest multiple of `10^-scale`.
+    ///
+    /// See [`Decimal::from_f64`] for details on the rounding and on when this errors.
+    ///
+    /// # Example
+    /// ```
+    /// use proof_of_sql::base::{
+    ///     database::OwnedColumn, math::decimal::Precision, scalar::Curve25519Scalar,
+    /// };
+    /// let column = OwnedColumn::<Curve25519Scalar>::try_decimal75_from_f64(
+    ///     Precision::new(10).unwrap(),
+    ///     2,
+    ///     [1.005, 2.5, -3.14],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     column,
+    ///     OwnedColumn::Decimal75(
+    ///         Precision::new(10).unwrap(),
+    ///         2,
+    ///         [100, 250, -314].map(Curve25519Scalar::from).to_vec()
+    ///     )
+    /// );
+    /// ```
+    pub fn try_decimal75_from_f64(
+        precision: Precision,
+        scale: i8,
+        data: impl IntoIterator<Item = f64>,
+    ) -> ConversionResult<Self> {
+        let values = data
+            .into_iter()
+            .map(|value| Ok(Decimal::from_f64(value, precision, scale)?.value))
+            .collect::<ConversionResult<_>>()?;
+        Ok(OwnedColumn::Decimal75(precision, scale, values))
+    }
+    /// Creates a decimal column from decimals, rounding those with more than `scale` fractional digits as `rounding` prescribes.
+    ///
+    /// See [`Decimal::try_from_intermediate_decimal`] for when this errors.
+    ///
+    /// # Example
+    /// ```
+    /// use proof_of_sql::base::{
+    ///     database::OwnedColumn,
+    ///     math::decimal::{DecimalRounding, Precision},
+    ///     scalar::Curve25519Scalar,
+    /// };
+    /// let ingest = |rounding| {
+    ///     OwnedColumn::<Curve25519Scalar>::try_decimal75_from_decimals(
+    ///         Precision::new(10).unwrap(),
+    ///         2,
+    ///         ["1.005", "2.5"].map(|value| value.parse().unwrap()),
+    ///         rounding,
+    ///     )
+    /// };
+    /// let decimal75 = |values: [i64; 2]| {
+    ///     OwnedColumn::Decimal75(
+    ///         Precision::new(10).unwrap(),
+    ///         2,
+    ///         values.map(Curve25519Scalar::from).to_vec(),
+    ///     )
+    /// };
+    /// assert_eq!(ingest(DecimalRounding::HalfUp).unwrap(), decimal75([101, 250]));
+    /// assert_eq!(ingest(DecimalRounding::HalfEven).unwrap(), decimal75([100, 250]));
+    /// assert_eq!(ingest(DecimalRounding::Truncate).unwrap(), decimal75([100, 250]));
+    /// assert!(ingest(DecimalRounding::Error).is_err());
+    /// ```
+    pub fn try_decimal75_from_decimals(
+        precision: Precision,
+        scale: i8,
+        data: impl IntoIterator<Item = IntermediateDecimal>,
+        rounding: DecimalRounding,
+    ) -> ConversionResult<Self> {
+        let values = data
+            .into_iter()
+            .map(|value| {
+                Ok(
+                    Decimal::try_from_intermediate_decimal(&value, precision, scale, rounding)?
+                        .value,
+                )
+            })
+            .collect::<ConversionResult<_>>()?;
+        Ok(OwnedColumn::Decimal75(precision, scale, values))
+    }
+    /// Returns the value at `index`, borrowing it from the column, or `None` if `index` is out of bounds.
+    pub fn value(&self, index: usize) -> Option<OwnedColumnValue<'_, S>> {
+        Some(match self {
+            OwnedColumn::Boolean(col) => OwnedColumnValue::Boolean(*col.get(index)?),
+            OwnedColumn::SmallInt(col) => OwnedColumnValue::SmallInt(*col.get(index)?),
+            OwnedColumn::Int(col) => OwnedColumnValue::Int(*col.get(index)?),
+            OwnedColumn::BigInt(col) => OwnedColumnValue::BigInt(*col.get(index)?),
+            OwnedColumn::VarChar(col) => OwnedColumnValue::VarChar(col.get(index)?),
+            OwnedColumn::Binary(_, col) => OwnedColumnValue::Binary(col.get(index)?),
+            OwnedColumn::Int128(col) => OwnedColumnValue::Int128(*col.get(index)?),
+            OwnedColumn::Decimal75(precision, scale, col) => {
+                OwnedColumnValue::Decimal75(*precision, *scale, *col.get(index)?)
+            }
+            OwnedColumn::Scalar(col) => OwnedColumnValue::Scalar(*col.get(index)?),
+        })
+    }
     /// Returns the type of the column.
     pub fn column_type(&self) -> ColumnType {
         match self {
@@ -61,6 +217,7 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Int(_) => ColumnType::Int,
             OwnedColumn::BigInt(_) => ColumnType::BigInt,
             OwnedColumn::VarChar(_) => ColumnType::VarChar,
+            OwnedColumn::Binary(length, _) => ColumnType::Binary(*length),
             OwnedColumn::Int128(_) => ColumnType::Int128,
             OwnedColumn::Scalar(_) => ColumnType::Scalar,
             OwnedColumn::Decimal75(precision, scale, _) => {
@@ -70,6 +227,51 @@ impl<S: Scalar> OwnedColumn<S> {
     }
 }
 
+/// A single value of an [`OwnedColumn`], such as one cell of a row of an [`OwnedTable`](super::OwnedTable).
+///
+/// Strings and bytes are borrowed from the column rather than cloned.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+#[non_exhaustive]
+pub enum OwnedColumnValue<'a, S: Scalar> {
+    /// Boolean values
+    Boolean(bool),
+    /// i16 values
+    SmallInt(i16),
+    /// i32 values
+    Int(i32),
+    /// i64 values
+    BigInt(i64),
+    /// String values
+    VarChar(&'a str),
+    /// Fixed-length binary values
+    Binary(&'a [u8]),
+    /// i128 values
+    Int128(i128),
+    /// Decimal values, with the precision and scale of their column
+    Decimal75(Precision, i8, S),
+    /// Scalar values
+    Scalar(S),
+}
+
+impl<S: Scalar> OwnedColumnValue<'_, S> {
+    /// Returns the type of the column that the value belongs to.
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            OwnedColumnValue::Boolean(_) => ColumnType::Boolean,
+            OwnedColumnValue::SmallInt(_) => ColumnType::SmallInt,
+            OwnedColumnValue::Int(_) => ColumnType::Int,
+            OwnedColumnValue::BigInt(_) => ColumnType::BigInt,
+            OwnedColumnValue::VarChar(_) => ColumnType::VarChar,
+            OwnedColumnValue::Binary(bytes) => ColumnType::Binary(bytes.len()),
+            OwnedColumnValue::Int128(_) => ColumnType::Int128,
+            OwnedColumnValue::Scalar(_) => ColumnType::Scalar,
+            OwnedColumnValue::Decimal75(precision, scale, _) => {
+                ColumnType::Decimal75(*precision, *scale)
+            }
+        }
+    }
+}
+
 impl<S: Scalar> FromIterator<bool> for OwnedColumn<S> {
     fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
         Self::Boolean(Vec::from_iter(iter))
@@ -1,7 +1,8 @@
-use super::OwnedColumn;
-use crate::base::scalar::Scalar;
+use super::{OwnedColumn, OwnedColumnValue};
+use crate::base::{math::decimal::Precision, scalar::Scalar};
 use indexmap::IndexMap;
 use proof_of_sql_parser::Identifier;
+use std::{collections::HashSet, ops::Range};
 use thiserror::Error;
 
 /// An error that occurs when working with tables.
@@ -10,7 +11,22 @@ pub enum OwnedTableError {
     /// The columns have different lengths.
     #[error("Columns have different lengths")]
     ColumnLengthMismatch,
+    /// The table has no column with the given name.
+    #[error("Column {0} does not exist")]
+    MissingColumn(Identifier),
+    /// The table would contain more than one column with the given name.
+    #[error("Column {0} appears more than once")]
+    DuplicateColumn(Identifier),
+    /// The tables do not have the same column names and types, in the same order.
+    #[error("Tables have different schemas")]
+    SchemaMismatch,
 }
+
+/// The blake3 key derivation context used by [`OwnedTable::checksum`].
+///
+/// This should only change if the way the checksum is computed changes.
+pub const OWNED_TABLE_CHECKSUM_CONTEXT: &str = "proof-of-sql 2024 owned table checksum v1";
+
 /// A table of data, with schema included. This is simply a map from `Identifier` to `OwnedColumn`,
 /// where columns order matters.
 /// This is primarily used as an internal result that is used before
@@ -22,6 +38,8 @@ pub struct OwnedTable<S: Scalar> {
 }
 impl<S: Scalar> OwnedTable<S> {
     /// Creates a new OwnedTable.
+    ///
+    /// Returns [`OwnedTableError::ColumnLengthMismatch`] if the columns do not all have the same length.
     pub fn try_new(table: IndexMap<Identifier, OwnedColumn<S>>) -> Result<Self, OwnedTableError> {
         if table.is_empty() {
             return Ok(Self { table });
@@ -33,11 +51,20 @@ impl<S: Scalar> OwnedTable<S> {
             Ok(Self { table })
         }
     }
-    /// Creates a new OwnedTable.
+    /// Creates a new OwnedTable from (Identifier, OwnedColumn) pairs, in order.
+    ///
+    /// Returns [`OwnedTableError::DuplicateColumn`] if an identifier appears more than once,
+    /// or [`OwnedTableError::ColumnLengthMismatch`] if the columns do not all have the same length.
     pub fn try_from_iter<T: IntoIterator<Item = (Identifier, OwnedColumn<S>)>>(
         iter: T,
     ) -> Result<Self, OwnedTableError> {
-        Self::try_new(IndexMap::from_iter(iter))
+        let mut table = IndexMap::new();
+        for (identifier, column) in iter {
+            if table.insert(identifier, column).is_some() {
+                return Err(OwnedTableError::DuplicateColumn(identifier));
+            }
+        }
+        Self::try_new(table)
     }
     /// Number of columns in the table.
     pub fn num_columns(&self) -> usize {
@@ -68,6 +95,207 @@ impl<S: Scalar> OwnedTable<S> {
         self.table.keys()
     }
 
+    // The typed getters below return `None` if there is no column named `name`
+    // or if the column has another type.
+
+    /// Returns the values of the `Boolean` column `name`.
+    pub fn boolean_column(&self, name: &Identifier) -> Option<&[bool]> {
+        match self.table.get(name)? {
+            OwnedColumn::Boolean(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the `SmallInt` column `name`.
+    pub fn smallint_column(&self, name: &Identifier) -> Option<&[i16]> {
+        match self.table.get(name)? {
+            OwnedColumn::SmallInt(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the `Int` column `name`.
+    pub fn int_column(&self, name: &Identifier) -> Option<&[i32]> {
+        match self.table.get(name)? {
+            OwnedColumn::Int(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the `BigInt` column `name`.
+    pub fn bigint_column(&self, name: &Identifier) -> Option<&[i64]> {
+        match self.table.get(name)? {
+            OwnedColumn::BigInt(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the `VarChar` column `name`.
+    pub fn varchar_column(&self, name: &Identifier) -> Option<&[String]> {
+        match self.table.get(name)? {
+            OwnedColumn::VarChar(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns the length and values of the `Binary` column `name`.
+    pub fn binary_column(&self, name: &Identifier) -> Option<(usize, &[Vec<u8>])> {
+        match self.table.get(name)? {
+            OwnedColumn::Binary(length, col) => Some((*length, col)),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the `Int128` column `name`.
+    pub fn int128_column(&self, name: &Identifier) -> Option<&[i128]> {
+        match self.table.get(name)? {
+            OwnedColumn::Int128(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns the precision, scale and values of the `Decimal75` column `name`.
+    pub fn decimal75_column(&self, name: &Identifier) -> Option<(Precision, i8, &[S])> {
+        match self.table.get(name)? {
+            OwnedColumn::Decimal75(precision, scale, col) => Some((*precision, *scale, col)),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the `Scalar` column `name`.
+    pub fn scalar_column(&self, name: &Identifier) -> Option<&[S]> {
+        match self.table.get(name)? {
+            OwnedColumn::Scalar(col) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the rows of this table.
+    ///
+    /// Each row holds one value per column, in column order, borrowed from the table
+    /// rather than cloned. The rows are produced lazily.
+    pub fn rows(&self) -> OwnedTableRows<'_, S> {
+        OwnedTableRows {
+            table: self,
+            range: 0..self.num_rows(),
+        }
+    }
+
+    /// Returns a deterministic 32-byte checksum of this table, so that a client can compare a
+    /// verified query result with an independently published one.
+    ///
+    /// The checksum is a blake3 hash in key derivation mode with the context string
+    /// [`OWNED_TABLE_CHECKSUM_CONTEXT`]. The hashed message is the postcard encoding of the number
+    /// of rows followed by, for every column in order, the identifier, column type, and values.
+    /// Decimal and scalar values are encoded as their four little-endian limbs.
+    ///
+    /// The column type includes the precision and scale of decimals, so equal values of different
+    /// types, as well as a different column order, give a different checksum.
+    pub fn checksum(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new_derive_key(OWNED_TABLE_CHECKSUM_CONTEXT);
+        hasher.update(
+            &postcard::to_allocvec(&(self.num_rows() as u64))
+                .expect("a row count should always be serializable"),
+        );
+        for (identifier, column) in &self.table {
+            hasher.update(
+                &postcard::to_allocvec(&(identifier, column.column_type()))
+                    .expect("column metadata should always be serializable"),
+            );
+            let values = match column {
+                OwnedColumn::Boolean(col) => postcard::to_allocvec(col),
+                OwnedColumn::SmallInt(col) => postcard::to_allocvec(col),
+                OwnedColumn::Int(col) => postcard::to_allocvec(col),
+                OwnedColumn::BigInt(col) => postcard::to_allocvec(col),
+                OwnedColumn::VarChar(col) => postcard::to_allocvec(col),
+                OwnedColumn::Binary(_, col) => postcard::to_allocvec(col),
+                OwnedColumn::Int128(col) => postcard::to_allocvec(col),
+                OwnedColumn::Decimal75(_, _, col) | OwnedColumn::Scalar(col) => {
+                    postcard::to_allocvec(&col.iter().map(|&s| s.into()).collect::<Vec<[u64; 4]>>())
+                }
+            };
+            hasher.update(&values.expect("column values should always be serializable"));
+        }
+        hasher.finalize().into()
+    }
+
+    /// Returns a new table containing only the columns in `names`, in that order.
+    ///
+    /// Errors if any of the names is not a column of this table or appears more than once.
+    pub fn select_columns(&self, names: &[Identifier]) -> Result<OwnedTable<S>, OwnedTableError> {
+        let mut table = IndexMap::with_capacity(names.len());
+        for name in names {
+            let column = self
+                .table
+                .get(name)
+                .ok_or(OwnedTableError::MissingColumn(*name))?;
+            if table.insert(*name, column.clone()).is_some() {
+                return Err(OwnedTableError::DuplicateColumn(*name));
+            }
+        }
+        Ok(Self { table })
+    }
+
+    /// Appends the rows of `other` to the end of this table.
+    ///
+    /// Errors, leaving the table unchanged, if the tables do not have the same column names
+    /// and types, in the same order.
+    pub fn append(&mut self, other: &OwnedTable<S>) -> Result<(), OwnedTableError> {
+        if self.table.len() != other.table.len()
+            || self.table.iter().zip(other.table.iter()).any(
+                |((name, column), (other_name, other_column))| {
+                    name != other_name || column.column_type() != other_column.column_type()
+                },
+            )
+        {
+            return Err(OwnedTableError::SchemaMismatch);
+        }
+        for (column, other_column) in self.table.values_mut().zip(other.table.values()) {
+            column.append(other_column);
+        }
+        Ok(())
+    }
+
+    /// Renames the column `old` to `new`, keeping its position within the table.
+    ///
+    /// Errors if there is no column named `old` or if another column is already named `new`.
+    pub fn rename_column(
+        &mut self,
+        old: &Identifier,
+        new: Identifier,
+    ) -> Result<(), OwnedTableError> {
+        self.rename([(*old, new)])
+    }
+
+    /// Renames several columns at once, keeping their positions within the table.
+    ///
+    /// All of the renames are applied simultaneously, so two columns can swap names.
+    /// Errors, leaving the table unchanged, if any old name does not exist
+    /// or if two columns would end up with the same name.
+    pub fn rename(
+        &mut self,
+        renames: impl IntoIterator<Item = (Identifier, Identifier)>,
+    ) -> Result<(), OwnedTableError> {
+        let renames = IndexMap::<_, _>::from_iter(renames);
+        if let Some(old) = renames.keys().find(|old| !self.table.contains_key(*old)) {
+            return Err(OwnedTableError::MissingColumn(*old));
+        }
+        let new_names: Vec<_> = self
+            .table
+            .keys()
+            .map(|name| *renames.get(name).unwrap_or(name))
+            .collect();
+        let mut unique_names = HashSet::with_capacity(new_names.len());
+        if let Some(name) = new_names.iter().find(|name| !unique_names.insert(**name)) {
+            return Err(OwnedTableError::DuplicateColumn(*name));
+        }
+        self.table = new_names
+            .into_iter()
+            .zip(self.table.drain(..).map(|(_, column)| column))
+            .collect();
+        Ok(())
+    }
+
     /// Applies a filter to this table via polars, returning a new table. This is useful for testing that a filter is executed correctly.
     #[cfg(test)]
     pub fn apply_polars_filter(
@@ -100,6 +328,47 @@ impl<S: Scalar> OwnedTable<S> {
     }
 }
 
+/// An iterator over the rows of an [`OwnedTable`], created by [`OwnedTable::rows`].
+#[derive(Debug, Clone)]
+pub struct OwnedTableRows<'a, S: Scalar> {
+    table: &'a OwnedTable<S>,
+    range: Range<usize>,
+}
+
+impl<'a, S: Scalar> OwnedTableRows<'a, S> {
+    fn row(&self, index: usize) -> Vec<OwnedColumnValue<'a, S>> {
+        self.table
+            .table
+            .values()
+            .map(|column| {
+                column
+                    .value(index)
+                    .expect("all columns have the same length")
+            })
+            .collect()
+    }
+}
+
+impl<'a, S: Scalar> Iterator for OwnedTableRows<'a, S> {
+    type Item = Vec<OwnedColumnValue<'a, S>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|index| self.row(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<S: Scalar> DoubleEndedIterator for OwnedTableRows<'_, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|index| self.row(index))
+    }
+}
+
+impl<S: Scalar> ExactSizeIterator for OwnedTableRows<'_, S> {}
+
 // Note: we modify the default PartialEq for IndexMap to also check for column ordering.
 // This is to align with the behaviour of a `RecordBatch`.
 impl<S: Scalar> PartialEq for OwnedTable<S> {
@@ -1,6 +1,9 @@
 use crate::{
     base::{
-        database::{owned_table_utility::*, OwnedColumn, OwnedTable, OwnedTableError},
+        database::{
+            owned_table_utility::*, OwnedColumn, OwnedColumnValue, OwnedTable, OwnedTableError,
+        },
+        math::decimal::Precision,
         scalar::Curve25519Scalar,
     },
     proof_primitive::dory::DoryScalar,
@@ -144,3 +147,312 @@ fn we_cannot_create_an_owned_table_with_differing_column_lengths() {
         Err(OwnedTableError::ColumnLengthMismatch)
     ));
 }
+#[test]
+fn we_cannot_create_an_owned_table_from_a_map_with_differing_column_lengths() {
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::try_new(IndexMap::from_iter([
+            ("a".parse().unwrap(), OwnedColumn::BigInt(vec![0, 1])),
+            (
+                "b".parse().unwrap(),
+                OwnedColumn::VarChar(vec!["0".to_string()])
+            ),
+        ])),
+        Err(OwnedTableError::ColumnLengthMismatch)
+    ));
+}
+#[test]
+fn we_cannot_create_an_owned_table_with_duplicate_identifiers() {
+    let result = OwnedTable::<Curve25519Scalar>::try_from_iter([
+        ("a".parse().unwrap(), OwnedColumn::BigInt(vec![0])),
+        ("b".parse().unwrap(), OwnedColumn::BigInt(vec![1])),
+        ("a".parse().unwrap(), OwnedColumn::BigInt(vec![2])),
+    ]);
+    assert!(matches!(
+        result,
+        Err(OwnedTableError::DuplicateColumn(identifier)) if identifier == "a".parse::<Identifier>().unwrap()
+    ));
+}
+#[test]
+#[should_panic]
+fn owned_table_panics_on_duplicate_identifiers() {
+    owned_table::<Curve25519Scalar>([bigint("a", [0]), bigint("a", [1])]);
+}
+#[test]
+fn we_can_rename_a_column_of_an_owned_table() {
+    let mut table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [0, 1]),
+        varchar("b", ["0", "1"]),
+        boolean("c", [true, false]),
+    ]);
+    table
+        .rename_column(&"b".parse().unwrap(), "d".parse().unwrap())
+        .unwrap();
+    assert_eq!(
+        table,
+        owned_table([
+            bigint("a", [0, 1]),
+            varchar("d", ["0", "1"]),
+            boolean("c", [true, false]),
+        ])
+    );
+}
+#[test]
+fn we_can_swap_column_names_of_an_owned_table_with_a_bulk_rename() {
+    let mut table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0, 1]), varchar("b", ["0", "1"])]);
+    table
+        .rename([
+            ("a".parse().unwrap(), "b".parse().unwrap()),
+            ("b".parse().unwrap(), "a".parse().unwrap()),
+        ])
+        .unwrap();
+    assert_eq!(
+        table,
+        owned_table([bigint("b", [0, 1]), varchar("a", ["0", "1"])])
+    );
+}
+#[test]
+fn we_cannot_rename_a_column_that_does_not_exist() {
+    let mut table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [0, 1])]);
+    assert!(matches!(
+        table.rename_column(&"b".parse().unwrap(), "c".parse().unwrap()),
+        Err(OwnedTableError::MissingColumn(name)) if name.as_str() == "b"
+    ));
+    assert_eq!(table, owned_table([bigint("a", [0, 1])]));
+}
+#[test]
+fn we_cannot_rename_a_column_to_the_name_of_another_column() {
+    let mut table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0, 1]), bigint("b", [2, 3])]);
+    assert!(matches!(
+        table.rename_column(&"a".parse().unwrap(), "b".parse().unwrap()),
+        Err(OwnedTableError::DuplicateColumn(name)) if name.as_str() == "b"
+    ));
+    assert_eq!(
+        table,
+        owned_table([bigint("a", [0, 1]), bigint("b", [2, 3])])
+    );
+}
+#[test]
+fn we_can_select_columns_of_an_owned_table_in_a_given_order() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [0, 1, 2]),
+        varchar("b", ["0", "1", "2"]),
+        boolean("c", [true, false, true]),
+        int128("d", [3, 4, 5]),
+    ]);
+    let selected = table
+        .select_columns(&["d".parse().unwrap(), "b".parse().unwrap()])
+        .unwrap();
+    assert_eq!(selected.num_rows(), 3);
+    assert_eq!(
+        selected,
+        owned_table([int128("d", [3, 4, 5]), varchar("b", ["0", "1", "2"])])
+    );
+}
+#[test]
+fn we_cannot_select_columns_that_do_not_exist_or_are_repeated() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0, 1]), bigint("b", [2, 3])]);
+    assert!(matches!(
+        table.select_columns(&["a".parse().unwrap(), "c".parse().unwrap()]),
+        Err(OwnedTableError::MissingColumn(name)) if name.as_str() == "c"
+    ));
+    assert!(matches!(
+        table.select_columns(&["a".parse().unwrap(), "a".parse().unwrap()]),
+        Err(OwnedTableError::DuplicateColumn(name)) if name.as_str() == "a"
+    ));
+}
+#[test]
+fn we_can_append_an_owned_table_with_the_same_schema() {
+    let mut table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [0, 1]),
+        varchar("b", ["0", "1"]),
+        decimal75("c", 10, 2, [100, 200]),
+    ]);
+    table
+        .append(&owned_table([
+            bigint("a", [2]),
+            varchar("b", ["2"]),
+            decimal75("c", 10, 2, [300]),
+        ]))
+        .unwrap();
+    assert_eq!(
+        table,
+        owned_table([
+            bigint("a", [0, 1, 2]),
+            varchar("b", ["0", "1", "2"]),
+            decimal75("c", 10, 2, [100, 200, 300]),
+        ])
+    );
+}
+#[test]
+fn we_cannot_append_an_owned_table_with_a_different_schema() {
+    let mut table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0, 1]), decimal75("b", 10, 2, [0, 1])]);
+    for other in [
+        owned_table([bigint("a", [2])]),
+        owned_table([bigint("b", [2]), decimal75("a", 10, 2, [2])]),
+        owned_table([bigint("a", [2]), decimal75("b", 10, 1, [2])]),
+        owned_table([int128("a", [2]), decimal75("b", 10, 2, [2])]),
+    ] {
+        assert!(matches!(
+            table.append(&other),
+            Err(OwnedTableError::SchemaMismatch)
+        ));
+    }
+    assert_eq!(
+        table,
+        owned_table([bigint("a", [0, 1]), decimal75("b", 10, 2, [0, 1])])
+    );
+}
+
+#[test]
+fn we_can_iterate_over_the_rows_of_a_mixed_type_table() {
+    let table = owned_table::<Curve25519Scalar>([
+        bigint("id", [1, 2, 3]),
+        varchar("name", ["a", "bb", "ccc"]),
+        boolean("flag", [true, false, true]),
+        decimal75("price", 10, 2, [100, -250, 0]),
+        int128("big", [i128::MAX, 0, i128::MIN]),
+    ]);
+    let mut rows = table.rows();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        rows.next().unwrap(),
+        vec![
+            OwnedColumnValue::BigInt(1),
+            OwnedColumnValue::VarChar("a"),
+            OwnedColumnValue::Boolean(true),
+            OwnedColumnValue::Decimal75(
+                Precision::new(10).unwrap(),
+                2,
+                Curve25519Scalar::from(100)
+            ),
+            OwnedColumnValue::Int128(i128::MAX),
+        ]
+    );
+    assert_eq!(rows.len(), 2);
+    let last = rows.next_back().unwrap();
+    assert_eq!(last[1], OwnedColumnValue::VarChar("ccc"));
+    assert_eq!(last[4], OwnedColumnValue::Int128(i128::MIN));
+    let middle = rows.next().unwrap();
+    assert_eq!(middle[0], OwnedColumnValue::BigInt(2));
+    assert_eq!(
+        middle[3],
+        OwnedColumnValue::Decimal75(Precision::new(10).unwrap(), 2, -Curve25519Scalar::from(250))
+    );
+    assert_eq!(rows.next(), None);
+
+    // The rows can be used to reconstruct the columns of the table.
+    let names: Vec<_> = table.rows().map(|row| row[1]).collect();
+    assert_eq!(
+        names,
+        ["a", "bb", "ccc"].map(OwnedColumnValue::VarChar).to_vec()
+    );
+    let column_types: Vec<_> = table
+        .inner_table()
+        .values()
+        .map(OwnedColumn::column_type)
+        .collect();
+    for row in table.rows() {
+        assert_eq!(
+            row.iter()
+                .map(OwnedColumnValue::column_type)
+                .collect::<Vec<_>>(),
+            column_types
+        );
+    }
+}
+
+#[test]
+fn a_table_without_columns_has_no_rows() {
+    let table = OwnedTable::<Curve25519Scalar>::try_new(IndexMap::new()).unwrap();
+    assert_eq!(table.rows().next(), None);
+}
+
+#[test]
+fn equal_tables_have_the_same_checksum() {
+    let table = || {
+        owned_table::<Curve25519Scalar>([
+            bigint("a", [1, 2, 3]),
+            varchar("b", ["x", "yy", "zzz"]),
+            decimal75("c", 10, 2, [100, -250, 0]),
+        ])
+    };
+    assert_eq!(table().checksum(), table().checksum());
+}
+
+#[test]
+fn swapping_two_values_of_a_column_changes_the_checksum() {
+    let table = owned_table::<Curve25519Scalar>([bigint("a", [1, 2]), varchar("b", ["x", "y"])]);
+    let swapped = owned_table::<Curve25519Scalar>([bigint("a", [2, 1]), varchar("b", ["x", "y"])]);
+    assert_ne!(table.checksum(), swapped.checksum());
+}
+
+#[test]
+fn the_checksum_depends_on_the_column_types_and_order() {
+    let table = owned_table::<Curve25519Scalar>([bigint("a", [1]), bigint("b", [2])]);
+    assert_ne!(
+        table.checksum(),
+        owned_table::<Curve25519Scalar>([bigint("b", [2]), bigint("a", [1])]).checksum()
+    );
+    assert_ne!(
+        table.checksum(),
+        owned_table::<Curve25519Scalar>([int("a", [1]), bigint("b", [2])]).checksum()
+    );
+    assert_ne!(
+        owned_table::<Curve25519Scalar>([decimal75("a", 10, 2, [1])]).checksum(),
+        owned_table::<Curve25519Scalar>([decimal75("a", 10, 3, [1])]).checksum()
+    );
+}
+
+#[test]
+fn we_can_get_the_values_of_a_column_of_each_type() {
+    let table = owned_table::<Curve25519Scalar>([
+        boolean("boolean", [true, false]),
+        smallint("smallint", [1_i16, -1]),
+        int("int", [2, -2]),
+        bigint("bigint", [3, -3]),
+        varchar("varchar", ["a", "b"]),
+        int128("int128", [4, -4]),
+        decimal75("decimal", 10, 2, [5, -5]),
+        scalar("scalar", [6, -6]),
+    ]);
+    let id = |name: &str| Identifier::try_new(name).unwrap();
+    assert_eq!(
+        table.boolean_column(&id("boolean")),
+        Some(&[true, false][..])
+    );
+    assert_eq!(table.smallint_column(&id("smallint")), Some(&[1, -1][..]));
+    assert_eq!(table.int_column(&id("int")), Some(&[2, -2][..]));
+    assert_eq!(table.bigint_column(&id("bigint")), Some(&[3, -3][..]));
+    assert_eq!(
+        table.varchar_column(&id("varchar")),
+        Some(&["a".to_string(), "b".to_string()][..])
+    );
+    assert_eq!(table.int128_column(&id("int128")), Some(&[4, -4][..]));
+    assert_eq!(
+        table.decimal75_column(&id("decimal")),
+        Some((
+            Precision::new(10).unwrap(),
+            2,
+            &[Curve25519Scalar::from(5), Curve25519Scalar::from(-5)][..]
+        ))
+    );
+    assert_eq!(
+        table.scalar_column(&id("scalar")),
+        Some(&[Curve25519Scalar::from(6), Curve25519Scalar::from(-6)][..])
+    );
+}
+
+#[test]
+fn we_cannot_get_the_values_of_a_column_with_another_type_or_that_does_not_exist() {
+    let table = owned_table::<Curve25519Scalar>([bigint("a", [1]), varchar("b", ["x"])]);
+    let id = |name: &str| Identifier::try_new(name).unwrap();
+    assert_eq!(table.int_column(&id("a")), None);
+    assert_eq!(table.int128_column(&id("a")), None);
+    assert_eq!(table.bigint_column(&id("b")), None);
+    assert_eq!(table.decimal75_column(&id("b")), None);
+    assert_eq!(table.bigint_column(&id("c")), None);
+}
@@ -1,8 +1,11 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
-    OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable,
+    SchemaAccessor, TableRef, TestAccessor,
+};
+use crate::base::{
+    commitment::{ColumnBounds, CommitmentEvaluationProof, CommittableColumn, VecCommitmentExt},
+    scalar::VarCharHash,
 };
-use crate::base::commitment::{CommitmentEvaluationProof, VecCommitmentExt};
 use bumpalo::Bump;
 use indexmap::IndexMap;
 use proof_of_sql_parser::Identifier;
@@ -13,6 +16,7 @@ pub struct OwnedTableTestAccessor<CP: CommitmentEvaluationProof> {
     tables: IndexMap<TableRef, (OwnedTable<CP::Scalar>, usize)>,
     alloc: Bump,
     setup: Option<CP::ProverPublicSetup>,
+    varchar_hash: VarCharHash,
 }
 
 impl<CP: CommitmentEvaluationProof> Default for OwnedTableTestAccessor<CP> {
@@ -21,6 +25,7 @@ impl<CP: CommitmentEvaluationProof> Default for OwnedTableTestAccessor<CP> {
             tables: Default::default(),
             alloc: Bump::new(),
             setup: None,
+            varchar_hash: VarCharHash::default(),
         }
     }
 }
@@ -33,6 +38,7 @@ where
         Self {
             tables: self.tables.clone(),
             setup: self.setup.clone(),
+            varchar_hash: self.varchar_hash,
             ..Default::default()
         }
     }
@@ -68,34 +74,19 @@ where
 }
 impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestAccessor<CP> {
     fn get_column(&self, column: ColumnRef) -> Column<CP::Scalar> {
-        match self
+        let owned_column = self
             .tables
             .get(&column.table_ref())
             .unwrap()
             .0
             .inner_table()
             .get(&column.column_id())
-            .unwrap()
-        {
-            OwnedColumn::Boolean(col) => Column::Boolean(col),
-            OwnedColumn::SmallInt(col) => Column::SmallInt(col),
-            OwnedColumn::Int(col) => Column::Int(col),
-            OwnedColumn::BigInt(col) => Column::BigInt(col),
-            OwnedColumn::Int128(col) => Column::Int128(col),
-            OwnedColumn::Decimal75(precision, scale, col) => {
-                Column::Decimal75(*precision, *scale, col)
-            }
-            OwnedColumn::Scalar(col) => Column::Scalar(col),
-            OwnedColumn::VarChar(col) => {
-                let col: &mut [&str] = self
-                    .alloc
-                    .alloc_slice_fill_iter(col.iter().map(|s| s.as_str()));
-                let scals: &mut [_] = self
-                    .alloc
-                    .alloc_slice_fill_iter(col.iter().map(|s| (*s).into()));
-                Column::VarChar((col, scals))
-            }
-        }
+            .unwrap();
+        Column::from_owned_column_with_varchar_hash(owned_column, self.varchar_hash, &self.alloc)
+    }
+
+    fn column_type(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.lookup_column(table_ref, column_id)
     }
 }
 impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
@@ -104,10 +95,23 @@ impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
     fn get_commitment(&self, column: ColumnRef) -> CP::Commitment {
         let (table, offset) = self.tables.get(&column.table_ref()).unwrap();
         let owned_column = table.inner_table().get(&column.column_id()).unwrap();
-        Vec::from_columns_with_offset([owned_column], *offset, self.setup.as_ref().unwrap())
+        let committable_column =
+            CommittableColumn::from_owned_column_with_varchar_hash(owned_column, self.varchar_hash);
+        Vec::from_columns_with_offset([committable_column], *offset, self.setup.as_ref().unwrap())
             .to_decompressed()
             .unwrap()[0]
     }
+
+    fn get_column_bounds(&self, column: ColumnRef) -> Option<ColumnBounds> {
+        let (table, _) = self.tables.get(&column.table_ref())?;
+        let owned_column = table.inner_table().get(&column.column_id())?;
+        Some(ColumnBounds::from_column(
+            &CommittableColumn::from_owned_column_with_varchar_hash(
+                owned_column,
+                self.varchar_hash,
+            ),
+        ))
+    }
 }
 impl<CP: CommitmentEvaluationProof> MetadataAccessor for OwnedTableTestAccessor<CP> {
     fn get_length(&self, table_ref: TableRef) -> usize {
@@ -117,6 +121,10 @@ impl<CP: CommitmentEvaluationProof> MetadataAccessor for OwnedTableTestAccessor<
     fn get_offset(&self, table_ref: TableRef) -> usize {
         self.tables.get(&table_ref).unwrap().1
     }
+
+    fn varchar_hash(&self) -> VarCharHash {
+        self.varchar_hash
+    }
 }
 impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<CP> {
     fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
@@ -152,4 +160,15 @@ where
         res.setup = Some(setup);
         res
     }
+
+    /// Create a new empty test accessor with the given setup,
+    /// which hashes the strings of `VarChar` columns with `varchar_hash`.
+    pub fn new_empty_with_setup_and_varchar_hash(
+        setup: CP::ProverPublicSetup,
+        varchar_hash: VarCharHash,
+    ) -> Self {
+        let mut res = Self::new_empty_with_setup(setup);
+        res.varchar_hash = varchar_hash;
+        res
+    }
 }
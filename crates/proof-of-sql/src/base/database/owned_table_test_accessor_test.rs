@@ -4,6 +4,7 @@ use super::{
 };
 use crate::base::{
     database::owned_table_utility::*,
+    math::decimal::Precision,
     scalar::{compute_commitment_for_testing, Curve25519Scalar},
 };
 use blitzar::proof::InnerProductProof;
@@ -173,6 +174,28 @@ fn we_can_access_the_type_of_table_columns() {
         .is_none());
 }
 
+#[test]
+fn we_can_check_the_existence_and_type_of_columns_without_fetching_data() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_ref_1 = "sxt.test".parse().unwrap();
+    let table_ref_2 = "sxt.test2".parse().unwrap();
+
+    let data1 = owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "z"])]);
+    accessor.add_table(table_ref_1, data1, 0_usize);
+
+    assert_eq!(
+        accessor.column_type(table_ref_1, "b".parse().unwrap()),
+        Some(ColumnType::VarChar)
+    );
+    assert!(accessor.has_column(table_ref_1, "a".parse().unwrap()));
+    assert_eq!(
+        accessor.column_type(table_ref_1, "c".parse().unwrap()),
+        None
+    );
+    assert!(!accessor.has_column(table_ref_1, "c".parse().unwrap()));
+    assert!(!accessor.has_column(table_ref_2, "a".parse().unwrap()));
+}
+
 #[test]
 fn we_can_access_schema_and_column_names() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
@@ -191,6 +214,41 @@ fn we_can_access_schema_and_column_names() {
     assert_eq!(accessor.get_column_names(table_ref_1), vec!["a", "b"]);
 }
 
+#[test]
+fn the_schema_of_a_table_lists_its_columns_in_order() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_ref = "sxt.test".parse().unwrap();
+
+    let data = owned_table([
+        varchar("z", ["x", "y"]),
+        smallint("b", [1_i16, 2]),
+        int("y", [1, 2]),
+        bigint("c", [1, 2]),
+        boolean("x", [true, false]),
+        int128("d", [1, 2]),
+        scalar("w", [1, 2]),
+        decimal75("e", 12, 1, [1, 2]),
+    ]);
+    accessor.add_table(table_ref, data, 0_usize);
+
+    assert_eq!(
+        accessor.lookup_schema(table_ref),
+        vec![
+            ("z".parse().unwrap(), ColumnType::VarChar),
+            ("b".parse().unwrap(), ColumnType::SmallInt),
+            ("y".parse().unwrap(), ColumnType::Int),
+            ("c".parse().unwrap(), ColumnType::BigInt),
+            ("x".parse().unwrap(), ColumnType::Boolean),
+            ("d".parse().unwrap(), ColumnType::Int128),
+            ("w".parse().unwrap(), ColumnType::Scalar),
+            (
+                "e".parse().unwrap(),
+                ColumnType::Decimal75(Precision::new(12).unwrap(), 1)
+            ),
+        ]
+    );
+}
+
 #[test]
 fn we_can_correctly_update_offsets() {
     let mut accessor1 = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
@@ -21,7 +21,8 @@ use proof_of_sql_parser::Identifier;
 /// Creates an OwnedTable from a list of (Identifier, OwnedColumn) pairs.
 /// This is a convenience wrapper around OwnedTable::try_from_iter primarily for use in tests and
 /// intended to be used along with the other methods in this module (e.g. [bigint], [boolean], etc).
-/// The function will panic under a variety of conditions. See [OwnedTable::try_from_iter] for more details.
+/// The function will panic if the columns have different lengths or if an identifier appears more than once.
+/// Use [OwnedTable::try_from_iter] instead to get an [OwnedTableError](super::OwnedTableError) in these cases.
 ///
 /// # Example
 /// ```
@@ -152,6 +153,56 @@ pub fn scalar<S: Scalar>(
     )
 }
 
+/// Creates a (Identifier, OwnedColumn) pair for a scalar column from hex strings.
+/// Each string is the big-endian hex encoding of a scalar, optionally prefixed with `0x`.
+/// This is primarily intended for use in conjunction with [owned_table].
+///
+/// The function will panic if a string is not between 1 and 64 hex digits,
+/// or if its value is not less than the modulus of the scalar field.
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     scalar_from_hex("a", ["0x01", "0xff", "0x0123456789abcdef0123456789abcdef"]),
+/// ]);
+/// ```
+pub fn scalar_from_hex<S: Scalar>(
+    name: impl Deref<Target = str>,
+    data: impl IntoIterator<Item = impl AsRef<str>>,
+) -> (Identifier, OwnedColumn<S>) {
+    (
+        name.parse().unwrap(),
+        OwnedColumn::Scalar(
+            data.into_iter()
+                .map(|hex| parse_hex_scalar(hex.as_ref()))
+                .collect(),
+        ),
+    )
+}
+
+fn parse_hex_scalar<S: Scalar>(hex: &str) -> S {
+    let digits = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+    assert!(
+        (1..=64).contains(&digits.len()) && digits.bytes().all(|b| b.is_ascii_hexdigit()),
+        "{hex} is not a hex string of 1 to 64 digits"
+    );
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(digits.as_bytes().rchunks(16)) {
+        *limb = u64::from_str_radix(core::str::from_utf8(chunk).unwrap(), 16).unwrap();
+    }
+    let value = S::from(limbs);
+    // Values that are not less than the modulus are reduced, so they do not round trip.
+    assert_eq!(
+        Into::<[u64; 4]>::into(value),
+        limbs,
+        "{hex} is not less than the scalar field modulus"
+    );
+    value
+}
+
 /// Creates a (Identifier, OwnedColumn) pair for a varchar column.
 /// This is primarily intended for use in conjunction with [owned_table].
 /// # Example
@@ -171,8 +222,34 @@ pub fn varchar<S: Scalar>(
     )
 }
 
+/// Creates a (Identifier, OwnedColumn) pair for a binary column whose values have `length` bytes.
+/// This is primarily intended for use in conjunction with [owned_table].
+///
+/// Panics if a value does not have `length` bytes.
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     binary("a", 2, [[1, 2], [3, 4], [5, 6]]),
+/// ]);
+/// ```
+pub fn binary<S: Scalar>(
+    name: impl Deref<Target = str>,
+    length: usize,
+    data: impl IntoIterator<Item = impl Into<Vec<u8>>>,
+) -> (Identifier, OwnedColumn<S>) {
+    let values: Vec<Vec<u8>> = data.into_iter().map(Into::into).collect();
+    assert!(
+        values.iter().all(|value| value.len() == length),
+        "every binary value must have {length} bytes"
+    );
+    (name.parse().unwrap(), OwnedColumn::Binary(length, values))
+}
+
 /// Creates a (Identifier, OwnedColumn) pair for a decimal75 column.
 /// This is primarily intended for use in conjunction with [owned_table].
+///
+/// Panics if a value has more digits than `precision`. See [`OwnedColumn::try_new_decimal75`].
 /// # Example
 /// ```
 /// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
@@ -188,10 +265,110 @@ pub fn decimal75<S: Scalar>(
 ) -> (Identifier, OwnedColumn<S>) {
     (
         name.parse().unwrap(),
-        OwnedColumn::Decimal75(
+        OwnedColumn::try_new_decimal75(
             crate::base::math::decimal::Precision::new(precision).unwrap(),
             scale,
             data.into_iter().map(Into::into).collect(),
-        ),
+        )
+        .unwrap_or_else(|error| panic!("{error}")),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{math::decimal::Precision, scalar::Curve25519Scalar},
+        sql::parse::ConversionError,
+    };
+
+    #[test]
+    fn we_can_create_a_scalar_column_from_hex_strings() {
+        let (_, column) = scalar_from_hex::<Curve25519Scalar>(
+            "a",
+            [
+                "0x0",
+                "0xFF",
+                "ff",
+                "0x0000000000000000000000000000000100000000000000000000000000000002",
+                "0x080000000000000000000000000000000a6f7cef517bce6b2c09318d2e7ae9f6",
+                "0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ec",
+            ],
+        );
+        assert_eq!(
+            column,
+            OwnedColumn::Scalar(vec![
+                Curve25519Scalar::from(0),
+                Curve25519Scalar::from(255),
+                Curve25519Scalar::from(255),
+                Curve25519Scalar::from([2, 0, 1, 0]),
+                Curve25519Scalar::MAX_SIGNED,
+                -Curve25519Scalar::ONE,
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is not less than the scalar field modulus")]
+    fn we_cannot_create_a_scalar_column_from_a_hex_string_of_the_modulus() {
+        scalar_from_hex::<Curve25519Scalar>(
+            "a",
+            ["0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed"],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a hex string of 1 to 64 digits")]
+    fn we_cannot_create_a_scalar_column_from_a_hex_string_that_is_too_long() {
+        scalar_from_hex::<Curve25519Scalar>(
+            "a",
+            ["0x01000000000000000000000000000000000000000000000000000000000000000"],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a hex string of 1 to 64 digits")]
+    fn we_cannot_create_a_scalar_column_from_a_string_that_is_not_hex() {
+        scalar_from_hex::<Curve25519Scalar>("a", ["0x+f"]);
+    }
+
+    #[test]
+    fn we_can_create_a_decimal_column_with_values_that_fit_the_precision() {
+        let (_, column) = decimal75::<Curve25519Scalar>("a", 3, 1, [999, -999, 0]);
+        assert_eq!(
+            column,
+            OwnedColumn::Decimal75(
+                Precision::new(3).unwrap(),
+                1,
+                vec![
+                    Curve25519Scalar::from(999),
+                    -Curve25519Scalar::from(999),
+                    Curve25519Scalar::ZERO,
+                ]
+            )
+        );
+        // Every scalar fits the maximum precision.
+        decimal75::<Curve25519Scalar>("a", 75, 0, [Curve25519Scalar::MAX_SIGNED]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal value 1234 has more digits than its precision of 3")]
+    fn we_cannot_create_a_decimal_column_with_a_value_that_exceeds_the_precision() {
+        decimal75::<Curve25519Scalar>("a", 3, 0, [1234]);
+    }
+
+    #[test]
+    fn we_cannot_create_a_decimal_column_with_a_negative_value_that_exceeds_the_precision() {
+        assert_eq!(
+            OwnedColumn::try_new_decimal75(
+                Precision::new(3).unwrap(),
+                0,
+                vec![Curve25519Scalar::from(-123), Curve25519Scalar::from(-1234)],
+            ),
+            Err(ConversionError::DecimalValueExceedsPrecision {
+                value: "-1234".to_string(),
+                precision: 3,
+            })
+        );
+    }
+}
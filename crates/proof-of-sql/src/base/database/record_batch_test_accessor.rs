@@ -197,4 +197,9 @@ impl DataAccessor<Curve25519Scalar> for RecordBatchTestAccessor {
             .to_column(&self.alloc, &(0..table.data.num_rows()), None)
             .unwrap()
     }
+
+    /// Return the column type associated with column_id, if exists.
+    fn column_type(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.lookup_column(table_ref, column_id)
+    }
 }
@@ -0,0 +1,99 @@
+use super::{ColumnType, OwnedTable};
+use crate::base::scalar::Scalar;
+use proof_of_sql_parser::Identifier;
+use thiserror::Error;
+
+/// A difference between a table and the schema that it is expected to have.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The table has no column with a name of the schema.
+    #[error("Column {column} of type {expected} is missing")]
+    MissingColumn {
+        /// The name of the missing column
+        column: Identifier,
+        /// The type that the schema expects the column to have
+        expected: ColumnType,
+    },
+    /// The table has a column that is not in the schema.
+    #[error("Column {column} of type {actual} is not in the schema")]
+    ExtraColumn {
+        /// The name of the extra column
+        column: Identifier,
+        /// The type of the extra column
+        actual: ColumnType,
+    },
+    /// A column of the table has another type than the schema expects.
+    #[error("Column {column} has type {actual}, but {expected} is expected")]
+    TypeMismatch {
+        /// The name of the column
+        column: Identifier,
+        /// The type that the schema expects the column to have
+        expected: ColumnType,
+        /// The type of the column
+        actual: ColumnType,
+    },
+    /// The table has the columns of the schema, but in another order.
+    #[error("Column {column} is at position {actual}, but position {expected} is expected")]
+    OrderMismatch {
+        /// The name of the first column that is out of place
+        column: Identifier,
+        /// The position that the schema expects the column to have
+        expected: usize,
+        /// The position of the column
+        actual: usize,
+    },
+}
+
+/// Checks that `table` has exactly the columns of `schema`, with the same names and types and in
+/// the same order, so that data that was ingested wrongly is rejected before it is committed to
+/// or proven against.
+///
+/// Errors with the first difference that is found, looking for missing columns, then for extra
+/// columns, then for columns of another type and finally for columns out of order.
+pub fn validate_against_schema<S: Scalar>(
+    table: &OwnedTable<S>,
+    schema: &[(Identifier, ColumnType)],
+) -> Result<(), SchemaError> {
+    let columns = table.inner_table();
+    if let Some((column, expected)) = schema
+        .iter()
+        .find(|(column, _)| !columns.contains_key(column))
+    {
+        return Err(SchemaError::MissingColumn {
+            column: *column,
+            expected: *expected,
+        });
+    }
+    if let Some((column, actual)) = columns
+        .iter()
+        .find(|(column, _)| !schema.iter().any(|(name, _)| name == *column))
+    {
+        return Err(SchemaError::ExtraColumn {
+            column: *column,
+            actual: actual.column_type(),
+        });
+    }
+    for (column, expected) in schema {
+        let actual = columns[column].column_type();
+        if actual != *expected {
+            return Err(SchemaError::TypeMismatch {
+                column: *column,
+                expected: *expected,
+                actual,
+            });
+        }
+    }
+    for (expected, (column, _)) in schema.iter().enumerate() {
+        let actual = columns
+            .get_index_of(column)
+            .expect("the table has every column of the schema");
+        if actual != expected {
+            return Err(SchemaError::OrderMismatch {
+                column: *column,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::base::{
+    database::{owned_table_utility::*, validate_against_schema, ColumnType, SchemaError},
+    math::decimal::Precision,
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::Identifier;
+
+fn schema(columns: &[(&str, ColumnType)]) -> Vec<(Identifier, ColumnType)> {
+    Vec::from_iter(
+        columns
+            .iter()
+            .map(|(name, column_type)| (name.parse().unwrap(), *column_type)),
+    )
+}
+
+fn ident(name: &str) -> Identifier {
+    name.parse().unwrap()
+}
+
+#[test]
+fn a_table_conforms_to_its_own_schema() {
+    let table = owned_table::<Curve25519Scalar>([
+        bigint("a", [1, 2]),
+        varchar("b", ["x", "y"]),
+        decimal75("c", 12, 2, [3, 4]),
+    ]);
+    assert_eq!(
+        validate_against_schema(
+            &table,
+            &schema(&[
+                ("a", ColumnType::BigInt),
+                ("b", ColumnType::VarChar),
+                ("c", ColumnType::Decimal75(Precision::new(12).unwrap(), 2)),
+            ])
+        ),
+        Ok(())
+    );
+    let empty_table = owned_table::<Curve25519Scalar>([]);
+    assert_eq!(validate_against_schema(&empty_table, &[]), Ok(()));
+}
+
+#[test]
+fn we_cannot_validate_a_table_with_a_missing_column() {
+    let table = owned_table::<Curve25519Scalar>([bigint("a", [1, 2])]);
+    assert_eq!(
+        validate_against_schema(
+            &table,
+            &schema(&[("a", ColumnType::BigInt), ("b", ColumnType::VarChar)])
+        ),
+        Err(SchemaError::MissingColumn {
+            column: ident("b"),
+            expected: ColumnType::VarChar,
+        })
+    );
+}
+
+#[test]
+fn we_cannot_validate_a_table_with_an_extra_column() {
+    let table = owned_table::<Curve25519Scalar>([bigint("a", [1, 2]), boolean("b", [true, false])]);
+    assert_eq!(
+        validate_against_schema(&table, &schema(&[("a", ColumnType::BigInt)])),
+        Err(SchemaError::ExtraColumn {
+            column: ident("b"),
+            actual: ColumnType::Boolean,
+        })
+    );
+}
+
+#[test]
+fn we_cannot_validate_a_table_with_a_column_of_another_type() {
+    let table = owned_table::<Curve25519Scalar>([bigint("a", [1, 2]), int("b", [3, 4])]);
+    assert_eq!(
+        validate_against_schema(
+            &table,
+            &schema(&[("a", ColumnType::BigInt), ("b", ColumnType::BigInt)])
+        ),
+        Err(SchemaError::TypeMismatch {
+            column: ident("b"),
+            expected: ColumnType::BigInt,
+            actual: ColumnType::Int,
+        })
+    );
+    let table = owned_table::<Curve25519Scalar>([decimal75("c", 12, 2, [3, 4])]);
+    assert_eq!(
+        validate_against_schema(
+            &table,
+            &schema(&[("c", ColumnType::Decimal75(Precision::new(12).unwrap(), 3))])
+        ),
+        Err(SchemaError::TypeMismatch {
+            column: ident("c"),
+            expected: ColumnType::Decimal75(Precision::new(12).unwrap(), 3),
+            actual: ColumnType::Decimal75(Precision::new(12).unwrap(), 2),
+        })
+    );
+}
+
+#[test]
+fn we_cannot_validate_a_table_with_columns_out_of_order() {
+    let table = owned_table::<Curve25519Scalar>([
+        bigint("a", [1, 2]),
+        bigint("c", [5, 6]),
+        bigint("b", [3, 4]),
+    ]);
+    assert_eq!(
+        validate_against_schema(
+            &table,
+            &schema(&[
+                ("a", ColumnType::BigInt),
+                ("b", ColumnType::BigInt),
+                ("c", ColumnType::BigInt),
+            ])
+        ),
+        Err(SchemaError::OrderMismatch {
+            column: ident("b"),
+            expected: 1,
+            actual: 2,
+        })
+    );
+}
+
+#[test]
+fn missing_columns_are_reported_before_other_differences() {
+    let table = owned_table::<Curve25519Scalar>([int("b", [1]), bigint("extra", [2])]);
+    assert!(matches!(
+        validate_against_schema(
+            &table,
+            &schema(&[("a", ColumnType::BigInt), ("b", ColumnType::BigInt)])
+        ),
+        Err(SchemaError::MissingColumn { column, .. }) if column == ident("a")
+    ));
+}
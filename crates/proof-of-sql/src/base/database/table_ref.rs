@@ -2,7 +2,7 @@ use proof_of_sql_parser::{impl_serde_from_str, Identifier, ResourceId};
 use std::str::FromStr;
 
 /// Expression for an SQL table
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 pub struct TableRef {
     resource_id: ResourceId,
 }
@@ -13,6 +13,11 @@ impl TableRef {
         Self { resource_id }
     }
 
+    /// Returns the identifier of the catalog, if the table is in one
+    pub fn catalog_id(&self) -> Option<Identifier> {
+        self.resource_id.catalog()
+    }
+
     /// Returns the identifier of the schema
     pub fn schema_id(&self) -> Identifier {
         self.resource_id.schema()
@@ -57,6 +57,10 @@ impl DataAccessor<Curve25519Scalar> for UnimplementedTestAccessor {
     fn get_column(&self, _column: ColumnRef) -> Column<Curve25519Scalar> {
         unimplemented!()
     }
+
+    fn column_type(&self, _table_ref: TableRef, _column_id: Identifier) -> Option<ColumnType> {
+        unimplemented!()
+    }
 }
 impl CommitmentAccessor<RistrettoPoint> for UnimplementedTestAccessor {
     fn get_commitment(&self, _column: ColumnRef) -> RistrettoPoint {
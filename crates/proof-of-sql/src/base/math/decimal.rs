@@ -1,8 +1,9 @@
 //! Module for parsing an `IntermediateDecimal` into a `Decimal75`.
 use crate::{
-    base::scalar::Scalar,
+    base::{database::scalar_and_i256_conversions::convert_scalar_to_i256, scalar::Scalar},
     sql::parse::{ConversionError, ConversionResult},
 };
+use bigdecimal::{BigDecimal, RoundingMode};
 use proof_of_sql_parser::intermediate_decimal::IntermediateDecimal;
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -44,6 +45,20 @@ impl<'de> Deserialize<'de> for Precision {
     }
 }
 
+/// How to ingest a decimal that has more fractional digits than the scale of its column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecimalRounding {
+    /// Round to the nearest value with ties rounded away from zero, so `1.005` is `1.01` at scale 2.
+    #[default]
+    HalfUp,
+    /// Round to the nearest value with ties rounded to an even last digit, so `1.005` is `1.00` at scale 2.
+    HalfEven,
+    /// Drop the extra digits, rounding toward zero, so `1.009` is `1.00` at scale 2.
+    Truncate,
+    /// Error with [`ConversionError::DecimalRoundingError`] rather than drop any nonzero digits.
+    Error,
+}
+
 /// A decimal type that is parameterized by the scalar type
 #[derive(Eq, PartialEq, Debug, Clone, Hash, Serialize)]
 pub struct Decimal<S: Scalar> {
@@ -116,6 +131,113 @@ impl<S: Scalar> Decimal<S> {
         let scaled_value = scale_scalar(S::from(&value), scale)?;
         Ok(Decimal::new(scaled_value, precision, scale))
     }
+
+    /// Get a decimal with given precision and scale from an f64, rounding to the nearest multiple of `10^-scale`.
+    ///
+    /// The exact binary value of the float is rounded, with ties rounded away from zero.
+    /// So, `2.5` is `3` at scale 0, but `1.005` is `1.00` at scale 2,
+    /// since the closest f64 to 1.005 is slightly less than it.
+    ///
+    /// Errors if the value is not finite, or if the rounded value has more than `precision` digits,
+    /// i.e. if its integer part has more than `precision - scale` digits.
+    pub fn from_f64(value: f64, precision: Precision, scale: i8) -> ConversionResult<Self> {
+        let exact = BigDecimal::try_from(value).map_err(|_| {
+            ConversionError::InvalidDecimal(format!("{value} is not a finite number"))
+        })?;
+        let rounded = exact.with_scale_round(scale.into(), RoundingMode::HalfUp);
+        Self::try_from_rounded(rounded, precision, scale, value)
+    }
+
+    /// Get a decimal with given precision and scale from an `IntermediateDecimal`,
+    /// rounding it as `rounding` prescribes if it has more than `scale` fractional digits.
+    ///
+    /// Errors with [`ConversionError::DecimalRoundingError`] if `rounding` is [`DecimalRounding::Error`]
+    /// and the value has nonzero digits past the scale,
+    /// and with [`ConversionError::InvalidDecimal`] if the rounded value has more than `precision` digits.
+    pub fn try_from_intermediate_decimal(
+        value: &IntermediateDecimal,
+        precision: Precision,
+        scale: i8,
+        rounding: DecimalRounding,
+    ) -> ConversionResult<Self> {
+        let exact = value.value();
+        let mode = match rounding {
+            DecimalRounding::HalfUp => RoundingMode::HalfUp,
+            DecimalRounding::HalfEven => RoundingMode::HalfEven,
+            DecimalRounding::Truncate | DecimalRounding::Error => RoundingMode::Down,
+        };
+        let rounded = exact.with_scale_round(scale.into(), mode);
+        if rounding == DecimalRounding::Error && rounded != exact {
+            return Err(ConversionError::DecimalRoundingError(format!(
+                "{value} has more than {scale} fractional digits"
+            )));
+        }
+        Self::try_from_rounded(rounded, precision, scale, value)
+    }
+
+    /// The decimal with the digits of `rounded`, which must already have a scale of `scale`,
+    /// erroring if it has more than `precision` digits. `value` is what was rounded, for the error message.
+    fn try_from_rounded(
+        rounded: BigDecimal,
+        precision: Precision,
+        scale: i8,
+        value: impl std::fmt::Display,
+    ) -> ConversionResult<Self> {
+        if rounded.digits() > precision.value().into() {
+            return Err(ConversionError::InvalidDecimal(format!(
+                "{value} has more than {} digits when rounded to a scale of {scale}",
+                precision.value()
+            )));
+        }
+        let (digits, _) = rounded.into_bigint_and_exponent();
+        Ok(Decimal::new(digits.try_into()?, precision, scale))
+    }
+}
+
+/// Checks that the unscaled decimal `value` has at most `precision` digits.
+///
+/// At the maximum precision of 75, every scalar is accepted, since the range of such decimals
+/// is limited by the scalar field rather than by a number of digits.
+pub(crate) fn check_decimal_precision<S: Scalar>(
+    value: &S,
+    precision: Precision,
+) -> ConversionResult<()> {
+    if precision.value() == MAX_SUPPORTED_PRECISION {
+        return Ok(());
+    }
+    let value = convert_scalar_to_i256(value).to_string();
+    if value.trim_start_matches('-').len() > precision.value().into() {
+        return Err(ConversionError::DecimalValueExceedsPrecision {
+            value,
+            precision: precision.value(),
+        });
+    }
+    Ok(())
+}
+
+/// The precision and scale of the `Decimal75` type of a decimal literal, which are the number of
+/// digits and of fractional digits of the literal without trailing zeros.
+///
+/// ## Errors
+/// Returns `ConversionError::InvalidScale` if the literal has more than 75 fractional digits,
+/// or needs a negative scale below -75, and `ConversionError::InvalidPrecision` if it has
+/// more than 75 digits.
+pub(crate) fn literal_precision_and_scale(
+    d: &IntermediateDecimal,
+) -> ConversionResult<(Precision, i8)> {
+    let value = d.value();
+    let scale = value.fractional_digit_count();
+    if scale.unsigned_abs() > MAX_SUPPORTED_PRECISION.into() {
+        return Err(ConversionError::InvalidScale(scale));
+    }
+    let digits = value.digits();
+    let precision = u8::try_from(digits)
+        .ok()
+        .and_then(|digits| Precision::new(digits).ok())
+        .ok_or(ConversionError::InvalidPrecision(
+            digits.min(u8::MAX.into()) as u8,
+        ))?;
+    Ok((precision, scale as i8))
 }
 
 /// Fallibly attempts to convert an `IntermediateDecimal` into the
@@ -320,4 +442,131 @@ mod scale_adjust_test {
         )
         .is_ok());
     }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn we_can_get_decimals_from_f64s_by_rounding_to_the_nearest_value() {
+        let precision = Precision::new(5).unwrap();
+        let decimals = [1.005, 2.5, -3.14, 0.015, -0.125, 999.994]
+            .map(|value| Decimal::<Curve25519Scalar>::from_f64(value, precision, 2).unwrap());
+        assert_eq!(
+            decimals.clone().map(|decimal| decimal.value),
+            // 1.005 and 0.015 are slightly less than their decimal representations,
+            // while -0.125 is exact, so it rounds away from zero.
+            [100, 250, -314, 1, -13, 99999].map(Curve25519Scalar::from)
+        );
+        assert!(decimals
+            .iter()
+            .all(|decimal| decimal.precision == precision && decimal.scale == 2));
+
+        assert_eq!(
+            Decimal::<Curve25519Scalar>::from_f64(2.5, precision, 0)
+                .unwrap()
+                .value,
+            Curve25519Scalar::from(3)
+        );
+        assert_eq!(
+            Decimal::<Curve25519Scalar>::from_f64(1234.0, precision, -2)
+                .unwrap()
+                .value,
+            Curve25519Scalar::from(12)
+        );
+    }
+
+    #[test]
+    fn we_cannot_get_decimals_from_f64s_that_exceed_the_precision_or_are_not_finite() {
+        let precision = Precision::new(5).unwrap();
+        for value in [999.995, -1000.0, 1e300, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                Decimal::<Curve25519Scalar>::from_f64(value, precision, 2),
+                Err(ConversionError::InvalidDecimal(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_ingest_a_decimal_with_more_fractional_digits_than_the_scale_with_each_rounding() {
+        let precision = Precision::new(5).unwrap();
+        let ingest = |value: &str, rounding| {
+            Decimal::<Curve25519Scalar>::try_from_intermediate_decimal(
+                &value.parse().unwrap(),
+                precision,
+                2,
+                rounding,
+            )
+            .map(|decimal| decimal.value)
+        };
+        for (rounding, expected) in [
+            (DecimalRounding::HalfUp, 101),
+            (DecimalRounding::HalfEven, 100),
+            (DecimalRounding::Truncate, 100),
+        ] {
+            assert_eq!(
+                ingest("1.005", rounding).unwrap(),
+                Curve25519Scalar::from(expected),
+                "{rounding:?}"
+            );
+        }
+        assert!(matches!(
+            ingest("1.005", DecimalRounding::Error),
+            Err(ConversionError::DecimalRoundingError(_))
+        ));
+
+        assert_eq!(
+            ingest("-1.015", DecimalRounding::HalfEven).unwrap(),
+            Curve25519Scalar::from(-102)
+        );
+        assert_eq!(
+            ingest("-1.019", DecimalRounding::Truncate).unwrap(),
+            Curve25519Scalar::from(-101)
+        );
+        // digits that are all zero can be dropped under any rounding
+        for rounding in [
+            DecimalRounding::HalfUp,
+            DecimalRounding::HalfEven,
+            DecimalRounding::Truncate,
+            DecimalRounding::Error,
+        ] {
+            assert_eq!(
+                ingest("1.2300", rounding).unwrap(),
+                Curve25519Scalar::from(123)
+            );
+            assert_eq!(ingest("7", rounding).unwrap(), Curve25519Scalar::from(700));
+        }
+        // rounding up can exceed the precision
+        assert!(matches!(
+            ingest("999.995", DecimalRounding::HalfUp),
+            Err(ConversionError::InvalidDecimal(_))
+        ));
+        assert_eq!(
+            ingest("999.995", DecimalRounding::Truncate).unwrap(),
+            Curve25519Scalar::from(99999)
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_precision_and_scale_of_decimal_literals_up_to_the_max_scale() {
+        let precision_and_scale = |literal: &str| {
+            literal_precision_and_scale(&IntermediateDecimal::try_from(literal).unwrap())
+                .map(|(precision, scale)| (precision.value(), scale))
+        };
+        assert_eq!(precision_and_scale("12.3400").unwrap(), (4, 2));
+        assert_eq!(
+            precision_and_scale("-0.000000000000000001").unwrap(),
+            (1, 18)
+        );
+        assert_eq!(precision_and_scale("1200.0").unwrap(), (2, -2));
+        let literal_of_scale = |scale: usize| format!("0.{}1", "0".repeat(scale - 1));
+        assert_eq!(precision_and_scale(&literal_of_scale(75)).unwrap(), (1, 75));
+        for scale in [76, 128, 300] {
+            assert!(matches!(
+                precision_and_scale(&literal_of_scale(scale)),
+                Err(ConversionError::InvalidScale(s)) if s == scale as i64
+            ));
+        }
+        assert!(matches!(
+            precision_and_scale(&format!("{}.5", "9".repeat(75))),
+            Err(ConversionError::InvalidPrecision(76))
+        ));
+    }
 }
@@ -99,7 +99,7 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::SmallInt(c) => c.inner_product(evaluation_vec),
             Column::Int(c) => c.inner_product(evaluation_vec),
             Column::BigInt(c) => c.inner_product(evaluation_vec),
-            Column::VarChar((_, c)) => c.inner_product(evaluation_vec),
+            Column::VarChar((_, c)) | Column::Binary(_, (_, c)) => c.inner_product(evaluation_vec),
             Column::Int128(c) => c.inner_product(evaluation_vec),
             Column::Decimal75(_, _, c) => c.inner_product(evaluation_vec),
         }
@@ -112,7 +112,7 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::SmallInt(c) => c.mul_add(res, multiplier),
             Column::Int(c) => c.mul_add(res, multiplier),
             Column::BigInt(c) => c.mul_add(res, multiplier),
-            Column::VarChar((_, c)) => c.mul_add(res, multiplier),
+            Column::VarChar((_, c)) | Column::Binary(_, (_, c)) => c.mul_add(res, multiplier),
             Column::Int128(c) => c.mul_add(res, multiplier),
             Column::Decimal75(_, _, c) => c.mul_add(res, multiplier),
         }
@@ -125,7 +125,7 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::SmallInt(c) => c.to_sumcheck_term(num_vars),
             Column::Int(c) => c.to_sumcheck_term(num_vars),
             Column::BigInt(c) => c.to_sumcheck_term(num_vars),
-            Column::VarChar((_, c)) => c.to_sumcheck_term(num_vars),
+            Column::VarChar((_, c)) | Column::Binary(_, (_, c)) => c.to_sumcheck_term(num_vars),
             Column::Int128(c) => c.to_sumcheck_term(num_vars),
             Column::Decimal75(_, _, c) => c.to_sumcheck_term(num_vars),
         }
@@ -138,7 +138,7 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::SmallInt(c) => MultilinearExtension::<S>::id(c),
             Column::Int(c) => MultilinearExtension::<S>::id(c),
             Column::BigInt(c) => MultilinearExtension::<S>::id(c),
-            Column::VarChar((_, c)) => MultilinearExtension::<S>::id(c),
+            Column::VarChar((_, c)) | Column::Binary(_, (_, c)) => MultilinearExtension::<S>::id(c),
             Column::Int128(c) => MultilinearExtension::<S>::id(c),
             Column::Decimal75(_, _, c) => MultilinearExtension::<S>::id(c),
         }
@@ -1,9 +1,74 @@
+use crate::base::database::ColumnType;
+use proof_of_sql_parser::{Identifier, ResourceId};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
-/// These errors occur when a proof failed to verify.
+/// These errors occur when a proof failed to be created or failed to verify.
 pub enum ProofError {
     #[error("Verification error: {0}")]
-    /// This error occurs when a proof failed to verify.
+    /// This error occurs when a proof failed to verify because it is malformed or does not fit the query,
+    /// for instance when it has the wrong number of terms.
     VerificationError(&'static str),
+    #[error("Constraint unsatisfied: {0}")]
+    /// This error occurs when a value of the proof fails a constraint that the verifier checks directly,
+    /// such as a bitwise decomposition or the bounds of a division remainder.
+    ConstraintUnsatisfied(&'static str),
+    #[error("Commitment mismatch: {0}")]
+    /// This error occurs when the commitments that the verifier uses do not open to the evaluations of the proof,
+    /// for instance because the data of a column differs from the data that the proof was created from.
+    CommitmentMismatch(&'static str),
+    #[error("Sumcheck failed: {0}")]
+    /// This error occurs when the sumcheck proof does not prove that the constraints of the query hold.
+    SumcheckFailed(&'static str),
+    #[error("Sumcheck round polynomial has degree {degree}, but at most {max_degree} is allowed")]
+    /// This error occurs when a round of the sumcheck proof has a polynomial of a higher degree than the query allows.
+    DegreeExceeded {
+        /// The highest degree that the query allows
+        max_degree: usize,
+        /// The degree of the round polynomial of the proof
+        degree: usize,
+    },
+    #[error("Proof creation was cancelled")]
+    /// This error occurs when the cancellation token was set while a proof was being created.
+    Cancelled,
+    #[error("Division by zero")]
+    /// This error occurs when a provable division has a zero divisor.
+    DivisionByZero,
+    #[error("Division overflow")]
+    /// This error occurs when a provable integer division has a quotient that does not fit in its type,
+    /// such as the minimum value of the type divided by -1.
+    DivisionOverflow,
+    #[error("Invalid plan: {0}")]
+    /// This error occurs when the plan cannot be proven, for instance because it was not created
+    /// through its constructors and has a division whose quotient is neither an integer nor a decimal.
+    InvalidPlan(&'static str),
+    #[error("Column '{column}' was not found in table '{table}'")]
+    /// This error occurs when the query references a column that the data accessor does not have.
+    UnknownColumn {
+        /// The missing column
+        column: Box<Identifier>,
+        /// The table that was expected to contain the column
+        table: Box<ResourceId>,
+    },
+    #[error("Column '{column}' has type {actual}, but the query expects {expected}")]
+    /// This error occurs when a column of the data accessor does not have the type that the query was planned with.
+    TypeMismatch {
+        /// The column with the unexpected type
+        column: Box<Identifier>,
+        /// The type that the query expects
+        expected: ColumnType,
+        /// The type of the column in the data accessor
+        actual: ColumnType,
+    },
+    #[error("Plan mismatch: the proof was created for a different query plan")]
+    /// This error occurs when a proof is verified against a different plan than the one it was created for.
+    PlanMismatch,
+    #[error("Proof version mismatch: expected {expected} but found {found}")]
+    /// This error occurs when a proof was created with an incompatible version of the proof format.
+    VersionMismatch {
+        /// The version of the proof format that the verifier supports
+        expected: u32,
+        /// The version of the proof format that the proof was created with
+        found: u32,
+    },
 }
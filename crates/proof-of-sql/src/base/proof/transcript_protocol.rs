@@ -138,6 +138,8 @@ pub enum MessageLabel {
     QueryResultData,
     /// Represents a query for bit distribution data.
     QueryBitDistributions,
+    /// Represents the values of the window aggregates of a query.
+    QueryWindowAggregates,
     /// Represents a challenge in a sumcheck query.
     QuerySumcheckChallenge,
     /// Represents a hash used for verification purposes.
@@ -148,12 +150,14 @@ pub enum MessageLabel {
     DoryChallenge,
     /// Represents challenges posted after result computation.
     PostResultChallenges,
-    /// Represents a SQL query
-    ProofExpr,
+    /// Represents the hash of the plan of a SQL query.
+    PlanHash,
     /// Represents the length of a table.
     TableLength,
     /// Represents an offset for a generator.
     GeneratorOffset,
+    /// Represents the domain separator of a deployment.
+    DomainSeparator,
 }
 
 impl MessageLabel {
@@ -172,6 +176,7 @@ impl MessageLabel {
             MessageLabel::QueryCommit => b"querycommit v1",
             MessageLabel::QueryResultData => b"queryresultdata v1",
             MessageLabel::QueryBitDistributions => b"querybitdistributions v1",
+            MessageLabel::QueryWindowAggregates => b"querywindowaggregates v1",
             MessageLabel::QueryMleEvaluations => b"querymleevaluations v1",
             MessageLabel::QueryMleEvaluationsChallenge => b"querymleevaluationschallenge v1",
             MessageLabel::QuerySumcheckChallenge => b"querysumcheckchallenge v1",
@@ -179,9 +184,10 @@ impl MessageLabel {
             MessageLabel::DoryMessage => b"dorymessage v1",
             MessageLabel::DoryChallenge => b"dorychallenge v1",
             MessageLabel::PostResultChallenges => b"postresultchallenges v1",
-            MessageLabel::ProofExpr => b"proofexpr v1",
+            MessageLabel::PlanHash => b"planhash v1",
             MessageLabel::TableLength => b"tablelength v1",
             MessageLabel::GeneratorOffset => b"generatoroffset v1",
+            MessageLabel::DomainSeparator => b"domainseparator v1",
         }
     }
 }
@@ -11,6 +11,10 @@ pub(crate) use mont_scalar::MontScalar;
 mod mont_scalar_from;
 #[cfg(test)]
 mod mont_scalar_from_test;
+mod varchar_hash;
+pub use varchar_hash::{VarCharHash, VARCHAR_HASH_CONTEXT};
+#[cfg(test)]
+mod varchar_hash_test;
 
 #[cfg(any(test, feature = "test"))]
 #[cfg(feature = "blitzar")]
@@ -28,6 +32,7 @@ pub trait Scalar:
     + PartialEq
     + Default
     + for<'a> From<&'a str>
+    + for<'a> From<&'a [u8]>
     + Sync
     + Send
     + num_traits::One
@@ -52,6 +57,7 @@ pub trait Scalar:
     + std::convert::Into<[u64; 4]>
     + std::convert::From<[u64; 4]>
     + core::cmp::Ord
+    + core::hash::Hash
     + std::ops::Neg<Output = Self>
     + num_traits::Zero
     + std::ops::AddAssign
@@ -0,0 +1,46 @@
+use super::Scalar;
+use serde::{Deserialize, Serialize};
+
+/// The blake3 key derivation context used by [`VarCharHash::DomainSeparatedBlake3`].
+pub const VARCHAR_HASH_CONTEXT: &str = "proof-of-sql 2024-06 varchar scalar hash";
+
+/// The hash function that maps the strings of `VarChar` columns and literals, and the bytes of `Binary` ones, to scalars.
+///
+/// Strings are committed and proven through their scalar hashes, so whoever computes the
+/// commitments, the prover and the verifier must all use the same hash.
+/// Both hashes truncate a 256-bit blake3 output to 252 bits, so they are expected to have
+/// about 126 bits of collision resistance. Two strings with the same hash are indistinguishable
+/// to the proof system, e.g. they compare as equal. The empty string always hashes to zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VarCharHash {
+    /// Plain blake3. This is the hash behind `From<&str>` and `From<&[u8]>` for the scalars of this crate.
+    #[default]
+    Blake3,
+    /// Blake3 in key derivation mode with the context string [`VARCHAR_HASH_CONTEXT`],
+    /// which separates it from any other use of blake3 on the same strings.
+    DomainSeparatedBlake3,
+}
+
+impl VarCharHash {
+    /// Hashes `value` to a scalar.
+    pub fn hash_to_scalar<S: Scalar>(&self, value: &str) -> S {
+        self.hash_bytes_to_scalar(value.as_bytes())
+    }
+
+    /// Hashes the bytes of a `Binary` value to a scalar, the same way as a string with those bytes.
+    pub fn hash_bytes_to_scalar<S: Scalar>(&self, value: &[u8]) -> S {
+        match self {
+            Self::Blake3 => value.into(),
+            Self::DomainSeparatedBlake3 if value.is_empty() => S::zero(),
+            Self::DomainSeparatedBlake3 => {
+                let mut hasher = blake3::Hasher::new_derive_key(VARCHAR_HASH_CONTEXT);
+                hasher.update(value);
+                let mut bytes: [u8; 32] = hasher.finalize().into();
+                bytes[31] &= 0b00001111_u8;
+                let limbs = [0, 1, 2, 3]
+                    .map(|i| u64::from_le_bytes(bytes[8 * i..8 * (i + 1)].try_into().unwrap()));
+                S::from(limbs)
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use super::{Curve25519Scalar, Scalar, VarCharHash};
+use crate::proof_primitive::dory::DoryScalar;
+
+#[test]
+fn the_default_hash_matches_the_scalar_conversion_from_strings() {
+    for value in ["", "a", "hello world", "🦀"] {
+        assert_eq!(
+            VarCharHash::default().hash_to_scalar::<Curve25519Scalar>(value),
+            Curve25519Scalar::from(value)
+        );
+        assert_eq!(
+            VarCharHash::Blake3.hash_to_scalar::<DoryScalar>(value),
+            DoryScalar::from(value)
+        );
+    }
+}
+
+#[test]
+fn the_domain_separated_hash_differs_from_the_plain_hash() {
+    for value in ["a", "hello world", "🦀"] {
+        let plain: Curve25519Scalar = VarCharHash::Blake3.hash_to_scalar(value);
+        let separated: Curve25519Scalar = VarCharHash::DomainSeparatedBlake3.hash_to_scalar(value);
+        assert_ne!(plain, separated);
+        assert_eq!(
+            separated,
+            VarCharHash::DomainSeparatedBlake3.hash_to_scalar(value)
+        );
+    }
+    assert_eq!(
+        VarCharHash::DomainSeparatedBlake3.hash_to_scalar::<Curve25519Scalar>(""),
+        Curve25519Scalar::ZERO
+    );
+}
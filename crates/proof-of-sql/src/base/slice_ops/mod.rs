@@ -14,10 +14,16 @@ mod mul_add_assign_test;
 mod slice_cast;
 #[cfg(test)]
 mod slice_cast_test;
+#[cfg(feature = "vectorized-equality")]
+mod slice_eq;
+#[cfg(all(test, feature = "vectorized-equality"))]
+mod slice_eq_test;
 
 pub use inner_product::*;
 pub use mul_add_assign::*;
 pub use slice_cast::*;
+#[cfg(feature = "vectorized-equality")]
+pub use slice_eq::*;
 
 mod batch_inverse;
 pub use batch_inverse::*;
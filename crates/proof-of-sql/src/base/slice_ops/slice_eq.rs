@@ -0,0 +1,31 @@
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::{ParallelSlice, ParallelSliceMut},
+};
+
+/// The number of elements that [`vectorized_eq`] compares in each inner loop.
+const EQ_CHUNK_LEN: usize = 64;
+
+/// This operation does `result[i] = lhs[i] == rhs[i]` for `i` in `0..result.len()`.
+///
+/// The slices are compared in fixed-size chunks with a branchless inner loop,
+/// which the compiler is able to turn into SIMD comparisons for primitive integers.
+///
+/// It panics if `lhs` or `rhs` has a different length than `result`.
+pub fn vectorized_eq<T>(result: &mut [bool], lhs: &[T], rhs: &[T])
+where
+    T: Send + Sync + PartialEq + Copy,
+{
+    assert_eq!(result.len(), lhs.len());
+    assert_eq!(result.len(), rhs.len());
+    result
+        .par_chunks_mut(EQ_CHUNK_LEN)
+        .zip(lhs.par_chunks(EQ_CHUNK_LEN))
+        .zip(rhs.par_chunks(EQ_CHUNK_LEN))
+        .with_min_len(super::MIN_RAYON_LEN / EQ_CHUNK_LEN)
+        .for_each(|((result_chunk, lhs_chunk), rhs_chunk)| {
+            for ((res_i, &lhs_i), &rhs_i) in result_chunk.iter_mut().zip(lhs_chunk).zip(rhs_chunk) {
+                *res_i = lhs_i == rhs_i;
+            }
+        });
+}
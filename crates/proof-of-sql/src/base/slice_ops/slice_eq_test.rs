@@ -0,0 +1,27 @@
+use super::*;
+
+#[test]
+fn test_vectorized_eq() {
+    let mut result = vec![false; 4];
+    vectorized_eq(&mut result, &[1i64, 2, 3, 4], &[1, 0, 3, -4]);
+    assert_eq!(result, vec![true, false, true, false]);
+}
+
+/// test vectorized_eq with slices that are not a multiple of the chunk length
+#[test]
+fn test_vectorized_eq_across_chunks() {
+    let lhs: Vec<i32> = (0..1000).collect();
+    let rhs: Vec<i32> = (0..1000).map(|i| if i % 3 == 0 { i } else { -i }).collect();
+    let mut result = vec![false; 1000];
+    vectorized_eq(&mut result, &lhs, &rhs);
+    let expected: Vec<bool> = (0..1000).map(|i| i % 3 == 0).collect();
+    assert_eq!(result, expected);
+}
+
+/// test vectorized_eq panics when the slices have different lengths
+#[test]
+#[should_panic]
+fn test_vectorized_eq_uneven_panic() {
+    let mut result = vec![false; 4];
+    vectorized_eq(&mut result, &[1i64, 2, 3, 4], &[1, 2, 3]);
+}
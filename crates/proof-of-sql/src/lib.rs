@@ -2,3 +2,5 @@
 pub mod base;
 pub mod proof_primitive;
 pub mod sql;
+#[cfg(any(test, feature = "test"))]
+pub mod testing;
@@ -96,9 +96,26 @@ impl Commitment for DoryCommitment {
         committable_columns: &[CommittableColumn],
         offset: usize,
         setup: &Self::PublicSetup,
+    ) {
+        Self::compute_commitments_with_msm_window(
+            commitments,
+            committable_columns,
+            offset,
+            setup,
+            None,
+        );
+    }
+
+    fn compute_commitments_with_msm_window(
+        commitments: &mut [Self],
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup,
+        msm_window_bits: Option<usize>,
     ) {
         assert_eq!(commitments.len(), committable_columns.len());
-        let c = super::compute_dory_commitments(committable_columns, offset, setup);
+        let c =
+            super::compute_dory_commitments(committable_columns, offset, setup, msm_window_bits);
         commitments.copy_from_slice(&c);
     }
 
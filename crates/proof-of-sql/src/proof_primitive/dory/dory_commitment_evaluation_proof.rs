@@ -6,7 +6,7 @@ use super::{
 };
 use crate::base::commitment::CommitmentEvaluationProof;
 use merlin::Transcript;
-use num_traits::One;
+use num_traits::{One, Zero};
 use thiserror::Error;
 
 /// The `CommitmentEvaluationProof` for the Dory PCS.
@@ -15,7 +15,8 @@ pub type DoryEvaluationProof = DoryMessages;
 /// The error type for the Dory PCS.
 #[derive(Error, Debug)]
 pub enum DoryError {
-    /// This error occurs when the generators offset is invalid.
+    /// This error occurs when the generators offset is invalid, that is, when it is not a multiple
+    /// of the size of the evaluation vector.
     #[error("invalid generators offset: {0}")]
     InvalidGeneratorsOffset(u64),
     /// This error occurs when the proof fails to verify.
@@ -26,6 +27,25 @@ pub enum DoryError {
     SmallSetup(usize, usize),
 }
 
+/// Extend `b_point` so that the evaluation of a vector shifted by `generators_offset` at the result
+/// is the evaluation of the unshifted vector at `b_point`.
+///
+/// The evaluation vector of `b_point` must stay tensor-structured, so this is only possible when
+/// the offset is a multiple of `2^b_point.len()`. Then the shifted vector lies within the block of
+/// that size whose index is `generators_offset >> b_point.len()`, and the bits of the index are
+/// appended to `b_point` as the high-order coordinates.
+fn offset_b_point(b_point: &[F], generators_offset: u64) -> Option<Vec<F>> {
+    let num_vars = b_point.len() as u32;
+    let block = generators_offset.checked_shr(num_vars).unwrap_or(0);
+    if generators_offset != block.checked_shl(num_vars).unwrap_or(0) {
+        return None;
+    }
+    let block_bits = u64::BITS - block.leading_zeros();
+    Some(Vec::from_iter(b_point.iter().copied().chain(
+        (0..block_bits).map(|bit| F::from((block >> bit) & 1)),
+    )))
+}
+
 impl CommitmentEvaluationProof for DoryEvaluationProof {
     type Scalar = DoryScalar;
     type Commitment = DoryCommitment;
@@ -42,13 +62,17 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
         setup: &Self::ProverPublicSetup,
     ) -> Self {
         // Dory PCS Logic
-        if generators_offset != 0 {
-            // TODO: support offsets other than 0.
-            // Note: this will always result in a verification error.
-            return Default::default();
-        }
-        let a: &[F] = bytemuck::TransparentWrapper::peel_slice(a);
         let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(b_point);
+        let Some(b_point) = offset_b_point(b_point, generators_offset) else {
+            return Default::default(); // Note: this will always result in a verification error.
+        };
+        let b_point = &b_point[..];
+        let a: &[F] = bytemuck::TransparentWrapper::peel_slice(a);
+        let a = &Vec::from_iter(
+            core::iter::repeat(F::zero())
+                .take(generators_offset as usize)
+                .chain(a.iter().copied()),
+        )[..];
         let prover_setup: &ProverSetup = &setup.public_parameters().into();
         let nu = compute_nu(b_point.len(), setup.sigma());
         if nu > prover_setup.max_nu {
@@ -101,10 +125,9 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
             batching_factors.iter().map(|f| f.0),
         );
         // Dory PCS Logic
-        if generators_offset != 0 {
-            return Err(DoryError::InvalidGeneratorsOffset(generators_offset));
-        }
         let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(b_point);
+        let b_point = &offset_b_point(b_point, generators_offset)
+            .ok_or(DoryError::InvalidGeneratorsOffset(generators_offset))?[..];
         let verifier_setup = setup.verifier_setup();
         let mut messages = self.clone();
         let nu = compute_nu(b_point.len(), setup.sigma());
@@ -1,4 +1,4 @@
-use super::{test_rng, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar};
+use super::{test_rng, DoryError, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar};
 use crate::base::commitment::{commitment_evaluation_proof_test::*, CommitmentEvaluationProof};
 use ark_std::UniformRand;
 use merlin::Transcript;
@@ -56,6 +56,48 @@ fn test_random_ipa_with_various_lengths() {
     }
 }
 
+#[test]
+fn test_random_ipa_with_offsets_that_are_multiples_of_the_evaluation_vector_size() {
+    let prover_setup = DoryProverPublicSetup::rand(6, 4, &mut test_rng());
+    let verifier_setup = (&prover_setup).into();
+    for (length, offset) in [(5, 8), (5, 1016), (16, 1008), (100, 512)] {
+        test_random_commitment_evaluation_proof::<DoryEvaluationProof>(
+            length,
+            offset,
+            &prover_setup,
+            &verifier_setup,
+        );
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_dory_evaluation_proof_with_an_unaligned_offset() {
+    let mut rng = ark_std::test_rng();
+    let prover_setup = DoryProverPublicSetup::rand(6, 4, &mut rng);
+    let verifier_setup = (&prover_setup).into();
+    let a = core::iter::repeat_with(|| DoryScalar::rand(&mut rng))
+        .take(5)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| DoryScalar::rand(&mut rng))
+        .take(3)
+        .collect::<Vec<_>>();
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = DoryEvaluationProof::new(&mut transcript, &a, &b_point, 123, &prover_setup);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    assert!(matches!(
+        proof.verify_proof(
+            &mut transcript,
+            &Default::default(),
+            &Default::default(),
+            &b_point,
+            123,
+            5,
+            &verifier_setup,
+        ),
+        Err(DoryError::InvalidGeneratorsOffset(123))
+    ));
+}
+
 #[test]
 fn we_can_serialize_and_deserialize_dory_evaluation_proofs() {
     let mut rng = ark_std::test_rng();
@@ -1,13 +1,18 @@
-use super::{pairings, DoryCommitment, DoryProverPublicSetup, DoryScalar, G1Projective};
+use super::{
+    pairings, pippenger::msm_with_window, DoryCommitment, DoryProverPublicSetup, DoryScalar,
+    G1Affine, G1Projective, F,
+};
 use crate::base::commitment::CommittableColumn;
 use ark_ec::VariableBaseMSM;
 use core::iter::once;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 #[tracing::instrument(name = "compute_dory_commitment_impl (cpu)", level = "debug", skip_all)]
 fn compute_dory_commitment_impl<'a, T>(
     column: &'a [T],
     offset: usize,
     setup: &DoryProverPublicSetup,
+    msm_window_bits: Option<usize>,
 ) -> DoryCommitment
 where
     &'a T: Into<DoryScalar>,
@@ -26,12 +31,16 @@ where
     let remaining_rows = remaining_elements.chunks(num_columns);
 
     // Compute commitments for the rows.
-    let first_row_commit = G1Projective::msm_unchecked(
+    let msm = |bases: &[G1Affine], scalars: &[F]| match msm_window_bits {
+        Some(window_bits) => msm_with_window(bases, scalars, window_bits),
+        None => G1Projective::msm_unchecked(bases, scalars),
+    };
+    let first_row_commit = msm(
         &setup.public_parameters().Gamma_1[first_row_offset..num_columns],
         &Vec::from_iter(first_row.iter().map(|s| s.into().0)),
     );
     let remaining_row_commits = remaining_rows.map(|row| {
-        G1Projective::msm_unchecked(
+        msm(
             &setup.public_parameters().Gamma_1[..num_columns],
             &Vec::from_iter(row.iter().map(|s| s.into().0)),
         )
@@ -48,28 +57,131 @@ fn compute_dory_commitment(
     committable_column: &CommittableColumn,
     offset: usize,
     setup: &DoryProverPublicSetup,
+    msm_window_bits: Option<usize>,
 ) -> DoryCommitment {
     match committable_column {
-        CommittableColumn::Scalar(column) => compute_dory_commitment_impl(column, offset, setup),
-        CommittableColumn::SmallInt(column) => compute_dory_commitment_impl(column, offset, setup),
-        CommittableColumn::Int(column) => compute_dory_commitment_impl(column, offset, setup),
-        CommittableColumn::BigInt(column) => compute_dory_commitment_impl(column, offset, setup),
-        CommittableColumn::Int128(column) => compute_dory_commitment_impl(column, offset, setup),
+        CommittableColumn::Scalar(column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
+        CommittableColumn::SmallInt(column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
+        CommittableColumn::Int(column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
+        CommittableColumn::BigInt(column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
+        CommittableColumn::Int128(column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
         CommittableColumn::Decimal75(_, _, column) => {
-            compute_dory_commitment_impl(column, offset, setup)
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
+        CommittableColumn::VarChar(column) | CommittableColumn::Binary(_, column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
+        }
+        CommittableColumn::Boolean(column) => {
+            compute_dory_commitment_impl(column, offset, setup, msm_window_bits)
         }
-        CommittableColumn::VarChar(column) => compute_dory_commitment_impl(column, offset, setup),
-        CommittableColumn::Boolean(column) => compute_dory_commitment_impl(column, offset, setup),
     }
 }
 
+/// Compute the commitments of the columns, each on a separate thread with the
+/// `parallel-commitments` feature. The columns are independent, so the commitments
+/// are the same either way.
+///
+/// The rows of each column are committed with multi-scalar multiplications over windows of
+/// `msm_window_bits` bits, or with the window that `ark-ec` chooses from the number of columns
+/// of the matrix if it is `None`. The window only changes the time that this takes.
 pub(super) fn compute_dory_commitments(
     committable_columns: &[CommittableColumn],
     offset: usize,
     setup: &DoryProverPublicSetup,
+    msm_window_bits: Option<usize>,
+) -> Vec<DoryCommitment> {
+    if cfg!(feature = "parallel-commitments") {
+        compute_dory_commitments_in_parallel(committable_columns, offset, setup, msm_window_bits)
+    } else {
+        compute_dory_commitments_sequentially(committable_columns, offset, setup, msm_window_bits)
+    }
+}
+
+fn compute_dory_commitments_sequentially(
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &DoryProverPublicSetup,
+    msm_window_bits: Option<usize>,
 ) -> Vec<DoryCommitment> {
     committable_columns
         .iter()
-        .map(|column| compute_dory_commitment(column, offset, setup))
+        .map(|column| compute_dory_commitment(column, offset, setup, msm_window_bits))
+        .collect()
+}
+
+fn compute_dory_commitments_in_parallel(
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &DoryProverPublicSetup,
+    msm_window_bits: Option<usize>,
+) -> Vec<DoryCommitment> {
+    // An indexed parallel iterator collects in the order of the columns.
+    committable_columns
+        .par_iter()
+        .map(|column| compute_dory_commitment(column, offset, setup, msm_window_bits))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_primitive::dory::test_rng;
+
+    #[test]
+    fn parallel_and_sequential_commitments_are_identical() {
+        let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
+        let bigints = Vec::from_iter((0..50).map(|i| i * i - 100));
+        let varchars = Vec::from_iter((0..50).map(|i| [i as u64, 1, 2, 3]));
+        let booleans = Vec::from_iter((0..50).map(|i| i % 3 == 0));
+        let columns = Vec::from_iter((0..50).map(|i| match i % 3 {
+            0 => CommittableColumn::BigInt(&bigints[i..]),
+            1 => CommittableColumn::VarChar(varchars[..i].to_vec()),
+            _ => CommittableColumn::Boolean(&booleans[i / 2..]),
+        }));
+        for offset in [0, 3, 20] {
+            let sequential = compute_dory_commitments_sequentially(&columns, offset, &setup, None);
+            let parallel = compute_dory_commitments_in_parallel(&columns, offset, &setup, None);
+            assert_eq!(parallel.len(), columns.len());
+            assert_eq!(parallel, sequential);
+            assert_eq!(
+                compute_dory_commitments(&columns, offset, &setup, None),
+                sequential
+            );
+        }
+    }
+
+    #[test]
+    fn commitments_with_any_msm_window_are_identical() {
+        let setup = DoryProverPublicSetup::rand(5, 3, &mut test_rng());
+        let bigints = Vec::from_iter((0..50).map(|i| i * i * i - 1000 * i));
+        let int128s = Vec::from_iter((0..50).map(|i| i128::MAX - i * i));
+        let varchars = Vec::from_iter((0..50).map(|i| [i as u64, u64::MAX, 2, 3]));
+        let booleans = Vec::from_iter((0..50).map(|i| i % 3 == 0));
+        let columns = [
+            CommittableColumn::BigInt(&bigints),
+            CommittableColumn::Int128(&int128s),
+            CommittableColumn::VarChar(varchars),
+            CommittableColumn::Boolean(&booleans),
+        ];
+        for offset in [0, 3, 20] {
+            let expected = compute_dory_commitments(&columns, offset, &setup, None);
+            for window_bits in 1..=10 {
+                assert_eq!(
+                    compute_dory_commitments(&columns, offset, &setup, Some(window_bits)),
+                    expected,
+                    "offset {offset}, window {window_bits}"
+                );
+            }
+        }
+    }
+}
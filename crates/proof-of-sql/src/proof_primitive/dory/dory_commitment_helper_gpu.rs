@@ -278,15 +278,20 @@ fn compute_dory_commitment(
             compute_dory_commitment_impl(column, offset, setup)
         }
         CommittableColumn::Scalar(column) => compute_dory_commitment_impl(column, offset, setup),
-        CommittableColumn::VarChar(column) => compute_dory_commitment_impl(column, offset, setup),
+        CommittableColumn::VarChar(column) | CommittableColumn::Binary(_, column) => {
+            compute_dory_commitment_impl(column, offset, setup)
+        }
         CommittableColumn::Boolean(column) => compute_dory_commitment_impl(column, offset, setup),
     }
 }
 
+/// Compute the commitments of the columns with blitzar, which chooses its own MSM windows
+/// and does not let the caller set them, so `_msm_window_bits` is ignored.
 pub(super) fn compute_dory_commitments(
     committable_columns: &[CommittableColumn],
     offset: usize,
     setup: &DoryProverPublicSetup,
+    _msm_window_bits: Option<usize>,
 ) -> Vec<DoryCommitment> {
     committable_columns
         .iter()
@@ -9,7 +9,7 @@ use num_traits::Zero;
 #[test]
 fn we_can_compute_a_dory_commitment_with_int128_values() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::Int128(&[0, -1, 2])], 0, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::Int128(&[0, -1, 2])], 0, &setup, None);
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[0], Gamma_2[0]) * F::from(0_i128)
@@ -25,6 +25,7 @@ fn we_can_compute_a_dory_commitment_with_boolean_values() {
         &[CommittableColumn::Boolean(&[true, false, true])],
         0,
         &setup,
+        None,
     );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
@@ -37,7 +38,7 @@ fn we_can_compute_a_dory_commitment_with_boolean_values() {
 #[test]
 fn we_can_compute_a_dory_commitment_with_only_one_row() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1, 2])], 0, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1, 2])], 0, &setup, None);
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[0], Gamma_2[0]) * F::from(0)
@@ -49,7 +50,8 @@ fn we_can_compute_a_dory_commitment_with_only_one_row() {
 #[test]
 fn we_can_compute_a_dory_commitment_with_exactly_one_full_row() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1, 2, 3])], 0, &setup);
+    let res =
+        compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1, 2, 3])], 0, &setup, None);
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[0], Gamma_2[0]) * F::from(0)
@@ -62,7 +64,7 @@ fn we_can_compute_a_dory_commitment_with_exactly_one_full_row() {
 #[test]
 fn we_can_compute_a_dory_commitment_with_exactly_one_full_row_and_an_offset() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[2, 3])], 2, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[2, 3])], 2, &setup, None);
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[2], Gamma_2[0]) * F::from(2)
@@ -73,7 +75,7 @@ fn we_can_compute_a_dory_commitment_with_exactly_one_full_row_and_an_offset() {
 #[test]
 fn we_can_compute_a_dory_commitment_with_exactly_one_full_row_and_an_offset_with_signed_data() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[-2, -3])], 2, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[-2, -3])], 2, &setup, None);
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[2], Gamma_2[0]) * F::from(-2)
@@ -88,6 +90,7 @@ fn we_can_compute_a_dory_commitment_with_fewer_rows_than_columns() {
         &[CommittableColumn::BigInt(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9])],
         0,
         &setup,
+        None,
     );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
@@ -113,6 +116,7 @@ fn we_can_compute_a_dory_commitment_with_more_rows_than_columns() {
         ])],
         0,
         &setup,
+        None,
     );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
@@ -141,7 +145,7 @@ fn we_can_compute_a_dory_commitment_with_more_rows_than_columns() {
 #[test]
 fn we_can_compute_a_dory_commitment_with_an_offset_and_only_one_row() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1])], 5, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1])], 5, &setup, None);
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[1], Gamma_2[1]) * F::from(0)
@@ -156,6 +160,7 @@ fn we_can_compute_a_dory_commitment_with_an_offset_and_fewer_rows_than_columns()
         &[CommittableColumn::BigInt(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9])],
         5,
         &setup,
+        None,
     );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
@@ -181,6 +186,7 @@ fn we_can_compute_a_dory_commitment_with_an_offset_and_more_rows_than_columns()
         ])],
         5,
         &setup,
+        None,
     );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
@@ -209,18 +215,23 @@ fn we_can_compute_a_dory_commitment_with_an_offset_and_more_rows_than_columns()
 #[test]
 fn we_can_compute_an_empty_dory_commitment() {
     let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 0])], 0, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 0])], 0, &setup, None);
     assert_eq!(res[0].0, GT::zero());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 0])], 5, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 0])], 5, &setup, None);
     assert_eq!(res[0].0, GT::zero());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 0])], 20, &setup);
+    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 0])], 20, &setup, None);
     assert_eq!(res[0].0, GT::zero());
 }
 
 #[test]
 fn test_compute_dory_commitment_when_sigma_is_zero() {
     let setup = DoryProverPublicSetup::rand(5, 0, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1, 2, 3, 4])], 0, &setup);
+    let res = compute_dory_commitments(
+        &[CommittableColumn::BigInt(&[0, 1, 2, 3, 4])],
+        0,
+        &setup,
+        None,
+    );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[0], Gamma_2[0]) * F::from(0)
@@ -234,7 +245,12 @@ fn test_compute_dory_commitment_when_sigma_is_zero() {
 #[test]
 fn test_compute_dory_commitment_with_zero_sigma_and_with_an_offset() {
     let setup = DoryProverPublicSetup::rand(5, 0, &mut test_rng());
-    let res = compute_dory_commitments(&[CommittableColumn::BigInt(&[0, 1, 2, 3, 4])], 5, &setup);
+    let res = compute_dory_commitments(
+        &[CommittableColumn::BigInt(&[0, 1, 2, 3, 4])],
+        5,
+        &setup,
+        None,
+    );
     let Gamma_1 = &setup.public_parameters().Gamma_1;
     let Gamma_2 = &setup.public_parameters().Gamma_2;
     let expected: GT = Pairing::pairing(Gamma_1[0], Gamma_2[5]) * F::from(0)
@@ -244,3 +260,38 @@ fn test_compute_dory_commitment_with_zero_sigma_and_with_an_offset() {
         + Pairing::pairing(Gamma_1[0], Gamma_2[9]) * F::from(4);
     assert_eq!(res[0].0, expected);
 }
+
+#[cfg(feature = "blitzar")]
+#[test]
+fn the_pure_rust_and_blitzar_backends_compute_the_same_dory_commitments() {
+    use crate::{
+        base::math::decimal::Precision,
+        proof_primitive::dory::{dory_commitment_helper_cpu, dory_commitment_helper_gpu},
+    };
+    let setup = DoryProverPublicSetup::rand(5, 2, &mut test_rng());
+    let limbs = vec![
+        [1, 0, 0, 0],
+        [0, 2, 0, 0],
+        [3, 0, 4, 0],
+        [u64::MAX, u64::MAX, 0, 0],
+        [0; 4],
+    ];
+    let columns = [
+        CommittableColumn::Boolean(&[true, false, true, true, false]),
+        CommittableColumn::SmallInt(&[0, -1, i16::MAX, i16::MIN, 7]),
+        CommittableColumn::Int(&[0, -1, i32::MAX, i32::MIN, 7]),
+        CommittableColumn::BigInt(&[0, -1, i64::MAX, i64::MIN, 7]),
+        CommittableColumn::Int128(&[0, -1, i128::MAX, i128::MIN, 7]),
+        CommittableColumn::Decimal75(Precision::new(75).unwrap(), 2, limbs.clone()),
+        CommittableColumn::Scalar(limbs.clone()),
+        CommittableColumn::VarChar(limbs.clone()),
+        CommittableColumn::Binary(20, limbs),
+    ];
+    for offset in [0, 1, 3, 4, 9] {
+        assert_eq!(
+            dory_commitment_helper_cpu::compute_dory_commitments(&columns, offset, &setup, None),
+            dory_commitment_helper_gpu::compute_dory_commitments(&columns, offset, &setup, None),
+            "offset {offset}"
+        );
+    }
+}
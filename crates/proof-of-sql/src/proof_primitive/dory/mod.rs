@@ -110,12 +110,16 @@ mod dory_commitment;
 #[cfg(test)]
 mod dory_commitment_test;
 
-#[cfg(not(feature = "blitzar"))]
+// The pure Rust backend computes the same commitments as the blitzar backend, so proofs made
+// with either backend verify with the other. It is always compiled for tests to check just that.
+#[cfg(any(test, not(feature = "blitzar")))]
 mod dory_commitment_helper_cpu;
 #[cfg(not(feature = "blitzar"))]
 use dory_commitment_helper_cpu::compute_dory_commitments;
 #[cfg(feature = "blitzar")]
 mod dory_commitment_helper_gpu;
+#[cfg(any(test, not(feature = "blitzar")))]
+mod pippenger;
 pub use dory_commitment::{DoryCommitment, DoryScalar};
 #[cfg(feature = "blitzar")]
 use dory_commitment_helper_gpu::compute_dory_commitments;
@@ -130,7 +134,7 @@ mod build_vmv_state;
 use build_vmv_state::{build_vmv_prover_state, build_vmv_verifier_state};
 
 mod dory_commitment_evaluation_proof;
-pub use dory_commitment_evaluation_proof::DoryEvaluationProof;
+pub use dory_commitment_evaluation_proof::{DoryError, DoryEvaluationProof};
 #[cfg(test)]
 mod dory_commitment_evaluation_proof_test;
 
@@ -0,0 +1,107 @@
+use super::{G1Affine, G1Projective, F};
+use ark_ec::Group;
+use ark_ff::PrimeField;
+use num_traits::Zero;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// The largest window that [`msm_with_window`] uses. Larger windows are clamped to it,
+/// since the buckets of a window take `2^window_bits` points of memory, which is already
+/// about 9 MiB for each window that is summed in parallel at 16 bits.
+pub(super) const MAX_MSM_WINDOW_BITS: usize = 16;
+
+/// Computes `sum scalars[i] * bases[i]` with Pippenger's bucket method over windows of `window_bits` bits.
+///
+/// The result does not depend on the window, only the time that it takes does. The window is
+/// clamped to `1..=MAX_MSM_WINDOW_BITS`. Without a configured window, `ark-ec` chooses about
+/// `0.69 * log2(len) + 2` bits, which balances the additions into the buckets of each window
+/// against the additions that sum up the buckets.
+///
+/// Like the MSM of `ark-ec`, the windows are summed in parallel.
+pub(super) fn msm_with_window(
+    bases: &[G1Affine],
+    scalars: &[F],
+    window_bits: usize,
+) -> G1Projective {
+    let window_bits = window_bits.clamp(1, MAX_MSM_WINDOW_BITS);
+    let scalars = Vec::from_iter(scalars.iter().map(|scalar| scalar.into_bigint()));
+    let window_starts = Vec::from_iter((0..F::MODULUS_BIT_SIZE as usize).step_by(window_bits));
+    let window_sums: Vec<G1Projective> = window_starts
+        .into_par_iter()
+        .map(|window_start| {
+            // bucket i holds the sum of the bases whose digit in this window is i + 1
+            let mut buckets = vec![G1Projective::zero(); (1 << window_bits) - 1];
+            for (base, scalar) in bases.iter().zip(&scalars) {
+                let digit = window_digit(scalar.as_ref(), window_start, window_bits);
+                if digit != 0 {
+                    buckets[digit - 1] += base;
+                }
+            }
+            // sum (i + 1) * bucket i, as a sum of the running sums from the highest bucket down
+            let mut running_sum = G1Projective::zero();
+            let mut window_sum = G1Projective::zero();
+            for bucket in buckets.into_iter().rev() {
+                running_sum += bucket;
+                window_sum += running_sum;
+            }
+            window_sum
+        })
+        .collect();
+    window_sums
+        .into_iter()
+        .rev()
+        .fold(G1Projective::zero(), |mut total, window_sum| {
+            for _ in 0..window_bits {
+                total.double_in_place();
+            }
+            total + window_sum
+        })
+}
+
+/// The `window_bits` bits of the little-endian `limbs` from bit `window_start` on
+fn window_digit(limbs: &[u64], window_start: usize, window_bits: usize) -> usize {
+    let (limb, shift) = (window_start / 64, window_start % 64);
+    let mut digit = limbs[limb] >> shift;
+    if shift + window_bits > 64 && limb + 1 < limbs.len() {
+        digit |= limbs[limb + 1] << (64 - shift);
+    }
+    (digit & ((1 << window_bits) - 1)) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_primitive::dory::test_rng;
+    use ark_ec::VariableBaseMSM;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn msms_with_any_window_match_the_msm_of_ark() {
+        let mut rng = test_rng();
+        for len in [0, 1, 2, 7, 33, 100] {
+            let bases = Vec::from_iter((0..len).map(|_| G1Affine::rand(&mut rng)));
+            let mut scalars = Vec::from_iter((0..len).map(|_| F::rand(&mut rng)));
+            if len > 2 {
+                scalars[0] = F::zero();
+                scalars[1] = -F::from(1);
+            }
+            let expected = G1Projective::msm_unchecked(&bases, &scalars);
+            for window_bits in 0..=10 {
+                assert_eq!(
+                    msm_with_window(&bases, &scalars, window_bits),
+                    expected,
+                    "len {len}, window {window_bits}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn we_can_extract_window_digits_across_limbs() {
+        let limbs = [0xF000_0000_0000_0001, 0x5, 0, 0];
+        assert_eq!(window_digit(&limbs, 0, 4), 1);
+        assert_eq!(window_digit(&limbs, 60, 4), 0xF);
+        assert_eq!(window_digit(&limbs, 62, 4), 0b0111);
+        assert_eq!(window_digit(&limbs, 64, 3), 0x5);
+        assert_eq!(window_digit(&limbs, 254, 4), 0);
+    }
+}
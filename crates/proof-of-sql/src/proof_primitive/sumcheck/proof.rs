@@ -21,11 +21,28 @@ pub struct SumcheckProof<S: Scalar> {
 }
 
 impl<S: Scalar> SumcheckProof<S> {
+    /// Proves the sum of `polynomial` over the hypercube, evaluating each round on multiple threads
+    /// with the `parallel-sumcheck` feature. The proof is the same either way.
+    /// Fixing the variable of each round is always done on multiple threads.
     #[tracing::instrument(name = "SumcheckProof::create", level = "debug", skip_all)]
     pub fn create(
         transcript: &mut Transcript,
         evaluation_point: &mut [S],
         polynomial: &CompositePolynomial<S>,
+    ) -> Self {
+        Self::create_with_parallelism(
+            transcript,
+            evaluation_point,
+            polynomial,
+            cfg!(feature = "parallel-sumcheck"),
+        )
+    }
+
+    pub(super) fn create_with_parallelism(
+        transcript: &mut Transcript,
+        evaluation_point: &mut [S],
+        polynomial: &CompositePolynomial<S>,
+        in_parallel: bool,
     ) -> Self {
         assert_eq!(evaluation_point.len(), polynomial.num_variables);
         transcript.append_auto(
@@ -36,7 +53,7 @@ impl<S: Scalar> SumcheckProof<S> {
         let mut state = ProverState::create(polynomial);
         let mut evaluations = Vec::with_capacity(polynomial.num_variables);
         for scalar in evaluation_point.iter_mut().take(polynomial.num_variables) {
-            let round_evaluations = prove_round(&mut state, &r);
+            let round_evaluations = prove_round(&mut state, &r, in_parallel);
             transcript.append_canonical_serialize(
                 MessageLabel::SumcheckRoundEvaluation,
                 &round_evaluations,
@@ -68,9 +85,7 @@ impl<S: Scalar> SumcheckProof<S> {
             ),
         );
         if self.evaluations.len() != polynomial_info.num_variables {
-            return Err(ProofError::VerificationError(
-                "invalid number of evaluations",
-            ));
+            return Err(ProofError::SumcheckFailed("invalid number of evaluations"));
         }
         let mut evaluation_point = Vec::with_capacity(polynomial_info.num_variables);
         for round_index in 0..polynomial_info.num_variables {
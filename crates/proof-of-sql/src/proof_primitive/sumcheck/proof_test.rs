@@ -1,6 +1,6 @@
 use crate::base::{
     polynomial::CompositePolynomial,
-    proof::{MessageLabel, TranscriptProtocol},
+    proof::{MessageLabel, ProofError, TranscriptProtocol},
     scalar::Curve25519Scalar,
 };
 /**
@@ -64,7 +64,7 @@ fn test_create_verify_proof() {
         poly.info(),
         &Curve25519Scalar::from(123u64),
     );
-    assert!(subclaim.is_err());
+    assert!(matches!(subclaim, Err(ProofError::SumcheckFailed(_))));
 
     // verify fails if evaluations are changed
     proof.evaluations[0][1] += Curve25519Scalar::from(3u64);
@@ -73,7 +73,54 @@ fn test_create_verify_proof() {
         poly.info(),
         &Curve25519Scalar::from(579u64),
     );
-    assert!(subclaim.is_err());
+    assert!(matches!(subclaim, Err(ProofError::SumcheckFailed(_))));
+}
+
+#[test]
+fn verify_fails_with_the_degree_of_a_round_polynomial_that_differs_from_the_query() {
+    let num_vars = 1;
+    let mut evaluation_point = [Curve25519Scalar::zero(); 1];
+    let mut poly = CompositePolynomial::new(num_vars);
+    let fa = Rc::new(vec![
+        Curve25519Scalar::from(123u64),
+        Curve25519Scalar::from(456u64),
+    ]);
+    poly.add_product([fa], Curve25519Scalar::from(1u64));
+    let mut transcript = Transcript::new(b"sumchecktest");
+    let mut proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, &poly);
+
+    proof.evaluations[0].push(Curve25519Scalar::zero());
+    let mut transcript = Transcript::new(b"sumchecktest");
+    let subclaim = proof.verify_without_evaluation(
+        &mut transcript,
+        poly.info(),
+        &Curve25519Scalar::from(579u64),
+    );
+    assert!(matches!(
+        subclaim,
+        Err(ProofError::DegreeExceeded {
+            max_degree: 1,
+            degree: 2
+        })
+    ));
+
+    proof.evaluations[0].truncate(1);
+    let mut transcript = Transcript::new(b"sumchecktest");
+    let subclaim = proof.verify_without_evaluation(
+        &mut transcript,
+        poly.info(),
+        &Curve25519Scalar::from(579u64),
+    );
+    assert!(matches!(subclaim, Err(ProofError::SumcheckFailed(_))));
+
+    proof.evaluations.clear();
+    let mut transcript = Transcript::new(b"sumchecktest");
+    let subclaim = proof.verify_without_evaluation(
+        &mut transcript,
+        poly.info(),
+        &Curve25519Scalar::from(579u64),
+    );
+    assert!(matches!(subclaim, Err(ProofError::SumcheckFailed(_))));
 }
 
 fn random_product(
@@ -160,3 +207,38 @@ fn test_normal_polynomial() {
 
     test_polynomial(nv, num_multiplicands_range, num_products);
 }
+
+#[test]
+fn parallel_and_sequential_sumcheck_proofs_are_identical() {
+    let mut rng = <ark_std::rand::rngs::StdRng as ark_std::rand::SeedableRng>::from_seed([0u8; 32]);
+    for (nv, num_multiplicands_range, num_products) in [(1, (1, 3), 1), (6, (2, 5), 20)] {
+        let (poly, _) = random_polynomial(nv, num_multiplicands_range, num_products, &mut rng);
+
+        let mut sequential_transcript = Transcript::new(b"sumchecktest");
+        let mut sequential_point = vec![Curve25519Scalar::zero(); nv];
+        let sequential = SumcheckProof::create_with_parallelism(
+            &mut sequential_transcript,
+            &mut sequential_point,
+            &poly,
+            false,
+        );
+
+        let mut parallel_transcript = Transcript::new(b"sumchecktest");
+        let mut parallel_point = vec![Curve25519Scalar::zero(); nv];
+        let parallel = SumcheckProof::create_with_parallelism(
+            &mut parallel_transcript,
+            &mut parallel_point,
+            &poly,
+            true,
+        );
+
+        assert_eq!(parallel.evaluations, sequential.evaluations);
+        assert_eq!(parallel_point, sequential_point);
+        assert_eq!(
+            parallel_transcript
+                .challenge_scalar_single::<Curve25519Scalar>(MessageLabel::SumcheckChallenge),
+            sequential_transcript
+                .challenge_scalar_single::<Curve25519Scalar>(MessageLabel::SumcheckChallenge)
+        );
+    }
+}
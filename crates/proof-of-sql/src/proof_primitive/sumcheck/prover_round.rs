@@ -8,7 +8,11 @@ use crate::proof_primitive::sumcheck::ProverState;
 use rayon::prelude::*;
 
 #[tracing::instrument(level = "debug", skip_all)]
-pub fn prove_round<S: Scalar>(prover_state: &mut ProverState<S>, r_maybe: &Option<S>) -> Vec<S> {
+pub fn prove_round<S: Scalar>(
+    prover_state: &mut ProverState<S>,
+    r_maybe: &Option<S>,
+    in_parallel: bool,
+) -> Vec<S> {
     if let Some(r) = r_maybe {
         if prover_state.round == 0 {
             panic!("first round should be prover first.");
@@ -37,59 +41,90 @@ pub fn prove_round<S: Scalar>(prover_state: &mut ProverState<S>, r_maybe: &Optio
         panic!("Prover is not active");
     }
 
-    let degree = prover_state.max_multiplicands; // the degree of univariate polynomial sent by prover at this round
-    let round_length = 1usize << (prover_state.num_vars - prover_state.round);
+    if in_parallel {
+        evaluate_round_in_parallel(prover_state)
+    } else {
+        evaluate_round_sequentially(prover_state)
+    }
+}
 
-    // The pseudocode of what this is trying to do is:
+// The pseudocode of what the round evaluation is trying to do is:
 
-    // foreach t in 0..=degree compute
-    //   sum over row in 0..round_length:
-    //     sum over product in list_of_products:
-    //       product over multiplicand in product:
-    //         table = the mle of the multiplicand
-    //         table[2b] * (1-t) + table[2b+1] * t
-    // This gives a vector of length degree + 1
+// foreach t in 0..=degree compute
+//   sum over row in 0..round_length:
+//     sum over product in list_of_products:
+//       product over multiplicand in product:
+//         table = the mle of the multiplicand
+//         table[2b] * (1-t) + table[2b+1] * t
+// This gives a vector of length degree + 1
 
-    // The order of these loops is changed for the purpose of efficiency.
+// The order of these loops is changed for the purpose of efficiency.
+// The outer loop is the loop over all products in the list_of_products, and the second loop is
+// the loop over the row (b) in 0..round_length, both of which are split across threads in parallel.
+// The sums are exact field arithmetic, so the evaluations are the same however they are split.
 
-    // The outer loop is the loop over all products in the list_of_products
-    let result = prover_state
+fn evaluate_round_sequentially<S: Scalar>(prover_state: &ProverState<S>) -> Vec<S> {
+    let degree = prover_state.max_multiplicands; // the degree of univariate polynomial sent by prover at this round
+    let round_length = 1usize << (prover_state.num_vars - prover_state.round);
+    prover_state
+        .list_of_products
+        .iter()
+        .flat_map(|(coefficient, multiplicand_indices)| {
+            (0..round_length).map(move |b| {
+                evaluate_product_at_row(prover_state, *coefficient, multiplicand_indices, b)
+            })
+        })
+        .fold(vec![S::zero(); degree + 1], vec_elementwise_add)
+}
+
+fn evaluate_round_in_parallel<S: Scalar>(prover_state: &ProverState<S>) -> Vec<S> {
+    let degree = prover_state.max_multiplicands; // the degree of univariate polynomial sent by prover at this round
+    let round_length = 1usize << (prover_state.num_vars - prover_state.round);
+    prover_state
         .list_of_products
         .par_iter()
         .map(|(coefficient, multiplicand_indices)| {
-            // The second loop is the loop over the row (b) in 0..round_length
             (0..round_length)
                 .into_par_iter()
                 .map(|b| {
-                    // We add a vector of products, which takes a bit of extra memory. The reason for this is for the efficient modification described below
-                    let mut products = vec![*coefficient; degree + 1];
-
-                    // The third loop is the loop over the factors/multiplicand in the product term.
-                    for &multiplicand_index in multiplicand_indices {
-                        let table = &prover_state.flattened_ml_extensions[multiplicand_index];
-
-                        // This third+final loop give an efficient way of computing
-                        // products[t] *= table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field;
-                        // It requires only 1 addition (plus the cumulative multiplication) to accomplish the same task.
-                        // It relies on the fact that
-                        // table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field == table[b << 1] + t * diff
-                        let mut start = table[b << 1];
-                        let step = table[(b << 1) + 1] - start;
-
-                        // The innermost loop loops over the values (t) that we are evaluating at.
-                        products.iter_mut().take(degree).for_each(|product| {
-                            *product *= start;
-                            start += step;
-                        });
-                        products[degree] *= start;
-                    }
-                    products
+                    evaluate_product_at_row(prover_state, *coefficient, multiplicand_indices, b)
                 })
                 .reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add)
         })
-        .reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add);
+        .reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add)
+}
 
-    result
+/// The evaluations of a single product of the round polynomial at the row `b`, at each `t` in `0..=degree`.
+fn evaluate_product_at_row<S: Scalar>(
+    prover_state: &ProverState<S>,
+    coefficient: S,
+    multiplicand_indices: &[usize],
+    b: usize,
+) -> Vec<S> {
+    let degree = prover_state.max_multiplicands;
+    // We add a vector of products, which takes a bit of extra memory. The reason for this is for the efficient modification described below
+    let mut products = vec![coefficient; degree + 1];
+
+    // The third loop is the loop over the factors/multiplicand in the product term.
+    for &multiplicand_index in multiplicand_indices {
+        let table = &prover_state.flattened_ml_extensions[multiplicand_index];
+
+        // This third+final loop give an efficient way of computing
+        // products[t] *= table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field;
+        // It requires only 1 addition (plus the cumulative multiplication) to accomplish the same task.
+        // It relies on the fact that
+        // table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field == table[b << 1] + t * diff
+        let mut start = table[b << 1];
+        let step = table[(b << 1) + 1] - start;
+
+        // The innermost loop loops over the values (t) that we are evaluating at.
+        products.iter_mut().take(degree).for_each(|product| {
+            *product *= start;
+            start += step;
+        });
+        products[degree] *= start;
+    }
+    products
 }
 
 /// This is equivalent to
@@ -24,13 +24,19 @@ impl<S: Scalar> Subclaim<S> {
         let mut expected_sum = *claimed_sum;
         for round_index in 0..num_vars {
             let round_evaluation = &evaluations[round_index];
+            if round_evaluation.len() > max_multiplicands + 1 {
+                return Err(ProofError::DegreeExceeded {
+                    max_degree: max_multiplicands,
+                    degree: round_evaluation.len() - 1,
+                });
+            }
             if round_evaluation.len() != max_multiplicands + 1 {
-                return Err(ProofError::VerificationError(
+                return Err(ProofError::SumcheckFailed(
                     "round evaluation length does not match max multiplicands",
                 ));
             }
             if expected_sum != round_evaluation[0] + round_evaluation[1] {
-                return Err(ProofError::VerificationError(
+                return Err(ProofError::SumcheckFailed(
                     "round evaluation does not match claimed sum",
                 ));
             }
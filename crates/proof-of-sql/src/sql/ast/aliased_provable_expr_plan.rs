@@ -0,0 +1,28 @@
+use super::{ColumnExpr, ProvableExpr, ProvableExprPlan};
+use crate::base::{commitment::Commitment, database::ColumnField};
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// A `ProvableExprPlan` with an alias, i.e. a result expression such as `total / count_dec AS ratio`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AliasedProvableExprPlan<C: Commitment> {
+    pub expr: ProvableExprPlan<C>,
+    pub alias: Identifier,
+}
+
+impl<C: Commitment> AliasedProvableExprPlan<C> {
+    /// Wrap the alias and the type of the expression within the ColumnField
+    pub fn get_column_field(&self) -> ColumnField {
+        ColumnField::new(self.alias, self.expr.data_type())
+    }
+}
+
+/// A column is aliased by its own name
+impl<C: Commitment> From<ColumnExpr<C>> for AliasedProvableExprPlan<C> {
+    fn from(expr: ColumnExpr<C>) -> Self {
+        Self {
+            alias: expr.get_column_reference().column_id(),
+            expr: ProvableExprPlan::Column(expr),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{ProvableExpr, ProvableExprPlan};
+use super::{commutative_operands_eq, hash_commutative_operands, ProvableExpr, ProvableExprPlan};
 use crate::{
     base::{
         commitment::Commitment,
@@ -10,15 +10,31 @@ use crate::{
 use bumpalo::Bump;
 use num_traits::One;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
 
 /// Provable logical AND expression
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct AndExpr<C: Commitment> {
     lhs: Box<ProvableExprPlan<C>>,
     rhs: Box<ProvableExprPlan<C>>,
 }
 
+/// `a AND b` equals `b AND a`
+impl<C: Commitment> PartialEq for AndExpr<C> {
+    fn eq(&self, other: &Self) -> bool {
+        commutative_operands_eq((&self.lhs, &self.rhs), (&other.lhs, &other.rhs))
+    }
+}
+
+impl<C: Commitment> Hash for AndExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_commutative_operands(&self.lhs, &self.rhs, state);
+    }
+}
+
 impl<C: Commitment> AndExpr<C> {
     /// Create logical AND expression
     pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>) -> Self {
@@ -36,6 +52,10 @@ impl<C: Commitment> ProvableExpr<C> for AndExpr<C> {
         Ok(())
     }
 
+    fn num_post_result_challenges(&self) -> usize {
+        self.lhs.num_post_result_challenges() + self.rhs.num_post_result_challenges()
+    }
+
     fn data_type(&self) -> ColumnType {
         ColumnType::Boolean
     }
@@ -85,6 +105,7 @@ impl<C: Commitment> ProvableExpr<C> for AndExpr<C> {
         Column::Boolean(lhs_and_rhs)
     }
 
+    #[tracing::instrument(name = "AndExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
@@ -3,8 +3,8 @@ use crate::{
     base::{
         commitment::InnerProductProof,
         database::{
-            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
-            OwnedTableTestAccessor, RandomTestAccessorDescriptor, TestAccessor,
+            owned_table_utility::*, Column, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TestAccessor,
         },
         scalar::Curve25519Scalar,
     },
@@ -15,6 +15,7 @@ use crate::{
         },
         proof::{exercise_verification, VerifiableQueryResult},
     },
+    testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
 };
 use bumpalo::Bump;
 use curve25519_dalek::ristretto::RistrettoPoint;
@@ -42,7 +43,7 @@ fn create_and_verify_test_and_expr(
         equal(column(t, rhs.0, &accessor), const_scalar(rhs.1.into())),
     );
     let ast = FilterExpr::new(cols_result(t, results, &accessor), tab(t), and_expr);
-    let res = VerifiableQueryResult::new(&ast, &accessor, &());
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
     exercise_verification(&res, &ast, &accessor, t);
     res.verify(&ast, &accessor, &()).unwrap().table
 }
@@ -125,6 +126,13 @@ fn we_can_query_random_tables_using_a_non_zero_offset() {
     test_random_tables_with_given_offset(123);
 }
 
+#[test]
+fn we_can_query_random_tables_with_offsets_near_a_power_of_two() {
+    for offset in [(1 << 16) - 1, 1 << 16, (1 << 16) + 1] {
+        test_random_tables_with_given_offset(offset);
+    }
+}
+
 #[test]
 fn we_can_compute_the_correct_output_of_an_and_expr_using_result_evaluate() {
     let data = owned_table([
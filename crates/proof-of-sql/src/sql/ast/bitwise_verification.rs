@@ -45,7 +45,7 @@ pub fn verify_constant_sign_decomposition<S: Scalar>(
     if lhs == rhs {
         Ok(())
     } else {
-        Err(ProofError::VerificationError(
+        Err(ProofError::ConstraintUnsatisfied(
             "constant sign bitwise decomposition is invalid",
         ))
     }
@@ -67,7 +67,7 @@ pub fn verify_constant_abs_decomposition<S: Scalar>(
     if S::from(dist.constant_part()) * t == eval {
         Ok(())
     } else {
-        Err(ProofError::VerificationError(
+        Err(ProofError::ConstraintUnsatisfied(
             "constant absolute bitwise decomposition is invalid",
         ))
     }
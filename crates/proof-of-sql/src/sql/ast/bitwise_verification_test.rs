@@ -4,6 +4,7 @@ use super::{
 };
 use crate::base::{
     bit::BitDistribution,
+    proof::ProofError,
     scalar::Curve25519Scalar,
     slice_ops::{inner_product, slice_cast},
 };
@@ -87,7 +88,10 @@ fn constant_verification_fails_if_the_commitment_doesnt_match() {
         vec![Curve25519Scalar::from(1235), Curve25519Scalar::from(1234)];
     let data_eval = inner_product(&data, &eval_vec);
     let one_eval = eval_vec.iter().sum();
-    assert!(verify_constant_sign_decomposition(&dist, data_eval, one_eval, &[]).is_err());
+    assert!(matches!(
+        verify_constant_sign_decomposition(&dist, data_eval, one_eval, &[]),
+        Err(ProofError::ConstraintUnsatisfied(_))
+    ));
 }
 
 #[test]
@@ -100,7 +104,10 @@ fn constant_verification_fails_if_the_sign_bit_doesnt_match() {
         vec![Curve25519Scalar::from(-1234), Curve25519Scalar::from(-1234)];
     let data_eval = inner_product(&data, &eval_vec);
     let one_eval = eval_vec.iter().sum();
-    assert!(verify_constant_sign_decomposition(&dist, data_eval, one_eval, &[]).is_err());
+    assert!(matches!(
+        verify_constant_sign_decomposition(&dist, data_eval, one_eval, &[]),
+        Err(ProofError::ConstraintUnsatisfied(_))
+    ));
 }
 
 #[test]
@@ -113,7 +120,10 @@ fn constant_verification_fails_if_a_varying_bit_doesnt_match() {
         vec![Curve25519Scalar::from(234), Curve25519Scalar::from(1234)];
     let data_eval = inner_product(&data, &eval_vec);
     let one_eval = eval_vec.iter().sum();
-    assert!(verify_constant_sign_decomposition(&dist, data_eval, one_eval, &[]).is_err());
+    assert!(matches!(
+        verify_constant_sign_decomposition(&dist, data_eval, one_eval, &[]),
+        Err(ProofError::ConstraintUnsatisfied(_))
+    ));
 }
 
 #[test]
@@ -135,7 +145,10 @@ fn constant_abs_verification_fails_if_the_sign_and_data_dont_match() {
     let data_eval = inner_product(&data, &eval_vec);
     let one_eval = eval_vec.iter().sum();
     let sign_eval = inner_product(&slice_cast(&[0, 1]), &eval_vec);
-    assert!(verify_constant_abs_decomposition(&dist, data_eval, one_eval, sign_eval).is_err());
+    assert!(matches!(
+        verify_constant_abs_decomposition(&dist, data_eval, one_eval, sign_eval),
+        Err(ProofError::ConstraintUnsatisfied(_))
+    ));
 }
 
 #[test]
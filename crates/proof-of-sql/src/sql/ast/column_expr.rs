@@ -9,28 +9,39 @@ use crate::{
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, marker::PhantomData};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
 /// Provable expression for a column
 ///
 /// Note: this is currently limited to named column expressions.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct ColumnExpr<C: Commitment> {
-    column_ref: ColumnRef,
+    // Boxed to keep the nodes of a plan small, since planning and proving recurse through them.
+    column_ref: Box<ColumnRef>,
     _phantom_data: PhantomData<C>,
 }
 
+impl<C: Commitment> Hash for ColumnExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.column_ref.hash(state);
+    }
+}
+
 impl<C: Commitment> ColumnExpr<C> {
     /// Create a new column expression
     pub fn new(column_ref: ColumnRef) -> Self {
         Self {
-            column_ref,
+            column_ref: Box::new(column_ref),
             _phantom_data: PhantomData,
         }
     }
 
     /// Return the column referenced by this ColumnExpr
     pub fn get_column_reference(&self) -> ColumnRef {
-        self.column_ref
+        *self.column_ref
     }
 
     /// Wrap the column output name and its type within the ColumnField
@@ -46,6 +57,11 @@ impl<C: Commitment> ProvableExpr<C> for ColumnExpr<C> {
         Ok(())
     }
 
+    /// A column does not need any post result challenges
+    fn num_post_result_challenges(&self) -> usize {
+        0
+    }
+
     /// Get the data type of the expression
     fn data_type(&self) -> ColumnType {
         *self.get_column_reference().column_type()
@@ -59,7 +75,7 @@ impl<C: Commitment> ProvableExpr<C> for ColumnExpr<C> {
         _alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        let column = accessor.get_column(self.column_ref);
+        let column = accessor.get_column(*self.column_ref);
         assert_eq!(column.len(), table_length);
         column
     }
@@ -72,7 +88,7 @@ impl<C: Commitment> ProvableExpr<C> for ColumnExpr<C> {
         _alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        let column = accessor.get_column(self.column_ref);
+        let column = accessor.get_column(*self.column_ref);
         builder.produce_anchored_mle(column.clone());
         column
     }
@@ -84,7 +100,7 @@ impl<C: Commitment> ProvableExpr<C> for ColumnExpr<C> {
         builder: &mut VerificationBuilder<C>,
         accessor: &dyn CommitmentAccessor<C>,
     ) -> Result<C::Scalar, ProofError> {
-        let col_commit = accessor.get_commitment(self.column_ref);
+        let col_commit = accessor.get_commitment(*self.column_ref);
         Ok(builder.consume_anchored_mle(col_commit))
     }
 
@@ -92,6 +108,6 @@ impl<C: Commitment> ProvableExpr<C> for ColumnExpr<C> {
     /// references in the BoolExpr or forwards the call to some
     /// subsequent bool_expr
     fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
-        columns.insert(self.column_ref);
+        columns.insert(*self.column_ref);
     }
 }
@@ -94,3 +94,32 @@ pub(crate) fn scale_and_subtract_eval<S: Scalar>(
     let scaled_rhs_eval = scale_scalar(rhs_eval, max_scale - rhs_scale)?;
     Ok(scaled_lhs_eval - scaled_rhs_eval)
 }
+
+/// The counterpart of `scale_and_subtract` for a column and a constant, such as the scalar of a
+/// literal, which subtracts the constant without materializing it as a column.
+///
+/// The difference is `column - constant`, or `constant - column` if `constant_is_lhs`,
+/// with both sides scaled to the larger of their scales as in `scale_and_subtract`.
+pub(crate) fn scale_and_subtract_constant<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: Column<'a, S>,
+    constant: S,
+    constant_scale: i8,
+    constant_is_lhs: bool,
+) -> ConversionResult<&'a [S]> {
+    let column_scale = column.column_type().scale().unwrap_or(0);
+    let max_scale = column_scale.max(constant_scale);
+    let column = column.to_scalar_with_scaling(max_scale - column_scale);
+    let constant = scale_scalar(constant, max_scale - constant_scale)?;
+    let res = alloc.alloc_slice_fill_default(column.len());
+    res.par_iter_mut()
+        .zip(column.par_iter())
+        .for_each(|(a, c)| {
+            *a = if constant_is_lhs {
+                constant - *c
+            } else {
+                *c - constant
+            };
+        });
+    Ok(res)
+}
@@ -0,0 +1,176 @@
+use super::{provable_expr_plan::ProvableExprPlan, ColumnExpr, ProvableExpr, TableExpr};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+            MetadataAccessor, TableRef,
+        },
+        proof::ProofError,
+    },
+    sql::proof::{
+        CountBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate, ResultBuilder,
+        SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use num_traits::One;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT COUNT(<counted_column>) as count_alias FROM <table> WHERE <where_clause>
+/// ```
+///
+/// Since columns have no nulls, this is also `COUNT(*)`. The counted column is only referenced
+/// so that its table is part of the query's commitments; none of its values are used.
+///
+/// The result is a single row with the number of selected rows. Besides what the where clause needs,
+/// nothing is committed, so the proof does not grow with the number of selected rows.
+/// The count is proven by checking that the selection sums to it.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CountExpr<C: Commitment> {
+    pub(super) counted_column: ColumnExpr<C>,
+    pub(super) count_alias: Identifier,
+    pub(super) table: TableExpr,
+    pub(super) where_clause: ProvableExprPlan<C>,
+}
+
+impl<C: Commitment> CountExpr<C> {
+    /// Creates a new count expression.
+    pub fn new(
+        counted_column: ColumnExpr<C>,
+        count_alias: Identifier,
+        table: TableExpr,
+        where_clause: ProvableExprPlan<C>,
+    ) -> Self {
+        Self {
+            counted_column,
+            count_alias,
+            table,
+            where_clause,
+        }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for CountExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.where_clause.count(builder)?;
+        builder.count_result_columns(1);
+        builder.count_subpolynomials(1);
+        // The sumcheck polynomial always has a degree 2 term, even when the subpolynomials are linear.
+        builder.count_degree(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    #[tracing::instrument(name = "CountExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        // 1. selection
+        let where_eval = self.where_clause.verifier_evaluate(builder, accessor)?;
+        // 2. indexes
+        let indexes_eval = builder
+            .mle_evaluations
+            .result_indexes_evaluation
+            .ok_or(ProofError::VerificationError("invalid indexes"))?;
+        if indexes_eval != builder.mle_evaluations.first_row_evaluation {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the count must be the only row of the result",
+            )))?;
+        }
+        // 3. count
+        let count_eval = builder.consume_result_mle();
+
+        // sum sel_in - count_out = 0
+        builder.produce_sumcheck_subpolynomial_evaluation(&(where_eval - count_eval));
+        Ok(())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        vec![ColumnField::new(self.count_alias, ColumnType::BigInt)]
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        let mut columns = HashSet::new();
+        columns.insert(self.counted_column.get_column_reference());
+        self.where_clause.get_column_references(&mut columns);
+        columns
+    }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for CountExpr<C> {
+    #[tracing::instrument(name = "CountExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(builder.table_length(), alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
+
+        // 2. count
+        builder.set_result_indexes(Indexes::Dense(0..1));
+        builder.produce_result_column(count_selection(alloc, selection));
+    }
+
+    #[tracing::instrument(name = "CountExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause.prover_evaluate(builder, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. count
+        let count_out = count_selection(alloc, selection);
+
+        // sum sel_in - count_out = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (C::Scalar::one(), vec![Box::new(selection)]),
+                (-C::Scalar::one(), vec![Box::new(count_out)]),
+            ],
+        );
+    }
+}
+
+/// Returns a column with the single value of the number of selected rows.
+pub(super) fn count_selection<'a>(alloc: &'a Bump, selection: &[bool]) -> &'a [i64] {
+    let count = selection.iter().filter(|&&selected| selected).count();
+    alloc.alloc_slice_copy(&[count as i64])
+}
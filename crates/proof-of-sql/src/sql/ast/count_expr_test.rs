@@ -0,0 +1,103 @@
+use super::test_utility::{col_expr, column, const_bigint, const_bool, count_all, equal, tab};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::ProofPlan,
+        parse::QueryExpr,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+
+#[test]
+fn we_can_prove_a_count_with_a_where_clause() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = count_all(
+        col_expr(t, "a", &accessor),
+        "n",
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("n", [4])]));
+}
+
+#[test]
+fn we_can_prove_a_count_without_selected_rows_or_without_a_where_clause() {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, owned_table([bigint("a", [1, 2, 3])]), 0);
+    for (where_clause, expected) in [
+        (equal(column(t, "a", &accessor), const_bigint(4)), 0),
+        (const_bool(true), 3),
+    ] {
+        let expr = count_all(col_expr(t, "a", &accessor), "n", tab(t), where_clause);
+        let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        assert_eq!(res, owned_table([bigint("n", [expected])]));
+    }
+}
+
+#[test]
+fn the_size_of_a_count_proof_does_not_depend_on_the_number_of_selected_rows() {
+    let t = "sxt.t".parse().unwrap();
+    let proof_size = |values: [i64; 8]| {
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, owned_table([bigint("a", values)]), 0);
+        let expr = count_all(
+            col_expr(t, "a", &accessor),
+            "n",
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(0)),
+        );
+        let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
+        res.verify(&expr, &accessor, &()).unwrap();
+        postcard::to_allocvec(&res).unwrap().len()
+    };
+    let none_selected = proof_size([1; 8]);
+    assert_eq!(proof_size([0, 1, 1, 1, 1, 1, 1, 1]), none_selected);
+    assert_eq!(proof_size([0; 8]), none_selected);
+}
+
+#[test]
+fn we_can_plan_and_prove_a_count_of_all_rows_from_sql() {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t,
+        owned_table([
+            varchar("region", ["east", "west", "east", "north"]),
+            bigint("amount", [5, -3, 8, 0]),
+        ]),
+        0,
+    );
+    let sql = "SELECT COUNT(*) AS n FROM t WHERE region = 'east'";
+    let query =
+        QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+    assert!(matches!(query.proof_expr(), ProofPlan::Count(_)));
+    let res = VerifiableQueryResult::new(query.proof_expr(), &accessor, &()).unwrap();
+    exercise_verification(&res, query.proof_expr(), &accessor, t);
+    let res = res
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    let res: OwnedTable<Curve25519Scalar> = query
+        .result()
+        .transform_results(res.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(res, owned_table([bigint("n", [2])]));
+}
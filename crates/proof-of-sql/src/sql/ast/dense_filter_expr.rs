@@ -2,13 +2,14 @@ use super::{
     dense_filter_util::{fold_columns, fold_vals},
     filter_columns,
     provable_expr_plan::ProvableExprPlan,
-    ColumnExpr, ProvableExpr, TableExpr,
+    AliasedProvableExprPlan, ProvableExpr, TableExpr,
 };
 use crate::{
     base::{
         commitment::Commitment,
         database::{
             Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            TableRef,
         },
         proof::ProofError,
         scalar::Scalar,
@@ -33,7 +34,7 @@ use std::{collections::HashSet, marker::PhantomData};
 /// This differs from the [`FilterExpr`] in that the result is not a sparse table.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct OstensibleDenseFilterExpr<C: Commitment, H: ProverHonestyMarker> {
-    pub(super) results: Vec<ColumnExpr<C>>,
+    pub(super) results: Vec<AliasedProvableExprPlan<C>>,
     pub(super) table: TableExpr,
     pub(super) where_clause: ProvableExprPlan<C>,
     phantom: PhantomData<H>,
@@ -42,7 +43,7 @@ pub struct OstensibleDenseFilterExpr<C: Commitment, H: ProverHonestyMarker> {
 impl<C: Commitment, H: ProverHonestyMarker> OstensibleDenseFilterExpr<C, H> {
     /// Creates a new dense_filter expression.
     pub fn new(
-        results: Vec<ColumnExpr<C>>,
+        results: Vec<AliasedProvableExprPlan<C>>,
         table: TableExpr,
         where_clause: ProvableExprPlan<C>,
     ) -> Self {
@@ -65,8 +66,8 @@ where
         _accessor: &dyn MetadataAccessor,
     ) -> Result<(), ProofError> {
         self.where_clause.count(builder)?;
-        for expr in self.results.iter() {
-            expr.count(builder)?;
+        for aliased_expr in self.results.iter() {
+            aliased_expr.expr.count(builder)?;
             builder.count_result_columns(1);
         }
         builder.count_intermediate_mles(2);
@@ -84,6 +85,7 @@ where
         accessor.get_offset(self.table.table_ref)
     }
 
+    #[tracing::instrument(name = "DenseFilterExpr::verifier_evaluate", level = "debug", skip_all)]
     #[allow(unused_variables)]
     fn verifier_evaluate(
         &self,
@@ -96,7 +98,7 @@ where
         let columns_evals = Vec::from_iter(
             self.results
                 .iter()
-                .map(|expr| expr.verifier_evaluate(builder, accessor))
+                .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
                 .collect::<Result<Vec<_>, _>>()?,
         );
         // 3. indexes
@@ -123,8 +125,8 @@ where
 
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         let mut columns = Vec::with_capacity(self.results.len());
-        for col in self.results.iter() {
-            columns.push(col.get_column_field());
+        for aliased_expr in self.results.iter() {
+            columns.push(aliased_expr.get_column_field());
         }
         columns
     }
@@ -132,14 +134,18 @@ where
     fn get_column_references(&self) -> HashSet<ColumnRef> {
         let mut columns = HashSet::new();
 
-        for col in self.results.iter() {
-            columns.insert(col.get_column_reference());
+        for aliased_expr in self.results.iter() {
+            aliased_expr.expr.get_column_references(&mut columns);
         }
 
         self.where_clause.get_column_references(&mut columns);
 
         columns
     }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
 }
 
 /// Alias for a dense filter expression with a honest prover.
@@ -160,13 +166,17 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
 
         // 2. columns
-        let columns = Vec::from_iter(
-            self.results
-                .iter()
-                .map(|expr| expr.result_evaluate(builder.table_length(), alloc, accessor)),
-        );
+        let columns = Vec::from_iter(self.results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
+        for aliased_expr in self.results.iter() {
+            builder.request_post_result_challenges(aliased_expr.expr.num_post_result_challenges());
+        }
         // Compute filtered_columns and indexes
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
         // 3. set indexes
@@ -197,7 +207,7 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
         let columns = Vec::from_iter(
             self.results
                 .iter()
-                .map(|expr| expr.prover_evaluate(builder, alloc, accessor)),
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
         );
         // Compute filtered_columns and indexes
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
@@ -281,15 +291,22 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
     slice_ops::batch_inversion(c_star);
     slice_ops::batch_inversion(&mut d_star[..m]);
 
+    // `d_star` and `chi` are zero after the first `m` rows, so only those rows need to be committed.
+    let (d_star, chi) = if builder.is_filter_compaction_enabled() {
+        (&d_star[..m], &chi[..m])
+    } else {
+        (&*d_star, &*chi)
+    };
+
     builder.produce_intermediate_mle(c_star as &[_]);
-    builder.produce_intermediate_mle(d_star as &[_]);
+    builder.produce_intermediate_mle(d_star);
 
     // sum c_star * s - d_star = 0
     builder.produce_sumcheck_subpolynomial(
         SumcheckSubpolynomialType::ZeroSum,
         vec![
             (S::one(), vec![Box::new(c_star as &[_]), Box::new(s)]),
-            (-S::one(), vec![Box::new(d_star as &[_])]),
+            (-S::one(), vec![Box::new(d_star)]),
         ],
     );
 
@@ -311,9 +328,9 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
         vec![
             (
                 S::one(),
-                vec![Box::new(d_star as &[_]), Box::new(d_bar_fold as &[_])],
+                vec![Box::new(d_star), Box::new(d_bar_fold as &[_])],
             ),
-            (-S::one(), vec![Box::new(chi as &[_])]),
+            (-S::one(), vec![Box::new(chi)]),
         ],
     );
 }
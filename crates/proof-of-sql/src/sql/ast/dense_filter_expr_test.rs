@@ -7,6 +7,7 @@ use crate::{
 };
 use crate::{
     base::{
+        commitment::{QueryCommitments, TableCommitment},
         database::{
             ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, OwnedTableTestAccessor,
             RecordBatchTestAccessor, TableRef, TestAccessor,
@@ -18,7 +19,10 @@ use crate::{
         ast::{
             // Making this explicit to ensure that we don't accidentally use the
             // sparse filter for these tests
-            test_utility::{cols_expr, column, const_int128, dense_filter, equal, tab},
+            test_utility::{
+                cols_expr_plan, column, const_binary, const_bool, const_int128, dense_filter,
+                equal, tab,
+            },
             ColumnExpr,
             DenseFilterExpr,
             LiteralExpr,
@@ -35,7 +39,10 @@ use bumpalo::Bump;
 use curve25519_dalek::RistrettoPoint;
 use indexmap::IndexMap;
 use proof_of_sql_parser::{Identifier, ResourceId};
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 #[test]
 fn we_can_correctly_fetch_the_query_result_schema() {
@@ -46,12 +53,14 @@ fn we_can_correctly_fetch_the_query_result_schema() {
                 table_ref,
                 Identifier::try_new("a").unwrap(),
                 ColumnType::BigInt,
-            )),
+            ))
+            .into(),
             ColumnExpr::new(ColumnRef::new(
                 table_ref,
                 Identifier::try_new("b").unwrap(),
                 ColumnType::BigInt,
-            )),
+            ))
+            .into(),
         ],
         TableExpr { table_ref },
         ProvableExprPlan::try_new_equals(
@@ -90,12 +99,14 @@ fn we_can_correctly_fetch_all_the_referenced_columns() {
                 table_ref,
                 Identifier::try_new("a").unwrap(),
                 ColumnType::BigInt,
-            )),
+            ))
+            .into(),
             ColumnExpr::new(ColumnRef::new(
                 table_ref,
                 Identifier::try_new("f").unwrap(),
                 ColumnType::BigInt,
-            )),
+            ))
+            .into(),
         ],
         TableExpr { table_ref },
         not::<RistrettoPoint>(and(
@@ -170,8 +181,8 @@ fn we_can_prove_and_get_the_correct_result_from_a_basic_dense_filter() {
     let mut accessor = RecordBatchTestAccessor::new_empty();
     accessor.add_table(t, data, 0);
     let where_clause = equal(column(t, "a", &accessor), const_int128(5_i128));
-    let expr = dense_filter(cols_expr(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let expr = dense_filter(cols_expr_plan(t, &["b"], &accessor), tab(t), where_clause);
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -197,7 +208,7 @@ fn we_can_get_an_empty_result_from_a_basic_dense_filter_on_an_empty_table_using_
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(999));
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         where_clause,
     );
@@ -242,7 +253,7 @@ fn we_can_get_an_empty_result_from_a_basic_dense_filter_using_result_evaluate()
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(999));
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         where_clause,
     );
@@ -287,7 +298,7 @@ fn we_can_get_no_columns_from_a_basic_dense_filter_with_no_selected_columns_usin
     accessor.add_table(t, data, 0);
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(5));
-    let expr = dense_filter(cols_expr(t, &[], &accessor), tab(t), where_clause);
+    let expr = dense_filter(cols_expr_plan(t, &[], &accessor), tab(t), where_clause);
     let alloc = Bump::new();
     let mut builder = ResultBuilder::new(5);
     expr.result_evaluate(&mut builder, &alloc, &accessor);
@@ -315,7 +326,7 @@ fn we_can_get_the_correct_result_from_a_basic_dense_filter_using_result_evaluate
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(5));
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         where_clause,
     );
@@ -357,11 +368,11 @@ fn we_can_prove_a_dense_filter_on_an_empty_table() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(106)),
     );
-    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
     exercise_verification(&res, &expr, &accessor, t);
     let res = res.verify(&expr, &accessor, &()).unwrap().table;
     let expected = owned_table([
@@ -386,11 +397,11 @@ fn we_can_prove_a_dense_filter_with_empty_results() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(106)),
     );
-    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
     exercise_verification(&res, &expr, &accessor, t);
     let res = res.verify(&expr, &accessor, &()).unwrap().table;
     let expected = owned_table([
@@ -402,6 +413,45 @@ fn we_can_prove_a_dense_filter_with_empty_results() {
     assert_eq!(res, expected);
 }
 
+#[test]
+fn we_check_the_root_of_the_table_of_a_dense_filter_that_references_no_columns() {
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let data = owned_table([bigint("a", [1, 2, 3])]);
+    let commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(&data, 0, &());
+    let other_commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+        &owned_table::<Curve25519Scalar>([bigint("a", [1, 2, 4])]),
+        0,
+        &(),
+    );
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = dense_filter::<RistrettoPoint>(vec![], tab(t), const_bool(true));
+    assert!(expr.get_column_references().is_empty());
+    assert_eq!(expr.get_table_references(), HashSet::from([t]));
+    let res =
+        VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
+    let commitments = QueryCommitments::from_iter([(t, commitment.clone())]);
+    assert!(res
+        .verify_with_roots(
+            &expr,
+            &commitments,
+            &HashMap::from([(t, commitment.root())]),
+            &(),
+            &[],
+        )
+        .is_ok());
+    // The table has the same length as the one the root was published for, which is all the proof depends on.
+    assert!(res
+        .verify_with_roots(
+            &expr,
+            &commitments,
+            &HashMap::from([(t, other_commitment.root())]),
+            &(),
+            &[],
+        )
+        .is_err());
+}
+
 #[test]
 fn we_can_prove_a_dense_filter() {
     let data = owned_table([
@@ -415,11 +465,11 @@ fn we_can_prove_a_dense_filter() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(105)),
     );
-    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
     exercise_verification(&res, &expr, &accessor, t);
     let res = res.verify(&expr, &accessor, &()).unwrap().table;
     let expected = owned_table([
@@ -430,3 +480,68 @@ fn we_can_prove_a_dense_filter() {
     ]);
     assert_eq!(res, expected);
 }
+
+#[test]
+fn we_can_prove_a_dense_filter_on_a_20_byte_binary_column_equal_to_a_literal() {
+    let addresses = [[0x11; 20], [0x22; 20], [0x33; 20], [0x22; 20], [0x44; 20]];
+    let data = owned_table([
+        binary("address", 20, addresses),
+        bigint("b", [1, 2, 3, 4, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = dense_filter(
+        cols_expr_plan(t, &["address", "b"], &accessor),
+        tab(t),
+        equal(column(t, "address", &accessor), const_binary(&[0x22; 20])),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        binary("address", 20, [[0x22; 20], [0x22; 20]]),
+        bigint("b", [2, 4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_dense_filter_on_a_binary_column_in_a_list_of_literals() {
+    let data = owned_table([
+        binary(
+            "address",
+            20,
+            [[0x11; 20], [0x22; 20], [0x33; 20], [0x44; 20]],
+        ),
+        bigint("b", [1, 2, 3, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = dense_filter(
+        cols_expr_plan(t, &["b"], &accessor),
+        tab(t),
+        or(
+            equal(column(t, "address", &accessor), const_binary(&[0x11; 20])),
+            equal(column(t, "address", &accessor), const_binary(&[0x44; 20])),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("b", [1, 4])]));
+}
+
+#[test]
+fn we_cannot_compare_binary_columns_of_different_lengths() {
+    let data = owned_table::<Curve25519Scalar>([binary("address", 20, [[0x11; 20]])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    assert!(ProvableExprPlan::<RistrettoPoint>::try_new_equals(
+        column(t, "address", &accessor),
+        const_binary(&[0x11; 32]),
+    )
+    .is_err());
+}
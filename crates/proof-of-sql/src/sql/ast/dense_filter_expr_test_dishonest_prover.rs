@@ -11,7 +11,7 @@ use crate::{
     sql::{
         // Making this explicit to ensure that we don't accidentally use the
         // sparse filter for these tests
-        ast::test_utility::{cols_expr, column, const_int128, equal, tab},
+        ast::test_utility::{cols_expr_plan, column, const_int128, equal, tab},
         proof::{
             Indexes, ProofBuilder, ProverEvaluate, ProverHonestyMarker, QueryError, ResultBuilder,
             VerifiableQueryResult,
@@ -47,12 +47,16 @@ impl ProverEvaluate<Curve25519Scalar> for DishonestDenseFilterExpr<RistrettoPoin
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
         // 2. columns
-        let columns = Vec::from_iter(
-            self.results
-                .iter()
-                .map(|expr| expr.result_evaluate(builder.table_length(), alloc, accessor)),
-        );
+        let columns = Vec::from_iter(self.results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
+        for aliased_expr in self.results.iter() {
+            builder.request_post_result_challenges(aliased_expr.expr.num_post_result_challenges());
+        }
         // Compute filtered_columns and indexes
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
         let filtered_columns = tamper_column(alloc, filtered_columns);
@@ -87,7 +91,7 @@ impl ProverEvaluate<Curve25519Scalar> for DishonestDenseFilterExpr<RistrettoPoin
         let columns = Vec::from_iter(
             self.results
                 .iter()
-                .map(|expr| expr.prover_evaluate(builder, alloc, accessor)),
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
         );
         // Compute filtered_columns and indexes
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
@@ -141,13 +145,13 @@ fn we_fail_to_verify_a_basic_dense_filter_with_a_dishonest_prover() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = DishonestDenseFilterExpr::new(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(105_i128)),
     );
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(matches!(
         res.verify(&expr, &accessor, &()),
-        Err(QueryError::ProofError(ProofError::VerificationError(_)))
+        Err(QueryError::ProofError(ProofError::SumcheckFailed(_)))
     ));
 }
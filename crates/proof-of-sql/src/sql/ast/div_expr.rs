@@ -0,0 +1,490 @@
+use super::{
+    count_sign, prover_evaluate_sign, verifier_evaluate_sign, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        math::decimal::scale_scalar,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    },
+};
+use bumpalo::Bump;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for the division of two numeric expressions, such as `total / count_dec`
+///
+/// The quotient is either a decimal with a target scale or, for the division of two integers such as
+/// `total / 3` or `a DIV b`, an integer. Either way it is truncated towards zero, so `-7 / 2 = -3`.
+/// Both operands are first scaled to integers `n` and `d` so that the quotient is `q = n / d`.
+/// The prover then commits to `q` and the remainder `r` and proves that
+/// ```ignore
+///     n = d * q + r,    |r| < |d|,    sign(r) = sign(n) or r = 0
+/// ```
+/// where the bounds are established with sign decompositions.
+///
+/// The constraints cannot be satisfied by a zero divisor, so the proof fails with
+/// [`ProofError::DivisionByZero`] instead. An integer quotient that does not fit in its type,
+/// such as `-32768 / -1` for `SmallInt`s, fails with [`ProofError::DivisionOverflow`], as does
+/// an operand whose value does not fit in the precision of its type.
+///
+/// With a `selection`, such as the where clause in `SELECT a / b FROM t WHERE b <> 0`, only the
+/// selected rows are divided. The prover commits to the divisor `d'` that is `d` on the selected
+/// rows and `1` on the others, proven with
+/// ```ignore
+///     d' = s * d + 1 - s
+/// ```
+/// for the selection `s`, and divides by `d'` instead, so a row that is not selected can neither
+/// divide by zero nor overflow. Its quotient is the dividend, which a filter drops with the row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DivExpr<C: Commitment> {
+    lhs: Box<ProvableExprPlan<C>>,
+    rhs: Box<ProvableExprPlan<C>>,
+    quotient_type: ColumnType,
+    selection: Option<Box<ProvableExprPlan<C>>>,
+}
+
+impl<C: Commitment> Hash for DivExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lhs.hash(state);
+        self.rhs.hash(state);
+        self.quotient_type.hash(state);
+        self.selection.hash(state);
+    }
+}
+
+/// The maximum number of digits of the operands and the quotient of a [`DivExpr`]
+pub(super) const MAX_DIVISION_PRECISION: u8 = 38;
+
+impl<C: Commitment> DivExpr<C> {
+    /// Create a new division expression with a quotient of the given type,
+    /// which is either an integer type or a decimal, that only divides the rows of `selection`, if any
+    ///
+    /// The sign decompositions used to bound the remainder limit the size of the operands, so this
+    /// errors with [`ConversionError::InvalidPrecision`] if a scaled operand could have more than
+    /// [`MAX_DIVISION_PRECISION`] digits. The divisor is one digit shorter so that
+    /// `divisor * quotient` cannot wrap around the field.
+    pub fn try_new(
+        lhs: Box<ProvableExprPlan<C>>,
+        rhs: Box<ProvableExprPlan<C>>,
+        quotient_type: ColumnType,
+        selection: Option<Box<ProvableExprPlan<C>>>,
+    ) -> ConversionResult<Self> {
+        if !matches!(
+            quotient_type,
+            ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt | ColumnType::Decimal75(..)
+        ) {
+            return Err(ConversionError::InvalidDataType {
+                expected: ColumnType::BigInt,
+                actual: quotient_type,
+            });
+        }
+        if let Some(selection) = &selection {
+            if selection.data_type() != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: selection.data_type(),
+                });
+            }
+        }
+        let expr = Self {
+            lhs,
+            rhs,
+            quotient_type,
+            selection,
+        };
+        let (lhs_upscale, rhs_upscale) = expr.upscales();
+        for (operand, upscale, max) in [
+            (&expr.lhs, lhs_upscale, MAX_DIVISION_PRECISION),
+            (&expr.rhs, rhs_upscale, MAX_DIVISION_PRECISION - 1),
+        ] {
+            let datatype = operand.data_type();
+            let precision = match datatype.precision_value() {
+                Some(precision) if datatype != ColumnType::Scalar => {
+                    precision as i16 + upscale as i16
+                }
+                _ => {
+                    return Err(ConversionError::DataTypeMismatch(
+                        expr.lhs.data_type().to_string(),
+                        expr.rhs.data_type().to_string(),
+                    ))
+                }
+            };
+            if precision > max as i16 {
+                return Err(ConversionError::InvalidPrecision(
+                    precision.try_into().unwrap_or(u8::MAX),
+                ));
+            }
+        }
+        Ok(expr)
+    }
+
+    /// The powers of ten that the dividend and the divisor must be scaled by
+    /// so that their integer quotient has the target scale.
+    fn upscales(&self) -> (i8, i8) {
+        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
+        let scale = self.quotient_type.scale().unwrap_or(0);
+        let upscale = rhs_scale as i16 + scale as i16 - lhs_scale as i16;
+        if upscale >= 0 {
+            (upscale as i8, 0)
+        } else {
+            (0, -upscale as i8)
+        }
+    }
+
+    fn scale_operands<'a>(
+        &self,
+        alloc: &'a Bump,
+        lhs: Column<'a, C::Scalar>,
+        rhs: Column<'a, C::Scalar>,
+    ) -> (&'a [C::Scalar], &'a [C::Scalar]) {
+        let (lhs_upscale, rhs_upscale) = self.upscales();
+        (
+            alloc.alloc_slice_copy(&lhs.to_scalar_with_scaling(lhs_upscale)),
+            alloc.alloc_slice_copy(&rhs.to_scalar_with_scaling(rhs_upscale)),
+        )
+    }
+
+    /// The divisor `d'` that is `divisor` on the rows of `selection` and one on the others.
+    fn guard_divisor<'a>(
+        alloc: &'a Bump,
+        divisor: &[C::Scalar],
+        selection: Column<'a, C::Scalar>,
+    ) -> (&'a [C::Scalar], &'a [bool]) {
+        let selection = selection.as_boolean().expect("selection is not boolean");
+        let guarded = alloc.alloc_slice_fill_with(divisor.len(), |i| {
+            if selection[i] {
+                divisor[i]
+            } else {
+                C::Scalar::one()
+            }
+        });
+        (guarded, selection)
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for DivExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.lhs.count(builder)?;
+        self.rhs.count(builder)?;
+        if let Some(selection) = &self.selection {
+            selection.count(builder)?;
+            builder.count_intermediate_mles(1);
+            builder.count_subpolynomials(1);
+        }
+        builder.count_intermediate_mles(2);
+        builder.count_subpolynomials(1);
+        builder.count_degree(3);
+        count_sign(builder)?;
+        count_sign(builder)?;
+        count_sign(builder)?;
+        builder.count_intermediate_mles(2);
+        builder.count_subpolynomials(2);
+        count_sign(builder)?;
+        count_sign(builder)?;
+        Ok(())
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        self.lhs.num_post_result_challenges()
+            + self.rhs.num_post_result_challenges()
+            + self
+                .selection
+                .as_ref()
+                .map_or(0, |selection| selection.num_post_result_challenges())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.quotient_type
+    }
+
+    #[tracing::instrument(name = "DivExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs_column = self.lhs.result_evaluate(table_length, alloc, accessor);
+        let rhs_column = self.rhs.result_evaluate(table_length, alloc, accessor);
+        let (dividend, mut divisor) = self.scale_operands(alloc, lhs_column, rhs_column);
+        if let Some(selection) = &self.selection {
+            let selection = selection.result_evaluate(table_length, alloc, accessor);
+            divisor = Self::guard_divisor(alloc, divisor, selection).0;
+        }
+        // Operands or quotients that overflow cannot be proven, so the proof fails in `prover_evaluate`.
+        let (quotient, _) = divide_columns(alloc, dividend, divisor)
+            .unwrap_or_else(|| zero_columns(alloc, dividend.len()));
+        quotient_column(alloc, self.quotient_type, quotient).unwrap_or(Column::Scalar(quotient))
+    }
+
+    #[tracing::instrument(name = "DivExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs_column = self.lhs.prover_evaluate(builder, alloc, accessor);
+        let rhs_column = self.rhs.prover_evaluate(builder, alloc, accessor);
+        let (dividend, mut divisor) = self.scale_operands(alloc, lhs_column, rhs_column);
+        if let Some(selection) = &self.selection {
+            let selection = selection.prover_evaluate(builder, alloc, accessor);
+            let (guarded, selection) = Self::guard_divisor(alloc, divisor, selection);
+            builder.produce_intermediate_mle(guarded);
+
+            // guarded - selection * divisor - 1 + selection = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(guarded)]),
+                    (
+                        -C::Scalar::one(),
+                        vec![Box::new(selection), Box::new(divisor)],
+                    ),
+                    (-C::Scalar::one(), vec![]),
+                    (C::Scalar::one(), vec![Box::new(selection)]),
+                ],
+            );
+            divisor = guarded;
+        }
+        if divisor.iter().any(Zero::is_zero) {
+            builder.fail_with(ProofError::DivisionByZero);
+        }
+        let (quotient, remainder) = divide_columns(alloc, dividend, divisor).unwrap_or_else(|| {
+            builder.fail_with(ProofError::DivisionOverflow);
+            zero_columns(alloc, dividend.len())
+        });
+        let quotient_column = quotient_column(alloc, self.quotient_type, quotient)
+            .map_err(|error| builder.fail_with(error))
+            .ok();
+        builder.produce_intermediate_mle(quotient);
+        builder.produce_intermediate_mle(remainder);
+
+        // dividend - divisor * quotient - remainder = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::one(), vec![Box::new(dividend)]),
+                (
+                    -C::Scalar::one(),
+                    vec![Box::new(divisor), Box::new(quotient)],
+                ),
+                (-C::Scalar::one(), vec![Box::new(remainder)]),
+            ],
+        );
+
+        let dividend_sign = prover_evaluate_sign(builder, alloc, dividend);
+        let divisor_sign = prover_evaluate_sign(builder, alloc, divisor);
+        // The sign of the quotient is not needed, but its decomposition bounds the quotient
+        // so that the first constraint cannot wrap around the field.
+        prover_evaluate_sign(builder, alloc, quotient);
+
+        // abs_remainder = (1 - 2 * sign(dividend)) * remainder
+        let abs_remainder = alloc.alloc_slice_fill_with(remainder.len(), |i| {
+            if dividend_sign[i] {
+                -remainder[i]
+            } else {
+                remainder[i]
+            }
+        });
+        // slack = (1 - 2 * sign(divisor)) * divisor - 1 - abs_remainder
+        let slack = alloc.alloc_slice_fill_with(divisor.len(), |i| {
+            let abs_divisor = if divisor_sign[i] {
+                -divisor[i]
+            } else {
+                divisor[i]
+            };
+            abs_divisor - C::Scalar::one() - abs_remainder[i]
+        });
+        builder.produce_intermediate_mle(abs_remainder as &[_]);
+        builder.produce_intermediate_mle(slack as &[_]);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::one(), vec![Box::new(abs_remainder as &[_])]),
+                (-C::Scalar::one(), vec![Box::new(remainder)]),
+                (
+                    C::Scalar::TWO,
+                    vec![Box::new(dividend_sign), Box::new(remainder)],
+                ),
+            ],
+        );
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::one(), vec![Box::new(slack as &[_])]),
+                (-C::Scalar::one(), vec![Box::new(divisor)]),
+                (
+                    C::Scalar::TWO,
+                    vec![Box::new(divisor_sign), Box::new(divisor)],
+                ),
+                (C::Scalar::one(), vec![]),
+                (C::Scalar::one(), vec![Box::new(abs_remainder as &[_])]),
+            ],
+        );
+
+        // abs_remainder >= 0 and slack >= 0
+        prover_evaluate_sign(builder, alloc, abs_remainder);
+        prover_evaluate_sign(builder, alloc, slack);
+
+        quotient_column.unwrap_or(Column::Scalar(quotient))
+    }
+
+    #[tracing::instrument(name = "DivExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let rand_eval = builder.mle_evaluations.random_evaluation;
+        let lhs_eval = self.lhs.verifier_evaluate(builder, accessor)?;
+        let rhs_eval = self.rhs.verifier_evaluate(builder, accessor)?;
+        let (lhs_upscale, rhs_upscale) = self.upscales();
+        let scale = |eval, upscale| {
+            scale_scalar(eval, upscale)
+                .map_err(|_| ProofError::VerificationError("division operands cannot be scaled"))
+        };
+        let dividend_eval = scale(lhs_eval, lhs_upscale)?;
+        let mut divisor_eval = scale(rhs_eval, rhs_upscale)?;
+        if let Some(selection) = &self.selection {
+            let selection_eval = selection.verifier_evaluate(builder, accessor)?;
+            let guarded_eval = builder.consume_intermediate_mle();
+
+            // guarded - selection * divisor - 1 + selection = 0
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &(rand_eval
+                    * (guarded_eval - selection_eval * divisor_eval - one_eval + selection_eval)),
+            );
+            divisor_eval = guarded_eval;
+        }
+        let quotient_eval = builder.consume_intermediate_mle();
+        let remainder_eval = builder.consume_intermediate_mle();
+
+        // dividend - divisor * quotient - remainder = 0
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(rand_eval * (dividend_eval - divisor_eval * quotient_eval - remainder_eval)),
+        );
+
+        let dividend_sign_eval = verifier_evaluate_sign(builder, dividend_eval, one_eval)?;
+        let divisor_sign_eval = verifier_evaluate_sign(builder, divisor_eval, one_eval)?;
+        verifier_evaluate_sign(builder, quotient_eval, one_eval)?;
+
+        let abs_remainder_eval = builder.consume_intermediate_mle();
+        let slack_eval = builder.consume_intermediate_mle();
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(rand_eval
+                * (abs_remainder_eval - remainder_eval
+                    + C::Scalar::TWO * dividend_sign_eval * remainder_eval)),
+        );
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(rand_eval
+                * (slack_eval - divisor_eval
+                    + C::Scalar::TWO * divisor_sign_eval * divisor_eval
+                    + one_eval
+                    + abs_remainder_eval)),
+        );
+
+        // abs_remainder >= 0 and slack >= 0
+        let abs_remainder_sign_eval =
+            verifier_evaluate_sign(builder, abs_remainder_eval, one_eval)?;
+        let slack_sign_eval = verifier_evaluate_sign(builder, slack_eval, one_eval)?;
+        if !abs_remainder_sign_eval.is_zero() || !slack_sign_eval.is_zero() {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "division remainder is out of bounds",
+            )))?;
+        }
+
+        Ok(quotient_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+        if let Some(selection) = &self.selection {
+            selection.get_column_references(columns);
+        }
+    }
+}
+
+/// Compute the quotients and remainders of `dividend / divisor`, truncated towards zero,
+/// or `None` if an operand does not fit in an `i128`.
+///
+/// A zero divisor results in a zero quotient, with the dividend as the remainder.
+fn divide_columns<'a, S: Scalar>(
+    alloc: &'a Bump,
+    dividend: &[S],
+    divisor: &[S],
+) -> Option<(&'a [S], &'a [S])> {
+    let quotient = alloc.alloc_slice_fill_default(dividend.len());
+    let remainder = alloc.alloc_slice_fill_default(dividend.len());
+    for (i, (&n, &d)) in dividend.iter().zip(divisor).enumerate() {
+        let (n, d): (i128, i128) = (n.try_into().ok()?, d.try_into().ok()?);
+        let (q, r) = if d == 0 { (0, n) } else { (n / d, n % d) };
+        quotient[i] = S::from(&q);
+        remainder[i] = S::from(&r);
+    }
+    Some((quotient, remainder))
+}
+
+/// Two columns of zeros of the given length, for quotients and remainders.
+fn zero_columns<S: Scalar>(alloc: &Bump, length: usize) -> (&[S], &[S]) {
+    (
+        alloc.alloc_slice_fill_default(length),
+        alloc.alloc_slice_fill_default(length),
+    )
+}
+
+/// The quotients as a column of `quotient_type`.
+///
+/// Errors with [`ProofError::DivisionOverflow`] if an integer quotient does not fit in it,
+/// and with [`ProofError::InvalidPlan`] if it is neither an integer type nor a decimal.
+fn quotient_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    quotient_type: ColumnType,
+    quotient: &'a [S],
+) -> Result<Column<'a, S>, ProofError> {
+    fn to_integers<'a, S: Scalar, T: TryFrom<i128> + Copy>(
+        alloc: &'a Bump,
+        quotient: &[S],
+    ) -> Option<&'a [T]> {
+        let values = quotient
+            .iter()
+            .map(|&q| {
+                let q: i128 = q.try_into().ok()?;
+                T::try_from(q).ok()
+            })
+            .collect::<Option<Vec<T>>>()?;
+        Some(alloc.alloc_slice_copy(&values))
+    }
+    let overflow = || ProofError::DivisionOverflow;
+    match quotient_type {
+        ColumnType::SmallInt => to_integers(alloc, quotient)
+            .map(Column::SmallInt)
+            .ok_or_else(overflow),
+        ColumnType::Int => to_integers(alloc, quotient)
+            .map(Column::Int)
+            .ok_or_else(overflow),
+        ColumnType::BigInt => to_integers(alloc, quotient)
+            .map(Column::BigInt)
+            .ok_or_else(overflow),
+        ColumnType::Decimal75(precision, scale) => {
+            Ok(Column::Decimal75(precision, scale, quotient))
+        }
+        _ => Err(ProofError::InvalidPlan(
+            "the quotient of a division must be an integer or a decimal",
+        )),
+    }
+}
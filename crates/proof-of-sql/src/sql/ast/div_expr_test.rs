@@ -0,0 +1,338 @@
+use super::{test_utility::*, ProvableExpr};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, Column, ColumnType, OwnedColumn, OwnedTable,
+            OwnedTableTestAccessor, TestAccessor,
+        },
+        math::decimal::Precision,
+        proof::ProofError,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::ProvableExprPlan,
+        parse::{ConversionError, QueryExpr},
+        proof::{exercise_verification, ProverConfig, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+#[test]
+fn we_can_prove_an_exact_decimal_division() {
+    let data = owned_table([
+        decimal75("total", 10, 2, [1000, 750, -900]),
+        decimal75("count_dec", 10, 2, [200, 250, 300]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(
+            div(
+                column(t, "total", &accessor),
+                column(t, "count_dec", &accessor),
+                2,
+            ),
+            "ratio",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    // 10.00 / 2.00 = 5.00, 7.50 / 2.50 = 3.00, -9.00 / 3.00 = -3.00
+    let expected = owned_table([decimal75("ratio", 12, 2, [500, 300, -300])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_repeating_decimal_division_truncated_to_the_target_scale() {
+    let data = owned_table([bigint("a", [1, 2, -1, 1, 0]), bigint("b", [3, 3, 3, -3, 7])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(
+            div(column(t, "a", &accessor), column(t, "b", &accessor), 4),
+            "q",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    // The quotients are truncated towards zero, e.g. 2 / 3 = 0.6666
+    let expected = owned_table([decimal75("q", 23, 4, [3333, 6666, -3333, -3333, 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_division_by_a_literal_with_a_smaller_target_scale() {
+    let data = owned_table([
+        decimal75("a", 6, 3, [1000, 2999, -5550]),
+        bigint("b", [1, 2, 3]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![
+            aliased_plan(div(column(t, "a", &accessor), const_bigint(3), 1), "q"),
+            col_expr_plan(t, "b", &accessor),
+        ],
+        tab(t),
+        gte(column(t, "b", &accessor), const_bigint(2)),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    // 2.999 / 3 = 0.9, -5.550 / 3 = -1.8
+    let expected = owned_table([decimal75("q", 4, 1, [9, -18]), bigint("b", [2, 3])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_prove_a_division_by_zero() {
+    let data = owned_table([
+        decimal75("a", 10, 2, [100, 200]),
+        decimal75("b", 10, 2, [100, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(
+            div(column(t, "a", &accessor), column(t, "b", &accessor), 2),
+            "q",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_config(
+        &ast,
+        &accessor,
+        &(),
+        &ProverConfig::default(),
+    );
+    assert!(matches!(res, Err(ProofError::DivisionByZero)));
+}
+
+#[test]
+fn we_cannot_divide_by_a_non_numeric_or_too_large_expression() {
+    let data = owned_table([
+        decimal75("a", 38, 0, [100]),
+        varchar("b", ["x"]),
+        bigint("c", [3]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_divide(
+            column(t, "c", &accessor),
+            column(t, "b", &accessor),
+            2,
+            None,
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    // The quotient would need 39 digits
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_divide(
+            column(t, "a", &accessor),
+            column(t, "c", &accessor),
+            1,
+            None,
+        ),
+        Err(ConversionError::InvalidPrecision(39))
+    ));
+    // The divisor may have at most 37 digits
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_divide(
+            column(t, "c", &accessor),
+            column(t, "a", &accessor),
+            0,
+            None,
+        ),
+        Err(ConversionError::InvalidPrecision(38))
+    ));
+}
+
+#[test]
+fn we_cannot_prove_a_division_of_a_value_that_exceeds_its_precision() {
+    // `decimal75` rejects such values, so the column is built directly
+    let too_large = Curve25519Scalar::from(i128::MAX) + Curve25519Scalar::from(1);
+    let data = owned_table([
+        (
+            "a".parse().unwrap(),
+            OwnedColumn::Decimal75(
+                Precision::new(38).unwrap(),
+                0,
+                vec![Curve25519Scalar::from(1), too_large],
+            ),
+        ),
+        bigint("c", [3, 3]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(
+            div(column(t, "a", &accessor), column(t, "c", &accessor), 0),
+            "q",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_config(
+        &ast,
+        &accessor,
+        &(),
+        &ProverConfig::default(),
+    );
+    assert!(matches!(res, Err(ProofError::DivisionOverflow)));
+}
+
+#[test]
+fn we_can_compute_the_correct_result_of_a_division_using_result_evaluate() {
+    let data = owned_table([
+        decimal75("a", 10, 2, [1000, 100, -700]),
+        int("b", [2, 3, 2]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let div_expr: ProvableExprPlan<RistrettoPoint> =
+        div(column(t, "a", &accessor), column(t, "b", &accessor), 3);
+    assert_eq!(
+        div_expr.data_type(),
+        ColumnType::Decimal75(Precision::new(11).unwrap(), 3)
+    );
+    let alloc = Bump::new();
+    let res = div_expr.result_evaluate(3, &alloc, &accessor);
+    let expected_scalars = [
+        Curve25519Scalar::from(5000),
+        Curve25519Scalar::from(333),
+        Curve25519Scalar::from(-3500),
+    ];
+    let expected_res = Column::Decimal75(Precision::new(11).unwrap(), 3, &expected_scalars);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_integer_division_truncated_towards_zero() {
+    let data = owned_table([
+        bigint("total", [-7, 7, -7, 7, 0, 9]),
+        smallint("d", [2_i16, 2, -2, -2, 5, 3]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![
+            aliased_plan(
+                int_div(column(t, "total", &accessor), column(t, "d", &accessor)),
+                "q",
+            ),
+            aliased_plan(
+                int_div(column(t, "d", &accessor), const_bigint(2)),
+                "half_d",
+            ),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    // -7 / 2 = -3 rather than -4, and the quotient has the type of the dividend
+    let expected = owned_table([
+        bigint("q", [-3, 3, 3, -3, 0, 3]),
+        smallint("half_d", [1_i16, 1, -1, -1, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_plan_and_prove_an_integer_division_by_a_literal_from_sql() {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, owned_table([bigint("total", [10, -10, 2, 3])]), 0);
+    for sql in [
+        "SELECT total / 3 AS q FROM t",
+        "SELECT total DIV 3 AS q FROM t",
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let res = VerifiableQueryResult::new(query.proof_expr(), &accessor, &()).unwrap();
+        exercise_verification(&res, query.proof_expr(), &accessor, t);
+        let res = res
+            .verify(query.proof_expr(), &accessor, &())
+            .unwrap()
+            .table;
+        let res: OwnedTable<Curve25519Scalar> = query
+            .result()
+            .transform_results(res.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(res, owned_table([bigint("q", [3, -3, 0, 1])]), "{sql}");
+    }
+}
+
+#[test]
+fn we_cannot_prove_an_integer_division_by_zero_or_an_overflowing_one() {
+    let data = owned_table([
+        smallint("a", [i16::MIN, 1]),
+        smallint("b", [-1_i16, 1]),
+        smallint("zero", [1_i16, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let prove = |divisor| {
+        let ast = dense_filter(
+            vec![aliased_plan(
+                int_div(column(t, "a", &accessor), column(t, divisor, &accessor)),
+                "q",
+            )],
+            tab(t),
+            const_bool(true),
+        );
+        VerifiableQueryResult::<InnerProductProof>::new_with_config(
+            &ast,
+            &accessor,
+            &(),
+            &ProverConfig::default(),
+        )
+    };
+    assert!(matches!(prove("zero"), Err(ProofError::DivisionByZero)));
+    // -32768 / -1 = 32768 does not fit in a `SmallInt`
+    assert!(matches!(prove("b"), Err(ProofError::DivisionOverflow)));
+}
+
+#[test]
+fn we_cannot_integer_divide_non_integers() {
+    let data = owned_table([
+        decimal75("a", 10, 2, [100]),
+        int128("b", [3]),
+        bigint("c", [3]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    for (lhs, rhs) in [("a", "c"), ("c", "a"), ("b", "c"), ("c", "b")] {
+        assert!(matches!(
+            ProvableExprPlan::<RistrettoPoint>::try_new_integer_divide(
+                column(t, lhs, &accessor),
+                column(t, rhs, &accessor),
+                None,
+            ),
+            Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+    }
+}
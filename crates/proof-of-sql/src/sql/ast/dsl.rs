@@ -0,0 +1,137 @@
+//! Combinators for building a [`ProvableExprPlan`] without parsing SQL, such as
+//! ```ignore
+//!     col(a).eq(lit(1)).or(!col(b).ge(lit("x"))).build()
+//! ```
+//! The plans are the same as the ones built from the equivalent SQL.
+//! Since any combination may be ill-typed, the first error is kept and returned by
+//! [`ExprBuilder::build`].
+use super::ProvableExprPlan;
+use crate::{
+    base::{commitment::Commitment, database::ColumnRef},
+    sql::parse::{ConversionError, WhereExprBuilder},
+};
+use proof_of_sql_parser::intermediate_ast::{Expression, Literal};
+use std::{collections::HashMap, ops::Not};
+
+/// A [`ProvableExprPlan`] under construction, or the first error encountered while building it.
+#[derive(Debug)]
+pub struct ExprBuilder<C: Commitment>(Result<ProvableExprPlan<C>, ConversionError>);
+
+/// A reference to a column.
+pub fn col<C: Commitment>(column_ref: ColumnRef) -> ExprBuilder<C> {
+    ExprBuilder(Ok(ProvableExprPlan::new_column(column_ref)))
+}
+
+/// A literal, such as `lit(1)`, `lit("x")` or `lit(true)`.
+pub fn lit<C: Commitment>(literal: impl Into<Literal>) -> ExprBuilder<C> {
+    ExprBuilder(
+        WhereExprBuilder::new(&HashMap::new()).build_expr(Expression::Literal(literal.into())),
+    )
+}
+
+/// The `AND` of all of `predicates` as a balanced tree, or `TRUE` if there are none.
+/// See [`ProvableExprPlan::try_new_and_all`].
+pub fn all<C: Commitment>(predicates: impl IntoIterator<Item = ExprBuilder<C>>) -> ExprBuilder<C> {
+    ExprBuilder(
+        predicates
+            .into_iter()
+            .map(ExprBuilder::build)
+            .collect::<Result<_, _>>()
+            .and_then(ProvableExprPlan::try_new_and_all),
+    )
+}
+
+impl<C: Commitment> From<ProvableExprPlan<C>> for ExprBuilder<C> {
+    fn from(plan: ProvableExprPlan<C>) -> Self {
+        Self(Ok(plan))
+    }
+}
+
+/// `NOT self`, which can also be written as `!self`
+impl<C: Commitment> Not for ExprBuilder<C> {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(self.0.and_then(ProvableExprPlan::try_new_not))
+    }
+}
+
+impl<C: Commitment> ExprBuilder<C> {
+    /// Returns the plan, or the first error encountered while building it.
+    pub fn build(self) -> Result<ProvableExprPlan<C>, ConversionError> {
+        self.0
+    }
+
+    fn then(
+        self,
+        other: Self,
+        f: impl FnOnce(
+            ProvableExprPlan<C>,
+            ProvableExprPlan<C>,
+        ) -> Result<ProvableExprPlan<C>, ConversionError>,
+    ) -> Self {
+        Self(self.0.and_then(|lhs| f(lhs, other.0?)))
+    }
+
+    /// `self AND other`
+    pub fn and(self, other: Self) -> Self {
+        self.then(other, ProvableExprPlan::try_new_and)
+    }
+
+    /// `self OR other`
+    pub fn or(self, other: Self) -> Self {
+        self.then(other, ProvableExprPlan::try_new_or)
+    }
+
+    /// `self = other`
+    pub fn eq(self, other: Self) -> Self {
+        self.then(other, ProvableExprPlan::try_new_equals)
+    }
+
+    /// `self <> other`, which is proven as `NOT (self = other)`
+    pub fn ne(self, other: Self) -> Self {
+        self.eq(other).not()
+    }
+
+    /// `self <= other`
+    pub fn le(self, other: Self) -> Self {
+        self.then(other, |lhs, rhs| {
+            ProvableExprPlan::try_new_inequality(lhs, rhs, true)
+        })
+    }
+
+    /// `self >= other`
+    pub fn ge(self, other: Self) -> Self {
+        self.then(other, |lhs, rhs| {
+            ProvableExprPlan::try_new_inequality(lhs, rhs, false)
+        })
+    }
+
+    /// `self < other`, which is proven as `NOT (self >= other)`
+    pub fn lt(self, other: Self) -> Self {
+        self.ge(other).not()
+    }
+
+    /// `self > other`, which is proven as `NOT (self <= other)`
+    pub fn gt(self, other: Self) -> Self {
+        self.le(other).not()
+    }
+
+    /// `self / other`, with the quotient truncated to `scale`
+    pub fn div(self, other: Self, scale: i8) -> Self {
+        self.then(other, |lhs, rhs| {
+            ProvableExprPlan::try_new_divide(lhs, rhs, scale, None)
+        })
+    }
+
+    /// `self DIV other`, the integer quotient truncated towards zero
+    pub fn int_div(self, other: Self) -> Self {
+        self.then(other, |lhs, rhs| {
+            ProvableExprPlan::try_new_integer_divide(lhs, rhs, None)
+        })
+    }
+
+    /// `SIGN(self)`
+    pub fn sign(self) -> Self {
+        Self(self.0.and_then(ProvableExprPlan::try_new_sign))
+    }
+}
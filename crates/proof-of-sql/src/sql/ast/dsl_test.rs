@@ -0,0 +1,125 @@
+use super::{dsl::*, test_utility::*, ProvableExprPlan};
+use crate::{
+    base::database::{
+        owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef, TestAccessor,
+    },
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+    sql::{
+        parse::{ConversionError, QueryExpr},
+        proof::VerifiableQueryResult,
+    },
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::intermediate_decimal::IntermediateDecimal;
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> (TableRef, OwnedTableTestAccessor<DoryEvaluationProof>) {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        t,
+        owned_table([
+            bigint("a", [1, -2, 3, 0, 1]),
+            varchar("b", ["x", "yy", "x", "zzzz", "w"]),
+            decimal75("c", 4, 2, [125, -300, 0, 50, 99]),
+        ]),
+        0,
+    );
+    (t, accessor)
+}
+
+#[test]
+fn we_can_build_the_same_plan_as_the_parsed_query_with_the_dsl() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let (t, accessor) = accessor_with_table(setup.clone());
+    let query = QueryExpr::<DoryCommitment>::try_new(
+        "select a, b from sxt.t where (a = 1 or b <> 'x') and c < 0.8 and sign(a) > -1"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let a = || col(col_ref(t, "a", &accessor));
+    let b = || col(col_ref(t, "b", &accessor));
+    let c = || col(col_ref(t, "c", &accessor));
+    let where_clause = a()
+        .eq(lit(1))
+        .or(b().ne(lit("x")))
+        .and(c().lt(lit(IntermediateDecimal::try_from("0.8").unwrap())))
+        .and(a().sign().gt(lit(-1)))
+        .build()
+        .unwrap();
+    let plan = dense_filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        where_clause,
+    );
+    assert_eq!(query.proof_expr(), &plan);
+
+    let res = VerifiableQueryResult::<DoryEvaluationProof>::new(&plan, &accessor, &setup)
+        .unwrap()
+        .verify(&plan, &accessor, &(&setup).into())
+        .unwrap()
+        .table;
+    assert_eq!(res, owned_table([bigint("a", [0]), varchar("b", ["zzzz"])]));
+}
+
+#[test]
+fn we_can_build_a_division_with_the_dsl() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let (t, accessor) = accessor_with_table(setup);
+    let quotient = col::<DoryCommitment>(col_ref(t, "c", &accessor))
+        .div(col(col_ref(t, "a", &accessor)), 2)
+        .build()
+        .unwrap();
+    assert_eq!(
+        quotient,
+        div(column(t, "c", &accessor), column(t, "a", &accessor), 2)
+    );
+}
+
+#[test]
+fn the_dsl_returns_the_first_type_error() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let (t, accessor) = accessor_with_table(setup);
+    let a = || col::<DoryCommitment>(col_ref(t, "a", &accessor));
+    let b = || col::<DoryCommitment>(col_ref(t, "b", &accessor));
+    assert!(matches!(
+        a().and(lit(true)).build(),
+        Err(ConversionError::InvalidDataType {
+            expected: ColumnType::Boolean,
+            actual: ColumnType::BigInt
+        })
+    ));
+    assert!(matches!(
+        a().eq(lit("x")).or(!b()).build(),
+        Err(ConversionError::DataTypeMismatch(..))
+    ));
+}
+
+#[test]
+fn we_can_build_the_and_of_several_predicates_with_the_dsl() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let (t, accessor) = accessor_with_table(setup);
+    let a = || col::<DoryCommitment>(col_ref(t, "a", &accessor));
+    let predicates = || [a().ge(lit(0)), a().le(lit(2)), a().ne(lit(1))];
+    assert_eq!(
+        all(predicates()).build().unwrap(),
+        ProvableExprPlan::try_new_and_all(
+            predicates()
+                .into_iter()
+                .map(|predicate| predicate.build().unwrap())
+                .collect()
+        )
+        .unwrap()
+    );
+    assert!(matches!(
+        all([a().ge(lit(0)), a()]).build(),
+        Err(ConversionError::InvalidDataType {
+            expected: ColumnType::Boolean,
+            actual: ColumnType::BigInt
+        })
+    ));
+}
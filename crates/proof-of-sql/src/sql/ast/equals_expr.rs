@@ -1,4 +1,7 @@
-use super::{scale_and_subtract, scale_and_subtract_eval, ProvableExpr, ProvableExprPlan};
+use super::{
+    scale_and_subtract, scale_and_subtract_constant, scale_and_subtract_eval, LiteralExpr,
+    ProvableExpr, ProvableExprPlan,
+};
 use crate::{
     base::{
         commitment::Commitment,
@@ -11,20 +14,51 @@ use crate::{
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// The column operand, the literal operand and whether the literal is on the left hand side
+/// of an equality between a column and a literal.
+type ColumnAndLiteral<'a, C> = (
+    &'a ProvableExprPlan<C>,
+    &'a LiteralExpr<<C as Commitment>::Scalar>,
+    bool,
+);
 
 /// Provable AST expression for an equals expression
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EqualsExpr<C: Commitment> {
     lhs: Box<ProvableExprPlan<C>>,
     rhs: Box<ProvableExprPlan<C>>,
 }
 
+impl<C: Commitment> Hash for EqualsExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lhs.hash(state);
+        self.rhs.hash(state);
+    }
+}
+
 impl<C: Commitment> EqualsExpr<C> {
     /// Create a new equals expression
     pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>) -> Self {
         Self { lhs, rhs }
     }
+
+    /// The operands of this expression if it compares a column with a literal.
+    fn column_and_literal(&self) -> Option<ColumnAndLiteral<C>> {
+        match (&*self.lhs, &*self.rhs) {
+            (column @ ProvableExprPlan::Column(_), ProvableExprPlan::Literal(literal)) => {
+                Some((column, literal, false))
+            }
+            (ProvableExprPlan::Literal(literal), column @ ProvableExprPlan::Column(_)) => {
+                Some((column, literal, true))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
@@ -35,6 +69,10 @@ impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
         Ok(())
     }
 
+    fn num_post_result_challenges(&self) -> usize {
+        self.lhs.num_post_result_challenges() + self.rhs.num_post_result_challenges()
+    }
+
     fn data_type(&self) -> ColumnType {
         ColumnType::Boolean
     }
@@ -48,6 +86,12 @@ impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
     ) -> Column<'a, C::Scalar> {
         let lhs_column = self.lhs.result_evaluate(table_length, alloc, accessor);
         let rhs_column = self.rhs.result_evaluate(table_length, alloc, accessor);
+        #[cfg(feature = "vectorized-equality")]
+        if let Some(selection) =
+            result_evaluate_integer_equals(table_length, alloc, &lhs_column, &rhs_column)
+        {
+            return Column::Boolean(selection);
+        }
         let res = scale_and_subtract(alloc, lhs_column, rhs_column, true)
             .expect("Failed to scale and subtract");
         Column::Boolean(result_evaluate_equals_zero(table_length, alloc, res))
@@ -60,13 +104,22 @@ impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
         alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        let lhs_column = self.lhs.prover_evaluate(builder, alloc, accessor);
-        let rhs_column = self.rhs.prover_evaluate(builder, alloc, accessor);
-        let res = scale_and_subtract(alloc, lhs_column, rhs_column, true)
-            .expect("Failed to scale and subtract");
+        // `column = literal` is the shape of a key lookup, so its difference is computed without
+        // materializing the literal as a column. The proof is the same as on the general path.
+        let res = if let Some((column, literal, literal_is_lhs)) = self.column_and_literal() {
+            let column = column.prover_evaluate(builder, alloc, accessor);
+            let (constant, constant_scale) = literal.scalar_and_scale(accessor.varchar_hash());
+            scale_and_subtract_constant(alloc, column, constant, constant_scale, literal_is_lhs)
+        } else {
+            let lhs_column = self.lhs.prover_evaluate(builder, alloc, accessor);
+            let rhs_column = self.rhs.prover_evaluate(builder, alloc, accessor);
+            scale_and_subtract(alloc, lhs_column, rhs_column, true)
+        }
+        .expect("Failed to scale and subtract");
         Column::Boolean(prover_evaluate_equals_zero(builder, alloc, res))
     }
 
+    #[tracing::instrument(name = "EqualsExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
@@ -96,6 +149,33 @@ pub fn result_evaluate_equals_zero<'a, S: Scalar>(
     alloc.alloc_slice_fill_with(table_length, |i| lhs[i] == S::zero())
 }
 
+/// Compares two integer columns of the same type directly, without converting them to scalars.
+///
+/// This produces the same selection as [`result_evaluate_equals_zero`] on the difference of the columns.
+/// `None` is returned for any other pair of columns, which must go through the scalar path instead.
+#[cfg(feature = "vectorized-equality")]
+pub fn result_evaluate_integer_equals<'a, S: Scalar>(
+    table_length: usize,
+    alloc: &'a Bump,
+    lhs: &Column<'a, S>,
+    rhs: &Column<'a, S>,
+) -> Option<&'a [bool]> {
+    let selection = match (lhs, rhs) {
+        (Column::BigInt(lhs), Column::BigInt(rhs)) => {
+            let selection = alloc.alloc_slice_fill_copy(table_length, false);
+            slice_ops::vectorized_eq(selection, lhs, rhs);
+            selection
+        }
+        (Column::Int(lhs), Column::Int(rhs)) => {
+            let selection = alloc.alloc_slice_fill_copy(table_length, false);
+            slice_ops::vectorized_eq(selection, lhs, rhs);
+            selection
+        }
+        _ => return None,
+    };
+    Some(selection)
+}
+
 pub fn prover_evaluate_equals_zero<'a, S: Scalar>(
     builder: &mut ProofBuilder<'a, S>,
     alloc: &'a Bump,
@@ -2,14 +2,14 @@ use crate::{
     base::{
         commitment::InnerProductProof,
         database::{
-            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
-            OwnedTableTestAccessor, RandomTestAccessorDescriptor, RecordBatchTestAccessor,
-            TestAccessor,
+            owned_table_utility::*, Column, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            RecordBatchTestAccessor, TestAccessor,
         },
         scalar::{Curve25519Scalar, Scalar},
     },
     record_batch,
     sql::ast::{test_expr::TestExprNode, test_utility::*, ProvableExpr, ProvableExprPlan},
+    testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
 };
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
@@ -470,6 +470,13 @@ fn we_can_query_random_tables_with_a_non_zero_offset() {
     we_can_query_random_tables_with_multiple_selected_rows_and_given_offset(121);
 }
 
+#[test]
+fn we_can_query_random_tables_with_offsets_near_a_power_of_two() {
+    for offset in [(1 << 16) - 1, 1 << 16, (1 << 16) + 1] {
+        we_can_query_random_tables_with_multiple_selected_rows_and_given_offset(offset);
+    }
+}
+
 #[test]
 fn we_can_compute_the_correct_output_of_an_equals_expr_using_result_evaluate() {
     let data: OwnedTable<Curve25519Scalar> = owned_table([
@@ -0,0 +1,64 @@
+use super::{result_evaluate_equals_zero, result_evaluate_integer_equals, scale_and_subtract};
+use crate::base::{database::Column, scalar::Curve25519Scalar};
+use bumpalo::Bump;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+    SeedableRng,
+};
+
+fn scalar_path<'a>(
+    alloc: &'a Bump,
+    lhs: Column<'a, Curve25519Scalar>,
+    rhs: Column<'a, Curve25519Scalar>,
+) -> &'a [bool] {
+    let diff = scale_and_subtract(alloc, lhs, rhs, true).unwrap();
+    result_evaluate_equals_zero(diff.len(), alloc, diff)
+}
+
+#[test]
+fn the_vectorized_path_matches_the_scalar_path_for_bigint_columns() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let dist = Uniform::new(-3i64, 4);
+    let alloc = Bump::new();
+    for n in [0, 1, 63, 64, 65, 1000] {
+        let lhs = alloc.alloc_slice_fill_with(n, |_| dist.sample(&mut rng));
+        let rhs = alloc.alloc_slice_fill_with(n, |_| dist.sample(&mut rng));
+        let (lhs, rhs) = (Column::BigInt(lhs), Column::BigInt(rhs));
+        let vectorized = result_evaluate_integer_equals(n, &alloc, &lhs, &rhs).unwrap();
+        assert_eq!(vectorized, scalar_path(&alloc, lhs, rhs));
+    }
+}
+
+#[test]
+fn the_vectorized_path_matches_the_scalar_path_for_int_columns() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let dist = Uniform::new(-3i32, 4);
+    let alloc = Bump::new();
+    let n = 1000;
+    let lhs = alloc.alloc_slice_fill_with(n, |_| dist.sample(&mut rng));
+    let rhs = alloc.alloc_slice_fill_with(n, |_| dist.sample(&mut rng));
+    let (lhs, rhs) = (Column::Int(lhs), Column::Int(rhs));
+    let vectorized = result_evaluate_integer_equals(n, &alloc, &lhs, &rhs).unwrap();
+    assert_eq!(vectorized, scalar_path(&alloc, lhs, rhs));
+}
+
+#[test]
+fn the_vectorized_path_matches_the_scalar_path_for_extreme_values() {
+    let alloc = Bump::new();
+    let lhs = Column::<Curve25519Scalar>::BigInt(&[i64::MIN, i64::MAX, 0, -1, i64::MIN]);
+    let rhs = Column::BigInt(&[i64::MIN, i64::MIN, 0, 1, i64::MAX]);
+    let vectorized = result_evaluate_integer_equals(5, &alloc, &lhs, &rhs).unwrap();
+    assert_eq!(vectorized, &[true, false, true, false, false]);
+    assert_eq!(vectorized, scalar_path(&alloc, lhs, rhs));
+}
+
+#[test]
+fn other_column_pairs_fall_back_to_the_scalar_path() {
+    let alloc = Bump::new();
+    let bigint = Column::<Curve25519Scalar>::BigInt(&[1, 2]);
+    let int = Column::Int(&[1, 2]);
+    let int128 = Column::<Curve25519Scalar>::Int128(&[1, 2]);
+    assert!(result_evaluate_integer_equals(2, &alloc, &bigint, &int).is_none());
+    assert!(result_evaluate_integer_equals(2, &alloc, &int128, &int128).is_none());
+}
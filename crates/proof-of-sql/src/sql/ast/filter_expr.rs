@@ -4,6 +4,7 @@ use crate::{
         commitment::Commitment,
         database::{
             Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            TableRef,
         },
         proof::ProofError,
     },
@@ -73,6 +74,7 @@ where
         accessor.get_offset(self.table.table_ref)
     }
 
+    #[tracing::instrument(name = "FilterExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
@@ -104,6 +106,10 @@ where
 
         columns
     }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
 }
 
 pub type FilterExpr<C> = OstensibleFilterExpr<C, HonestProver>;
@@ -122,6 +128,7 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for FilterExpr<C> {
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
 
         // set result indexes
         let indexes = selection
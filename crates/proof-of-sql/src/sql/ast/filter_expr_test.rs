@@ -159,7 +159,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_basic_filter() {
     accessor.add_table(t, data, 0);
     let where_clause = equal(column(t, "a", &accessor), const_int128(5));
     let expr = filter(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -42,6 +42,7 @@ impl ProverEvaluate<Curve25519Scalar> for DishonestFilterExpr {
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
 
         // set result indexes
         let mut indexes: Vec<_> = selection
@@ -90,9 +91,9 @@ fn we_fail_to_verify_a_basic_filter_with_a_dishonest_prover() {
     accessor.add_table(t, data, 0);
     let where_clause = equal(column(t, "a", &accessor), const_int128(5_i128));
     let expr = DishonestFilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(matches!(
         res.verify(&expr, &accessor, &()),
-        Err(QueryError::ProofError(ProofError::VerificationError(_)))
+        Err(QueryError::ProofError(ProofError::SumcheckFailed(_)))
     ));
 }
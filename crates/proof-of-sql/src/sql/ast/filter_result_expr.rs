@@ -74,7 +74,9 @@ impl FilterResultExpr {
             // (in particular the tests need to used the OwnedTableTestAccessor)
             Column::Scalar(_col) => todo!(),
             Column::Decimal75(_, _, col) => prover_evaluate_impl(builder, alloc, selection, col),
-            Column::VarChar((_, scals)) => prover_evaluate_impl(builder, alloc, selection, scals),
+            Column::VarChar((_, scals)) | Column::Binary(_, (_, scals)) => {
+                prover_evaluate_impl(builder, alloc, selection, scals)
+            }
         };
     }
 
@@ -1,13 +1,13 @@
 use super::{
     aggregate_columns, fold_columns, fold_vals, group_by_util::AggregatedColumns,
-    provable_expr_plan::ProvableExprPlan, ColumnExpr, ProvableExpr, TableExpr,
+    provable_expr_plan::ProvableExprPlan, ColumnExpr, PostAggregationExpr, ProvableExpr, TableExpr,
 };
 use crate::{
     base::{
         commitment::Commitment,
         database::{
             Column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
-            MetadataAccessor,
+            MetadataAccessor, TableRef,
         },
         proof::ProofError,
         scalar::Scalar,
@@ -28,7 +28,8 @@ use std::collections::HashSet;
 /// Provable expressions for queries of the form
 /// ```ignore
 ///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
-///         SUM(<sum_expr1>.0) as <sum_expr1>.1, ..., SUM(<sum_exprN>.0) as <sum_exprN>.1,
+///         SUM(<sum_expr1>.0) FILTER (WHERE <sum_filter1>) as <sum_expr1>.1, ...,
+///         SUM(<sum_exprN>.0) FILTER (WHERE <sum_filterN>) as <sum_exprN>.1,
 ///         COUNT(*) as count_alias
 ///     FROM <table>
 ///     WHERE <where_clause>
@@ -36,6 +37,15 @@ use std::collections::HashSet;
 /// ```
 ///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+///
+/// Each `FILTER` clause is optional. A filtered sum is proven by committing to the sum column
+/// multiplied by the filter indicator and aggregating that column instead.
+///
+/// Only groups with selected rows are in the result. This is proven by committing to the
+/// inverse of each count, so that a group with a count of zero cannot be added to the result.
+///
+/// The count may be followed by arithmetic on the aggregated columns, such as
+/// `SUM(revenue) - SUM(cost) as profit`, which is proven on each group by a [`PostAggregationExpr`].
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct GroupByExpr<C: Commitment> {
     pub(super) group_by_exprs: Vec<ColumnExpr<C>>,
@@ -43,6 +53,8 @@ pub struct GroupByExpr<C: Commitment> {
     pub(super) count_alias: Identifier,
     pub(super) table: TableExpr,
     pub(super) where_clause: ProvableExprPlan<C>,
+    pub(super) sum_filters: Vec<Option<ProvableExprPlan<C>>>,
+    pub(super) post_aggregation_exprs: Vec<(PostAggregationExpr, ColumnField)>,
 }
 
 impl<C: Commitment> GroupByExpr<C> {
@@ -54,14 +66,82 @@ impl<C: Commitment> GroupByExpr<C> {
         table: TableExpr,
         where_clause: ProvableExprPlan<C>,
     ) -> Self {
+        let sum_filters = sum_expr.iter().map(|_| None).collect();
+        Self::new_with_sum_filters(
+            group_by_exprs,
+            sum_expr,
+            count_alias,
+            table,
+            where_clause,
+            sum_filters,
+        )
+    }
+
+    /// Creates a new group_by expression where each sum may have a `FILTER` clause.
+    ///
+    /// `sum_filters` must have the same length as `sum_expr`.
+    pub fn new_with_sum_filters(
+        group_by_exprs: Vec<ColumnExpr<C>>,
+        sum_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+        count_alias: Identifier,
+        table: TableExpr,
+        where_clause: ProvableExprPlan<C>,
+        sum_filters: Vec<Option<ProvableExprPlan<C>>>,
+    ) -> Self {
+        Self::new_with_post_aggregation_exprs(
+            group_by_exprs,
+            sum_expr,
+            count_alias,
+            table,
+            where_clause,
+            sum_filters,
+            Vec::new(),
+        )
+    }
+
+    /// Creates a new group_by expression whose count is followed by arithmetic on the aggregated columns.
+    ///
+    /// `sum_filters` must have the same length as `sum_expr`, and the columns of
+    /// `post_aggregation_exprs` must be group keys, sums or the count.
+    pub fn new_with_post_aggregation_exprs(
+        group_by_exprs: Vec<ColumnExpr<C>>,
+        sum_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+        count_alias: Identifier,
+        table: TableExpr,
+        where_clause: ProvableExprPlan<C>,
+        sum_filters: Vec<Option<ProvableExprPlan<C>>>,
+        post_aggregation_exprs: Vec<(PostAggregationExpr, ColumnField)>,
+    ) -> Self {
+        assert_eq!(sum_expr.len(), sum_filters.len());
         Self {
             group_by_exprs,
             sum_expr,
             table,
             count_alias,
             where_clause,
+            sum_filters,
+            post_aggregation_exprs,
         }
     }
+
+    /// The aggregated columns that the post aggregation expressions refer to:
+    /// the group keys, followed by the sums and the count, as scalars
+    fn aggregated_scalar_columns<'a>(
+        alloc: &'a Bump,
+        group_by_result_columns: &[Column<'a, C::Scalar>],
+        sum_result_columns: &[&'a [C::Scalar]],
+        count_column: &'a [i64],
+    ) -> Vec<&'a [C::Scalar]> {
+        let group_by_result_columns = group_by_result_columns
+            .iter()
+            .map(|column| &*alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0)));
+        let count_column: &[C::Scalar] =
+            alloc.alloc_slice_fill_with(count_column.len(), |i| count_column[i].into());
+        group_by_result_columns
+            .chain(sum_result_columns.iter().copied())
+            .chain([count_column])
+            .collect()
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
@@ -79,11 +159,21 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
             expr.0.count(builder)?;
             builder.count_result_columns(1);
         }
+        for filter in self.sum_filters.iter().flatten() {
+            filter.count(builder)?;
+            builder.count_intermediate_mles(1);
+            builder.count_subpolynomials(1);
+        }
         builder.count_result_columns(1);
-        builder.count_intermediate_mles(2);
-        builder.count_subpolynomials(3);
+        builder.count_intermediate_mles(3);
+        builder.count_subpolynomials(4);
         builder.count_degree(3);
         builder.count_post_result_challenges(2);
+        for (expr, _) in self.post_aggregation_exprs.iter() {
+            builder.count_result_columns(1);
+            builder.count_subpolynomials(1);
+            builder.count_degree(expr.degree());
+        }
         Ok(())
     }
 
@@ -95,6 +185,7 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         accessor.get_offset(self.table.table_ref)
     }
 
+    #[tracing::instrument(name = "GroupByExpr::verifier_evaluate", level = "debug", skip_all)]
     #[allow(unused_variables)]
     fn verifier_evaluate(
         &self,
@@ -114,6 +205,7 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
             .iter()
             .map(|expr| expr.0.verifier_evaluate(builder, accessor))
             .collect::<Result<Vec<_>, _>>()?;
+        let aggregate_evals = self.verify_sum_filters(builder, accessor, aggregate_evals)?;
         // 3. indexes
         let indexes_eval = builder
             .mle_evaluations
@@ -127,6 +219,16 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         let sum_result_columns_evals =
             Vec::from_iter(repeat_with(|| builder.consume_result_mle()).take(self.sum_expr.len()));
         let count_column_eval = builder.consume_result_mle();
+        let post_aggregation_result_evals = Vec::from_iter(
+            repeat_with(|| builder.consume_result_mle()).take(self.post_aggregation_exprs.len()),
+        );
+        let aggregated_evals = Vec::from_iter(
+            group_by_result_columns_evals
+                .iter()
+                .chain(&sum_result_columns_evals)
+                .chain([&count_column_eval])
+                .copied(),
+        );
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
@@ -141,11 +243,20 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
                 sum_result_columns_evals,
                 count_column_eval,
             ),
-        )
+            indexes_eval,
+        )?;
+        for ((expr, _), result_eval) in self
+            .post_aggregation_exprs
+            .iter()
+            .zip(post_aggregation_result_evals)
+        {
+            expr.verify(builder, &aggregated_evals, result_eval, indexes_eval);
+        }
 
         // todo!: check that the group_by results are unique.
         //        When the GroupByExpr is the root node of the Proof plan,
         //        this can be done by simply looking at the results returned by the prover.
+        Ok(())
     }
 
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
@@ -157,6 +268,9 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
             fields.push(col.1);
         }
         fields.push(ColumnField::new(self.count_alias, ColumnType::BigInt));
+        for (_, field) in self.post_aggregation_exprs.iter() {
+            fields.push(*field);
+        }
         fields
     }
 
@@ -169,11 +283,18 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         for col in self.sum_expr.iter() {
             columns.insert(col.0.get_column_reference());
         }
+        for filter in self.sum_filters.iter().flatten() {
+            filter.get_column_references(&mut columns);
+        }
 
         self.where_clause.get_column_references(&mut columns);
 
         columns
     }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
 }
 
 impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
@@ -192,6 +313,7 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
 
         // 2. columns
         let group_by_columns = Vec::from_iter(
@@ -203,6 +325,17 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
             expr.0
                 .result_evaluate(builder.table_length(), alloc, accessor)
         }));
+        let sum_columns = Vec::from_iter(sum_columns.into_iter().zip(&self.sum_filters).map(
+            |(column, filter)| match filter {
+                Some(filter) => {
+                    let filter_column =
+                        filter.result_evaluate(builder.table_length(), alloc, accessor);
+                    builder.request_post_result_challenges(filter.num_post_result_challenges());
+                    Column::Scalar(filter_sum_column(alloc, &column, &filter_column))
+                }
+                None => column,
+            },
+        ));
         // Compute filtered_columns and indexes
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
@@ -210,6 +343,12 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
             count_column,
         } = aggregate_columns(alloc, &group_by_columns, &sum_columns, selection)
             .expect("columns should be aggregatable");
+        let aggregated_columns = Self::aggregated_scalar_columns(
+            alloc,
+            &group_by_result_columns,
+            &sum_result_columns,
+            count_column,
+        );
         // 3. set indexes
         builder.set_result_indexes(Indexes::Dense(0..(count_column.len() as u64)));
         // 4. set filtered_columns
@@ -220,6 +359,13 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
             builder.produce_result_column(col);
         }
         builder.produce_result_column(count_column);
+        for (expr, _) in self.post_aggregation_exprs.iter() {
+            builder.produce_result_column(expr.evaluate(
+                alloc,
+                &aggregated_columns,
+                count_column.len(),
+            ));
+        }
         builder.request_post_result_challenges(2);
     }
 
@@ -249,6 +395,7 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
                 .iter()
                 .map(|expr| expr.0.prover_evaluate(builder, alloc, accessor)),
         );
+        let sum_columns = self.prove_sum_filters(builder, alloc, accessor, sum_columns);
         // Compute filtered_columns and indexes
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
@@ -268,15 +415,121 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
             (&group_by_columns, &sum_columns, selection),
             (&group_by_result_columns, &sum_result_columns, count_column),
         );
+        let aggregated_columns = Self::aggregated_scalar_columns(
+            alloc,
+            &group_by_result_columns,
+            &sum_result_columns,
+            count_column,
+        );
+        for (expr, _) in self.post_aggregation_exprs.iter() {
+            let result = expr.evaluate(alloc, &aggregated_columns, count_column.len());
+            expr.prove(builder, alloc, &aggregated_columns, result);
+        }
     }
 }
 
+impl<C: Commitment> GroupByExpr<C> {
+    /// Replaces each filtered sum column with the sum column multiplied by its filter indicator
+    /// and proves that the product is correct.
+    fn prove_sum_filters<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+        sum_columns: Vec<Column<'a, C::Scalar>>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let filter_columns = Vec::from_iter(
+            self.sum_filters
+                .iter()
+                .map(|filter| Some(filter.as_ref()?.prover_evaluate(builder, alloc, accessor))),
+        );
+        Vec::from_iter(sum_columns.into_iter().zip(filter_columns).map(
+            |(column, filter_column)| match filter_column {
+                Some(filter_column) => {
+                    let filtered = filter_sum_column(alloc, &column, &filter_column);
+                    builder.produce_intermediate_mle(filtered);
+
+                    // filtered - sum_in * filter = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (C::Scalar::one(), vec![Box::new(filtered)]),
+                            (
+                                -C::Scalar::one(),
+                                vec![Box::new(column), Box::new(filter_column)],
+                            ),
+                        ],
+                    );
+                    Column::Scalar(filtered)
+                }
+                None => column,
+            },
+        ))
+    }
+
+    /// Replaces the evaluation of each filtered sum column with the evaluation of the sum column
+    /// multiplied by its filter indicator.
+    fn verify_sum_filters(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        sum_evals: Vec<C::Scalar>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let filter_evals = self
+            .sum_filters
+            .iter()
+            .map(|filter| {
+                filter
+                    .as_ref()
+                    .map(|filter| filter.verifier_evaluate(builder, accessor))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let rand_eval = builder.mle_evaluations.random_evaluation;
+        Ok(Vec::from_iter(sum_evals.into_iter().zip(filter_evals).map(
+            |(sum_eval, filter_eval)| match filter_eval {
+                Some(filter_eval) => {
+                    let filtered_eval = builder.consume_intermediate_mle();
+
+                    // filtered - sum_in * filter = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(rand_eval * (filtered_eval - sum_eval * filter_eval)),
+                    );
+                    filtered_eval
+                }
+                None => sum_eval,
+            },
+        )))
+    }
+}
+
+/// Returns the values of `column` where `filter` is true and zero elsewhere.
+fn filter_sum_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &Column<'a, S>,
+    filter: &Column<'a, S>,
+) -> &'a [S] {
+    let filter = filter.as_boolean().expect("filter is not boolean");
+    let values = column.to_scalar_with_scaling(0);
+    alloc.alloc_slice_fill_with(
+        values.len(),
+        |i| {
+            if filter[i] {
+                values[i]
+            } else {
+                S::zero()
+            }
+        },
+    )
+}
+
 fn verify_group_by<C: Commitment>(
     builder: &mut VerificationBuilder<C>,
     alpha: C::Scalar,
     beta: C::Scalar,
     (g_in_evals, sum_in_evals, sel_in_eval): (Vec<C::Scalar>, Vec<C::Scalar>, C::Scalar),
     (g_out_evals, sum_out_evals, count_out_eval): (Vec<C::Scalar>, Vec<C::Scalar>, C::Scalar),
+    indexes_eval: C::Scalar,
 ) -> Result<(), ProofError> {
     let one_eval = builder.mle_evaluations.one_evaluation;
     let rand_eval = builder.mle_evaluations.random_evaluation;
@@ -292,6 +545,7 @@ fn verify_group_by<C: Commitment>(
 
     let g_in_star_eval = builder.consume_intermediate_mle();
     let g_out_star_eval = builder.consume_intermediate_mle();
+    let count_out_star_eval = builder.consume_intermediate_mle();
 
     // sum g_in_star * sel_in * sum_in_fold - g_out_star * sum_out_bar_fold = 0
     builder.produce_sumcheck_subpolynomial_evaluation(
@@ -309,6 +563,11 @@ fn verify_group_by<C: Commitment>(
         &(rand_eval * (g_out_star_eval * g_out_bar_fold_eval - one_eval)),
     );
 
+    // count_out * count_out_star - out_indicator = 0, so that no group has a count of zero
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (count_out_eval * count_out_star_eval - indexes_eval)),
+    );
+
     Ok(())
 }
 
@@ -349,8 +608,14 @@ pub fn prove_group_by<'a, S: Scalar>(
     g_out_star[m_out..].fill(alpha.inv().expect("alpha should never be 0"));
     slice_ops::batch_inversion(&mut g_out_star[..m_out]);
 
+    // count_out_star = count_out^(-1), which is zero beyond the output length
+    let count_out_star: &mut [S] = alloc.alloc_slice_fill_default(m_out);
+    slice_ops::slice_cast_mut(count_out, count_out_star);
+    slice_ops::batch_inversion(count_out_star);
+
     builder.produce_intermediate_mle(g_in_star as &[_]);
     builder.produce_intermediate_mle(g_out_star as &[_]);
+    builder.produce_intermediate_mle(count_out_star as &[_]);
 
     // sum g_in_star * sel_in * sum_in_fold - g_out_star * sum_out_bar_fold = 0
     builder.produce_sumcheck_subpolynomial(
@@ -400,4 +665,17 @@ pub fn prove_group_by<'a, S: Scalar>(
             (-S::one(), vec![]),
         ],
     );
+
+    // count_out * count_out_star - out_indicator = 0
+    let out_indicator: &[_] = alloc.alloc_slice_fill_copy(m_out, true);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(count_out), Box::new(count_out_star as &[_])],
+            ),
+            (-S::one(), vec![Box::new(out_indicator)]),
+        ],
+    );
 }
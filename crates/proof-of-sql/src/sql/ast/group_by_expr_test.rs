@@ -1,14 +1,23 @@
 use super::test_utility::{
-    and, cols_expr, column, const_int128, const_varchar, equal, group_by, sums_expr, tab,
+    and, cols_expr, column, const_bigint, const_int128, const_varchar, equal, group_by,
+    group_by_with_sum_filters, gte, sums_expr, tab,
 };
 use crate::{
     base::{
         commitment::InnerProductProof,
-        database::{owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TestAccessor},
+        database::{
+            owned_table_utility::*, ColumnType, OwnedColumn, OwnedTable, OwnedTableTestAccessor,
+            TestAccessor,
+        },
         scalar::Curve25519Scalar,
     },
-    sql::proof::{exercise_verification, VerifiableQueryResult},
+    sql::{
+        ast::ProofPlan,
+        parse::QueryExpr,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
 };
+use std::collections::HashMap;
 
 #[test]
 fn we_can_prove_a_simple_group_by_with_bigint_columns() {
@@ -27,7 +36,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
         tab(t),
         equal(column(t, "b", &accessor), const_int128(99)),
     );
-    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
     exercise_verification(&res, &expr, &accessor, t);
     let res = res.verify(&expr, &accessor, &()).unwrap().table;
     let expected = owned_table([
@@ -38,6 +47,42 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+#[test]
+fn we_can_prove_a_group_by_with_a_filtered_sum() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, -102, 103, -104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_sum_filters(
+        cols_expr(t, &["a"], &accessor),
+        sums_expr(
+            t,
+            &["c", "c"],
+            &["sum_c", "pos_c"],
+            &[ColumnType::BigInt, ColumnType::BigInt],
+            &accessor,
+        ),
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+        vec![None, Some(gte(column(t, "c", &accessor), const_bigint(0)))],
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2]),
+        bigint("sum_c", [101 - 104, -102 + 103]),
+        bigint("pos_c", [101, 103]),
+        bigint("__count__", [2, 2]),
+    ]);
+    assert_eq!(res, expected);
+}
+
 #[test]
 fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     let scalar_filter_data: Vec<Curve25519Scalar> = [
@@ -141,7 +186,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
             equal(column(t, "varchar_filter", &accessor), const_varchar("f2")),
         ),
     );
-    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
     exercise_verification(&res, &expr, &accessor, t);
     let res = res.verify(&expr, &accessor, &()).unwrap().table;
     let expected = owned_table([
@@ -173,7 +218,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
             equal(column(t, "varchar_filter", &accessor), const_varchar("f2")),
         ),
     );
-    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::new(&expr, &accessor, &()).unwrap();
     exercise_verification(&res, &expr, &accessor, t);
     let res = res.verify(&expr, &accessor, &()).unwrap().table;
     let expected = owned_table([
@@ -184,3 +229,58 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     ]);
     assert_eq!(res, expected);
 }
+
+#[test]
+fn we_can_prove_the_count_of_each_group_matching_a_reference_tally() {
+    let regions = [
+        "east", "west", "east", "north", "west", "east", "south", "north",
+    ];
+    let amounts = [5_i64, -3, 8, 0, 2, 7, -1, 4];
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t,
+        owned_table([varchar("region", regions), bigint("amount", amounts)]),
+        0,
+    );
+    for (sql, selects) in [
+        (
+            "SELECT region, COUNT(*) FROM t GROUP BY region",
+            (|_| true) as fn(i64) -> bool,
+        ),
+        // No amount in the south is above 1, so the south has no group.
+        (
+            "SELECT region, COUNT(*) AS n FROM t WHERE amount > 1 GROUP BY region",
+            |amount| amount > 1,
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        assert!(matches!(query.proof_expr(), ProofPlan::GroupBy(_)), "{sql}");
+        let res = VerifiableQueryResult::new(query.proof_expr(), &accessor, &()).unwrap();
+        exercise_verification(&res, query.proof_expr(), &accessor, t);
+        let res = res
+            .verify(query.proof_expr(), &accessor, &())
+            .unwrap()
+            .table;
+        let res: OwnedTable<Curve25519Scalar> = query
+            .result()
+            .transform_results(res.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let mut expected = HashMap::<&str, i64>::new();
+        for (region, _) in regions.iter().zip(amounts).filter(|&(_, a)| selects(a)) {
+            *expected.entry(*region).or_default() += 1;
+        }
+        let columns = Vec::from_iter(res.inner_table().values());
+        let (OwnedColumn::VarChar(groups), OwnedColumn::BigInt(counts)) = (columns[0], columns[1])
+        else {
+            panic!("unexpected result columns for {sql}");
+        };
+        let actual = HashMap::from_iter(groups.iter().map(String::as_str).zip(counts.clone()));
+        assert_eq!(actual, expected, "{sql}");
+        assert_eq!(groups.len(), expected.len(), "{sql}");
+    }
+}
@@ -114,6 +114,7 @@ pub(super) fn sum_aggregate_column_by_index_counts<'a, S: Scalar>(
         }
         Column::Scalar(col) => sum_aggregate_slice_by_index_counts(alloc, col, counts, indexes),
         Column::VarChar(_) => unimplemented!("Cannot sum varchar columns"),
+        Column::Binary(_, _) => unimplemented!("Cannot sum binary columns"),
     }
 }
 
@@ -175,6 +176,7 @@ pub(super) fn compare_indexes_by_columns<S: Scalar>(
             Column::Decimal75(_, _, _) => todo!("TODO: unimplemented"),
             Column::Scalar(col) => col[i].cmp(&col[j]),
             Column::VarChar((col, _)) => col[i].cmp(col[j]),
+            Column::Binary(_, (col, _)) => col[i].cmp(col[j]),
         })
         .find(|&ord| ord != Ordering::Equal)
         .unwrap_or(Ordering::Equal)
@@ -0,0 +1,239 @@
+use super::{
+    count_expr::count_selection, provable_expr_plan::ProvableExprPlan, ColumnExpr, ProvableExpr,
+    TableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+            LiteralValue, MetadataAccessor, OwnedColumn, OwnedTable, TableRef,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{
+            CountBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate, ResultBuilder,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+    },
+};
+use bumpalo::Bump;
+use num_traits::One;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The verified histogram of a column, with the number of rows in each bucket
+///
+/// Bucket `i` holds the values `v` with `boundaries[i] <= v < boundaries[i + 1]`,
+/// so there is one count fewer than there are boundaries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// The boundaries of the buckets, in increasing order
+    pub boundaries: Vec<i64>,
+    /// The number of rows in each bucket
+    pub counts: Vec<i64>,
+}
+
+/// Provable expressions for the histogram of a numeric column, such as
+/// ```ignore
+///     SELECT COUNT(*) FILTER (WHERE <boundary0> <= <column> AND <column> < <boundary1>) as bucket_0, ...,
+///         COUNT(*) FILTER (WHERE <boundaryN-1> <= <column> AND <column> < <boundaryN>) as bucket_N-1
+///     FROM <table>
+/// ```
+///
+/// Buckets are left-closed and right-open, so a value on a boundary is in the bucket that the
+/// boundary starts, and values below the first boundary or from the last boundary on are in no bucket.
+///
+/// The result is a single row with the count of each bucket. Each count is proven like
+/// a [`CountExpr`](super::CountExpr), by checking that the selection of its bucket sums to it.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistogramExpr<C: Commitment> {
+    column: ColumnExpr<C>,
+    boundaries: Vec<i64>,
+    table: TableExpr,
+    buckets: Vec<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> HistogramExpr<C> {
+    /// Creates a new histogram expression of `column` with the buckets between consecutive `boundaries`.
+    ///
+    /// Errors if there are fewer than two boundaries, if they are not strictly increasing,
+    /// or if the column can't be compared with integers.
+    pub fn try_new(
+        column: ColumnExpr<C>,
+        boundaries: Vec<i64>,
+        table: TableExpr,
+    ) -> ConversionResult<Self> {
+        if boundaries.len() < 2 || boundaries.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ConversionError::InvalidExpression(
+                "a histogram needs at least two strictly increasing boundaries".to_owned(),
+            ));
+        }
+        let at_least = |boundary: i64| {
+            ProvableExprPlan::try_new_inequality(
+                ProvableExprPlan::Column(column.clone()),
+                ProvableExprPlan::new_literal(LiteralValue::BigInt(boundary)),
+                false,
+            )
+        };
+        let buckets = boundaries
+            .windows(2)
+            .map(|pair| {
+                ProvableExprPlan::try_new_and(
+                    at_least(pair[0])?,
+                    ProvableExprPlan::try_new_not(at_least(pair[1])?)?,
+                )
+            })
+            .collect::<ConversionResult<_>>()?;
+        Ok(Self {
+            column,
+            boundaries,
+            table,
+            buckets,
+        })
+    }
+
+    /// The names of the result columns with the counts of the buckets
+    fn bucket_aliases(&self) -> impl Iterator<Item = Identifier> + '_ {
+        (0..self.buckets.len()).map(|i| format!("bucket_{i}").parse().unwrap())
+    }
+
+    /// Reads the histogram from the verified result of this expression
+    ///
+    /// Returns `None` if `result` does not have a single row with the count of each bucket.
+    pub fn histogram<S: Scalar>(&self, result: &OwnedTable<S>) -> Option<Histogram> {
+        if result.num_rows() != 1 || result.num_columns() != self.buckets.len() {
+            return None;
+        }
+        let counts = self
+            .bucket_aliases()
+            .map(|alias| match result.inner_table().get(&alias)? {
+                OwnedColumn::BigInt(count) => count.first().copied(),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+        Some(Histogram {
+            boundaries: self.boundaries.clone(),
+            counts,
+        })
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for HistogramExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        for bucket in &self.buckets {
+            bucket.count(builder)?;
+        }
+        builder.count_result_columns(self.buckets.len());
+        builder.count_subpolynomials(self.buckets.len());
+        builder.count_degree(1);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    #[tracing::instrument(name = "HistogramExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        let indexes_eval = builder
+            .mle_evaluations
+            .result_indexes_evaluation
+            .ok_or(ProofError::VerificationError("invalid indexes"))?;
+        if indexes_eval != builder.mle_evaluations.first_row_evaluation {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the counts must be the only row of the result",
+            )))?;
+        }
+        for bucket in &self.buckets {
+            let bucket_eval = bucket.verifier_evaluate(builder, accessor)?;
+            let count_eval = builder.consume_result_mle();
+
+            // sum bucket_in - count_out = 0
+            builder.produce_sumcheck_subpolynomial_evaluation(&(bucket_eval - count_eval));
+        }
+        Ok(())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.bucket_aliases()
+            .map(|alias| ColumnField::new(alias, ColumnType::BigInt))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        let mut columns = HashSet::new();
+        columns.insert(self.column.get_column_reference());
+        for bucket in &self.buckets {
+            bucket.get_column_references(&mut columns);
+        }
+        columns
+    }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for HistogramExpr<C> {
+    #[tracing::instrument(name = "HistogramExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        builder.set_result_indexes(Indexes::Dense(0..1));
+        for bucket in &self.buckets {
+            let selection_column: Column<'a, C::Scalar> =
+                bucket.result_evaluate(builder.table_length(), alloc, accessor);
+            let selection = selection_column
+                .as_boolean()
+                .expect("selection is not boolean");
+            builder.request_post_result_challenges(bucket.num_post_result_challenges());
+            builder.produce_result_column(count_selection(alloc, selection));
+        }
+    }
+
+    #[tracing::instrument(name = "HistogramExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        for bucket in &self.buckets {
+            let selection_column: Column<'a, C::Scalar> =
+                bucket.prover_evaluate(builder, alloc, accessor);
+            let selection = selection_column
+                .as_boolean()
+                .expect("selection is not boolean");
+            let count_out = count_selection(alloc, selection);
+
+            // sum bucket_in - count_out = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(selection)]),
+                    (-C::Scalar::one(), vec![Box::new(count_out)]),
+                ],
+            );
+        }
+    }
+}
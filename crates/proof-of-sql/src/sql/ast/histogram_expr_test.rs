@@ -0,0 +1,113 @@
+use super::Histogram;
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult, QueryExpr},
+        proof::VerifiableQueryResult,
+    },
+};
+use ark_std::test_rng;
+
+const A: [i64; 12] = [5, 3, 9, -2, 10, 3, 0, 7, 20, -8, 5, 1];
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([bigint("a", A), varchar("s", ["x"; 12])]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    column: &str,
+    boundaries: Vec<i64>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> ConversionResult<QueryExpr<DoryCommitment>> {
+    QueryExpr::try_new_histogram(
+        "sxt.t".parse().unwrap(),
+        column.parse().unwrap(),
+        boundaries,
+        accessor,
+    )
+}
+
+#[test]
+fn we_can_prove_a_histogram_with_four_buckets() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let boundaries = vec![-5, 0, 3, 5, 10];
+    let query = plan("a", boundaries.clone(), &accessor).unwrap();
+    let table =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap()
+            .verify(query.proof_expr(), &accessor, &(&setup).into())
+            .unwrap()
+            .table;
+
+    // Values on a boundary belong to the bucket that the boundary starts,
+    // and -8, 10 and 20 are outside of every bucket.
+    let expected_counts: Vec<i64> = boundaries
+        .windows(2)
+        .map(|pair| A.iter().filter(|&&a| pair[0] <= a && a < pair[1]).count() as i64)
+        .collect();
+    assert_eq!(expected_counts, [1, 2, 2, 4]);
+    assert_eq!(
+        query.histogram(&table),
+        Some(Histogram {
+            boundaries,
+            counts: expected_counts,
+        })
+    );
+}
+
+#[test]
+fn we_cannot_plan_a_histogram_without_increasing_boundaries() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    for boundaries in [vec![], vec![1], vec![1, 1], vec![0, 5, 3]] {
+        assert!(
+            matches!(
+                plan("a", boundaries.clone(), &accessor),
+                Err(ConversionError::InvalidExpression(_))
+            ),
+            "{boundaries:?}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_plan_a_histogram_of_a_missing_or_non_numeric_column() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    assert!(matches!(
+        plan("b", vec![0, 1], &accessor),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        plan("s", vec![0, 1], &accessor),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
+
+#[test]
+fn only_a_histogram_query_has_a_histogram() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let query = QueryExpr::<DoryCommitment>::try_new(
+        "SELECT a FROM t".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    assert_eq!(
+        query.histogram::<DoryScalar>(&owned_table([bigint("bucket_0", [1])])),
+        None
+    );
+}
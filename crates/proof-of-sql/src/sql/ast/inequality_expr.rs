@@ -14,16 +14,27 @@ use crate::{
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
 
 /// Provable AST expression for an inequality expression
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InequalityExpr<C: Commitment> {
     lhs: Box<ProvableExprPlan<C>>,
     rhs: Box<ProvableExprPlan<C>>,
     is_lte: bool,
 }
 
+impl<C: Commitment> Hash for InequalityExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lhs.hash(state);
+        self.rhs.hash(state);
+        self.is_lte.hash(state);
+    }
+}
+
 impl<C: Commitment> InequalityExpr<C> {
     /// Create a new less than or equal expression
     pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>, is_lte: bool) -> Self {
@@ -41,6 +52,10 @@ impl<C: Commitment> ProvableExpr<C> for InequalityExpr<C> {
         Ok(())
     }
 
+    fn num_post_result_challenges(&self) -> usize {
+        self.lhs.num_post_result_challenges() + self.rhs.num_post_result_challenges()
+    }
+
     fn data_type(&self) -> ColumnType {
         ColumnType::Boolean
     }
@@ -99,6 +114,7 @@ impl<C: Commitment> ProvableExpr<C> for InequalityExpr<C> {
         Column::Boolean(prover_evaluate_or(builder, alloc, equals_zero, sign))
     }
 
+    #[tracing::instrument(name = "InequalityExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
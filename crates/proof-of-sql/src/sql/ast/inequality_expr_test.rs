@@ -4,9 +4,8 @@ use crate::{
         bit::BitDistribution,
         commitment::InnerProductProof,
         database::{
-            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
-            OwnedTableTestAccessor, RandomTestAccessorDescriptor, RecordBatchTestAccessor,
-            TestAccessor,
+            owned_table_utility::*, Column, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            RecordBatchTestAccessor, TestAccessor,
         },
         math::decimal::scale_scalar,
         proof::{MessageLabel, TranscriptProtocol},
@@ -17,10 +16,11 @@ use crate::{
         ast::{test_expr::TestExprNode, test_utility::*, ProvableExprPlan},
         parse::ConversionError,
         proof::{
-            make_transcript, Indexes, ProofBuilder, ProofExpr, QueryProof, ResultBuilder,
-            VerifiableQueryResult,
+            make_transcript, plan_hash, Indexes, ProofBuilder, ProofExpr, QueryProof,
+            ResultBuilder, VerifiableQueryResult,
         },
     },
+    testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
 };
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
@@ -46,7 +46,7 @@ fn we_can_compare_a_constant_column() {
     let lit_expr = const_bigint(5);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -70,7 +70,7 @@ fn we_can_compare_a_varying_column_with_constant_sign() {
     let lit_expr = const_bigint(5);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -107,7 +107,7 @@ fn we_can_compare_columns_with_extreme_values() {
         tab(t),
         where_clause,
     );
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -262,7 +262,7 @@ fn we_can_compare_two_columns() {
     let rhs_expr = column(t, "b", &accessor);
     let where_clause = lte(lhs_expr, rhs_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -286,7 +286,7 @@ fn we_can_compare_a_varying_column_with_constant_absolute_value() {
     let lit_expr = const_bigint(0);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -310,7 +310,7 @@ fn we_can_compare_a_constant_column_of_negative_columns() {
     let lit_expr = const_bigint(5);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -334,7 +334,7 @@ fn we_can_compare_a_varying_column_with_negative_only_signs() {
     let lit_expr = const_bigint(5);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -358,7 +358,7 @@ fn we_can_compare_a_column_with_varying_absolute_values_and_signs() {
     let lit_expr = const_bigint(1);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -382,7 +382,7 @@ fn we_can_compare_column_with_greater_than_or_equal() {
     let lit_expr = const_bigint(1);
     let where_clause = gte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -408,7 +408,7 @@ fn we_can_run_nested_comparison() {
     let bool_expr = column(t, "boolean", &accessor);
     let where_clause = equal(gte(lhs_expr, rhs_expr), bool_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -432,7 +432,7 @@ fn we_can_compare_a_column_with_varying_absolute_values_and_signs_and_a_constant
     let lit_expr = const_bigint(0);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -456,7 +456,7 @@ fn we_can_compare_a_constant_column_of_zeros() {
     let lit_expr = const_bigint(0);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -491,7 +491,8 @@ fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
     let table_length = expr.get_length(&accessor);
     let generator_offset = expr.get_offset(&accessor);
 
-    let mut transcript = make_transcript(&expr, &provable_result, table_length, generator_offset);
+    let mut transcript =
+        make_transcript(&expr, &provable_result, table_length, generator_offset, &[]);
     transcript.challenge_scalars::<Curve25519Scalar>(&mut [], MessageLabel::PostResultChallenges);
 
     let mut builder = ProofBuilder::new(3, 2, Vec::new());
@@ -513,7 +514,13 @@ fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
     let selection = [true; 3];
     result_cols[0].prover_evaluate(&mut builder, &alloc, &accessor, &selection);
 
-    let proof = QueryProof::<InnerProductProof>::new_from_builder(builder, 0, transcript, &());
+    let proof = QueryProof::<InnerProductProof>::new_from_builder(
+        builder,
+        plan_hash(&expr),
+        0,
+        transcript,
+        &(),
+    );
     let res = proof
         .verify(&expr, &accessor, &provable_result, &())
         .unwrap()
@@ -538,7 +545,7 @@ fn verification_fails_if_commitments_dont_match_for_a_constant_column() {
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
 
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
 
     let data = record_batch!(
         "a" => [321_i64, 321, 321],
@@ -567,7 +574,7 @@ fn verification_fails_if_commitments_dont_match_for_a_constant_absolute_column()
     let lit_expr = const_bigint(0);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
 
     let data = record_batch!(
         "a" => [-321_i64, 321, -321],
@@ -596,7 +603,7 @@ fn verification_fails_if_commitments_dont_match_for_a_constant_sign_column() {
     let lit_expr = const_bigint(5);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
 
     let data = record_batch!(
         "a" => [321_i64, 321, 321],
@@ -625,7 +632,7 @@ fn verification_fails_if_commitments_dont_match() {
     let lit_expr = const_bigint(5);
     let where_clause = lte(col_expr, lit_expr);
     let expr = FilterExpr::new(cols_result(t, &["b"], &accessor), tab(t), where_clause);
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
 
     let data = record_batch!(
         "a" => [-523_i64, 923, 83],
@@ -0,0 +1,200 @@
+use super::{ProvableExpr, ProvableExprPlan};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for some `limit` rows of the selection, as for `LIMIT k` without an `ORDER BY`
+///
+/// Like SQL's `LIMIT` without an order, which rows are kept is not determined: the proof only shows
+/// that the kept rows are selected and that there are `limit` of them, or all of the selected rows
+/// if fewer are selected. The honest prover keeps the first selected rows in the order of the table.
+///
+/// The prover commits to the kept rows `k` and sends the number of selected rows
+/// `selected = Σ s_i` and the number of kept rows `kept = Σ k_i`, each proven like the sum of
+/// a window aggregate. The verifier then checks that
+/// ```ignore
+///     k_i * k_i - k_i = 0,    k_i - k_i * s_i = 0,    kept = min(limit, selected)
+/// ```
+/// So `k` is boolean and only keeps selected rows, which is cheaper than the order of [`TopNExpr`](super::TopNExpr).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitExpr<C: Commitment> {
+    selection: Box<ProvableExprPlan<C>>,
+    limit: u64,
+}
+
+impl<C: Commitment> Hash for LimitExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.selection.hash(state);
+        self.limit.hash(state);
+    }
+}
+
+impl<C: Commitment> LimitExpr<C> {
+    /// Create a new expression for some `limit` rows of a boolean selection
+    pub fn new(selection: Box<ProvableExprPlan<C>>, limit: u64) -> Self {
+        Self { selection, limit }
+    }
+
+    /// The first `limit` selected rows
+    fn keep<'a>(
+        &self,
+        alloc: &'a Bump,
+        selection: Column<'a, C::Scalar>,
+    ) -> (&'a [bool], &'a [bool]) {
+        let selection = selection.as_boolean().expect("selection is not boolean");
+        let mut remaining = self.limit;
+        let kept = alloc.alloc_slice_fill_with(selection.len(), |i| {
+            let keep = selection[i] && remaining > 0;
+            remaining -= u64::from(keep);
+            keep
+        });
+        (selection, kept)
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for LimitExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.selection.count(builder)?;
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(4);
+        builder.count_window_aggregates(2);
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        self.selection.num_post_result_challenges()
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "LimitExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let selection = self
+            .selection
+            .result_evaluate(table_length, alloc, accessor);
+        let (_, kept) = self.keep(alloc, selection);
+        Column::Boolean(kept)
+    }
+
+    #[tracing::instrument(name = "LimitExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let selection = self.selection.prover_evaluate(builder, alloc, accessor);
+        let (selection, kept) = self.keep(alloc, selection);
+        let table_length = selection.len();
+        builder.produce_intermediate_mle(kept);
+
+        // subpolynomial: k * k - k
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(kept), Box::new(kept)]),
+                (-C::Scalar::ONE, vec![Box::new(kept)]),
+            ],
+        );
+
+        // subpolynomial: k - k * s
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(kept)]),
+                (-C::Scalar::ONE, vec![Box::new(kept), Box::new(selection)]),
+            ],
+        );
+
+        let n = C::Scalar::from([table_length as u64, 0, 0, 0]);
+        let ones: &[bool] = alloc.alloc_slice_fill_copy(table_length, true);
+        for rows in [selection, kept] {
+            // sum n * rows - count = 0
+            let count = C::Scalar::from([rows.iter().filter(|&&row| row).count() as u64, 0, 0, 0]);
+            builder.produce_window_aggregate(count);
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![(n, vec![Box::new(rows)]), (-count, vec![Box::new(ones)])],
+            );
+        }
+        Column::Boolean(kept)
+    }
+
+    #[tracing::instrument(name = "LimitExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let random_eval = builder.mle_evaluations.random_evaluation;
+        let n = C::Scalar::from([builder.table_length() as u64, 0, 0, 0]);
+        let selection_eval = self.selection.verifier_evaluate(builder, accessor)?;
+        let kept_eval = builder.consume_intermediate_mle();
+
+        // subpolynomial: k * k - k
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(random_eval * (kept_eval * kept_eval - kept_eval)),
+        );
+
+        // subpolynomial: k - k * s
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(random_eval * (kept_eval - kept_eval * selection_eval)),
+        );
+
+        // sum n * s - selected = 0, sum n * k - kept = 0
+        let mut counts = [C::Scalar::ZERO; 2];
+        for (count, rows_eval) in counts.iter_mut().zip([selection_eval, kept_eval]) {
+            *count = builder.consume_window_aggregate();
+            builder.produce_sumcheck_subpolynomial_evaluation(&(n * rows_eval - *count * one_eval));
+        }
+
+        // Without rows, the constraints hold for any counts.
+        if builder.table_length() == 0 && counts.iter().any(|count| *count != C::Scalar::ZERO) {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the limit counts of an empty table must be zero",
+            )))?;
+        }
+        let [selected, kept] = counts.map(|count| {
+            TryInto::<i64>::try_into(count)
+                .ok()
+                .and_then(|count| u64::try_from(count).ok())
+        });
+        let (Some(selected), Some(kept)) = (selected, kept) else {
+            return Err(ProofError::ConstraintUnsatisfied(
+                "the limit counts must be row counts",
+            ));
+        };
+        if kept != self.limit.min(selected) {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the limit must keep as many selected rows as it can",
+            )))?;
+        }
+
+        Ok(kept_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.selection.get_column_references(columns);
+    }
+}
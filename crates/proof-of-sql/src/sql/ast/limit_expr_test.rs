@@ -0,0 +1,128 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Scalar,
+    },
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use ark_std::test_rng;
+
+const A: [i64; 12] = [5, 3, 9, -2, 9, 3, 0, 7, 3, -8, 5, 1];
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([bigint("a", A), bigint("b", 0..12)]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryExpr<DoryCommitment> {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor).unwrap()
+}
+
+/// The verified result of the query, after the post-proof slice
+fn prove_and_verify(
+    query: &QueryExpr<DoryCommitment>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+    setup: &DoryProverPublicSetup,
+) -> OwnedTable<DoryScalar> {
+    let table =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), accessor, setup)
+            .unwrap()
+            .verify(query.proof_expr(), accessor, &setup.into())
+            .unwrap()
+            .table;
+    query
+        .result()
+        .transform_results(table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+fn is_limit(query: &QueryExpr<DoryCommitment>) -> bool {
+    format!("{:?}", query.proof_expr()).contains("Limit(")
+}
+
+#[test]
+fn a_limit_without_an_order_returns_that_many_rows_that_satisfy_the_where_clause() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan("SELECT a, b FROM t WHERE a >= 3 LIMIT 5", &accessor);
+    assert!(is_limit(&query));
+    let result = prove_and_verify(&query, &accessor, &setup);
+    assert_eq!(result.num_rows(), 5);
+    let rows = result.bigint_column(&"b".parse().unwrap()).unwrap();
+    assert!(rows.iter().all(|&b| A[b as usize] >= 3));
+    // The honest prover keeps the first selected rows in the order of the table.
+    assert_eq!(rows, [0, 1, 2, 4, 5]);
+}
+
+#[test]
+fn a_limit_beyond_the_selection_returns_every_selected_row() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    for (sql, expected) in [
+        ("SELECT b FROM t WHERE a = 9 LIMIT 5", vec![2, 4]),
+        ("SELECT b FROM t WHERE a = 4 LIMIT 5", vec![]),
+        ("SELECT b FROM t LIMIT 3 OFFSET 10", vec![10, 11]),
+        ("SELECT b FROM t WHERE a < 5 LIMIT 2 OFFSET 1", vec![3, 5]),
+    ] {
+        let query = plan(sql, &accessor);
+        assert!(is_limit(&query), "{sql}");
+        assert_eq!(
+            prove_and_verify(&query, &accessor, &setup),
+            owned_table([bigint("b", expected)]),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn a_limit_is_not_proven_when_the_result_needs_every_selected_row() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    for sql in [
+        "SELECT a, b FROM t",
+        "SELECT a, b FROM t OFFSET 2",
+        "SELECT a, b FROM t LIMIT 0",
+        "SELECT a, b FROM t LIMIT 2 OFFSET -1",
+        "SELECT a, b FROM t ORDER BY a LIMIT 2",
+        "SELECT a, SELECTED() AS s FROM t WHERE b > 2 LIMIT 2",
+        "SELECT a, ROW_NUMBER() OVER (ORDER BY b) AS rn FROM t LIMIT 2",
+        "SELECT a, MAX(b) AS m FROM t GROUP BY a LIMIT 2",
+    ] {
+        assert!(!is_limit(&plan(sql, &accessor)), "{sql}");
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_limit_proof_with_tampered_counts() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan("SELECT a, b FROM t WHERE a >= 3 LIMIT 5", &accessor);
+    let res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    assert_eq!(res.proof.as_ref().unwrap().window_aggregates.len(), 2);
+    for index in 0..2 {
+        for delta in [DoryScalar::ONE, -DoryScalar::ONE] {
+            let mut tampered = res.clone();
+            tampered.proof.as_mut().unwrap().window_aggregates[index] += delta;
+            assert!(tampered
+                .verify(query.proof_expr(), &accessor, &(&setup).into())
+                .is_err());
+        }
+    }
+}
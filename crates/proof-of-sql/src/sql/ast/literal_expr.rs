@@ -4,7 +4,7 @@ use crate::{
         commitment::Commitment,
         database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LiteralValue},
         proof::ProofError,
-        scalar::Scalar,
+        scalar::{Scalar, VarCharHash},
     },
     sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
 };
@@ -23,7 +23,7 @@ use std::collections::HashSet;
 /// While this wouldn't be as efficient as using a new custom expression for
 /// such queries, it allows us to easily support projects with minimal code
 /// changes, and the performance is sufficient for present.
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct LiteralExpr<S: Scalar> {
     value: LiteralValue<S>,
 }
@@ -33,6 +33,28 @@ impl<S: Scalar> LiteralExpr<S> {
     pub fn new(value: LiteralValue<S>) -> Self {
         Self { value }
     }
+
+    /// The value of the literal, with a `VarChar` or `Binary` literal hashed by `varchar_hash`
+    /// so that it matches the hashes of the committed `VarChar` and `Binary` columns.
+    fn value_with_varchar_hash(&self, varchar_hash: VarCharHash) -> LiteralValue<S> {
+        match &self.value {
+            LiteralValue::VarChar((string, _)) => {
+                LiteralValue::VarChar((string.clone(), varchar_hash.hash_to_scalar(string)))
+            }
+            LiteralValue::Binary((bytes, _)) => {
+                LiteralValue::Binary((bytes.clone(), varchar_hash.hash_bytes_to_scalar(bytes)))
+            }
+            value => value.clone(),
+        }
+    }
+
+    /// The scalar of the literal, hashed as in [`Self::value_with_varchar_hash`], and its scale.
+    pub(crate) fn scalar_and_scale(&self, varchar_hash: VarCharHash) -> (S, i8) {
+        (
+            self.value_with_varchar_hash(varchar_hash).to_scalar(),
+            self.value.column_type().scale().unwrap_or(0),
+        )
+    }
 }
 
 impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
@@ -40,6 +62,10 @@ impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
         Ok(())
     }
 
+    fn num_post_result_challenges(&self) -> usize {
+        0
+    }
+
     fn data_type(&self) -> ColumnType {
         self.value.column_type()
     }
@@ -49,9 +75,10 @@ impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
         &self,
         table_length: usize,
         alloc: &'a Bump,
-        _accessor: &'a dyn DataAccessor<C::Scalar>,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        Column::from_literal_with_length(&self.value, table_length, alloc)
+        let value = self.value_with_varchar_hash(accessor.varchar_hash());
+        Column::from_literal_with_length(&value, table_length, alloc)
     }
 
     #[tracing::instrument(name = "LiteralExpr::prover_evaluate", level = "debug", skip_all)]
@@ -59,19 +86,23 @@ impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
         alloc: &'a Bump,
-        _accessor: &'a dyn DataAccessor<C::Scalar>,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
         let table_length = builder.table_length();
-        Column::from_literal_with_length(&self.value, table_length, alloc)
+        let value = self.value_with_varchar_hash(accessor.varchar_hash());
+        Column::from_literal_with_length(&value, table_length, alloc)
     }
 
+    #[tracing::instrument(name = "LiteralExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
-        _accessor: &dyn CommitmentAccessor<C>,
+        accessor: &dyn CommitmentAccessor<C>,
     ) -> Result<C::Scalar, ProofError> {
         let mut commitment = builder.mle_evaluations.one_evaluation;
-        commitment *= self.value.to_scalar();
+        commitment *= self
+            .value_with_varchar_hash(accessor.varchar_hash())
+            .to_scalar();
         Ok(commitment)
     }
 
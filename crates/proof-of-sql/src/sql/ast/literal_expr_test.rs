@@ -1,11 +1,11 @@
 use super::{ProvableExpr, ProvableExprPlan};
 use crate::{
     base::database::{
-        make_random_test_accessor_data, Column, ColumnType, RandomTestAccessorDescriptor,
-        RecordBatchTestAccessor, TestAccessor, UnimplementedTestAccessor,
+        Column, ColumnType, RecordBatchTestAccessor, TestAccessor, UnimplementedTestAccessor,
     },
     record_batch,
     sql::ast::{test_expr::TestExprNode, test_utility::const_bool},
+    testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
 };
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
@@ -0,0 +1,212 @@
+use super::{
+    filter_columns, provable_expr_plan::ProvableExprPlan, ColumnExpr, ProvableExpr, TableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            TableRef,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate, ResultBuilder,
+        SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable expressions for the lookup of a single row by its key, such as
+/// ```ignore
+///     SELECT <column1>, ..., <columnN> FROM <table> WHERE <key> = <value>
+/// ```
+///
+/// The result is the row that the key selects, or no row if it selects none. If the key
+/// selects more than one row, the result can't be attested, and verification fails.
+///
+/// This is cheaper than the [`DenseFilterExpr`](super::DenseFilterExpr) of the same query:
+/// with `s` the selection, `chi` the indicator of the result rows and `d` a result column of `c`,
+/// the verifier checks that
+/// ```ignore
+///     sum s - chi = 0,    sum s * c - d = 0
+/// ```
+/// and that the result has at most one row. So there is exactly one selected row when the result
+/// has a row, and its values are those of the result, without any intermediate MLEs.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LookupExpr<C: Commitment> {
+    columns: Vec<ColumnExpr<C>>,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+}
+
+impl<C: Commitment> LookupExpr<C> {
+    /// Creates a new lookup of `columns` in the row of `table` that `where_clause` selects
+    pub fn new(
+        columns: Vec<ColumnExpr<C>>,
+        table: TableExpr,
+        where_clause: ProvableExprPlan<C>,
+    ) -> Self {
+        Self {
+            columns,
+            table,
+            where_clause,
+        }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for LookupExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.where_clause.count(builder)?;
+        for column in &self.columns {
+            column.count(builder)?;
+        }
+        builder.count_result_columns(self.columns.len());
+        builder.count_subpolynomials(self.columns.len() + 1);
+        builder.count_degree(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    #[tracing::instrument(name = "LookupExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        let selection_eval = self.where_clause.verifier_evaluate(builder, accessor)?;
+        let columns_evals = self
+            .columns
+            .iter()
+            .map(|column| column.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+        let chi_eval = builder
+            .mle_evaluations
+            .result_indexes_evaluation
+            .ok_or(ProofError::VerificationError("invalid indexes"))?;
+        if chi_eval != builder.mle_evaluations.first_row_evaluation && chi_eval != C::Scalar::ZERO {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "a lookup must have at most one row",
+            )))?;
+        }
+        let results_evals =
+            Vec::from_iter(repeat_with(|| builder.consume_result_mle()).take(self.columns.len()));
+
+        // sum s - chi = 0
+        builder.produce_sumcheck_subpolynomial_evaluation(&(selection_eval - chi_eval));
+
+        for (column_eval, result_eval) in columns_evals.into_iter().zip(results_evals) {
+            // sum s * c - d = 0
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &(selection_eval * column_eval - result_eval),
+            );
+        }
+        Ok(())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.columns
+            .iter()
+            .map(ColumnExpr::get_column_field)
+            .collect()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        let mut columns = HashSet::new();
+        for column in &self.columns {
+            column.get_column_references(&mut columns);
+        }
+        self.where_clause.get_column_references(&mut columns);
+        columns
+    }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for LookupExpr<C> {
+    #[tracing::instrument(name = "LookupExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(builder.table_length(), alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        builder.request_post_result_challenges(self.where_clause.num_post_result_challenges());
+        let columns = Vec::from_iter(
+            self.columns
+                .iter()
+                .map(|column| column.result_evaluate(builder.table_length(), alloc, accessor)),
+        );
+
+        // An honest prover returns every selected row, so that more than one fails verification.
+        let (results, result_len) = filter_columns(alloc, &columns, selection);
+        builder.set_result_indexes(Indexes::Dense(0..(result_len as u64)));
+        for result in results {
+            builder.produce_result_column(result);
+        }
+    }
+
+    #[tracing::instrument(name = "LookupExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause.prover_evaluate(builder, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let columns = Vec::from_iter(
+            self.columns
+                .iter()
+                .map(|column| column.prover_evaluate(builder, alloc, accessor)),
+        );
+        let (results, result_len) = filter_columns(alloc, &columns, selection);
+        let chi: &[bool] = alloc.alloc_slice_fill_copy(result_len, true);
+
+        // sum s - chi = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(selection)]),
+                (-C::Scalar::ONE, vec![Box::new(chi)]),
+            ],
+        );
+
+        for (column, result) in columns.into_iter().zip(results) {
+            // sum s * c - d = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (C::Scalar::ONE, vec![Box::new(selection), Box::new(column)]),
+                    (-C::Scalar::ONE, vec![Box::new(result)]),
+                ],
+            );
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{
+        parse::{ConversionError, QueryExpr},
+        proof::{QueryError, VerifiableQueryResult},
+    },
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::intermediate_ast::Literal;
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("id", [10, 20, 30, 40, 50]),
+            varchar("name", ["ann", "bob", "cy", "dee", "eve"]),
+            boolean("active", [true, false, true, true, false]),
+            bigint("owner", [1, 2, 2, 3, 4]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn lookup(
+    key: &str,
+    value: impl Into<Literal>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryExpr<DoryCommitment> {
+    QueryExpr::try_new_lookup(
+        "sxt.t".parse().unwrap(),
+        key.parse().unwrap(),
+        value.into(),
+        accessor,
+    )
+    .unwrap()
+}
+
+fn prove_and_verify(
+    query: &QueryExpr<DoryCommitment>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+    setup: &DoryProverPublicSetup,
+) -> Result<OwnedTable<DoryScalar>, QueryError> {
+    VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), accessor, setup)
+        .unwrap()
+        .verify(query.proof_expr(), accessor, &setup.into())
+        .map(|data| data.table)
+}
+
+#[test]
+fn we_can_attest_the_full_contents_of_a_single_row() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = lookup("id", 30, &accessor);
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &setup).unwrap(),
+        owned_table([
+            bigint("id", [30]),
+            varchar("name", ["cy"]),
+            boolean("active", [true]),
+            bigint("owner", [2]),
+        ])
+    );
+    let query = lookup("name", "eve", &accessor);
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &setup).unwrap(),
+        owned_table([
+            bigint("id", [50]),
+            varchar("name", ["eve"]),
+            boolean("active", [false]),
+            bigint("owner", [4]),
+        ])
+    );
+}
+
+#[test]
+fn we_can_attest_that_there_is_no_row_with_a_key() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = lookup("id", 35, &accessor);
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &setup).unwrap(),
+        owned_table([
+            bigint("id", [0; 0]),
+            varchar("name", [""; 0]),
+            boolean("active", [true; 0]),
+            bigint("owner", [0; 0]),
+        ])
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_lookup_of_a_key_in_more_than_one_row() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = lookup("owner", 2, &accessor);
+    assert!(prove_and_verify(&query, &accessor, &setup).is_err());
+}
+
+#[test]
+fn we_cannot_verify_a_lookup_with_the_row_of_another_key() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = lookup("id", 30, &accessor);
+    let mut res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    let other_query = lookup("id", 20, &accessor);
+    res.provable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        other_query.proof_expr(),
+        &accessor,
+        &setup,
+    )
+    .unwrap()
+    .provable_result;
+    assert!(res
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .is_err());
+}
+
+#[test]
+fn a_lookup_proof_has_fewer_commitments_than_the_proof_of_the_same_filter() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = lookup("id", 30, &accessor);
+    let filter_query = QueryExpr::<DoryCommitment>::try_new(
+        "SELECT * FROM t WHERE id = 30".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let num_commitments = |query: &QueryExpr<DoryCommitment>| {
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap()
+            .proof
+            .unwrap()
+            .commitments
+            .len()
+    };
+    assert!(num_commitments(&query) < num_commitments(&filter_query));
+}
+
+#[test]
+fn we_cannot_plan_a_lookup_of_a_missing_key_or_a_value_of_another_type() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let plan = |key: &str, value: Literal| {
+        QueryExpr::<DoryCommitment>::try_new_lookup(
+            "sxt.t".parse().unwrap(),
+            key.parse().unwrap(),
+            value,
+            &accessor,
+        )
+    };
+    assert!(matches!(
+        plan("missing", 1.into()),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        plan("id", "ann".into()),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
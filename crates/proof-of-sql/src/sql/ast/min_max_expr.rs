@@ -0,0 +1,232 @@
+use super::{ColumnExpr, TableExpr};
+use crate::{
+    base::{
+        commitment::{Bounds, ColumnBounds, Commitment},
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            TableRef,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate, ResultBuilder,
+        SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Whether an aggregate of a [`MinMaxExpr`] is the minimum or the maximum of its column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Extreme {
+    /// The smallest value of the column
+    Min,
+    /// The largest value of the column
+    Max,
+}
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT MAX(<column1>) as <alias1>, ..., MIN(<columnN>) as <aliasN> FROM <table>
+/// ```
+/// over integer columns whose commitments come with [`Bounds::Sharp`] bounds.
+///
+/// The result is a single row with the extreme of each column. Rather than being proven from the
+/// values of the column, each extreme is checked against the bounds that the verifier has along with
+/// the commitment to the column, so nothing is committed and the proof does not scan the column.
+/// The bounds are of the whole table, so there can't be a where clause.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxExpr<C: Commitment> {
+    aggregates: Vec<(ColumnExpr<C>, Extreme, ColumnField)>,
+    table: TableExpr,
+}
+
+impl<C: Commitment> MinMaxExpr<C> {
+    /// Creates a new expression with the `extreme` of each column, in the result column of each field.
+    pub fn new(aggregates: Vec<(ColumnExpr<C>, Extreme, ColumnField)>, table: TableExpr) -> Self {
+        Self { aggregates, table }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for MinMaxExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        builder.count_result_columns(self.aggregates.len());
+        builder.count_subpolynomials(self.aggregates.len());
+        // The sumcheck polynomial always has a degree 2 term, even when the subpolynomials are linear.
+        builder.count_degree(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    #[tracing::instrument(name = "MinMaxExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        let indexes_eval = builder
+            .mle_evaluations
+            .result_indexes_evaluation
+            .ok_or(ProofError::VerificationError("invalid indexes"))?;
+        let first_row_eval = builder.mle_evaluations.first_row_evaluation;
+        if indexes_eval != first_row_eval {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the extremes must be the only row of the result",
+            )))?;
+        }
+        for (column, extreme, _) in &self.aggregates {
+            let bound = accessor
+                .get_column_bounds(column.get_column_reference())
+                .and_then(|bounds| sharp_extreme(&bounds, *extreme))
+                .ok_or(ProofError::VerificationError(
+                    "the column has no exact bounds",
+                ))?;
+            let extreme_eval = builder.consume_result_mle();
+
+            // sum extreme_out - bound * first_row = 0
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &(extreme_eval - C::Scalar::from(bound) * first_row_eval),
+            );
+        }
+        Ok(())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aggregates.iter().map(|(_, _, field)| *field).collect()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        self.aggregates
+            .iter()
+            .map(|(column, _, _)| column.get_column_reference())
+            .collect()
+    }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        HashSet::from([self.table.table_ref])
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for MinMaxExpr<C> {
+    #[tracing::instrument(name = "MinMaxExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        builder.set_result_indexes(Indexes::Dense(0..1));
+        for (column, extreme, _) in &self.aggregates {
+            let column = accessor.get_column(column.get_column_reference());
+            // Columns without an extreme cannot be proven, so the proof fails in `prover_evaluate`.
+            let (extreme_out, _) = extreme_of_column(alloc, column, *extreme)
+                .unwrap_or((Column::BigInt(alloc.alloc_slice_copy(&[0])), 0));
+            builder.produce_result_column(extreme_out);
+        }
+    }
+
+    #[tracing::instrument(name = "MinMaxExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let first_row: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        for (column, extreme, _) in &self.aggregates {
+            let column = accessor.get_column(column.get_column_reference());
+            let (extreme_out, bound) =
+                extreme_of_column(alloc, column, *extreme).unwrap_or_else(|error| {
+                    builder.fail_with(error);
+                    (Column::BigInt(alloc.alloc_slice_copy(&[0])), 0)
+                });
+
+            // sum extreme_out - bound * first_row = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(extreme_out)]),
+                    (-C::Scalar::from(bound), vec![Box::new(first_row)]),
+                ],
+            );
+        }
+    }
+}
+
+/// The extreme of the sharp `bounds` of an integer column, or `None` if the bounds are not sharp
+pub(crate) fn sharp_extreme(bounds: &ColumnBounds, extreme: Extreme) -> Option<i128> {
+    fn of<T: Ord + Copy + Into<i128>>(bounds: &Bounds<T>, extreme: Extreme) -> Option<i128> {
+        match bounds {
+            Bounds::Sharp(inner) => Some(match extreme {
+                Extreme::Min => (*inner.min()).into(),
+                Extreme::Max => (*inner.max()).into(),
+            }),
+            Bounds::Empty | Bounds::Bounded(_) => None,
+        }
+    }
+    match bounds {
+        ColumnBounds::SmallInt(bounds) => of(bounds, extreme),
+        ColumnBounds::Int(bounds) => of(bounds, extreme),
+        ColumnBounds::BigInt(bounds) => of(bounds, extreme),
+        ColumnBounds::Int128(bounds) => of(bounds, extreme),
+        ColumnBounds::NoOrder => None,
+    }
+}
+
+/// Returns a column with the single value of the extreme of the integer `column`, along with the value
+///
+/// Errors with [`ProofError::InvalidPlan`] if the column is empty or not an integer column, since neither has bounds.
+fn extreme_of_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: Column<'a, S>,
+    extreme: Extreme,
+) -> Result<(Column<'a, S>, i128), ProofError> {
+    fn of<T: Ord + Copy>(values: &[T], extreme: Extreme) -> Result<T, ProofError> {
+        let value = match extreme {
+            Extreme::Min => values.iter().min(),
+            Extreme::Max => values.iter().max(),
+        };
+        value
+            .copied()
+            .ok_or(ProofError::InvalidPlan("an empty column has no extreme"))
+    }
+    Ok(match column {
+        Column::SmallInt(values) => {
+            let value = of(values, extreme)?;
+            (
+                Column::SmallInt(alloc.alloc_slice_copy(&[value])),
+                value.into(),
+            )
+        }
+        Column::Int(values) => {
+            let value = of(values, extreme)?;
+            (Column::Int(alloc.alloc_slice_copy(&[value])), value.into())
+        }
+        Column::BigInt(values) => {
+            let value = of(values, extreme)?;
+            (
+                Column::BigInt(alloc.alloc_slice_copy(&[value])),
+                value.into(),
+            )
+        }
+        Column::Int128(values) => {
+            let value = of(values, extreme)?;
+            (Column::Int128(alloc.alloc_slice_copy(&[value])), value)
+        }
+        _ => Err(ProofError::InvalidPlan("only integer columns have bounds"))?,
+    })
+}
@@ -0,0 +1,213 @@
+use crate::{
+    base::{
+        commitment::{QueryCommitments, QueryCommitmentsExt, TableCommitment},
+        database::{
+            owned_table_utility::*, ColumnField, ColumnRef, ColumnType, CommitmentAccessor,
+            OwnedTable, OwnedTableTestAccessor, TestAccessor,
+        },
+        proof::ProofError,
+    },
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{
+        ast::{ColumnExpr, Extreme, MinMaxExpr, ProofPlan, TableExpr},
+        parse::QueryExpr,
+        proof::{ProofExpr, QueryError, QueryProof, VerifiableQueryResult},
+    },
+};
+use ark_std::test_rng;
+
+fn table() -> OwnedTable<DoryScalar> {
+    owned_table([
+        bigint("price", [40, -7, 125, 3, 125, 0]),
+        int("qty", [5, 1, 9, 2, 2, 7]),
+        varchar("sym", ["a", "b", "c", "d", "e", "f"]),
+    ])
+}
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table("sxt.t".parse().unwrap(), table(), 0);
+    accessor
+}
+
+fn plan_with_column_bounds(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryExpr<DoryCommitment> {
+    QueryExpr::try_new_with_column_bounds(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor)
+        .unwrap()
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryExpr<DoryCommitment> {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor).unwrap()
+}
+
+fn is_min_max(query: &QueryExpr<DoryCommitment>) -> bool {
+    matches!(query.proof_expr(), ProofPlan::MinMax(_))
+}
+
+/// The verified result of the query, after the post-proof transform
+fn prove_and_verify(
+    query: &QueryExpr<DoryCommitment>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+    verifier_accessor: &impl CommitmentAccessor<DoryCommitment>,
+    setup: &DoryProverPublicSetup,
+) -> Result<OwnedTable<DoryScalar>, QueryError> {
+    let table =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), accessor, setup)
+            .unwrap()
+            .verify(query.proof_expr(), verifier_accessor, &setup.into())?
+            .table;
+    Ok(query
+        .result()
+        .transform_results(table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap())
+}
+
+#[test]
+fn the_max_from_the_column_bounds_equals_the_max_from_the_general_path() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let sql = "SELECT MAX(price) AS high FROM t";
+    let fast_query = plan_with_column_bounds(sql, &accessor);
+    let general_query = plan(sql, &accessor);
+    assert!(is_min_max(&fast_query));
+    assert!(!is_min_max(&general_query));
+    let fast_result = prove_and_verify(&fast_query, &accessor, &accessor, &setup).unwrap();
+    assert_eq!(fast_result, owned_table([bigint("high", [125])]));
+    assert_eq!(
+        fast_result,
+        prove_and_verify(&general_query, &accessor, &accessor, &setup).unwrap()
+    );
+}
+
+#[test]
+fn we_can_verify_several_extremes_against_the_bounds_of_table_commitments() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let commitments: QueryCommitments<DoryCommitment> = QueryCommitments::from([(
+        "sxt.t".parse().unwrap(),
+        TableCommitment::from_owned_table_with_offset(&table(), 0, &setup),
+    )]);
+    let query = plan_with_column_bounds(
+        "SELECT MIN(price) AS low, MAX(price) AS high, MAX(qty) AS most FROM t",
+        &accessor,
+    );
+    assert!(is_min_max(&query));
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &commitments, &setup).unwrap(),
+        owned_table([bigint("low", [-7]), bigint("high", [125]), int("most", [9])])
+    );
+}
+
+#[test]
+fn we_cannot_verify_extremes_against_commitments_without_exact_bounds() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan_with_column_bounds("SELECT MAX(price) AS high FROM t", &accessor);
+    let commitments: QueryCommitments<DoryCommitment> =
+        QueryCommitments::from_accessor_with_max_bounds(
+            query.proof_expr().get_column_references(),
+            &accessor,
+        );
+    assert!(prove_and_verify(&query, &accessor, &commitments, &setup).is_err());
+}
+
+#[test]
+fn we_cannot_verify_the_max_with_the_result_of_the_min() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan_with_column_bounds("SELECT MAX(price) AS x FROM t", &accessor);
+    let other_query = plan_with_column_bounds("SELECT MIN(price) AS x FROM t", &accessor);
+    let mut res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    res.provable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        other_query.proof_expr(),
+        &accessor,
+        &setup,
+    )
+    .unwrap()
+    .provable_result;
+    assert!(res
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .is_err());
+}
+
+#[test]
+fn filtered_or_grouped_extremes_fall_back_to_the_general_path() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    for sql in [
+        "SELECT MAX(price) AS high FROM t WHERE qty > 2",
+        "SELECT MAX(price) AS high, SUM(qty) AS total FROM t",
+        "SELECT sym, MAX(price) AS high FROM t GROUP BY sym",
+    ] {
+        let fast_query = plan_with_column_bounds(sql, &accessor);
+        assert!(!is_min_max(&fast_query), "{sql}");
+        assert_eq!(fast_query, plan(sql, &accessor), "{sql}");
+    }
+    let query =
+        plan_with_column_bounds("SELECT MAX(price) AS high FROM t WHERE qty > 2", &accessor);
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &accessor, &setup).unwrap(),
+        owned_table([bigint("high", [125])])
+    );
+}
+
+#[test]
+fn we_cannot_prove_the_extreme_of_an_empty_table() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let query = plan_with_column_bounds(
+        "SELECT MAX(price) AS high FROM t",
+        &accessor_with_table(setup.clone()),
+    );
+    assert!(is_min_max(&query));
+    let mut empty_accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+    empty_accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("price", [0; 0]),
+            int("qty", [0; 0]),
+            varchar("sym", [""; 0]),
+        ]),
+        0,
+    );
+    assert!(matches!(
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &empty_accessor, &setup),
+        Err(ProofError::InvalidPlan(_))
+    ));
+}
+
+#[test]
+fn we_cannot_prove_the_extreme_of_a_column_that_is_not_an_integer() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let table_ref = "sxt.t".parse().unwrap();
+    let expr = MinMaxExpr::<DoryCommitment>::new(
+        vec![(
+            ColumnExpr::new(ColumnRef::new(
+                table_ref,
+                "sym".parse().unwrap(),
+                ColumnType::VarChar,
+            )),
+            Extreme::Max,
+            ColumnField::new("high".parse().unwrap(), ColumnType::VarChar),
+        )],
+        TableExpr { table_ref },
+    );
+    assert!(matches!(
+        QueryProof::<DoryEvaluationProof>::new(&expr, &accessor, &setup),
+        Err(ProofError::InvalidPlan(_))
+    ));
+}
@@ -17,7 +17,17 @@ use bitwise_verification::*;
 mod bitwise_verification_test;
 
 mod provable_expr_plan;
-pub(crate) use provable_expr_plan::ProvableExprPlan;
+pub use provable_expr_plan::ProvableExprPlan;
+pub(crate) use provable_expr_plan::{commutative_operands_eq, hash_commutative_operands};
+#[cfg(test)]
+mod provable_expr_plan_test;
+
+pub mod dsl;
+#[cfg(test)]
+mod dsl_test;
+
+mod aliased_provable_expr_plan;
+pub(crate) use aliased_provable_expr_plan::AliasedProvableExprPlan;
 
 mod provable_expr;
 pub(crate) use provable_expr::ProvableExpr;
@@ -50,18 +60,51 @@ use not_expr::NotExpr;
 mod not_expr_test;
 
 mod comparison_util;
-pub(crate) use comparison_util::{scale_and_subtract, scale_and_subtract_eval};
+pub(crate) use comparison_util::{
+    scale_and_subtract, scale_and_subtract_constant, scale_and_subtract_eval,
+};
 
 mod equals_expr;
 use equals_expr::*;
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
+#[cfg(all(test, feature = "vectorized-equality"))]
+mod equals_expr_vectorized_test;
+
+mod tuple_equals_expr;
+use tuple_equals_expr::TupleEqualsExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod tuple_equals_expr_test;
+
+mod div_expr;
+use div_expr::{DivExpr, MAX_DIVISION_PRECISION};
+#[cfg(all(test, feature = "blitzar"))]
+mod div_expr_test;
 
 mod sign_expr;
 use sign_expr::*;
 #[cfg(all(test, feature = "blitzar"))]
 mod sign_expr_test;
 
+mod window_aggregate_expr;
+use window_aggregate_expr::WindowAggregateExpr;
+#[cfg(test)]
+mod window_aggregate_expr_test;
+
+mod top_n_expr;
+use top_n_expr::TopNExpr;
+#[cfg(test)]
+mod top_n_expr_test;
+
+mod limit_expr;
+use limit_expr::LimitExpr;
+#[cfg(test)]
+mod limit_expr_test;
+
+mod subtract_expr;
+pub(crate) use subtract_expr::difference_type;
+use subtract_expr::SubtractExpr;
+
 mod table_expr;
 pub(crate) use table_expr::TableExpr;
 
@@ -95,9 +138,35 @@ mod dense_filter_util_test;
 mod group_by_expr;
 pub(crate) use group_by_expr::GroupByExpr;
 
+mod post_aggregation_expr;
+pub(crate) use post_aggregation_expr::PostAggregationExpr;
+#[cfg(test)]
+mod post_aggregation_expr_test;
+
 #[cfg(all(test, feature = "blitzar"))]
 mod group_by_expr_test;
 
+mod count_expr;
+pub(crate) use count_expr::CountExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod count_expr_test;
+
+mod histogram_expr;
+pub use histogram_expr::Histogram;
+pub(crate) use histogram_expr::HistogramExpr;
+#[cfg(test)]
+mod histogram_expr_test;
+
+mod lookup_expr;
+pub(crate) use lookup_expr::LookupExpr;
+#[cfg(test)]
+mod lookup_expr_test;
+
+mod min_max_expr;
+pub(crate) use min_max_expr::{sharp_extreme, Extreme, MinMaxExpr};
+#[cfg(test)]
+mod min_max_expr_test;
+
 mod group_by_util;
 use group_by_util::aggregate_columns;
 #[cfg(test)]
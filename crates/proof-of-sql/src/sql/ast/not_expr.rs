@@ -9,14 +9,23 @@ use crate::{
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
 
 /// Provable logical NOT expression
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NotExpr<C: Commitment> {
     expr: Box<ProvableExprPlan<C>>,
 }
 
+impl<C: Commitment> Hash for NotExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expr.hash(state);
+    }
+}
+
 impl<C: Commitment> NotExpr<C> {
     /// Create logical NOT expression
     pub fn new(expr: Box<ProvableExprPlan<C>>) -> Self {
@@ -29,6 +38,10 @@ impl<C: Commitment> ProvableExpr<C> for NotExpr<C> {
         self.expr.count(builder)
     }
 
+    fn num_post_result_challenges(&self) -> usize {
+        self.expr.num_post_result_challenges()
+    }
+
     fn data_type(&self) -> ColumnType {
         ColumnType::Boolean
     }
@@ -59,6 +72,7 @@ impl<C: Commitment> ProvableExpr<C> for NotExpr<C> {
         Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| !expr[i]))
     }
 
+    #[tracing::instrument(name = "NotExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
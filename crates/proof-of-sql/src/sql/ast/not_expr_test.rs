@@ -2,9 +2,8 @@ use crate::{
     base::{
         commitment::InnerProductProof,
         database::{
-            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType,
-            OwnedTableTestAccessor, RandomTestAccessorDescriptor, RecordBatchTestAccessor,
-            TestAccessor,
+            owned_table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            RecordBatchTestAccessor, TestAccessor,
         },
         scalar::Curve25519Scalar,
     },
@@ -14,6 +13,7 @@ use crate::{
         test_utility::{column, const_int128, const_scalar, equal, not as unot},
         ProvableExpr, ProvableExprPlan,
     },
+    testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
 };
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
@@ -1,4 +1,4 @@
-use super::{ProvableExpr, ProvableExprPlan};
+use super::{commutative_operands_eq, hash_commutative_operands, ProvableExpr, ProvableExprPlan};
 use crate::{
     base::{
         commitment::Commitment,
@@ -10,15 +10,31 @@ use crate::{
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
 
 /// Provable logical OR expression
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct OrExpr<C: Commitment> {
     lhs: Box<ProvableExprPlan<C>>,
     rhs: Box<ProvableExprPlan<C>>,
 }
 
+/// `a OR b` equals `b OR a`
+impl<C: Commitment> PartialEq for OrExpr<C> {
+    fn eq(&self, other: &Self) -> bool {
+        commutative_operands_eq((&self.lhs, &self.rhs), (&other.lhs, &other.rhs))
+    }
+}
+
+impl<C: Commitment> Hash for OrExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_commutative_operands(&self.lhs, &self.rhs, state);
+    }
+}
+
 impl<C: Commitment> OrExpr<C> {
     /// Create logical OR expression
     pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>) -> Self {
@@ -34,6 +50,10 @@ impl<C: Commitment> ProvableExpr<C> for OrExpr<C> {
         Ok(())
     }
 
+    fn num_post_result_challenges(&self) -> usize {
+        self.lhs.num_post_result_challenges() + self.rhs.num_post_result_challenges()
+    }
+
     fn data_type(&self) -> ColumnType {
         ColumnType::Boolean
     }
@@ -68,6 +88,7 @@ impl<C: Commitment> ProvableExpr<C> for OrExpr<C> {
         Column::Boolean(prover_evaluate_or(builder, alloc, lhs, rhs))
     }
 
+    #[tracing::instrument(name = "OrExpr::verifier_evaluate", level = "debug", skip_all)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
@@ -2,14 +2,14 @@ use crate::{
     base::{
         commitment::InnerProductProof,
         database::{
-            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType,
-            OwnedTableTestAccessor, RandomTestAccessorDescriptor, RecordBatchTestAccessor,
-            TestAccessor,
+            owned_table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            RecordBatchTestAccessor, TestAccessor,
         },
         scalar::Curve25519Scalar,
     },
     record_batch,
     sql::ast::{test_expr::TestExprNode, test_utility::*, ProvableExpr, ProvableExprPlan},
+    testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
 };
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
@@ -136,6 +136,13 @@ fn we_can_query_random_tables_with_a_non_zero_offset() {
     test_random_tables_with_given_offset(1001);
 }
 
+#[test]
+fn we_can_query_random_tables_with_offsets_near_a_power_of_two() {
+    for offset in [(1 << 16) - 1, 1 << 16, (1 << 16) + 1] {
+        test_random_tables_with_given_offset(offset);
+    }
+}
+
 #[test]
 fn we_can_compute_the_correct_output_of_an_or_expr_using_result_evaluate() {
     let data = owned_table([
@@ -156,3 +163,38 @@ fn we_can_compute_the_correct_output_of_an_or_expr_using_result_evaluate() {
     let expected_res = Column::Boolean(&[false, true, true, true]);
     assert_eq!(res, expected_res);
 }
+
+#[cfg(feature = "inspect-subpolynomials")]
+#[test]
+fn we_can_inspect_the_subpolynomial_of_an_or_expression() {
+    use super::prover_evaluate_or;
+    use crate::sql::proof::{ProofBuilder, SubpolynomialRecord, SumcheckSubpolynomialType};
+    use num_traits::One;
+
+    let alloc = Bump::new();
+    let lhs = [true, false, true, false];
+    let rhs = [true, true, false, false];
+    let mut builder = ProofBuilder::<Curve25519Scalar>::new(4, 2, Vec::new());
+    builder.record_subpolynomials();
+    let selection = prover_evaluate_or(&mut builder, &alloc, &lhs, &rhs);
+    assert_eq!(selection, [true, true, true, false]);
+
+    let to_scalars = |bools: [bool; 4]| Vec::from_iter(bools.map(Curve25519Scalar::from));
+    let lhs_and_rhs = to_scalars([true, false, false, false]);
+    let records = builder.take_subpolynomial_records().unwrap();
+    assert_eq!(
+        records,
+        [SubpolynomialRecord {
+            subpolynomial_type: SumcheckSubpolynomialType::Identity,
+            terms: vec![
+                (Curve25519Scalar::one(), vec![lhs_and_rhs]),
+                (
+                    -Curve25519Scalar::one(),
+                    vec![to_scalars(lhs), to_scalars(rhs)]
+                ),
+            ],
+        }]
+    );
+    assert!(records[0].is_satisfied());
+    assert!(builder.take_subpolynomial_records().is_none());
+}
@@ -0,0 +1,157 @@
+use crate::{
+    base::{commitment::Commitment, scalar::Scalar},
+    sql::proof::{ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Arithmetic on the aggregated columns of a [`GroupByExpr`](super::GroupByExpr), such as
+/// `SUM(revenue) - SUM(cost)`, which is evaluated on each group after the aggregation.
+///
+/// The result column `d` of the expression is checked against the aggregated columns with
+/// ```ignore
+///     d - expr(aggregated columns) = 0
+/// ```
+/// on every row, where a constant is multiplied by the indicator of the groups. The aggregated
+/// columns are already in the result, so nothing needs to be committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostAggregationExpr {
+    /// The aggregated column at this index, where the group keys are followed by the sums and the count
+    Column(usize),
+    /// An integer constant
+    Literal(i128),
+    /// The sum of two expressions
+    Add(Box<PostAggregationExpr>, Box<PostAggregationExpr>),
+    /// The difference of two expressions
+    Subtract(Box<PostAggregationExpr>, Box<PostAggregationExpr>),
+    /// The product of two expressions
+    Multiply(Box<PostAggregationExpr>, Box<PostAggregationExpr>),
+}
+
+impl PostAggregationExpr {
+    /// Expands the expression into a sum of monomials, each a coefficient times a product of columns
+    fn monomials<S: Scalar>(&self) -> Vec<(S, Vec<usize>)> {
+        match self {
+            Self::Column(index) => vec![(S::ONE, vec![*index])],
+            Self::Literal(value) => vec![(S::from(*value), vec![])],
+            Self::Add(lhs, rhs) => [lhs.monomials(), rhs.monomials()].concat(),
+            Self::Subtract(lhs, rhs) => {
+                let mut monomials = lhs.monomials::<S>();
+                monomials.extend(
+                    rhs.monomials::<S>()
+                        .into_iter()
+                        .map(|(coefficient, columns)| (-coefficient, columns)),
+                );
+                monomials
+            }
+            Self::Multiply(lhs, rhs) => {
+                let rhs_monomials = rhs.monomials::<S>();
+                lhs.monomials::<S>()
+                    .into_iter()
+                    .flat_map(|(lhs_coefficient, lhs_columns)| {
+                        rhs_monomials
+                            .iter()
+                            .map(move |(rhs_coefficient, rhs_columns)| {
+                                (
+                                    lhs_coefficient * *rhs_coefficient,
+                                    [lhs_columns.clone(), rhs_columns.clone()].concat(),
+                                )
+                            })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The degree of the sumcheck subpolynomial of the expression
+    pub(super) fn degree(&self) -> usize {
+        // the identity constraint multiplies each monomial by the random evaluation
+        self.num_factors().max(1) + 1
+    }
+
+    /// The largest number of columns in a monomial of the expression
+    fn num_factors(&self) -> usize {
+        match self {
+            Self::Column(_) => 1,
+            Self::Literal(_) => 0,
+            Self::Add(lhs, rhs) | Self::Subtract(lhs, rhs) => {
+                lhs.num_factors().max(rhs.num_factors())
+            }
+            Self::Multiply(lhs, rhs) => lhs.num_factors() + rhs.num_factors(),
+        }
+    }
+
+    /// Evaluates the expression on each of the `num_groups` rows of the aggregated `columns`
+    pub(super) fn evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        columns: &[&[S]],
+        num_groups: usize,
+    ) -> &'a [S] {
+        let monomials = self.monomials::<S>();
+        alloc.alloc_slice_fill_with(num_groups, |i| {
+            monomials
+                .iter()
+                .map(|(coefficient, monomial)| {
+                    monomial.iter().fold(*coefficient, |product, &column| {
+                        product * columns[column][i]
+                    })
+                })
+                .sum()
+        })
+    }
+
+    /// Proves that `result` is the expression evaluated on each group
+    pub(super) fn prove<'a, S: Scalar>(
+        &self,
+        builder: &mut ProofBuilder<'a, S>,
+        alloc: &'a Bump,
+        columns: &[&'a [S]],
+        result: &'a [S],
+    ) {
+        let chi: &'a [bool] = alloc.alloc_slice_fill_copy(result.len(), true);
+        let mut terms = vec![(S::ONE, vec![Box::new(result) as Box<_>])];
+        for (coefficient, monomial) in self.monomials::<S>() {
+            let factors = if monomial.is_empty() {
+                vec![Box::new(chi) as Box<_>]
+            } else {
+                Vec::from_iter(
+                    monomial
+                        .into_iter()
+                        .map(|column| Box::new(columns[column]) as Box<_>),
+                )
+            };
+            terms.push((-coefficient, factors));
+        }
+
+        // d - expr(aggregated columns) = 0
+        builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+    }
+
+    /// Checks the evaluation of the result of the expression against the evaluations of the aggregated columns
+    pub(super) fn verify<C: Commitment>(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        column_evals: &[C::Scalar],
+        result_eval: C::Scalar,
+        chi_eval: C::Scalar,
+    ) {
+        let expr_eval: C::Scalar = self
+            .monomials::<C::Scalar>()
+            .into_iter()
+            .map(|(coefficient, monomial)| {
+                if monomial.is_empty() {
+                    coefficient * chi_eval
+                } else {
+                    monomial.into_iter().fold(coefficient, |product, column| {
+                        product * column_evals[column]
+                    })
+                }
+            })
+            .sum();
+        let rand_eval = builder.mle_evaluations.random_evaluation;
+
+        // d - expr(aggregated columns) = 0
+        builder.produce_sumcheck_subpolynomial_evaluation(&(rand_eval * (result_eval - expr_eval)));
+    }
+}
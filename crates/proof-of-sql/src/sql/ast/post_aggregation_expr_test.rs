@@ -0,0 +1,172 @@
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{
+        ast::ProofPlan,
+        parse::{ConversionError, QueryExpr},
+        proof::VerifiableQueryResult,
+    },
+};
+use ark_std::test_rng;
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.sales".parse().unwrap(),
+        owned_table([
+            varchar("region", ["east", "west", "east", "north", "west", "east"]),
+            bigint("revenue", [100, 250, 80, 40, 300, 20]),
+            bigint("cost", [60, 200, 90, 10, 120, 5]),
+            int("units", [1, 5, 2, 1, 3, 1]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> Result<QueryExpr<DoryCommitment>, ConversionError> {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor)
+}
+
+/// The verified result of the query, after the post-proof transform
+fn prove_and_verify(
+    query: &QueryExpr<DoryCommitment>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+    setup: &DoryProverPublicSetup,
+) -> OwnedTable<DoryScalar> {
+    let table =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), accessor, setup)
+            .unwrap()
+            .verify(query.proof_expr(), accessor, &setup.into())
+            .unwrap()
+            .table;
+    query
+        .result()
+        .transform_results(table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+fn is_group_by(query: &QueryExpr<DoryCommitment>) -> bool {
+    matches!(query.proof_expr(), ProofPlan::GroupBy(_))
+}
+
+#[test]
+fn we_can_prove_the_profit_of_each_group() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan(
+        "SELECT region, SUM(revenue) - SUM(cost) AS profit FROM sales GROUP BY region ORDER BY region",
+        &accessor,
+    )
+    .unwrap();
+    assert!(is_group_by(&query));
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &setup),
+        owned_table([
+            varchar("region", ["east", "north", "west"]),
+            bigint("profit", [200 - 155, 40 - 10, 550 - 320]),
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_arithmetic_with_counts_products_and_literals_on_aggregates() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan(
+        "SELECT COUNT(*) AS n, 2 * SUM(revenue) - SUM(cost) * COUNT(*) + 1 AS x, \
+            SUM(units) * SUM(units) AS u, SUM(cost) AS c, region \
+            FROM sales WHERE revenue > 30 GROUP BY region ORDER BY region",
+        &accessor,
+    )
+    .unwrap();
+    assert!(is_group_by(&query));
+    assert_eq!(
+        prove_and_verify(&query, &accessor, &setup),
+        owned_table([
+            bigint("n", [2, 1, 2]),
+            bigint(
+                "x",
+                [
+                    2 * 180 - 150 * 2 + 1,
+                    2 * 40 - 10 + 1,
+                    2 * 550 - 320 * 2 + 1
+                ]
+            ),
+            bigint("u", [9, 1, 64]),
+            bigint("c", [150, 10, 320]),
+            varchar("region", ["east", "north", "west"]),
+        ])
+    );
+}
+
+#[test]
+fn we_cannot_verify_arithmetic_on_aggregates_with_the_result_of_other_arithmetic() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan(
+        "SELECT region, SUM(revenue) - SUM(cost) AS profit FROM sales GROUP BY region",
+        &accessor,
+    )
+    .unwrap();
+    let other_query = plan(
+        "SELECT region, SUM(revenue) + SUM(cost) AS profit FROM sales GROUP BY region",
+        &accessor,
+    )
+    .unwrap();
+    let mut res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    res.provable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        other_query.proof_expr(),
+        &accessor,
+        &setup,
+    )
+    .unwrap()
+    .provable_result;
+    assert!(res
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .is_err());
+}
+
+#[test]
+fn the_operands_of_arithmetic_on_aggregates_must_be_aggregates_or_group_keys() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    assert!(matches!(
+        plan(
+            "SELECT region, SUM(revenue) - cost AS profit FROM sales GROUP BY region",
+            &accessor,
+        ),
+        Err(ConversionError::InvalidGroupByColumnRef(column)) if column == "cost"
+    ));
+    assert!(matches!(
+        plan(
+            "SELECT region, SUM(revenue) - region AS profit FROM sales GROUP BY region",
+            &accessor,
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
+
+#[test]
+fn arithmetic_that_cannot_be_proven_on_the_groups_is_computed_after_the_proof() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    for sql in [
+        "SELECT region, SUM(revenue) / SUM(cost) AS ratio FROM sales GROUP BY region",
+        "SELECT region, MAX(revenue) - MIN(cost) AS spread FROM sales GROUP BY region",
+    ] {
+        let query = plan(sql, &accessor).unwrap();
+        assert!(!is_group_by(&query), "{sql}");
+    }
+}
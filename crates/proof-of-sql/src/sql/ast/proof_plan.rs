@@ -1,4 +1,6 @@
-use super::{DenseFilterExpr, FilterExpr, GroupByExpr};
+use super::{
+    CountExpr, DenseFilterExpr, FilterExpr, GroupByExpr, HistogramExpr, LookupExpr, MinMaxExpr,
+};
 use crate::{
     base::commitment::Commitment,
     sql::proof::{ProofExpr, ProverEvaluate},
@@ -7,6 +9,8 @@ use serde::{Deserialize, Serialize};
 
 /// The query plan for proving a query
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
+// The other variants are as large as `MinMax`, but clippy can't size them since they depend on `C`.
+#[allow(clippy::large_enum_variant)]
 pub enum ProofPlan<C: Commitment> {
     /// Provable expressions for queries of the form, where the result is sent in a sparse form
     /// ```ignore
@@ -28,6 +32,42 @@ pub enum ProofPlan<C: Commitment> {
     ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
     /// ```
     DenseFilter(DenseFilterExpr<C>),
+    /// Provable expressions for queries of the form, where only the count is sent
+    /// ```ignore
+    ///     SELECT COUNT(*) as count_alias FROM <table> WHERE <where_clause>
+    /// ```
+    Count(CountExpr<C>),
+    /// Provable expressions for the histogram of a numeric column, where only the count of each bucket is sent
+    /// ```ignore
+    ///     SELECT COUNT(*) FILTER (WHERE <boundary0> <= <column> AND <column> < <boundary1>) as bucket_0, ...
+    ///     FROM <table>
+    /// ```
+    Histogram(HistogramExpr<C>),
+    /// Provable expressions for the lookup of a single row by its key, where the result is at most one row
+    /// ```ignore
+    ///     SELECT <column1>, ..., <columnN> FROM <table> WHERE <key> = <value>
+    /// ```
+    Lookup(LookupExpr<C>),
+    /// Provable expressions for the extremes of integer columns with sharp bounds, where only the extremes are sent
+    /// ```ignore
+    ///     SELECT MAX(<column1>) as <alias1>, ..., MIN(<columnN>) as <aliasN> FROM <table>
+    /// ```
+    MinMax(MinMaxExpr<C>),
+}
+
+impl<C: Commitment> ProofPlan<C> {
+    /// The name of the node type, as used in a [`ProverReport`](crate::sql::proof::ProverReport)
+    fn node_type(&self) -> &'static str {
+        match self {
+            ProofPlan::Filter(_) => "FilterExpr",
+            ProofPlan::GroupBy(_) => "GroupByExpr",
+            ProofPlan::DenseFilter(_) => "DenseFilterExpr",
+            ProofPlan::Count(_) => "CountExpr",
+            ProofPlan::Histogram(_) => "HistogramExpr",
+            ProofPlan::Lookup(_) => "LookupExpr",
+            ProofPlan::MinMax(_) => "MinMaxExpr",
+        }
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
@@ -40,6 +80,10 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.count(builder, accessor),
             ProofPlan::GroupBy(expr) => expr.count(builder, accessor),
             ProofPlan::DenseFilter(expr) => expr.count(builder, accessor),
+            ProofPlan::Count(expr) => expr.count(builder, accessor),
+            ProofPlan::Histogram(expr) => expr.count(builder, accessor),
+            ProofPlan::Lookup(expr) => expr.count(builder, accessor),
+            ProofPlan::MinMax(expr) => expr.count(builder, accessor),
         }
     }
 
@@ -48,6 +92,10 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_length(accessor),
             ProofPlan::GroupBy(expr) => expr.get_length(accessor),
             ProofPlan::DenseFilter(expr) => expr.get_length(accessor),
+            ProofPlan::Count(expr) => expr.get_length(accessor),
+            ProofPlan::Histogram(expr) => expr.get_length(accessor),
+            ProofPlan::Lookup(expr) => expr.get_length(accessor),
+            ProofPlan::MinMax(expr) => expr.get_length(accessor),
         }
     }
 
@@ -56,6 +104,10 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_offset(accessor),
             ProofPlan::GroupBy(expr) => expr.get_offset(accessor),
             ProofPlan::DenseFilter(expr) => expr.get_offset(accessor),
+            ProofPlan::Count(expr) => expr.get_offset(accessor),
+            ProofPlan::Histogram(expr) => expr.get_offset(accessor),
+            ProofPlan::Lookup(expr) => expr.get_offset(accessor),
+            ProofPlan::MinMax(expr) => expr.get_offset(accessor),
         }
     }
 
@@ -69,6 +121,10 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.verifier_evaluate(builder, accessor),
             ProofPlan::GroupBy(expr) => expr.verifier_evaluate(builder, accessor),
             ProofPlan::DenseFilter(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::Count(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::Histogram(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::Lookup(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::MinMax(expr) => expr.verifier_evaluate(builder, accessor),
         }
     }
 
@@ -77,6 +133,10 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_column_result_fields(),
             ProofPlan::GroupBy(expr) => expr.get_column_result_fields(),
             ProofPlan::DenseFilter(expr) => expr.get_column_result_fields(),
+            ProofPlan::Count(expr) => expr.get_column_result_fields(),
+            ProofPlan::Histogram(expr) => expr.get_column_result_fields(),
+            ProofPlan::Lookup(expr) => expr.get_column_result_fields(),
+            ProofPlan::MinMax(expr) => expr.get_column_result_fields(),
         }
     }
 
@@ -85,6 +145,22 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_column_references(),
             ProofPlan::GroupBy(expr) => expr.get_column_references(),
             ProofPlan::DenseFilter(expr) => expr.get_column_references(),
+            ProofPlan::Count(expr) => expr.get_column_references(),
+            ProofPlan::Histogram(expr) => expr.get_column_references(),
+            ProofPlan::Lookup(expr) => expr.get_column_references(),
+            ProofPlan::MinMax(expr) => expr.get_column_references(),
+        }
+    }
+
+    fn get_table_references(&self) -> std::collections::HashSet<crate::base::database::TableRef> {
+        match self {
+            ProofPlan::Filter(expr) => expr.get_table_references(),
+            ProofPlan::GroupBy(expr) => expr.get_table_references(),
+            ProofPlan::DenseFilter(expr) => expr.get_table_references(),
+            ProofPlan::Count(expr) => expr.get_table_references(),
+            ProofPlan::Histogram(expr) => expr.get_table_references(),
+            ProofPlan::Lookup(expr) => expr.get_table_references(),
+            ProofPlan::MinMax(expr) => expr.get_table_references(),
         }
     }
 }
@@ -101,6 +177,10 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.result_evaluate(builder, alloc, accessor),
             ProofPlan::GroupBy(expr) => expr.result_evaluate(builder, alloc, accessor),
             ProofPlan::DenseFilter(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::Count(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::Histogram(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::Lookup(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::MinMax(expr) => expr.result_evaluate(builder, alloc, accessor),
         }
     }
 
@@ -111,10 +191,17 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProofPlan<C> {
         alloc: &'a bumpalo::Bump,
         accessor: &'a dyn crate::base::database::DataAccessor<C::Scalar>,
     ) {
-        match self {
+        if builder.is_cancelled() {
+            return;
+        }
+        builder.record_node(self.node_type(), alloc, |builder| match self {
             ProofPlan::Filter(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::GroupBy(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::DenseFilter(expr) => expr.prover_evaluate(builder, alloc, accessor),
-        }
+            ProofPlan::Count(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::Histogram(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::Lookup(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::MinMax(expr) => expr.prover_evaluate(builder, alloc, accessor),
+        })
     }
 }
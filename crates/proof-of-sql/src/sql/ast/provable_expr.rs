@@ -10,10 +10,24 @@ use bumpalo::Bump;
 use std::{collections::HashSet, fmt::Debug};
 
 /// Provable AST column expression that evaluates to a `Column`
+///
+/// # Offsets
+/// A table with offset `o` has its row `i` committed with the generator `o + i`.
+/// Expressions are unaware of the offset: the columns from the accessor and the slices that an
+/// expression produces are indexed from the first row of the table, and
+/// [`QueryProof`](crate::sql::proof::QueryProof) commits the intermediate MLEs and proves their
+/// evaluations with the offset of the table. So an implementation must not compute commitments
+/// itself, and any commitment it gets from the accessor, such as the commitment to a column,
+/// must have been computed with the table's offset.
 pub trait ProvableExpr<C: Commitment>: Debug + Send + Sync {
     /// Count the number of proof terms needed for this expression
     fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError>;
 
+    /// The number of post result challenges consumed while proving this expression.
+    ///
+    /// A `ProofExpr` containing this expression must request these from the `ResultBuilder`.
+    fn num_post_result_challenges(&self) -> usize;
+
     /// Get the data type of the expression
     fn data_type(&self) -> ColumnType;
 
@@ -1,10 +1,13 @@
 use super::{
-    AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr, NotExpr, OrExpr, ProvableExpr,
+    difference_type, AndExpr, ColumnExpr, DivExpr, EqualsExpr, InequalityExpr, LimitExpr,
+    LiteralExpr, NotExpr, OrExpr, ProvableExpr, SignExpr, SubtractExpr, TopNExpr, TupleEqualsExpr,
+    WindowAggregateExpr, MAX_DIVISION_PRECISION,
 };
 use crate::{
     base::{
         commitment::Commitment,
         database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LiteralValue},
+        math::decimal::Precision,
         proof::ProofError,
     },
     sql::{
@@ -13,12 +16,24 @@ use crate::{
     },
 };
 use bumpalo::Bump;
-use proof_of_sql_parser::intermediate_ast::BinaryOperator;
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, WindowAggregationOperator};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, fmt::Debug};
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    mem,
+};
 
 /// Enum of AST column expression types that implement `ProvableExpr`. Is itself a `ProvableExpr`.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+///
+/// Identical subexpressions of a query, such as the two copies of `a = 1` in
+/// `(a = 1 AND b = 2) OR (a = 1 AND c = 3)`, are only proven once: the later copies reuse the
+/// column and the evaluation of the first, so their intermediate MLEs are committed once.
+///
+/// Plans are compared structurally, except that the operands of `AND` and `OR` may appear in
+/// either order, so `a AND b` equals `b AND a`. `Hash` agrees with this equality.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProvableExprPlan<C: Commitment> {
     /// Column
     Column(ColumnExpr<C>),
@@ -34,7 +49,67 @@ pub enum ProvableExprPlan<C: Commitment> {
     Equals(EqualsExpr<C>),
     /// Provable AST expression for an inequality expression
     Inequality(InequalityExpr<C>),
+    /// Provable AST expression for a tuple equality expression
+    TupleEquals(TupleEqualsExpr<C>),
+    /// Provable numeric division expression
+    Divide(DivExpr<C>),
+    /// Provable numeric signum expression
+    Sign(SignExpr<C>),
+    /// Provable aggregate over the whole selection, such as `AVG(x) OVER ()`
+    WindowAggregate(WindowAggregateExpr<C>),
+    /// Provable numeric subtraction expression
+    Subtract(SubtractExpr<C>),
+    /// Provable selection of the rows that may be among the first rows of an order,
+    /// as for `ORDER BY x DESC LIMIT k`
+    TopN(TopNExpr<C>),
+    /// Provable selection of some rows of a selection, as for `LIMIT k` without an `ORDER BY`
+    Limit(LimitExpr<C>),
+}
+// `Hash` is implemented by hand for the plan and its nodes, since deriving it would require `C: Hash`.
+impl<C: Commitment> Hash for ProvableExprPlan<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            ProvableExprPlan::Column(expr) => expr.hash(state),
+            ProvableExprPlan::And(expr) => expr.hash(state),
+            ProvableExprPlan::Or(expr) => expr.hash(state),
+            ProvableExprPlan::Not(expr) => expr.hash(state),
+            ProvableExprPlan::Literal(expr) => expr.hash(state),
+            ProvableExprPlan::Equals(expr) => expr.hash(state),
+            ProvableExprPlan::Inequality(expr) => expr.hash(state),
+            ProvableExprPlan::TupleEquals(expr) => expr.hash(state),
+            ProvableExprPlan::Divide(expr) => expr.hash(state),
+            ProvableExprPlan::Sign(expr) => expr.hash(state),
+            ProvableExprPlan::WindowAggregate(expr) => expr.hash(state),
+            ProvableExprPlan::Subtract(expr) => expr.hash(state),
+            ProvableExprPlan::TopN(expr) => expr.hash(state),
+            ProvableExprPlan::Limit(expr) => expr.hash(state),
+        }
+    }
+}
+
+/// Compare the operands of two applications of a commutative operator, in either order.
+pub(crate) fn commutative_operands_eq<C: Commitment>(
+    (lhs, rhs): (&ProvableExprPlan<C>, &ProvableExprPlan<C>),
+    (other_lhs, other_rhs): (&ProvableExprPlan<C>, &ProvableExprPlan<C>),
+) -> bool {
+    (lhs == other_lhs && rhs == other_rhs) || (lhs == other_rhs && rhs == other_lhs)
+}
+
+/// Hash the operands of a commutative operator, so that swapping them does not change the hash.
+///
+/// The operands are hashed on their own with the unkeyed `DefaultHasher`, so the hash is the
+/// same in every run, and then fed to `state` in sorted order.
+pub(crate) fn hash_commutative_operands<C: Commitment, H: Hasher>(
+    lhs: &ProvableExprPlan<C>,
+    rhs: &ProvableExprPlan<C>,
+    state: &mut H,
+) {
+    let (lhs, rhs) = (lhs.structural_hash(), rhs.structural_hash());
+    state.write_u64(lhs.min(rhs));
+    state.write_u64(lhs.max(rhs));
 }
+
 impl<C: Commitment> ProvableExprPlan<C> {
     /// Create column expression
     pub fn new_column(column_ref: ColumnRef) -> Self {
@@ -49,6 +124,51 @@ impl<C: Commitment> ProvableExprPlan<C> {
         rhs.check_data_type(ColumnType::Boolean)?;
         Ok(Self::And(AndExpr::new(Box::new(lhs), Box::new(rhs))))
     }
+    /// Create the logical AND of all of `predicates`, or `TRUE` if there are none.
+    ///
+    /// The predicates are combined pairwise, level by level, into a balanced tree, so `n`
+    /// predicates are nested `ceil(log2(n))` deep instead of `n - 1` deep as with repeated
+    /// [`Self::try_new_and`].
+    pub fn try_new_and_all(predicates: Vec<ProvableExprPlan<C>>) -> ConversionResult<Self> {
+        for predicate in &predicates {
+            predicate.check_data_type(ColumnType::Boolean)?;
+        }
+        Ok(Self::combine_balanced(predicates, |lhs, rhs| {
+            Self::And(AndExpr::new(Box::new(lhs), Box::new(rhs)))
+        })
+        .unwrap_or_else(|| Self::new_literal(LiteralValue::Boolean(true))))
+    }
+    /// Create the logical OR of all of `predicates`, or `FALSE` if there are none.
+    ///
+    /// Like [`Self::try_new_and_all`], the predicates are combined into a balanced tree that is
+    /// nested `ceil(log2(n))` deep.
+    pub fn try_new_or_any(predicates: Vec<ProvableExprPlan<C>>) -> ConversionResult<Self> {
+        for predicate in &predicates {
+            predicate.check_data_type(ColumnType::Boolean)?;
+        }
+        Ok(Self::combine_balanced(predicates, |lhs, rhs| {
+            Self::Or(OrExpr::new(Box::new(lhs), Box::new(rhs)))
+        })
+        .unwrap_or_else(|| Self::new_literal(LiteralValue::Boolean(false))))
+    }
+    /// Combine `operands` pairwise, level by level, into a balanced tree of `combine`.
+    fn combine_balanced(
+        operands: Vec<ProvableExprPlan<C>>,
+        combine: impl Fn(ProvableExprPlan<C>, ProvableExprPlan<C>) -> Self,
+    ) -> Option<Self> {
+        let mut level = operands;
+        while level.len() > 1 {
+            let mut operands = level.into_iter();
+            level = Vec::with_capacity(operands.len().div_ceil(2));
+            while let Some(lhs) = operands.next() {
+                level.push(match operands.next() {
+                    Some(rhs) => combine(lhs, rhs),
+                    None => lhs,
+                });
+            }
+        }
+        level.pop()
+    }
     /// Create logical OR expression
     pub fn try_new_or(
         lhs: ProvableExprPlan<C>,
@@ -63,6 +183,23 @@ impl<C: Commitment> ProvableExprPlan<C> {
         expr.check_data_type(ColumnType::Boolean)?;
         Ok(Self::Not(NotExpr::new(Box::new(expr))))
     }
+    /// Create an IS TRUE expression
+    ///
+    /// Since columns are never NULL, a boolean expression is true exactly when it is not false,
+    /// so this is `expr` itself.
+    pub fn try_new_is_true(expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        expr.check_data_type(ColumnType::Boolean)?;
+        Ok(expr)
+    }
+    /// Create an IS FALSE expression, which is the logical NOT of `expr` since columns are never NULL
+    pub fn try_new_is_false(expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        Self::try_new_not(expr)
+    }
+    /// Create an IS UNKNOWN expression, which is always false since columns are never NULL
+    pub fn try_new_is_unknown(expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        expr.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::new_literal(LiteralValue::Boolean(false)))
+    }
     /// Create CONST expression
     pub fn new_literal(value: LiteralValue<C::Scalar>) -> Self {
         Self::Literal(LiteralExpr::new(value))
@@ -83,6 +220,30 @@ impl<C: Commitment> ProvableExprPlan<C> {
             Ok(Self::Equals(EqualsExpr::new(Box::new(lhs), Box::new(rhs))))
         }
     }
+    /// Create a new tuple equality expression, such as `(a, b) = (1, 'x')`
+    pub fn try_new_tuple_equals(
+        lhs: Vec<ProvableExprPlan<C>>,
+        rhs: Vec<ProvableExprPlan<C>>,
+    ) -> ConversionResult<Self> {
+        if lhs.len() != rhs.len() || lhs.is_empty() {
+            return Err(ConversionError::InvalidExpression(format!(
+                "cannot compare a tuple of {} elements with a tuple of {} elements",
+                lhs.len(),
+                rhs.len()
+            )));
+        }
+        for (lhs, rhs) in lhs.iter().zip(&rhs) {
+            let lhs_datatype = lhs.data_type();
+            let rhs_datatype = rhs.data_type();
+            if !type_check_binary_operation(&lhs_datatype, &rhs_datatype, BinaryOperator::Equal) {
+                return Err(ConversionError::DataTypeMismatch(
+                    lhs_datatype.to_string(),
+                    rhs_datatype.to_string(),
+                ));
+            }
+        }
+        Ok(Self::TupleEquals(TupleEqualsExpr::new(lhs, rhs)))
+    }
     /// Create a new inequality expression
     pub fn try_new_inequality(
         lhs: ProvableExprPlan<C>,
@@ -109,6 +270,217 @@ impl<C: Commitment> ProvableExprPlan<C> {
         }
     }
 
+    /// Create a new division expression whose quotient is truncated to `scale`
+    ///
+    /// Both operands must be integers or decimals. The quotient is a decimal,
+    /// whose precision is as large as needed to hold any quotient of the operands.
+    /// With a `selection`, only its rows are divided, so the other rows may have a zero divisor.
+    pub fn try_new_divide(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+        scale: i8,
+        selection: Option<ProvableExprPlan<C>>,
+    ) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        let (lhs_precision, lhs_scale, rhs_scale) = match (
+            lhs_datatype.precision_value(),
+            lhs_datatype.scale(),
+            rhs_datatype.precision_value(),
+            rhs_datatype.scale(),
+        ) {
+            (Some(lhs_precision), Some(lhs_scale), Some(_), Some(rhs_scale))
+                if lhs_datatype != ColumnType::Scalar && rhs_datatype != ColumnType::Scalar =>
+            {
+                (lhs_precision as i16, lhs_scale as i16, rhs_scale as i16)
+            }
+            _ => {
+                return Err(ConversionError::DataTypeMismatch(
+                    lhs_datatype.to_string(),
+                    rhs_datatype.to_string(),
+                ))
+            }
+        };
+        let precision = (lhs_precision - lhs_scale + rhs_scale + scale as i16).max(1);
+        // The sizes of the operands are checked by `DivExpr::try_new`.
+        if precision > MAX_DIVISION_PRECISION as i16 {
+            return Err(ConversionError::InvalidPrecision(
+                precision.try_into().unwrap_or(u8::MAX),
+            ));
+        }
+        Ok(Self::Divide(DivExpr::try_new(
+            Box::new(lhs),
+            Box::new(rhs),
+            ColumnType::Decimal75(Precision::new(precision as u8)?, scale),
+            selection.map(Box::new),
+        )?))
+    }
+
+    /// Create a new integer division expression, such as `a DIV b`, whose quotient is truncated towards zero
+    ///
+    /// Both operands must be `SmallInt`, `Int` or `BigInt`. The quotient has the type of the dividend,
+    /// since it is never larger than the dividend except when dividing the minimum value by -1.
+    /// With a `selection`, only its rows are divided, so the other rows may have a zero divisor.
+    pub fn try_new_integer_divide(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+        selection: Option<ProvableExprPlan<C>>,
+    ) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        let is_integer = |datatype: ColumnType| {
+            matches!(
+                datatype,
+                ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt
+            )
+        };
+        if !is_integer(lhs_datatype) || !is_integer(rhs_datatype) {
+            return Err(ConversionError::DataTypeMismatch(
+                lhs_datatype.to_string(),
+                rhs_datatype.to_string(),
+            ));
+        }
+        Ok(Self::Divide(DivExpr::try_new(
+            Box::new(lhs),
+            Box::new(rhs),
+            lhs_datatype,
+            selection.map(Box::new),
+        )?))
+    }
+
+    /// Create a new signum expression
+    pub fn try_new_sign(expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        let datatype = expr.data_type();
+        if !datatype.is_numeric() {
+            return Err(ConversionError::non_numeric_expr_in_sign(datatype));
+        }
+        Ok(Self::Sign(SignExpr::new(Box::new(expr))))
+    }
+    /// Create a new aggregate of `expr` over the rows of `selection`, whose value is repeated for every row
+    ///
+    /// `expr` must be numeric and `selection` boolean. See [`WindowAggregateExpr`].
+    pub fn try_new_window_aggregate(
+        op: WindowAggregationOperator,
+        expr: ProvableExprPlan<C>,
+        selection: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        let datatype = expr.data_type();
+        if !datatype.is_numeric() || datatype == ColumnType::Scalar {
+            return Err(ConversionError::non_numeric_expr_in_agg(
+                datatype.to_string(),
+                op.to_string(),
+            ));
+        }
+        selection.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::WindowAggregate(WindowAggregateExpr::new(
+            op,
+            Box::new(expr),
+            Box::new(selection),
+        )))
+    }
+    /// Create a new subtraction expression
+    ///
+    /// Both operands must be integers or decimals. The difference is a decimal with the larger
+    /// of their scales and one more integral digit than either of them.
+    pub fn try_new_subtract(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        if difference_type(lhs_datatype, rhs_datatype).is_none() {
+            return Err(ConversionError::DataTypeMismatch(
+                lhs_datatype.to_string(),
+                rhs_datatype.to_string(),
+            ));
+        }
+        Ok(Self::Subtract(SubtractExpr::new(
+            Box::new(lhs),
+            Box::new(rhs),
+        )))
+    }
+    /// Create a new expression for the rows of `selection` that may be among the first `limit` of them
+    /// when sorted by `expr`
+    ///
+    /// `expr` must be numeric, `selection` boolean and `limit` positive. See [`TopNExpr`].
+    pub fn try_new_top_n(
+        expr: ProvableExprPlan<C>,
+        selection: ProvableExprPlan<C>,
+        limit: u64,
+        descending: bool,
+    ) -> ConversionResult<Self> {
+        let datatype = expr.data_type();
+        if !datatype.is_numeric() || datatype == ColumnType::Scalar {
+            return Err(ConversionError::InvalidExpression(format!(
+                "top-n is not supported on {datatype}"
+            )));
+        }
+        selection.check_data_type(ColumnType::Boolean)?;
+        if limit == 0 {
+            return Err(ConversionError::InvalidExpression(
+                "top-n requires a positive limit".into(),
+            ));
+        }
+        Ok(Self::TopN(TopNExpr::new(
+            Box::new(expr),
+            Box::new(selection),
+            limit,
+            descending,
+        )))
+    }
+    /// Create a new expression for some `limit` rows of `selection`, or all of them if fewer are selected
+    ///
+    /// `selection` must be boolean and `limit` positive. See [`LimitExpr`].
+    pub fn try_new_limit(selection: ProvableExprPlan<C>, limit: u64) -> ConversionResult<Self> {
+        selection.check_data_type(ColumnType::Boolean)?;
+        if limit == 0 {
+            return Err(ConversionError::InvalidExpression(
+                "a provable limit must be positive".into(),
+            ));
+        }
+        Ok(Self::Limit(LimitExpr::new(Box::new(selection), limit)))
+    }
+    /// The name of the node type, as used in a [`ProverReport`](crate::sql::proof::ProverReport)
+    fn node_type(&self) -> &'static str {
+        match self {
+            ProvableExprPlan::Column(_) => "ColumnExpr",
+            ProvableExprPlan::And(_) => "AndExpr",
+            ProvableExprPlan::Or(_) => "OrExpr",
+            ProvableExprPlan::Not(_) => "NotExpr",
+            ProvableExprPlan::Literal(_) => "LiteralExpr",
+            ProvableExprPlan::Equals(_) => "EqualsExpr",
+            ProvableExprPlan::Inequality(_) => "InequalityExpr",
+            ProvableExprPlan::TupleEquals(_) => "TupleEqualsExpr",
+            ProvableExprPlan::Divide(_) => "DivExpr",
+            ProvableExprPlan::Sign(_) => "SignExpr",
+            ProvableExprPlan::WindowAggregate(_) => "WindowAggregateExpr",
+            ProvableExprPlan::Subtract(_) => "SubtractExpr",
+            ProvableExprPlan::TopN(_) => "TopNExpr",
+            ProvableExprPlan::Limit(_) => "LimitExpr",
+        }
+    }
+
+    /// The key under which identical copies of this subexpression share a single proof.
+    ///
+    /// Columns and literals commit nothing, and the post-result challenges of a subexpression are
+    /// requested once per copy, so such subexpressions are not shared and `None` is returned.
+    /// The key is the plan itself, so copies are found with its structural `Eq`.
+    fn shared_subexpression_key(&self) -> Option<&Self> {
+        match self {
+            ProvableExprPlan::Column(_) | ProvableExprPlan::Literal(_) => None,
+            _ if self.num_post_result_challenges() > 0 => None,
+            _ => Some(self),
+        }
+    }
+
+    /// The hash of the plan with the unkeyed `DefaultHasher`, which only depends on the structure
+    /// of the plan and so is the same in every run and for the prover and the verifier.
+    fn structural_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Check that the plan has the correct data type
     fn check_data_type(&self, data_type: ColumnType) -> ConversionResult<()> {
         if self.data_type() == data_type {
@@ -124,6 +496,11 @@ impl<C: Commitment> ProvableExprPlan<C> {
 
 impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
     fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        if let Some(key) = self.shared_subexpression_key() {
+            if !builder.count_shared_subexpression(key) {
+                return Ok(());
+            }
+        }
         match self {
             ProvableExprPlan::Column(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::And(expr) => ProvableExpr::<C>::count(expr, builder),
@@ -132,6 +509,32 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Literal(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::Equals(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::Inequality(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::TupleEquals(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Divide(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Sign(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::WindowAggregate(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Subtract(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::TopN(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Limit(expr) => ProvableExpr::<C>::count(expr, builder),
+        }
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        match self {
+            ProvableExprPlan::Column(expr) => ProvableExpr::<C>::num_post_result_challenges(expr),
+            ProvableExprPlan::And(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Or(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Not(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Literal(expr) => ProvableExpr::<C>::num_post_result_challenges(expr),
+            ProvableExprPlan::Equals(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Inequality(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::TupleEquals(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Divide(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Sign(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::WindowAggregate(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Subtract(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::TopN(expr) => expr.num_post_result_challenges(),
+            ProvableExprPlan::Limit(expr) => expr.num_post_result_challenges(),
         }
     }
 
@@ -139,11 +542,18 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
         match self {
             ProvableExprPlan::Column(expr) => expr.data_type(),
             ProvableExprPlan::Literal(expr) => ProvableExpr::<C>::data_type(expr),
+            ProvableExprPlan::Divide(expr) => expr.data_type(),
+            ProvableExprPlan::Sign(expr) => expr.data_type(),
+            ProvableExprPlan::WindowAggregate(expr) => expr.data_type(),
+            ProvableExprPlan::Subtract(expr) => expr.data_type(),
             ProvableExprPlan::And(_)
             | ProvableExprPlan::Or(_)
             | ProvableExprPlan::Not(_)
             | ProvableExprPlan::Equals(_)
-            | ProvableExprPlan::Inequality(_) => ColumnType::Boolean,
+            | ProvableExprPlan::Inequality(_)
+            | ProvableExprPlan::TupleEquals(_)
+            | ProvableExprPlan::TopN(_)
+            | ProvableExprPlan::Limit(_) => ColumnType::Boolean,
         }
     }
 
@@ -175,6 +585,27 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Inequality(expr) => {
                 ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
+            ProvableExprPlan::TupleEquals(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Divide(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Sign(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::WindowAggregate(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Subtract(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::TopN(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Limit(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
         }
     }
 
@@ -184,7 +615,20 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
         alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        match self {
+        if builder.is_cancelled() {
+            // The proof will be abandoned, so the nodes that remain only need a column of the right shape.
+            return Column::default_with_length(
+                self.data_type(),
+                builder.table_length(),
+                accessor.varchar_hash(),
+                alloc,
+            );
+        }
+        let key = self.shared_subexpression_key();
+        if let Some(column) = key.and_then(|key| builder.get_shared_subexpression(key)) {
+            return column;
+        }
+        let column = builder.record_node(self.node_type(), alloc, |builder| match self {
             ProvableExprPlan::Column(expr) => {
                 ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
@@ -206,7 +650,32 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Inequality(expr) => {
                 ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
+            ProvableExprPlan::TupleEquals(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Divide(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Sign(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::WindowAggregate(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Subtract(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::TopN(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Limit(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+        });
+        if let Some(key) = key {
+            builder.insert_shared_subexpression(key, column.clone());
         }
+        column
     }
 
     fn verifier_evaluate(
@@ -214,7 +683,11 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
         builder: &mut VerificationBuilder<C>,
         accessor: &dyn CommitmentAccessor<C>,
     ) -> Result<C::Scalar, ProofError> {
-        match self {
+        let key = self.shared_subexpression_key();
+        if let Some(eval) = key.and_then(|key| builder.get_shared_subexpression(key)) {
+            return Ok(eval);
+        }
+        let eval = match self {
             ProvableExprPlan::Column(expr) => {
                 ProvableExpr::<C>::verifier_evaluate(expr, builder, accessor)
             }
@@ -224,7 +697,18 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Literal(expr) => expr.verifier_evaluate(builder, accessor),
             ProvableExprPlan::Equals(expr) => expr.verifier_evaluate(builder, accessor),
             ProvableExprPlan::Inequality(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::TupleEquals(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Divide(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Sign(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::WindowAggregate(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Subtract(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::TopN(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Limit(expr) => expr.verifier_evaluate(builder, accessor),
+        }?;
+        if let Some(key) = key {
+            builder.insert_shared_subexpression(key, eval);
         }
+        Ok(eval)
     }
 
     fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
@@ -244,6 +728,23 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Inequality(expr) => {
                 ProvableExpr::<C>::get_column_references(expr, columns)
             }
+            ProvableExprPlan::TupleEquals(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Divide(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Sign(expr) => ProvableExpr::<C>::get_column_references(expr, columns),
+            ProvableExprPlan::WindowAggregate(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Subtract(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::TopN(expr) => ProvableExpr::<C>::get_column_references(expr, columns),
+            ProvableExprPlan::Limit(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
         }
     }
 }
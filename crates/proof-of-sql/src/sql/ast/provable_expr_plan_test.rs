@@ -0,0 +1,280 @@
+use super::{test_utility::*, ProvableExpr, ProvableExprPlan};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+    sql::{
+        parse::QueryExpr,
+        proof::{CountBuilder, ProofCounts, VerifiableQueryResult},
+    },
+};
+use ark_std::test_rng;
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+};
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 1, 3]),
+            bigint("b", [2, 0, 2, 2, 0]),
+            bigint("c", [0, 3, 3, 0, 3]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn counts(expr: &ProvableExprPlan<DoryCommitment>) -> ProofCounts {
+    let mut builder = CountBuilder::new(&[]);
+    expr.count(&mut builder).unwrap();
+    builder.counts().unwrap()
+}
+
+#[test]
+fn a_shared_subexpression_is_only_counted_once() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let t = "sxt.t".parse().unwrap();
+    let filter = |a: i64| -> ProvableExprPlan<DoryCommitment> {
+        or(
+            and(
+                equal(column(t, "a", &accessor), const_bigint(1)),
+                equal(column(t, "b", &accessor), const_bigint(2)),
+            ),
+            and(
+                equal(column(t, "a", &accessor), const_bigint(a)),
+                equal(column(t, "c", &accessor), const_bigint(3)),
+            ),
+        )
+    };
+    let shared = counts(&filter(1));
+    let distinct = counts(&filter(2));
+    // The shared `a = 1` saves the two intermediate MLEs and the two constraints of an equality.
+    assert_eq!(shared.intermediate_mles + 2, distinct.intermediate_mles);
+    assert_eq!(
+        shared.sumcheck_subpolynomials + 2,
+        distinct.sumcheck_subpolynomials
+    );
+    // Its column is only used in the first copy.
+    assert_eq!(shared.anchored_mles + 1, distinct.anchored_mles);
+}
+
+#[test]
+fn a_shared_subexpression_is_found_with_its_operands_in_either_order() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let t = "sxt.t".parse().unwrap();
+    let a = || equal(column(t, "a", &accessor), const_bigint(1));
+    let b = || equal(column(t, "b", &accessor), const_bigint(2));
+    let c = || equal(column(t, "c", &accessor), const_bigint(3));
+    let swapped = counts(&or(and(a(), b()), and(c(), and(b(), a()))));
+    let identical = counts(&or(and(a(), b()), and(c(), and(a(), b()))));
+    let distinct = counts(&or(and(a(), b()), and(c(), and(a(), c()))));
+    assert_eq!(swapped, identical);
+    assert!(swapped.intermediate_mles < distinct.intermediate_mles);
+}
+
+#[test]
+fn we_can_prove_a_query_with_a_shared_subexpression_with_fewer_commitments() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let prove = |sql: &str| {
+        let query = QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &setup,
+        )
+        .unwrap();
+        let num_commitments = verifiable_result.proof.as_ref().unwrap().commitments.len();
+        let table = verifiable_result
+            .verify(query.proof_expr(), &accessor, &(&setup).into())
+            .unwrap()
+            .table;
+        (table, num_commitments)
+    };
+    let (shared_table, shared_commitments) =
+        prove("select a from sxt.t where (a = 1 and b = 2) or (a = 1 and c = 3)");
+    assert_eq!(shared_table, owned_table([bigint("a", [1, 1, 1])]));
+    let (distinct_table, distinct_commitments) =
+        prove("select a from sxt.t where (a = 1 and b = 2) or (a = 2 and c = 3)");
+    assert_eq!(distinct_table, owned_table([bigint("a", [1, 2, 1])]));
+    assert_eq!(shared_commitments + 2, distinct_commitments);
+}
+
+fn hash_of(expr: &ProvableExprPlan<DoryCommitment>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    expr.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn the_operands_of_and_and_or_can_be_in_either_order() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let t = "sxt.t".parse().unwrap();
+    let a = || equal(column(t, "a", &accessor), const_bigint(1));
+    let b = || not(equal(column(t, "b", &accessor), const_bigint(2)));
+    let c = || lte(column(t, "c", &accessor), const_bigint(3));
+
+    assert_eq!(and(a(), b()), and(b(), a()));
+    assert_eq!(hash_of(&and(a(), b())), hash_of(&and(b(), a())));
+    assert_eq!(or(a(), and(b(), c())), or(and(c(), b()), a()));
+    assert_eq!(
+        hash_of(&or(a(), and(b(), c()))),
+        hash_of(&or(and(c(), b()), a()))
+    );
+
+    assert_ne!(and(a(), b()), or(a(), b()));
+    assert_ne!(and(a(), b()), and(a(), c()));
+    assert_ne!(and(a(), and(b(), c())), and(and(a(), b()), c()));
+    // Other operators are still order sensitive.
+    assert_ne!(
+        lte::<DoryCommitment>(column(t, "a", &accessor), column(t, "b", &accessor)),
+        lte(column(t, "b", &accessor), column(t, "a", &accessor))
+    );
+}
+
+#[test]
+fn we_can_dedup_plans_in_a_hash_set() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let t = "sxt.t".parse().unwrap();
+    let a = || equal::<DoryCommitment>(column(t, "a", &accessor), const_bigint(1));
+    let b = || equal::<DoryCommitment>(column(t, "b", &accessor), const_bigint(0));
+    let plans = HashSet::from([
+        and(a(), b()),
+        and(b(), a()),
+        or(a(), b()),
+        or(b(), a()),
+        a(),
+    ]);
+    assert_eq!(plans.len(), 3);
+}
+
+#[test]
+fn the_hash_of_a_plan_does_not_depend_on_the_instance() {
+    // The hash only depends on the structure of the plan, so that it is the same in every run.
+    let build = || -> ProvableExprPlan<DoryCommitment> {
+        let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+        let accessor = accessor_with_table(setup);
+        let t = "sxt.t".parse().unwrap();
+        or(
+            equal(column(t, "b", &accessor), const_bigint(2)),
+            not(lte(column(t, "a", &accessor), const_bigint(-1))),
+        )
+    };
+    let (first, second) = (build(), build());
+    assert_eq!(first, second);
+    assert_eq!(hash_of(&first), hash_of(&second));
+}
+
+#[test]
+fn we_can_combine_predicates_into_a_balanced_and_tree() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let t = "sxt.t".parse().unwrap();
+    let predicate = |i: usize| -> ProvableExprPlan<DoryCommitment> {
+        let name = ["a", "b", "c"][i % 3];
+        not(equal(
+            column(t, name, &accessor),
+            const_bigint(i as i64 % 4),
+        ))
+    };
+    let balanced = ProvableExprPlan::try_new_and_all((0..8).map(predicate).collect()).unwrap();
+    assert_eq!(
+        balanced,
+        and(
+            and(
+                and(predicate(0), predicate(1)),
+                and(predicate(2), predicate(3))
+            ),
+            and(
+                and(predicate(4), predicate(5)),
+                and(predicate(6), predicate(7))
+            ),
+        )
+    );
+    let left_deep = (1..8).map(predicate).fold(predicate(0), and);
+
+    let prove_and_verify = |where_clause| {
+        let expr = dense_filter(
+            cols_expr_plan(t, &["a", "b", "c"], &accessor),
+            tab(t),
+            where_clause,
+        );
+        VerifiableQueryResult::<DoryEvaluationProof>::new(&expr, &accessor, &setup)
+            .unwrap()
+            .verify(&expr, &accessor, &(&setup).into())
+            .unwrap()
+            .table
+    };
+    let res = prove_and_verify(balanced);
+    assert_eq!(res, prove_and_verify(left_deep));
+    assert_eq!(
+        res,
+        owned_table([
+            bigint("a", [1, 1]),
+            bigint("b", [2, 2]),
+            bigint("c", [0, 0])
+        ])
+    );
+}
+
+#[test]
+fn we_can_combine_fewer_than_two_predicates() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let t = "sxt.t".parse().unwrap();
+    let predicate = || -> ProvableExprPlan<DoryCommitment> {
+        equal(column(t, "a", &accessor), const_bigint(1))
+    };
+    assert_eq!(
+        ProvableExprPlan::try_new_and_all(vec![predicate()]).unwrap(),
+        predicate()
+    );
+    assert_eq!(
+        ProvableExprPlan::<DoryCommitment>::try_new_and_all(vec![]).unwrap(),
+        const_bool(true)
+    );
+    assert!(
+        ProvableExprPlan::try_new_and_all(vec![predicate(), column(t, "b", &accessor)]).is_err()
+    );
+}
+
+#[test]
+fn we_can_combine_predicates_into_a_balanced_or_tree() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    let t = "sxt.t".parse().unwrap();
+    let predicate = |i: i64| -> ProvableExprPlan<DoryCommitment> {
+        equal(column(t, "a", &accessor), const_bigint(i))
+    };
+    assert_eq!(
+        ProvableExprPlan::try_new_or_any((0..5).map(predicate).collect()).unwrap(),
+        or(
+            or(
+                or(predicate(0), predicate(1)),
+                or(predicate(2), predicate(3))
+            ),
+            predicate(4),
+        )
+    );
+    assert_eq!(
+        ProvableExprPlan::<DoryCommitment>::try_new_or_any(vec![]).unwrap(),
+        const_bool(false)
+    );
+    assert!(
+        ProvableExprPlan::try_new_or_any(vec![predicate(0), column(t, "b", &accessor)]).is_err()
+    );
+}
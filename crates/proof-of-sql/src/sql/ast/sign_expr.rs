@@ -1,11 +1,13 @@
 use super::{
-    is_within_acceptable_range, verify_constant_abs_decomposition,
-    verify_constant_sign_decomposition,
+    count_equals_zero, is_within_acceptable_range, prover_evaluate_equals_zero,
+    result_evaluate_equals_zero, verifier_evaluate_equals_zero, verify_constant_abs_decomposition,
+    verify_constant_sign_decomposition, ProvableExpr, ProvableExprPlan,
 };
 use crate::{
     base::{
         bit::{compute_varying_bit_matrix, BitDistribution},
         commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
         proof::ProofError,
         scalar::Scalar,
     },
@@ -15,6 +17,104 @@ use crate::{
     },
 };
 use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for the signum of a numeric expression, such as `SIGN(balance)`
+///
+/// The result is -1, 0, or 1 as a `SMALLINT`. It is proven as `(1 - is_zero) * (1 - 2 * sign_bit)`,
+/// which is linear since the sign decomposition of zero always has a sign bit of zero.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> Hash for SignExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expr.hash(state);
+    }
+}
+
+impl<C: Commitment> SignExpr<C> {
+    /// Create a new signum expression
+    pub fn new(expr: Box<ProvableExprPlan<C>>) -> Self {
+        Self { expr }
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for SignExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        count_equals_zero(builder);
+        count_sign(builder)
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        self.expr.num_post_result_challenges()
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::SmallInt
+    }
+
+    #[tracing::instrument(name = "SignExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let values = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let is_zero = result_evaluate_equals_zero(table_length, alloc, values);
+        let sign = result_evaluate_sign(table_length, alloc, values);
+        Column::SmallInt(signum(alloc, is_zero, sign))
+    }
+
+    #[tracing::instrument(name = "SignExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let values = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let is_zero = prover_evaluate_equals_zero(builder, alloc, values);
+        let sign = prover_evaluate_sign(builder, alloc, values);
+        Column::SmallInt(signum(alloc, is_zero, sign))
+    }
+
+    #[tracing::instrument(name = "SignExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let is_zero_eval = verifier_evaluate_equals_zero(builder, eval);
+        let sign_eval = verifier_evaluate_sign(builder, eval, one_eval)?;
+
+        // 1 - is_zero - 2 * sign
+        Ok(one_eval - is_zero_eval - C::Scalar::TWO * sign_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+fn signum<'a>(alloc: &'a Bump, is_zero: &[bool], sign: &[bool]) -> &'a [i16] {
+    alloc.alloc_slice_fill_with(is_zero.len(), |i| match (is_zero[i], sign[i]) {
+        (true, _) => 0,
+        (false, true) => -1,
+        (false, false) => 1,
+    })
+}
 
 /// Count the number of components needed to prove a sign decomposition
 pub fn count_sign(builder: &mut CountBuilder) -> Result<(), ProofError> {
@@ -126,12 +226,17 @@ pub fn verifier_evaluate_sign<C: Commitment>(
 
     // handle the special case of the sign bit being constant
     if !dist.has_varying_sign_bit() {
-        return verifier_const_sign_evaluate(&dist, eval, one_eval, &bit_evals);
+        return verifier_const_sign_evaluate(builder, &dist, eval, one_eval, &bit_evals);
     }
 
     // handle the special case of the absolute part being constant
     if dist.num_varying_bits() == 1 {
-        verify_constant_abs_decomposition(&dist, eval, one_eval, bit_evals[0])?;
+        builder.check_constraint(verify_constant_abs_decomposition(
+            &dist,
+            eval,
+            one_eval,
+            bit_evals[0],
+        ))?;
     } else {
         verify_bit_decomposition(builder, eval, &bit_evals, &dist);
     }
@@ -139,17 +244,20 @@ pub fn verifier_evaluate_sign<C: Commitment>(
     Ok(*bit_evals.last().unwrap())
 }
 
-fn verifier_const_sign_evaluate<S: Scalar>(
+fn verifier_const_sign_evaluate<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
     dist: &BitDistribution,
-    eval: S,
-    one_eval: S,
-    bit_evals: &[S],
-) -> Result<S, ProofError> {
-    verify_constant_sign_decomposition(dist, eval, one_eval, bit_evals)?;
+    eval: C::Scalar,
+    one_eval: C::Scalar,
+    bit_evals: &[C::Scalar],
+) -> Result<C::Scalar, ProofError> {
+    builder.check_constraint(verify_constant_sign_decomposition(
+        dist, eval, one_eval, bit_evals,
+    ))?;
     if dist.sign_bit() {
         Ok(one_eval)
     } else {
-        Ok(S::zero())
+        Ok(C::Scalar::ZERO)
     }
 }
 
@@ -1,17 +1,22 @@
-use super::{count_sign, prover_evaluate_sign, verifier_evaluate_sign};
+use super::{count_sign, prover_evaluate_sign, test_utility::*, verifier_evaluate_sign};
 use crate::{
     base::{
         bit::BitDistribution,
-        database::{RecordBatchTestAccessor, TestAccessor},
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            RecordBatchTestAccessor, TestAccessor,
+        },
         polynomial::MultilinearExtension,
         scalar::Curve25519Scalar,
     },
     record_batch,
     sql::{
-        ast::result_evaluate_sign,
+        ast::{result_evaluate_sign, ProvableExpr, ProvableExprPlan},
+        parse::ConversionError,
         proof::{
-            CountBuilder, ProofBuilder, SumcheckMleEvaluations, SumcheckRandomScalars,
-            VerificationBuilder,
+            exercise_verification, CountBuilder, ProofBuilder, SumcheckMleEvaluations,
+            SumcheckRandomScalars, VerifiableQueryResult, VerificationBuilder,
         },
     },
 };
@@ -84,8 +89,16 @@ fn we_can_verify_a_constant_decomposition() {
     );
     let one_eval = sumcheck_evaluations.one_evaluation;
 
-    let mut builder: VerificationBuilder<RistrettoPoint> =
-        VerificationBuilder::new(0, sumcheck_evaluations, &dists, &[], &[], &[], Vec::new());
+    let mut builder: VerificationBuilder<RistrettoPoint> = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        &[],
+        &[],
+        Vec::new(),
+    );
     let data_eval = (&data).evaluate_at_point(&evaluation_point);
     let eval = verifier_evaluate_sign(&mut builder, data_eval, one_eval).unwrap();
     assert_eq!(eval, Curve25519Scalar::zero());
@@ -109,8 +122,16 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
     );
     let one_eval = sumcheck_evaluations.one_evaluation;
 
-    let mut builder: VerificationBuilder<RistrettoPoint> =
-        VerificationBuilder::new(0, sumcheck_evaluations, &dists, &[], &[], &[], Vec::new());
+    let mut builder: VerificationBuilder<RistrettoPoint> = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        &[],
+        &[],
+        Vec::new(),
+    );
     let data_eval = Curve25519Scalar::from(2) * (&data).evaluate_at_point(&evaluation_point);
     assert!(verifier_evaluate_sign(&mut builder, data_eval, one_eval).is_err());
 }
@@ -149,3 +170,89 @@ fn we_can_compute_the_correct_sign_of_scalars_using_result_evaluate_sign_with_va
     let expected_res = [false, true, false, false, true];
     assert_eq!(res, expected_res);
 }
+
+#[test]
+fn we_can_prove_the_sign_of_a_column_with_negative_zero_and_positive_values() {
+    let data = owned_table([bigint("balance", [-5, 0, 7, -1, 3, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(sign(column(t, "balance", &accessor)), "s")],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([smallint("s", [-1_i16, 0, 1, -1, 1, 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_the_sign_of_a_decimal_column_with_a_where_clause() {
+    let data = owned_table([
+        decimal75("a", 10, 2, [-150, 0, 25, 1]),
+        bigint("b", [1, 2, 3, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![
+            aliased_plan(sign(column(t, "a", &accessor)), "s"),
+            col_expr_plan(t, "b", &accessor),
+        ],
+        tab(t),
+        gte(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([smallint("s", [-1_i16, 0, 1]), bigint("b", [1, 2, 3])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_the_sign_of_a_constant_zero_column() {
+    let data = owned_table([int("a", [0, 0, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(sign(column(t, "a", &accessor)), "s")],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([smallint("s", [0_i16, 0, 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_take_the_sign_of_a_non_numeric_expression() {
+    let data = owned_table([varchar("a", ["x"])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_sign(column(t, "a", &accessor)),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_sign_of_a_column_using_result_evaluate() {
+    let data = owned_table([bigint("a", [-3, 0, 12])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let sign_expr: ProvableExprPlan<RistrettoPoint> = sign(column(t, "a", &accessor));
+    assert_eq!(sign_expr.data_type(), ColumnType::SmallInt);
+    let alloc = Bump::new();
+    let res = sign_expr.result_evaluate(3, &alloc, &accessor);
+    let expected_res = Column::SmallInt(&[-1, 0, 1]);
+    assert_eq!(res, expected_res);
+}
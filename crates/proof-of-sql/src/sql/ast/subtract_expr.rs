@@ -0,0 +1,146 @@
+use super::{scale_and_subtract, scale_and_subtract_eval, ProvableExpr, ProvableExprPlan};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        math::decimal::Precision,
+        proof::ProofError,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
+};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for the difference of two numeric expressions, such as `x - AVG(x) OVER ()`
+///
+/// The operands are scaled to the larger of their scales and subtracted, which is linear,
+/// so the evaluation of the difference is the difference of the scaled evaluations
+/// and nothing needs to be committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtractExpr<C: Commitment> {
+    lhs: Box<ProvableExprPlan<C>>,
+    rhs: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> Hash for SubtractExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lhs.hash(state);
+        self.rhs.hash(state);
+    }
+}
+
+impl<C: Commitment> SubtractExpr<C> {
+    /// Create a new subtraction expression
+    pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>) -> Self {
+        Self { lhs, rhs }
+    }
+
+    fn subtract<'a>(
+        &self,
+        alloc: &'a Bump,
+        lhs: Column<'a, C::Scalar>,
+        rhs: Column<'a, C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let ColumnType::Decimal75(precision, scale) = self.data_type() else {
+            unreachable!("a difference is a decimal");
+        };
+        let difference =
+            scale_and_subtract(alloc, lhs, rhs, true).expect("the operands were type checked");
+        Column::Decimal75(precision, scale, difference)
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for SubtractExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.lhs.count(builder)?;
+        self.rhs.count(builder)?;
+        Ok(())
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        self.lhs.num_post_result_challenges() + self.rhs.num_post_result_challenges()
+    }
+
+    fn data_type(&self) -> ColumnType {
+        difference_type(self.lhs.data_type(), self.rhs.data_type())
+            .expect("the operands were type checked")
+    }
+
+    #[tracing::instrument(name = "SubtractExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs = self.lhs.result_evaluate(table_length, alloc, accessor);
+        let rhs = self.rhs.result_evaluate(table_length, alloc, accessor);
+        self.subtract(alloc, lhs, rhs)
+    }
+
+    #[tracing::instrument(name = "SubtractExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs = self.lhs.prover_evaluate(builder, alloc, accessor);
+        let rhs = self.rhs.prover_evaluate(builder, alloc, accessor);
+        self.subtract(alloc, lhs, rhs)
+    }
+
+    #[tracing::instrument(name = "SubtractExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let lhs_eval = self.lhs.verifier_evaluate(builder, accessor)?;
+        let rhs_eval = self.rhs.verifier_evaluate(builder, accessor)?;
+        Ok(scale_and_subtract_eval(
+            lhs_eval,
+            rhs_eval,
+            self.lhs.data_type().scale().unwrap_or(0),
+            self.rhs.data_type().scale().unwrap_or(0),
+        )
+        .expect("the operands were type checked"))
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+}
+
+/// The type of the difference of numeric types, or `None` if either is not numeric or the
+/// difference would not fit in a decimal.
+///
+/// It has the larger of the scales, and one more integral digit than the larger operand.
+pub(crate) fn difference_type(lhs: ColumnType, rhs: ColumnType) -> Option<ColumnType> {
+    if !lhs.is_numeric()
+        || !rhs.is_numeric()
+        || lhs == ColumnType::Scalar
+        || rhs == ColumnType::Scalar
+    {
+        return None;
+    }
+    let (lhs_scale, rhs_scale) = (lhs.scale()?, rhs.scale()?);
+    let scale = lhs_scale.max(rhs_scale);
+    let integral_digits = (lhs.precision_value()? as i16 - lhs_scale as i16)
+        .max(rhs.precision_value()? as i16 - rhs_scale as i16);
+    // Both operands must fit in a decimal once scaled, as in `scale_and_subtract`,
+    // and the precision of the difference is capped at the precision of any decimal.
+    if integral_digits + scale as i16 > 75 {
+        return None;
+    }
+    let precision = (integral_digits + scale as i16 + 1).clamp(1, 75) as u8;
+    Some(ColumnType::Decimal75(
+        Precision::new(precision).ok()?,
+        scale,
+    ))
+}
@@ -47,11 +47,11 @@ impl TestExprNode {
     }
 
     pub fn create_verifiable_result(&self) -> VerifiableQueryResult<InnerProductProof> {
-        VerifiableQueryResult::new(&self.ast, &self.accessor, &())
+        VerifiableQueryResult::new(&self.ast, &self.accessor, &()).unwrap()
     }
 
     pub fn verify_expr(&self) -> RecordBatch {
-        let res = VerifiableQueryResult::new(&self.ast, &self.accessor, &());
+        let res = VerifiableQueryResult::new(&self.ast, &self.accessor, &()).unwrap();
         exercise_verification(&res, &self.ast, &self.accessor, self.table_ref);
         res.verify(&self.ast, &self.accessor, &())
             .unwrap()
@@ -1,10 +1,11 @@
 use super::{
-    ColumnExpr, DenseFilterExpr, FilterExpr, FilterResultExpr, GroupByExpr, ProofPlan,
-    ProvableExprPlan, TableExpr,
+    AliasedProvableExprPlan, ColumnExpr, CountExpr, DenseFilterExpr, FilterExpr, FilterResultExpr,
+    GroupByExpr, ProofPlan, ProvableExprPlan, TableExpr,
 };
 use crate::base::{
     commitment::Commitment,
     database::{ColumnField, ColumnRef, ColumnType, LiteralValue, SchemaAccessor, TableRef},
+    math::decimal::Precision,
 };
 
 pub fn col_ref(tab: TableRef, name: &str, accessor: &impl SchemaAccessor) -> ColumnRef {
@@ -30,6 +31,13 @@ pub fn equal<C: Commitment>(
     ProvableExprPlan::try_new_equals(left, right).unwrap()
 }
 
+pub fn tuple_equal<C: Commitment>(
+    left: Vec<ProvableExprPlan<C>>,
+    right: Vec<ProvableExprPlan<C>>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_tuple_equals(left, right).unwrap()
+}
+
 pub fn lte<C: Commitment>(
     left: ProvableExprPlan<C>,
     right: ProvableExprPlan<C>,
@@ -62,6 +70,38 @@ pub fn or<C: Commitment>(
     ProvableExprPlan::try_new_or(left, right).unwrap()
 }
 
+pub fn div<C: Commitment>(
+    left: ProvableExprPlan<C>,
+    right: ProvableExprPlan<C>,
+    scale: i8,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_divide(left, right, scale, None).unwrap()
+}
+
+pub fn int_div<C: Commitment>(
+    left: ProvableExprPlan<C>,
+    right: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_integer_divide(left, right, None).unwrap()
+}
+
+pub fn sign<C: Commitment>(expr: ProvableExprPlan<C>) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_sign(expr).unwrap()
+}
+
+pub fn top_n<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    selection: ProvableExprPlan<C>,
+    limit: u64,
+    descending: bool,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_top_n(expr, selection, limit, descending).unwrap()
+}
+
+pub fn limit<C: Commitment>(selection: ProvableExprPlan<C>, limit: u64) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_limit(selection, limit).unwrap()
+}
+
 pub fn const_bool<C: Commitment>(val: bool) -> ProvableExprPlan<C> {
     ProvableExprPlan::new_literal(LiteralValue::Boolean(val))
 }
@@ -81,10 +121,26 @@ pub fn const_varchar<C: Commitment>(val: &str) -> ProvableExprPlan<C> {
     )))
 }
 
+pub fn const_binary<C: Commitment>(val: &[u8]) -> ProvableExprPlan<C> {
+    ProvableExprPlan::new_literal(LiteralValue::Binary((val.to_vec(), C::Scalar::from(val))))
+}
+
 pub fn const_scalar<C: Commitment, T: Into<C::Scalar>>(val: T) -> ProvableExprPlan<C> {
     ProvableExprPlan::new_literal(LiteralValue::Scalar(val.into()))
 }
 
+pub fn const_decimal75<C: Commitment, T: Into<C::Scalar>>(
+    precision: u8,
+    scale: i8,
+    val: T,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::new_literal(LiteralValue::Decimal75(
+        Precision::new(precision).unwrap(),
+        scale,
+        val.into(),
+    ))
+}
+
 pub fn tab(tab: TableRef) -> TableExpr {
     TableExpr { table_ref: tab }
 }
@@ -131,8 +187,37 @@ pub fn cols_expr<C: Commitment>(
         .collect()
 }
 
+pub fn aliased_plan<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    alias: &str,
+) -> AliasedProvableExprPlan<C> {
+    AliasedProvableExprPlan {
+        expr,
+        alias: alias.parse().unwrap(),
+    }
+}
+
+pub fn col_expr_plan<C: Commitment>(
+    tab: TableRef,
+    name: &str,
+    accessor: &impl SchemaAccessor,
+) -> AliasedProvableExprPlan<C> {
+    col_expr(tab, name, accessor).into()
+}
+
+pub fn cols_expr_plan<C: Commitment>(
+    tab: TableRef,
+    names: &[&str],
+    accessor: &impl SchemaAccessor,
+) -> Vec<AliasedProvableExprPlan<C>> {
+    names
+        .iter()
+        .map(|name| col_expr_plan(tab, name, accessor))
+        .collect()
+}
+
 pub fn dense_filter<C: Commitment>(
-    results: Vec<ColumnExpr<C>>,
+    results: Vec<AliasedProvableExprPlan<C>>,
     table: TableExpr,
     where_clause: ProvableExprPlan<C>,
 ) -> ProofPlan<C> {
@@ -181,3 +266,35 @@ pub fn group_by<C: Commitment>(
         where_clause,
     ))
 }
+
+pub fn group_by_with_sum_filters<C: Commitment>(
+    group_by_exprs: Vec<ColumnExpr<C>>,
+    sum_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+    count_alias: &str,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+    sum_filters: Vec<Option<ProvableExprPlan<C>>>,
+) -> ProofPlan<C> {
+    ProofPlan::GroupBy(GroupByExpr::new_with_sum_filters(
+        group_by_exprs,
+        sum_expr,
+        count_alias.parse().unwrap(),
+        table,
+        where_clause,
+        sum_filters,
+    ))
+}
+
+pub fn count_all<C: Commitment>(
+    counted_column: ColumnExpr<C>,
+    count_alias: &str,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+) -> ProofPlan<C> {
+    ProofPlan::Count(CountExpr::new(
+        counted_column,
+        count_alias.parse().unwrap(),
+        table,
+        where_clause,
+    ))
+}
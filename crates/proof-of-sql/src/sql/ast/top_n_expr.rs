@@ -0,0 +1,348 @@
+use super::{
+    count_equals_zero, count_or, count_sign, prover_evaluate_equals_zero, prover_evaluate_or,
+    prover_evaluate_sign, result_evaluate_equals_zero, result_evaluate_or, result_evaluate_sign,
+    verifier_evaluate_equals_zero, verifier_evaluate_or, verifier_evaluate_sign, ProvableExpr,
+    ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            scalar_and_i256_conversions::{convert_i256_to_scalar, convert_scalar_to_i256},
+            Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use arrow::datatypes::i256;
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for the selected rows that may be among the first `limit` rows
+/// of the selection sorted by a numeric expression, as for `ORDER BY x DESC LIMIT k`
+///
+/// The prover sends the threshold `t`, which for a descending order is the `limit`-th largest
+/// selected value, or the smallest one if fewer rows are selected. The expression is true for the
+/// selected rows with `x >= t`, which are found with the sign of `d = t - x` as for an inequality.
+/// For an ascending order, `d = x - t` instead. The prover also sends the number of selected
+/// rows `selected = Σ s_i`, the number of kept rows `kept = Σ s_i * (d_i <= 0)` and the number
+/// of those that tie with the threshold `ties = Σ s_i * (d_i == 0)`, each proven like the sum of
+/// a window aggregate. The verifier then checks that
+/// ```ignore
+///     kept - ties < limit
+///     kept >= limit OR kept == selected
+/// ```
+/// So fewer than `limit` rows precede the threshold, and every selected row is kept unless
+/// at least `limit` rows are. No row that is left out precedes a kept row, and every row that
+/// ties with the threshold is kept, so the first `limit` kept rows in the order, with ties in
+/// the order of the table, are the first `limit` selected rows in the same order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopNExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+    selection: Box<ProvableExprPlan<C>>,
+    limit: u64,
+    descending: bool,
+}
+
+impl<C: Commitment> Hash for TopNExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expr.hash(state);
+        self.selection.hash(state);
+        self.limit.hash(state);
+        self.descending.hash(state);
+    }
+}
+
+impl<C: Commitment> TopNExpr<C> {
+    /// Create a new expression for the rows of a boolean selection that may be among the first
+    /// `limit` of them when sorted by a numeric expression
+    pub fn new(
+        expr: Box<ProvableExprPlan<C>>,
+        selection: Box<ProvableExprPlan<C>>,
+        limit: u64,
+        descending: bool,
+    ) -> Self {
+        Self {
+            expr,
+            selection,
+            limit,
+            descending,
+        }
+    }
+
+    /// Compare two values by their position in the order, so that the first one is the least
+    fn cmp_in_order(&self, lhs: &i256, rhs: &i256) -> Ordering {
+        if self.descending {
+            rhs.cmp(lhs)
+        } else {
+            lhs.cmp(rhs)
+        }
+    }
+
+    /// The `limit`-th selected value in the order, or the last one if fewer rows are selected
+    fn threshold(&self, values: &[C::Scalar], selection: &[bool]) -> C::Scalar {
+        let mut selected = values
+            .iter()
+            .zip(selection)
+            .filter(|(_, &s)| s)
+            .map(|(value, _)| convert_scalar_to_i256(value))
+            .collect::<Vec<_>>();
+        let index = usize::try_from(self.limit - 1).unwrap_or(usize::MAX);
+        let threshold = if index < selected.len() {
+            Some(
+                *selected
+                    .select_nth_unstable_by(index, |lhs, rhs| self.cmp_in_order(lhs, rhs))
+                    .1,
+            )
+        } else {
+            selected
+                .into_iter()
+                .max_by(|lhs, rhs| self.cmp_in_order(lhs, rhs))
+        };
+        threshold.map_or(C::Scalar::ZERO, |threshold| {
+            convert_i256_to_scalar(&threshold).expect("the threshold is one of the values")
+        })
+    }
+
+    /// The values and selection as slices, the threshold, and `d`, which is at most zero
+    /// for the values that are not beyond the threshold
+    #[allow(clippy::type_complexity)]
+    fn evaluate<'a>(
+        &self,
+        alloc: &'a Bump,
+        values: Column<'a, C::Scalar>,
+        selection: Column<'a, C::Scalar>,
+    ) -> (&'a [C::Scalar], &'a [bool], C::Scalar, &'a [C::Scalar]) {
+        let values = alloc.alloc_slice_copy(&values.to_scalar_with_scaling(0));
+        let selection = selection.as_boolean().expect("selection is not boolean");
+        let threshold = self.threshold(values, selection);
+        let difference = alloc.alloc_slice_fill_with(values.len(), |i| {
+            if self.descending {
+                threshold - values[i]
+            } else {
+                values[i] - threshold
+            }
+        });
+        (values, selection, threshold, difference)
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for TopNExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        self.selection.count(builder)?;
+        count_equals_zero(builder);
+        count_sign(builder)?;
+        count_or(builder);
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(4);
+        builder.count_window_aggregates(4);
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        self.expr.num_post_result_challenges() + self.selection.num_post_result_challenges()
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "TopNExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let values = self.expr.result_evaluate(table_length, alloc, accessor);
+        let selection = self
+            .selection
+            .result_evaluate(table_length, alloc, accessor);
+        let (_, selection, _, difference) = self.evaluate(alloc, values, selection);
+
+        // d == 0
+        let equals_zero = result_evaluate_equals_zero(table_length, alloc, difference);
+
+        // sign(d) == -1
+        let sign = result_evaluate_sign(table_length, alloc, difference);
+
+        // (d == 0) || (sign(d) == -1)
+        let not_beyond = result_evaluate_or(table_length, alloc, equals_zero, sign);
+
+        // s && ((d == 0) || (sign(d) == -1))
+        Column::Boolean(
+            alloc.alloc_slice_fill_with(table_length, |i| selection[i] && not_beyond[i]),
+        )
+    }
+
+    #[tracing::instrument(name = "TopNExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let values = self.expr.prover_evaluate(builder, alloc, accessor);
+        let selection = self.selection.prover_evaluate(builder, alloc, accessor);
+        let (values, selection, threshold, difference) = self.evaluate(alloc, values, selection);
+        let table_length = values.len();
+        builder.produce_window_aggregate(threshold);
+
+        // d == 0
+        let equals_zero = prover_evaluate_equals_zero(builder, alloc, difference);
+
+        // sign(d) == -1
+        let sign = prover_evaluate_sign(builder, alloc, difference);
+
+        // (d == 0) || (sign(d) == -1)
+        let not_beyond = prover_evaluate_or(builder, alloc, equals_zero, sign);
+
+        // kept
+        let kept: &[bool] =
+            alloc.alloc_slice_fill_with(table_length, |i| selection[i] && not_beyond[i]);
+        builder.produce_intermediate_mle(kept);
+
+        // subpolynomial: kept - s * not_beyond
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(kept)]),
+                (
+                    -C::Scalar::ONE,
+                    vec![Box::new(selection), Box::new(not_beyond)],
+                ),
+            ],
+        );
+
+        let n = C::Scalar::from([table_length as u64, 0, 0, 0]);
+        let ones: &[bool] = alloc.alloc_slice_fill_copy(table_length, true);
+        let count_of = |rows: &[bool]| {
+            C::Scalar::from([rows.iter().filter(|&&row| row).count() as u64, 0, 0, 0])
+        };
+
+        // sum n * s - selected = 0
+        let selected = count_of(selection);
+        builder.produce_window_aggregate(selected);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (n, vec![Box::new(selection)]),
+                (-selected, vec![Box::new(ones)]),
+            ],
+        );
+
+        // sum n * kept - kept = 0
+        let kept_count = count_of(kept);
+        builder.produce_window_aggregate(kept_count);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (n, vec![Box::new(kept)]),
+                (-kept_count, vec![Box::new(ones)]),
+            ],
+        );
+
+        // sum n * s * (d == 0) - ties = 0
+        let ties =
+            count_of(alloc.alloc_slice_fill_with(table_length, |i| selection[i] && equals_zero[i]));
+        builder.produce_window_aggregate(ties);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (n, vec![Box::new(selection), Box::new(equals_zero)]),
+                (-ties, vec![Box::new(ones)]),
+            ],
+        );
+        Column::Boolean(kept)
+    }
+
+    #[tracing::instrument(name = "TopNExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let random_eval = builder.mle_evaluations.random_evaluation;
+        let n = C::Scalar::from([builder.table_length() as u64, 0, 0, 0]);
+        let values_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let selection_eval = self.selection.verifier_evaluate(builder, accessor)?;
+        let threshold = builder.consume_window_aggregate();
+        let difference_eval = if self.descending {
+            threshold * one_eval - values_eval
+        } else {
+            values_eval - threshold * one_eval
+        };
+
+        // d == 0
+        let equals_zero_eval = verifier_evaluate_equals_zero(builder, difference_eval);
+
+        // sign(d) == -1
+        let sign_eval = verifier_evaluate_sign(builder, difference_eval, one_eval)?;
+
+        // (d == 0) || (sign(d) == -1)
+        let not_beyond_eval = verifier_evaluate_or(builder, &equals_zero_eval, &sign_eval);
+
+        // kept
+        let kept_eval = builder.consume_intermediate_mle();
+
+        // subpolynomial: kept - s * not_beyond
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(random_eval * (kept_eval - selection_eval * not_beyond_eval)),
+        );
+
+        // sum n * s - selected = 0, sum n * kept - kept = 0, sum n * s * (d == 0) - ties = 0
+        let mut counts = [C::Scalar::ZERO; 3];
+        for (count, rows_eval) in
+            counts
+                .iter_mut()
+                .zip([selection_eval, kept_eval, selection_eval * equals_zero_eval])
+        {
+            *count = builder.consume_window_aggregate();
+            builder.produce_sumcheck_subpolynomial_evaluation(&(n * rows_eval - *count * one_eval));
+        }
+
+        // Without rows, the constraints hold for any counts.
+        if builder.table_length() == 0 && counts.iter().any(|count| *count != C::Scalar::ZERO) {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the top-n counts of an empty table must be zero",
+            )))?;
+        }
+        let [selected, kept, ties] = counts.map(|count| {
+            TryInto::<i64>::try_into(count)
+                .ok()
+                .and_then(|count| u64::try_from(count).ok())
+        });
+        let (Some(selected), Some(kept), Some(ties)) = (selected, kept, ties) else {
+            return Err(ProofError::ConstraintUnsatisfied(
+                "the top-n counts must be row counts",
+            ));
+        };
+        let preceding = kept
+            .checked_sub(ties)
+            .ok_or(ProofError::ConstraintUnsatisfied(
+                "the top-n ties must be kept",
+            ))?;
+        if preceding >= self.limit || (kept < self.limit && kept != selected) {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "the kept rows must be the first rows in the top-n order",
+            )))?;
+        }
+
+        Ok(kept_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        self.selection.get_column_references(columns);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Scalar,
+    },
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use ark_std::test_rng;
+
+const A: [i64; 12] = [5, 3, 9, -2, 9, 3, 0, 7, 3, -8, 5, 1];
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", A),
+            bigint("b", 0..12),
+            varchar(
+                "name",
+                ["x", "y", "z", "x", "y", "z", "x", "y", "z", "x", "y", "z"],
+            ),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryExpr<DoryCommitment> {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor).unwrap()
+}
+
+/// The verified result of the proof, before the post-proof sort and slice
+fn prove_and_verify(
+    query: &QueryExpr<DoryCommitment>,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+    setup: &DoryProverPublicSetup,
+) -> OwnedTable<DoryScalar> {
+    VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), accessor, setup)
+        .unwrap()
+        .verify(query.proof_expr(), accessor, &setup.into())
+        .unwrap()
+        .table
+}
+
+fn is_top_n(query: &QueryExpr<DoryCommitment>) -> bool {
+    format!("{:?}", query.proof_expr()).contains("TopN")
+}
+
+#[test]
+fn the_first_rows_of_an_order_match_a_reference_partial_sort() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    for (direction, where_clause, limit, offset) in [
+        ("DESC", "", 3, 0),
+        ("DESC", "", 1, 0),
+        ("DESC", "", 4, 2),
+        ("DESC", " WHERE a < 9", 2, 0),
+        ("DESC", " WHERE a < 9", 5, 1),
+        ("ASC", "", 3, 0),
+        ("ASC", " WHERE a > -8", 4, 3),
+        ("DESC", " WHERE b > 8", 10, 0),
+        ("ASC", " WHERE a > 9", 2, 0),
+        ("DESC", "", 12, 0),
+        ("ASC", "", 20, 5),
+    ] {
+        let sql = format!(
+            "SELECT a, b FROM t{where_clause} ORDER BY a {direction} LIMIT {limit} OFFSET {offset}"
+        );
+        let query = plan(&sql, &accessor);
+        assert!(is_top_n(&query), "{sql}");
+        let result: OwnedTable<DoryScalar> = query
+            .result()
+            .transform_results(
+                prove_and_verify(&query, &accessor, &setup)
+                    .try_into()
+                    .unwrap(),
+            )
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let selects = |b: i64| match where_clause {
+            " WHERE a < 9" => A[b as usize] < 9,
+            " WHERE a > -8" => A[b as usize] > -8,
+            " WHERE b > 8" => b > 8,
+            " WHERE a > 9" => A[b as usize] > 9,
+            _ => true,
+        };
+        let mut rows = (0..12).filter(|&b| selects(b)).collect::<Vec<_>>();
+        rows.sort_by_key(|&b| {
+            if direction == "DESC" {
+                -A[b as usize]
+            } else {
+                A[b as usize]
+            }
+        });
+        let rows = rows
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            owned_table([
+                bigint("a", rows.iter().map(|&b| A[b as usize])),
+                bigint("b", rows),
+            ]),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn the_proven_rows_are_those_not_beyond_the_threshold_with_every_tie() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    // The 4th largest is the 5 of row 10, which ties with row 0.
+    assert_eq!(
+        prove_and_verify(
+            &plan("SELECT a, b FROM t ORDER BY a DESC LIMIT 4", &accessor),
+            &accessor,
+            &setup
+        ),
+        owned_table([bigint("a", [5, 9, 9, 7, 5]), bigint("b", [0, 2, 4, 7, 10])])
+    );
+    // `a > -8` leaves out row 9, so the 3rd smallest is the 1 of row 11.
+    // The proof has the columns of the table, which the alias only renames afterwards.
+    assert_eq!(
+        prove_and_verify(
+            &plan(
+                "SELECT b AS id, a FROM t WHERE a > -8 ORDER BY a, id LIMIT 2 OFFSET 1",
+                &accessor
+            ),
+            &accessor,
+            &setup
+        ),
+        owned_table([bigint("a", [-2, 0, 1]), bigint("b", [3, 6, 11])])
+    );
+}
+
+#[test]
+fn only_limits_after_a_numeric_order_of_independent_rows_are_proven_as_top_n() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    assert!(is_top_n(&plan(
+        "SELECT name, a AS x FROM t ORDER BY x DESC, name LIMIT 2",
+        &accessor
+    )));
+    for sql in [
+        "SELECT a, b FROM t ORDER BY a DESC",
+        "SELECT a, b FROM t LIMIT 3",
+        "SELECT a, b FROM t ORDER BY a DESC OFFSET 2",
+        "SELECT a, b FROM t ORDER BY a DESC LIMIT 0",
+        "SELECT a, b FROM t ORDER BY a DESC LIMIT 2 OFFSET -1",
+        "SELECT a, name FROM t ORDER BY name LIMIT 2",
+        "SELECT a - b AS d FROM t ORDER BY d LIMIT 2",
+        "SELECT a, SELECTED() AS s FROM t WHERE b > 2 ORDER BY a LIMIT 2",
+        "SELECT a, ROW_NUMBER() OVER (ORDER BY b) AS rn FROM t ORDER BY a LIMIT 2",
+        "SELECT name, MAX(a) AS a FROM t GROUP BY name ORDER BY a LIMIT 2",
+    ] {
+        assert!(!is_top_n(&plan(sql, &accessor)), "{sql}");
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_top_n_proof_with_tampered_counts_or_threshold() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan(
+        "SELECT a, b FROM t WHERE b > 1 ORDER BY a DESC LIMIT 3",
+        &accessor,
+    );
+    let res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    assert_eq!(res.proof.as_ref().unwrap().window_aggregates.len(), 4);
+    for index in 0..4 {
+        for delta in [DoryScalar::ONE, -DoryScalar::ONE] {
+            let mut tampered = res.clone();
+            tampered.proof.as_mut().unwrap().window_aggregates[index] += delta;
+            assert!(tampered
+                .verify(query.proof_expr(), &accessor, &(&setup).into())
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::{
+    count_equals_zero, fold_columns, fold_vals, prover_evaluate_equals_zero,
+    result_evaluate_equals_zero, scale_and_subtract, scale_and_subtract_eval,
+    verifier_evaluate_equals_zero, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
+};
+use bumpalo::Bump;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for a tuple equality such as `(a, b) = (1, 'x')`
+///
+/// Rather than proving each component equality separately and combining them with `AND`,
+/// the differences of the components are folded together with a post-result challenge
+/// and a single equals-zero check is proven on the folded column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TupleEqualsExpr<C: Commitment> {
+    lhs: Vec<ProvableExprPlan<C>>,
+    rhs: Vec<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> Hash for TupleEqualsExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lhs.hash(state);
+        self.rhs.hash(state);
+    }
+}
+
+impl<C: Commitment> TupleEqualsExpr<C> {
+    /// Create a new tuple equals expression
+    ///
+    /// `lhs` and `rhs` must have the same, non-zero length.
+    pub fn new(lhs: Vec<ProvableExprPlan<C>>, rhs: Vec<ProvableExprPlan<C>>) -> Self {
+        assert_eq!(lhs.len(), rhs.len());
+        assert!(!lhs.is_empty());
+        Self { lhs, rhs }
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for TupleEqualsExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        for (lhs, rhs) in self.lhs.iter().zip(&self.rhs) {
+            lhs.count(builder)?;
+            rhs.count(builder)?;
+        }
+        builder.count_post_result_challenges(1);
+        count_equals_zero(builder);
+        Ok(())
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        let components: usize = self
+            .lhs
+            .iter()
+            .chain(&self.rhs)
+            .map(ProvableExpr::<C>::num_post_result_challenges)
+            .sum();
+        components + 1
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "TupleEqualsExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let selection = alloc.alloc_slice_fill_copy(table_length, true);
+        for (lhs, rhs) in self.lhs.iter().zip(&self.rhs) {
+            let lhs_column = lhs.result_evaluate(table_length, alloc, accessor);
+            let rhs_column = rhs.result_evaluate(table_length, alloc, accessor);
+            let diff = scale_and_subtract(alloc, lhs_column, rhs_column, true)
+                .expect("Failed to scale and subtract");
+            let equals = result_evaluate_equals_zero(table_length, alloc, diff);
+            for (s, e) in selection.iter_mut().zip(equals) {
+                *s &= e;
+            }
+        }
+        Column::Boolean(selection)
+    }
+
+    #[tracing::instrument(name = "TupleEqualsExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let diffs = Vec::from_iter(self.lhs.iter().zip(&self.rhs).map(|(lhs, rhs)| {
+            let lhs_column = lhs.prover_evaluate(builder, alloc, accessor);
+            let rhs_column = rhs.prover_evaluate(builder, alloc, accessor);
+            scale_and_subtract(alloc, lhs_column, rhs_column, true)
+                .expect("Failed to scale and subtract")
+        }));
+        let alpha = builder.consume_post_result_challenge();
+
+        // folded = sum (alpha^j * diffs[j])
+        let folded = alloc.alloc_slice_fill_copy(builder.table_length(), Zero::zero());
+        fold_columns(folded, One::one(), alpha, &diffs);
+        Column::Boolean(prover_evaluate_equals_zero(builder, alloc, folded))
+    }
+
+    #[tracing::instrument(name = "TupleEqualsExpr::verifier_evaluate", level = "debug", skip_all)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let diff_evals = self
+            .lhs
+            .iter()
+            .zip(&self.rhs)
+            .map(|(lhs, rhs)| {
+                let lhs_eval = lhs.verifier_evaluate(builder, accessor)?;
+                let rhs_eval = rhs.verifier_evaluate(builder, accessor)?;
+                let lhs_scale = lhs.data_type().scale().unwrap_or(0);
+                let rhs_scale = rhs.data_type().scale().unwrap_or(0);
+                Ok(
+                    scale_and_subtract_eval(lhs_eval, rhs_eval, lhs_scale, rhs_scale)
+                        .expect("Failed to scale and subtract"),
+                )
+            })
+            .collect::<Result<Vec<_>, ProofError>>()?;
+        let alpha = builder.consume_post_result_challenge();
+
+        let folded_eval = fold_vals(alpha, &diff_evals);
+        Ok(verifier_evaluate_equals_zero(builder, folded_eval))
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        for expr in self.lhs.iter().chain(&self.rhs) {
+            expr.get_column_references(columns);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use super::{test_utility::*, ProvableExpr};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, Column, ColumnType, OwnedTableTestAccessor, TestAccessor,
+        },
+    },
+    sql::{
+        ast::ProvableExprPlan,
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+#[test]
+fn we_can_prove_a_tuple_equality_query_and_it_matches_the_equivalent_and_query() {
+    let data = owned_table([
+        bigint("a", [1, 1, 2, 1, 3]),
+        varchar("b", ["x", "y", "x", "x", "x"]),
+        bigint("c", [10, 20, 30, 40, 50]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+
+    let tuple_ast = dense_filter(
+        cols_expr_plan(t, &["c"], &accessor),
+        tab(t),
+        tuple_equal(
+            vec![column(t, "a", &accessor), column(t, "b", &accessor)],
+            vec![const_bigint(1), const_varchar("x")],
+        ),
+    );
+    let tuple_res = VerifiableQueryResult::new(&tuple_ast, &accessor, &()).unwrap();
+    exercise_verification(&tuple_res, &tuple_ast, &accessor, t);
+    let tuple_table = tuple_res.verify(&tuple_ast, &accessor, &()).unwrap().table;
+
+    let and_ast = dense_filter(
+        cols_expr_plan(t, &["c"], &accessor),
+        tab(t),
+        and(
+            equal(column(t, "a", &accessor), const_bigint(1)),
+            equal(column(t, "b", &accessor), const_varchar("x")),
+        ),
+    );
+    let and_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&and_ast, &accessor, &()).unwrap();
+    let and_table = and_res.verify(&and_ast, &accessor, &()).unwrap().table;
+
+    assert_eq!(tuple_table, owned_table([bigint("c", [10, 40])]));
+    assert_eq!(tuple_table, and_table);
+
+    // The tuple equality proves a single equals-zero check instead of two, plus the `AND`.
+    let num_tuple_mles = tuple_res.proof.unwrap().commitments.len();
+    let num_and_mles = and_res.proof.unwrap().commitments.len();
+    assert!(num_tuple_mles < num_and_mles);
+}
+
+#[test]
+fn we_can_prove_a_tuple_equality_of_columns_with_different_scales_in_a_sparse_filter() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        decimal75("b", 10, 2, [100, 200, 300, 401]),
+        bigint("c", [0, 2, 3, 4]),
+        bigint("d", [1, 2, 0, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = filter(
+        cols_result(t, &["a"], &accessor),
+        tab(t),
+        tuple_equal(
+            vec![column(t, "a", &accessor), column(t, "c", &accessor)],
+            vec![column(t, "b", &accessor), column(t, "d", &accessor)],
+        ),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [2])]));
+}
+
+#[test]
+fn we_can_prove_a_group_by_with_a_tuple_equality_where_clause() {
+    let data = owned_table([
+        bigint("a", [1, 1, 2, 1, 1]),
+        bigint("b", [5, 5, 5, 6, 5]),
+        bigint("c", [1, 2, 3, 4, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = group_by(
+        cols_expr(t, &["a"], &accessor),
+        sums_expr(t, &["c"], &["sum_c"], &[ColumnType::BigInt], &accessor),
+        "__count__",
+        tab(t),
+        tuple_equal(
+            vec![column(t, "a", &accessor), column(t, "b", &accessor)],
+            vec![const_bigint(1), const_bigint(5)],
+        ),
+    );
+    let res = VerifiableQueryResult::new(&ast, &accessor, &()).unwrap();
+    exercise_verification(&res, &ast, &accessor, t);
+    let res = res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1]),
+        bigint("sum_c", [8]),
+        bigint("__count__", [3]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_tuple_equals_expr_using_result_evaluate() {
+    let data = owned_table([
+        bigint("a", [1, 2, 1, 1]),
+        varchar("b", ["x", "x", "y", "x"]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let tuple_equals_expr: ProvableExprPlan<RistrettoPoint> = tuple_equal(
+        vec![column(t, "a", &accessor), column(t, "b", &accessor)],
+        vec![const_bigint(1), const_varchar("x")],
+    );
+    let alloc = Bump::new();
+    let res = tuple_equals_expr.result_evaluate(4, &alloc, &accessor);
+    let expected_res = Column::Boolean(&[true, false, false, true]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_compare_tuples_of_different_lengths() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, owned_table([bigint("a", [1]), bigint("b", [2])]), 0);
+    let res = ProvableExprPlan::<RistrettoPoint>::try_new_tuple_equals(
+        vec![column(t, "a", &accessor), column(t, "b", &accessor)],
+        vec![const_bigint(1)],
+    );
+    assert!(matches!(res, Err(ConversionError::InvalidExpression(_))));
+}
@@ -0,0 +1,281 @@
+use super::{ProvableExpr, ProvableExprPlan};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            scalar_and_i256_conversions::{convert_i256_to_scalar, convert_scalar_to_i256},
+            Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+        },
+        math::decimal::Precision,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::WindowAggregationOperator;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Provable AST expression for an aggregate over every selected row, such as `AVG(x) OVER ()`,
+/// whose value is repeated for every row of the table
+///
+/// The prover sends the sum `sum = Σ s_i * x_i` of the expression `x` over the rows that the
+/// `selection` `s` selects, and proves it with the zero sum constraint
+/// ```ignore
+///     Σ (n * s_i * x_i - sum) = 0
+/// ```
+/// over the `n` rows of the table. An average also sends the number of selected rows
+/// `count = Σ s_i`, proven the same way, and is `sum / count` truncated toward zero at the
+/// scale of the expression, or zero if no row is selected. Since the verifier computes the
+/// average itself, nothing else needs to be proven about the division.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowAggregateExpr<C: Commitment> {
+    op: WindowAggregationOperator,
+    expr: Box<ProvableExprPlan<C>>,
+    selection: Box<ProvableExprPlan<C>>,
+}
+
+/// The values and selection of a window aggregate, the sum and count of the selected values,
+/// and the resulting column
+struct WindowAggregateEvaluation<'a, S: Scalar> {
+    values: &'a [S],
+    selection: &'a [bool],
+    sum: S,
+    count: S,
+    column: Column<'a, S>,
+}
+
+impl<C: Commitment> Hash for WindowAggregateExpr<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.op.hash(state);
+        self.expr.hash(state);
+        self.selection.hash(state);
+    }
+}
+
+impl<C: Commitment> WindowAggregateExpr<C> {
+    /// Create a new window aggregate of a numeric expression over the rows of a boolean selection
+    pub fn new(
+        op: WindowAggregationOperator,
+        expr: Box<ProvableExprPlan<C>>,
+        selection: Box<ProvableExprPlan<C>>,
+    ) -> Self {
+        Self {
+            op,
+            expr,
+            selection,
+        }
+    }
+
+    /// The number of aggregates that the prover sends: the sum, and the count for an average
+    fn num_aggregates(&self) -> usize {
+        match self.op {
+            WindowAggregationOperator::Sum => 1,
+            WindowAggregationOperator::Avg => 2,
+        }
+    }
+
+    /// The value of the aggregate given the sum and the count of the selected rows
+    fn value(&self, sum: C::Scalar, count: C::Scalar) -> C::Scalar {
+        match self.op {
+            WindowAggregationOperator::Sum => sum,
+            WindowAggregationOperator::Avg => truncated_quotient(sum, count),
+        }
+    }
+
+    /// Compute the sum and the count of the selected rows, and the column of the aggregate
+    fn evaluate<'a>(
+        &self,
+        alloc: &'a Bump,
+        values: Column<'a, C::Scalar>,
+        selection: Column<'a, C::Scalar>,
+    ) -> WindowAggregateEvaluation<'a, C::Scalar> {
+        let values = alloc.alloc_slice_copy(&values.to_scalar_with_scaling(0));
+        let selection = selection.as_boolean().expect("selection is not boolean");
+        let (sum, count) = values.iter().zip(selection).filter(|(_, &s)| s).fold(
+            (C::Scalar::ZERO, C::Scalar::ZERO),
+            |(sum, count), (value, _)| (sum + *value, count + C::Scalar::ONE),
+        );
+        let column = self.column_of(alloc, self.value(sum, count), values.len());
+        WindowAggregateEvaluation {
+            values,
+            selection,
+            sum,
+            count,
+            column,
+        }
+    }
+
+    fn column_of<'a>(
+        &self,
+        alloc: &'a Bump,
+        value: C::Scalar,
+        table_length: usize,
+    ) -> Column<'a, C::Scalar> {
+        let ColumnType::Decimal75(precision, scale) = self.data_type() else {
+            unreachable!("a window aggregate is a decimal");
+        };
+        Column::Decimal75(
+            precision,
+            scale,
+            alloc.alloc_slice_fill_copy(table_length, value),
+        )
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for WindowAggregateExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        self.selection.count(builder)?;
+        builder.count_window_aggregates(self.num_aggregates());
+        builder.count_subpolynomials(self.num_aggregates());
+        builder.count_degree(2);
+        Ok(())
+    }
+
+    fn num_post_result_challenges(&self) -> usize {
+        self.expr.num_post_result_challenges() + self.selection.num_post_result_challenges()
+    }
+
+    fn data_type(&self) -> ColumnType {
+        let expr_type = self.expr.data_type();
+        let scale = expr_type.scale().unwrap_or(0);
+        let precision = match self.op {
+            // A sum may have as many digits as any decimal.
+            WindowAggregationOperator::Sum => Precision::new(75),
+            // An average lies between the smallest and the largest selected value.
+            WindowAggregationOperator::Avg => Precision::new(
+                expr_type
+                    .precision_value()
+                    .expect("a window aggregate is of a numeric expression"),
+            ),
+        }
+        .expect("the precision of a numeric type is valid");
+        ColumnType::Decimal75(precision, scale)
+    }
+
+    #[tracing::instrument(
+        name = "WindowAggregateExpr::result_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let values = self.expr.result_evaluate(table_length, alloc, accessor);
+        let selection = self
+            .selection
+            .result_evaluate(table_length, alloc, accessor);
+        self.evaluate(alloc, values, selection).column
+    }
+
+    #[tracing::instrument(
+        name = "WindowAggregateExpr::prover_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let values = self.expr.prover_evaluate(builder, alloc, accessor);
+        let selection = self.selection.prover_evaluate(builder, alloc, accessor);
+        let WindowAggregateEvaluation {
+            values,
+            selection,
+            sum,
+            count,
+            column,
+        } = self.evaluate(alloc, values, selection);
+        let n = C::Scalar::from([values.len() as u64, 0, 0, 0]);
+        let ones = alloc.alloc_slice_fill_copy(values.len(), true);
+
+        // sum n * s * x - sum = 0
+        builder.produce_window_aggregate(sum);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (n, vec![Box::new(selection), Box::new(values)]),
+                (-sum, vec![Box::new(ones as &[_])]),
+            ],
+        );
+
+        // sum n * s - count = 0
+        if self.op == WindowAggregationOperator::Avg {
+            builder.produce_window_aggregate(count);
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (n, vec![Box::new(selection)]),
+                    (-count, vec![Box::new(ones as &[_])]),
+                ],
+            );
+        }
+        column
+    }
+
+    #[tracing::instrument(
+        name = "WindowAggregateExpr::verifier_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let n = C::Scalar::from([builder.table_length() as u64, 0, 0, 0]);
+        let values_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let selection_eval = self.selection.verifier_evaluate(builder, accessor)?;
+
+        // sum n * s * x - sum = 0
+        let sum = builder.consume_window_aggregate();
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(n * selection_eval * values_eval - sum * one_eval),
+        );
+
+        // sum n * s - count = 0
+        let count = if self.op == WindowAggregationOperator::Avg {
+            let count = builder.consume_window_aggregate();
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &(n * selection_eval - count * one_eval),
+            );
+            count
+        } else {
+            C::Scalar::ZERO
+        };
+
+        // Without rows, the constraints hold for any aggregates.
+        if builder.table_length() == 0 && (sum != C::Scalar::ZERO || count != C::Scalar::ZERO) {
+            builder.check_constraint(Err(ProofError::ConstraintUnsatisfied(
+                "window aggregates of an empty table must be zero",
+            )))?;
+        }
+
+        Ok(self.value(sum, count) * one_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        self.selection.get_column_references(columns);
+    }
+}
+
+/// `numerator / denominator` truncated toward zero, or zero if `denominator` is zero
+fn truncated_quotient<S: Scalar>(numerator: S, denominator: S) -> S {
+    if denominator == S::ZERO {
+        return S::ZERO;
+    }
+    let quotient = convert_scalar_to_i256(&numerator) / convert_scalar_to_i256(&denominator);
+    convert_i256_to_scalar(&quotient).expect("a quotient is no larger than its numerator")
+}
@@ -0,0 +1,187 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Scalar,
+    },
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::{
+        parse::{ConversionError, QueryExpr},
+        proof::VerifiableQueryResult,
+    },
+};
+use ark_std::test_rng;
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            decimal75("x", 6, 2, [100, -250, 300, 450, 1000]),
+            bigint("y", [1, -2, -4, 0, 7]),
+            varchar("name", ["a", "b", "c", "d", "e"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> Result<QueryExpr<DoryCommitment>, ConversionError> {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor)
+}
+
+fn prove_and_verify(sql: &str) -> OwnedTable<DoryScalar> {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan(sql, &accessor).unwrap();
+    VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .unwrap()
+        .table
+}
+
+#[test]
+fn we_can_prove_the_deviation_of_each_row_from_the_mean_of_the_selection() {
+    // mean(1.00, -2.50, 3.00, 4.50) = 1.50
+    assert_eq!(
+        prove_and_verify("SELECT x, x - AVG(x) OVER () AS deviation FROM t WHERE x < 10"),
+        owned_table([
+            decimal75("x", 6, 2, [100, -250, 300, 450]),
+            decimal75("deviation", 7, 2, [-50, -400, 150, 300]),
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_a_sum_over_the_selection_next_to_each_row() {
+    assert_eq!(
+        prove_and_verify("SELECT y, SUM(x) OVER () AS total FROM t WHERE y <> 0"),
+        owned_table([
+            bigint("y", [1, -2, -4, 7]),
+            decimal75("total", 75, 2, [1150; 4]),
+        ])
+    );
+    assert_eq!(
+        prove_and_verify("SELECT SUM(y) OVER () - y AS others FROM t"),
+        owned_table([decimal75("others", 75, 0, [1, 4, 6, 2, -5])])
+    );
+}
+
+#[test]
+fn an_average_is_truncated_toward_zero_at_the_scale_of_the_expression() {
+    // mean(1, -2, -4, 0) = -1.25
+    assert_eq!(
+        prove_and_verify("SELECT y - AVG(y) OVER () AS d FROM t WHERE y < 7"),
+        owned_table([decimal75("d", 20, 0, [2, -1, -3, 1])])
+    );
+}
+
+#[test]
+fn the_aggregates_of_an_empty_selection_are_zero() {
+    assert_eq!(
+        prove_and_verify("SELECT x - AVG(x) OVER () AS d, SUM(y) OVER () AS s FROM t WHERE y > 7"),
+        owned_table([decimal75("d", 7, 2, [0; 0]), decimal75("s", 75, 0, [0; 0])])
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_proof_with_tampered_window_aggregates() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let query = plan(
+        "SELECT x, x - AVG(x) OVER () AS deviation FROM t WHERE x < 10",
+        &accessor,
+    )
+    .unwrap();
+    let res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    assert_eq!(res.proof.as_ref().unwrap().window_aggregates.len(), 2);
+    for index in 0..2 {
+        let mut tampered = res.clone();
+        tampered.proof.as_mut().unwrap().window_aggregates[index] += DoryScalar::ONE;
+        assert!(tampered
+            .verify(query.proof_expr(), &accessor, &(&setup).into())
+            .is_err());
+    }
+    let mut truncated = res.clone();
+    truncated.proof.as_mut().unwrap().window_aggregates.pop();
+    assert!(truncated
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .is_err());
+}
+
+#[test]
+fn we_cannot_plan_window_aggregates_outside_of_an_ungrouped_result() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    for sql in [
+        "SELECT x FROM t WHERE x > AVG(x) OVER ()",
+        "SELECT y, SUM(x) OVER () AS s FROM t GROUP BY y",
+        "SELECT SUM(AVG(x) OVER ()) AS s FROM t",
+        "SELECT AVG(name) OVER () AS a FROM t",
+        "SELECT x + AVG(x) OVER () AS a FROM t",
+        "SELECT SUM(x) OVER () * 2 AS a FROM t",
+    ] {
+        assert!(
+            matches!(
+                plan(sql, &accessor),
+                Err(ConversionError::InvalidExpression(_))
+            ),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_plan_window_aggregates_over_partitions() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    for sql in [
+        "SELECT y, SUM(y) OVER (PARTITION BY name) AS s FROM t",
+        "SELECT y - AVG(y) OVER (PARTITION BY name) AS d FROM t WHERE y > 0",
+        "SELECT SUM(y) OVER (PARTITION BY name, y) - SUM(y) OVER () AS d FROM t",
+    ] {
+        assert!(
+            matches!(
+                plan(sql, &accessor),
+                Err(ConversionError::InvalidExpression(_))
+            ),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_plan_row_numbers_outside_of_an_ungrouped_result_or_with_selected() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup);
+    for sql in [
+        "SELECT y FROM t WHERE y > ROW_NUMBER() OVER (ORDER BY y)",
+        "SELECT y, ROW_NUMBER() OVER (ORDER BY y) AS rn FROM t GROUP BY y",
+        "SELECT SUM(ROW_NUMBER() OVER (ORDER BY y)) AS s FROM t",
+        "SELECT SELECTED() AS s, ROW_NUMBER() OVER (ORDER BY y) AS rn FROM t WHERE y > 0",
+    ] {
+        assert!(
+            matches!(
+                plan(sql, &accessor),
+                Err(ConversionError::InvalidExpression(_))
+            ),
+            "{sql}"
+        );
+    }
+    assert!(matches!(
+        plan(
+            "SELECT ROW_NUMBER() OVER (ORDER BY region) AS rn FROM t",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(..))
+    ));
+}
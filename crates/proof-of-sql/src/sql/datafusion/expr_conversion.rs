@@ -0,0 +1,75 @@
+use datafusion::{
+    logical_expr::{BinaryExpr, Expr, Operator},
+    scalar::ScalarValue,
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression, Literal, UnaryOperator},
+    Identifier,
+};
+
+/// Convert a DataFusion filter into a proof-of-sql expression.
+///
+/// Only columns, non-null boolean, integer and string literals, `NOT`, `IS TRUE`, `IS FALSE`,
+/// `IS UNKNOWN`, the comparisons, `AND`, `OR`, `+`, `-` and `*` are converted. Anything else returns `None`.
+pub fn try_convert_filter(expr: &Expr) -> Option<Expression> {
+    match expr {
+        Expr::Column(column) => Identifier::try_new(&column.name)
+            .ok()
+            .map(Expression::Column),
+        Expr::Literal(value) => try_convert_literal(value).map(Expression::Literal),
+        Expr::Not(expr) => Some(not(try_convert_filter(expr)?)),
+        Expr::IsTrue(expr) => Some(unary(UnaryOperator::IsTrue, try_convert_filter(expr)?)),
+        Expr::IsFalse(expr) => Some(unary(UnaryOperator::IsFalse, try_convert_filter(expr)?)),
+        Expr::IsUnknown(expr) => Some(unary(UnaryOperator::IsUnknown, try_convert_filter(expr)?)),
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            let left = try_convert_filter(left)?;
+            let right = try_convert_filter(right)?;
+            match op {
+                Operator::Eq => Some(binary(BinaryOperator::Equal, left, right)),
+                Operator::NotEq => Some(not(binary(BinaryOperator::Equal, left, right))),
+                Operator::LtEq => Some(binary(BinaryOperator::LessThanOrEqual, left, right)),
+                Operator::Gt => Some(not(binary(BinaryOperator::LessThanOrEqual, left, right))),
+                Operator::GtEq => Some(binary(BinaryOperator::GreaterThanOrEqual, left, right)),
+                Operator::Lt => Some(not(binary(BinaryOperator::GreaterThanOrEqual, left, right))),
+                Operator::And => Some(binary(BinaryOperator::And, left, right)),
+                Operator::Or => Some(binary(BinaryOperator::Or, left, right)),
+                Operator::Plus => Some(binary(BinaryOperator::Add, left, right)),
+                Operator::Minus => Some(binary(BinaryOperator::Subtract, left, right)),
+                Operator::Multiply => Some(binary(BinaryOperator::Multiply, left, right)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn try_convert_literal(value: &ScalarValue) -> Option<Literal> {
+    match value {
+        ScalarValue::Boolean(Some(value)) => Some(Literal::Boolean(*value)),
+        ScalarValue::Int8(Some(value)) => Some(Literal::BigInt((*value).into())),
+        ScalarValue::Int16(Some(value)) => Some(Literal::BigInt((*value).into())),
+        ScalarValue::Int32(Some(value)) => Some(Literal::BigInt((*value).into())),
+        ScalarValue::Int64(Some(value)) => Some(Literal::BigInt(*value)),
+        ScalarValue::Utf8(Some(value)) => Some(Literal::VarChar(value.clone())),
+        _ => None,
+    }
+}
+
+fn binary(op: BinaryOperator, left: Expression, right: Expression) -> Expression {
+    Expression::Binary {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+fn unary(op: UnaryOperator, expr: Expression) -> Expression {
+    Expression::Unary {
+        op,
+        expr: Box::new(expr),
+    }
+}
+
+fn not(expr: Expression) -> Expression {
+    unary(UnaryOperator::Not, expr)
+}
@@ -0,0 +1,65 @@
+use super::expr_conversion::try_convert_filter;
+use datafusion::logical_expr::{col, lit, Expr};
+use proof_of_sql_parser::{
+    intermediate_ast::{Expression, SetExpression},
+    SelectStatement,
+};
+
+fn where_expr(where_clause: &str) -> Expression {
+    let statement: SelectStatement = format!("SELECT * FROM t WHERE {where_clause}")
+        .parse()
+        .unwrap();
+    match *statement.expr {
+        SetExpression::Query { where_expr, .. } => *where_expr.unwrap(),
+    }
+}
+
+#[test]
+fn we_can_convert_the_filters_that_proof_of_sql_can_prove() {
+    for (filter, where_clause) in [
+        (col("a").eq(lit(3i64)), "a = 3"),
+        (col("a").not_eq(lit(3i32)), "a <> 3"),
+        (col("a").lt(lit(3i16)), "a < 3"),
+        (col("a").lt_eq(lit(3i8)), "a <= 3"),
+        (col("a").gt(lit(-3i64)), "a > -3"),
+        (col("a").gt_eq(lit(3i64)), "a >= 3"),
+        (col("name").eq(lit("x")), "name = 'x'"),
+        (
+            col("a").gt(lit(3i64)).and(col("flag").eq(lit(true))),
+            "a > 3 AND flag = true",
+        ),
+        (
+            !col("a").eq(lit(3i64)).or(col("flag")),
+            "NOT (a = 3 OR flag)",
+        ),
+        (
+            col("flag").is_true().and(col("a").eq(lit(1i64)).is_false()),
+            "flag IS TRUE AND a = 1 IS FALSE",
+        ),
+        (col("flag").is_unknown(), "flag IS UNKNOWN"),
+        (
+            (col("a") + col("b") * lit(2i64)).lt(col("a") - lit(1i64)),
+            "a + b * 2 < a - 1",
+        ),
+    ] {
+        assert_eq!(
+            try_convert_filter(&filter),
+            Some(where_expr(where_clause)),
+            "{where_clause}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_convert_filters_that_proof_of_sql_cannot_prove() {
+    for filter in [
+        col("a").is_null(),
+        col("a").eq(Expr::Literal(datafusion::scalar::ScalarValue::Int64(None))),
+        col("x").eq(lit(1.5f64)),
+        (col("a") / lit(2i64)).eq(lit(1i64)),
+        col("name").like(lit("x%")),
+        col("a").gt(lit(3i64)).and(col("a").is_not_null()),
+    ] {
+        assert_eq!(try_convert_filter(&filter), None, "{filter}");
+    }
+}
@@ -0,0 +1,7 @@
+//! This module contains the integration of Proof of SQL with DataFusion.
+mod expr_conversion;
+#[cfg(test)]
+mod expr_conversion_test;
+
+mod provable_table_provider;
+pub use provable_table_provider::{ProvableTableProvider, ProvenScan};
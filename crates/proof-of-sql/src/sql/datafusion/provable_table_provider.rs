@@ -0,0 +1,275 @@
+use super::expr_conversion::try_convert_filter;
+use crate::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{CommitmentAccessor, DataAccessor, SchemaAccessor, TableRef},
+    },
+    sql::{
+        parse::QueryExpr,
+        proof::{ProofExpr, ProverConfig, QueryResult, VerifiableQueryResult},
+    },
+};
+use arrow::{
+    array::ArrayRef,
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use datafusion::{
+    datasource::TableProvider,
+    error::{DataFusionError, Result},
+    execution::context::SessionState,
+    logical_expr::{Expr, TableProviderFilterPushDown, TableType},
+    physical_plan::{memory::MemoryExec, ExecutionPlan},
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, BinaryOperator, Expression, SelectResultExpr, SetExpression,
+        TableExpression,
+    },
+    Identifier, SelectStatement,
+};
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
+
+/// A scan of a [`ProvableTableProvider`] together with the proof of its rows.
+pub struct ProvenScan<CP: CommitmentEvaluationProof> {
+    /// The query that the scan was planned as, which the verifier should check the result against.
+    pub query: QueryExpr<CP::Commitment>,
+    /// The rows of the scan along with the proof that they are the result of `query`.
+    pub result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> ProvenScan<CP> {
+    /// Verify the rows of the scan against commitments to the table.
+    ///
+    /// Note: This does NOT transform the result, so its columns are those of the proof.
+    pub fn verify(
+        &self,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup,
+    ) -> QueryResult<CP::Scalar> {
+        self.result.verify(self.query.proof_expr(), accessor, setup)
+    }
+}
+
+/// A read-only DataFusion [`TableProvider`] over a table of a proof-of-sql accessor.
+///
+/// Each scan is planned as a proof-of-sql query selecting the projected columns, with the filters that
+/// proof-of-sql can prove pushed down as its `WHERE` clause. The scan is proven when DataFusion creates
+/// its physical plan, and the proven scans can be taken with [`Self::take_proven_scans`] to be verified.
+///
+/// Pushed down filters are reported as [`TableProviderFilterPushDown::Inexact`], so DataFusion still
+/// applies them to the rows of the scan. Filters that proof-of-sql cannot prove stay with DataFusion.
+///
+/// Note: the proof is computed synchronously within [`TableProvider::scan`].
+pub struct ProvableTableProvider<CP: CommitmentEvaluationProof, A> {
+    accessor: Mutex<A>,
+    table_ref: TableRef,
+    schema: SchemaRef,
+    setup: CP::ProverPublicSetup,
+    proven_scans: Mutex<Vec<ProvenScan<CP>>>,
+}
+
+impl<CP, A> ProvableTableProvider<CP, A>
+where
+    CP: CommitmentEvaluationProof,
+    A: DataAccessor<CP::Scalar> + SchemaAccessor,
+{
+    /// Create a provider for the table `table_ref` of `accessor`, proving its scans with `setup`.
+    ///
+    /// # Panics
+    /// Panics if the table has a `Scalar` column, which has no Arrow type.
+    pub fn new(accessor: A, table_ref: TableRef, setup: CP::ProverPublicSetup) -> Self {
+        let schema = Arc::new(Schema::new(
+            accessor
+                .lookup_schema(table_ref)
+                .iter()
+                .map(|(name, column_type)| {
+                    Field::new(name.as_str(), DataType::from(column_type), false)
+                })
+                .collect::<Vec<_>>(),
+        ));
+        Self {
+            accessor: Mutex::new(accessor),
+            table_ref,
+            schema,
+            setup,
+            proven_scans: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Take the scans that were proven since the last call, in the order that they were executed.
+    pub fn take_proven_scans(&self) -> Vec<ProvenScan<CP>> {
+        std::mem::take(&mut self.proven_scans.lock().unwrap())
+    }
+
+    /// Plan the scan of `columns` where every filter holds as a proof-of-sql query.
+    fn plan_scan(
+        &self,
+        accessor: &A,
+        columns: &[Identifier],
+        filters: Vec<Expression>,
+    ) -> Result<QueryExpr<CP::Commitment>> {
+        let where_expr = filters
+            .into_iter()
+            .reduce(|left, right| Expression::Binary {
+                op: BinaryOperator::And,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+            .map(Box::new);
+        let ast = SelectStatement {
+            expr: Box::new(SetExpression::Query {
+                result_exprs: columns
+                    .iter()
+                    .map(|&column| {
+                        SelectResultExpr::AliasedResultExpr(AliasedResultExpr::new(
+                            Expression::Column(column),
+                            column,
+                        ))
+                    })
+                    .collect(),
+                from: vec![Box::new(TableExpression::Named {
+                    table: self.table_ref.table_id(),
+                    schema: Some(self.table_ref.schema_id()),
+                    catalog: self.table_ref.catalog_id(),
+                })],
+                where_expr,
+                group_by: vec![],
+            }),
+            order_by: vec![],
+            slice: None,
+        };
+        QueryExpr::try_new(ast, self.table_ref.schema_id(), accessor)
+            .map_err(|err| DataFusionError::Plan(err.to_string()))
+    }
+
+    /// The first column of the table, which is scanned when DataFusion needs only the number of rows.
+    fn first_column(&self) -> Result<Identifier> {
+        let field = self.schema.fields().first().ok_or_else(|| {
+            DataFusionError::Plan(format!("table {} has no columns", self.table_ref))
+        })?;
+        Identifier::try_new(field.name()).map_err(|err| DataFusionError::Plan(err.to_string()))
+    }
+
+    /// Prove the scan and return its rows with the columns of `schema`.
+    fn prove_scan(
+        &self,
+        columns: &[Identifier],
+        filters: &[Expr],
+        schema: &SchemaRef,
+    ) -> Result<RecordBatch> {
+        let accessor = self.accessor.lock().unwrap();
+        let query = self.plan_scan(
+            &accessor,
+            columns,
+            filters.iter().filter_map(try_convert_filter).collect(),
+        )?;
+        let (result, _) = VerifiableQueryResult::<CP>::new_with_config(
+            query.proof_expr(),
+            &*accessor,
+            &self.setup,
+            &ProverConfig::default(),
+        )
+        .map_err(|err| DataFusionError::Execution(err.to_string()))?;
+        let batch = match &result.provable_result {
+            Some(provable_result) => {
+                let table = provable_result
+                    .to_owned_table::<CP::Scalar>(&query.proof_expr().get_column_result_fields())
+                    .map_err(|err| DataFusionError::Execution(err.to_string()))?
+                    .select_columns(columns)
+                    .map_err(|err| DataFusionError::Execution(err.to_string()))?;
+                RecordBatch::try_new(
+                    schema.clone(),
+                    table
+                        .into_inner()
+                        .into_values()
+                        .map(ArrayRef::from)
+                        .collect(),
+                )?
+            }
+            None => RecordBatch::new_empty(schema.clone()),
+        };
+        self.proven_scans
+            .lock()
+            .unwrap()
+            .push(ProvenScan { query, result });
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl<CP, A> TableProvider for ProvableTableProvider<CP, A>
+where
+    CP: CommitmentEvaluationProof + Send + Sync + 'static,
+    CP::ProverPublicSetup: Send + Sync,
+    A: DataAccessor<CP::Scalar> + SchemaAccessor + Send + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn supports_filter_pushdown(&self, filter: &Expr) -> Result<TableProviderFilterPushDown> {
+        let provable = match try_convert_filter(filter) {
+            Some(filter) => self
+                .plan_scan(
+                    &self.accessor.lock().unwrap(),
+                    &[self.first_column()?],
+                    vec![filter],
+                )
+                .is_ok(),
+            None => false,
+        };
+        Ok(if provable {
+            TableProviderFilterPushDown::Inexact
+        } else {
+            TableProviderFilterPushDown::Unsupported
+        })
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let projected_schema = match projection {
+            Some(projection) => Arc::new(self.schema.project(projection)?),
+            None => self.schema.clone(),
+        };
+        let (columns, schema, projection) = if projected_schema.fields().is_empty() {
+            // A proof needs a result column, so the first column is proven and then projected away.
+            let column = self.first_column()?;
+            let schema = Arc::new(self.schema.project(&[0])?);
+            (vec![column], schema, Some(vec![]))
+        } else {
+            let columns = projected_schema
+                .fields()
+                .iter()
+                .map(|field| {
+                    Identifier::try_new(field.name())
+                        .map_err(|err| DataFusionError::Plan(err.to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            (columns, projected_schema, None)
+        };
+        let batch = self.prove_scan(&columns, filters, &schema)?;
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![batch]],
+            schema,
+            projection,
+        )?))
+    }
+}
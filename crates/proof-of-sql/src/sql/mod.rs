@@ -1,5 +1,12 @@
 //! This module contains the main logic for Proof of SQL.
 pub mod ast;
+#[cfg(feature = "datafusion")]
+pub mod datafusion;
 pub mod parse;
 pub mod proof;
 pub mod transform;
+
+mod query_api;
+pub use query_api::{prove_query, verify_query, SerializedProof};
+#[cfg(test)]
+mod query_api_test;
@@ -0,0 +1,21 @@
+use crate::base::database::{ColumnRef, ColumnType, TableRef};
+use proof_of_sql_parser::Identifier;
+use std::collections::BTreeMap;
+
+/// Decides which columns a query may read, such as the columns that a tenant of a service may query.
+///
+/// Queries planned with [`QueryExpr::try_new_with_access_policy`](super::QueryExpr::try_new_with_access_policy)
+/// are checked against the policy before anything is proven, and fail with
+/// [`ConversionError::AccessDenied`](super::ConversionError::AccessDenied) if it denies a column that they read.
+pub trait AccessPolicy {
+    /// Whether the query may read `column`.
+    ///
+    /// `referenced_schema` has every column that the query reads, as returned by
+    /// [`QueryExpr::referenced_schema`](super::QueryExpr::referenced_schema),
+    /// for policies that depend on which columns are read together.
+    fn authorize(
+        &self,
+        column: &ColumnRef,
+        referenced_schema: &BTreeMap<TableRef, Vec<(Identifier, ColumnType)>>,
+    ) -> bool;
+}
@@ -0,0 +1,141 @@
+use super::{AccessPolicy, ConversionError, QueryExpr, QueryExprOptions};
+use crate::{
+    base::database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTableTestAccessor, TableRef,
+        TestAccessor,
+    },
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::Identifier;
+use std::{cell::RefCell, collections::BTreeMap};
+
+type ReferencedSchema = BTreeMap<TableRef, Vec<(Identifier, ColumnType)>>;
+
+/// A policy that denies one column and records the referenced schemas that it is asked about
+struct DenyColumn {
+    denied: Identifier,
+    referenced_schemas: RefCell<Vec<ReferencedSchema>>,
+}
+
+impl DenyColumn {
+    fn new(denied: &str) -> Self {
+        Self {
+            denied: denied.parse().unwrap(),
+            referenced_schemas: RefCell::default(),
+        }
+    }
+}
+
+impl AccessPolicy for DenyColumn {
+    fn authorize(&self, column: &ColumnRef, referenced_schema: &ReferencedSchema) -> bool {
+        self.referenced_schemas
+            .borrow_mut()
+            .push(referenced_schema.clone());
+        column.column_id() != self.denied
+    }
+}
+
+fn accessor_with_table() -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            bigint("salary", [100, 200, 300]),
+            varchar("name", ["x", "y", "z"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    policy: &dyn AccessPolicy,
+) -> Result<QueryExpr<DoryCommitment>, ConversionError> {
+    QueryExpr::try_new_with_access_policy(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor_with_table(),
+        policy,
+    )
+}
+
+#[test]
+fn a_policy_rejects_a_query_that_reads_a_denied_column_and_allows_one_that_does_not() {
+    let policy = DenyColumn::new("salary");
+    let accessor = accessor_with_table();
+    let sql = "SELECT a, name FROM t WHERE a >= 2";
+    assert_eq!(
+        plan(sql, &policy).unwrap(),
+        QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap()
+    );
+    for sql in [
+        "SELECT a, salary FROM t",
+        "SELECT a FROM t WHERE salary >= 200",
+        "SELECT * FROM t",
+        "SELECT a, SUM(salary) AS s FROM t GROUP BY a",
+    ] {
+        assert_eq!(
+            plan(sql, &policy),
+            Err(ConversionError::AccessDenied {
+                column: Box::new(ColumnRef::new(
+                    "sxt.t".parse().unwrap(),
+                    "salary".parse().unwrap(),
+                    ColumnType::BigInt
+                ))
+            }),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn a_policy_is_given_every_column_that_the_query_reads() {
+    let policy = DenyColumn::new("salary");
+    plan("SELECT name FROM t WHERE a = 2", &policy).unwrap();
+    let expected = BTreeMap::from([(
+        "sxt.t".parse().unwrap(),
+        vec![
+            ("a".parse().unwrap(), ColumnType::BigInt),
+            ("name".parse().unwrap(), ColumnType::VarChar),
+        ],
+    )]);
+    assert_eq!(
+        *policy.referenced_schemas.borrow(),
+        [expected.clone(), expected]
+    );
+}
+
+#[test]
+fn a_policy_can_be_combined_with_other_options() {
+    let policy = DenyColumn::new("salary");
+    let accessor = accessor_with_table();
+    let plan_with_options = |sql: &str, options: &QueryExprOptions<DoryCommitment>| {
+        QueryExpr::try_new_with_options(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+            options,
+        )
+    };
+    let options = QueryExprOptions::default()
+        .with_access_policy(&policy)
+        .with_strict(true)
+        .with_max_depth(2);
+    assert!(plan_with_options("SELECT a FROM t WHERE a = 2 ORDER BY a LIMIT 1", &options).is_ok());
+    assert_eq!(
+        plan_with_options("SELECT a FROM t LIMIT 1", &options),
+        Err(ConversionError::NonDeterministicOrder)
+    );
+    assert!(matches!(
+        plan_with_options("SELECT salary FROM t", &options),
+        Err(ConversionError::AccessDenied { .. })
+    ));
+    assert_eq!(
+        plan_with_options("SELECT a FROM t WHERE a = 2 OR a = 3", &options),
+        Err(ConversionError::PlanTooDeep { depth: 3, max: 2 })
+    );
+}
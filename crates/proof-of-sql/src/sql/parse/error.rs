@@ -1,4 +1,4 @@
-use crate::base::database::ColumnType;
+use crate::base::database::{ColumnRef, ColumnType};
 use proof_of_sql_parser::{intermediate_decimal::DecimalError, Identifier, ResourceId};
 use thiserror::Error;
 
@@ -62,6 +62,10 @@ pub enum ConversionError {
     /// Decimal precision exceeds the allowed limit
     InvalidPrecision(u8),
 
+    #[error("Decimal scale is not valid: {0}")]
+    /// Decimal scale exceeds the allowed limit
+    InvalidScale(i64),
+
     #[error("Encountered parsing error: {0}")]
     /// General parsing error
     ParseError(String),
@@ -77,6 +81,49 @@ pub enum ConversionError {
     #[error("Invalid decimal format or value: {0}")]
     /// Error when a decimal format or value is incorrect
     InvalidDecimal(String),
+
+    #[error("Decimal value {value} has more digits than its precision of {precision}")]
+    /// A decimal value needs more digits than the precision of its type
+    DecimalValueExceedsPrecision {
+        /// The unscaled decimal value
+        value: String,
+        /// The precision of the decimal type
+        precision: u8,
+    },
+
+    #[error("Query could not be proven: {0}")]
+    /// The query was planned, but creating its proof or its result failed
+    ProofCreationError(String),
+
+    #[error("Query plan has depth {depth}, which exceeds the maximum of {max}")]
+    /// An expression of the query is nested too deeply to be planned and proven
+    PlanTooDeep {
+        /// The depth of the most deeply nested expression
+        depth: usize,
+        /// The maximum depth that was allowed
+        max: usize,
+    },
+
+    #[error("Access to column '{}' of table '{}' is denied", .column.column_id(), .column.table_ref())]
+    /// The access policy does not authorize the query to read a column
+    AccessDenied {
+        /// The column that the query may not read
+        column: Box<ColumnRef>,
+    },
+
+    #[error("View '{0}' is defined in terms of itself")]
+    /// A view refers to itself, either directly or through other views
+    RecursiveView(Box<ResourceId>),
+
+    #[error("Invalid view: {0}")]
+    /// The definition of a view cannot be inlined into the queries that select from it
+    InvalidView(String),
+
+    #[error("LIMIT and OFFSET require an ORDER BY to select the same rows every time")]
+    /// The query has a `LIMIT` or an `OFFSET` but no `ORDER BY`, so which rows it returns depends
+    /// on the order of the table. Only returned when planning with
+    /// [`QueryExprOptions::with_strict`](super::QueryExprOptions::with_strict).
+    NonDeterministicOrder,
 }
 
 impl From<DecimalError> for ConversionError {
@@ -117,6 +164,21 @@ impl ConversionError {
             func.into().to_lowercase()
         ))
     }
+
+    /// Returns a `ConversionError::InvalidExpression` for non-numeric types used in `SIGN`.
+    pub fn non_numeric_expr_in_sign(dtype: ColumnType) -> Self {
+        ConversionError::InvalidExpression(format!(
+            "cannot use expression of type '{}' with numeric function 'sign'",
+            dtype.to_string().to_lowercase()
+        ))
+    }
+
+    /// Returns a `ConversionError::InvalidExpression` for a `NULL` literal outside of a comparison in the where clause.
+    pub fn null_literal() -> Self {
+        ConversionError::InvalidExpression(
+            "null is only supported in comparisons in the where clause".into(),
+        )
+    }
 }
 
 pub type ConversionResult<T> = std::result::Result<T, ConversionError>;
@@ -2,17 +2,28 @@ use super::{where_expr_builder::WhereExprBuilder, ConversionError};
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, LiteralValue, TableRef},
+        database::{ColumnRef, ColumnType, LiteralValue, TableRef},
     },
-    sql::ast::{ColumnExpr, DenseFilterExpr, ProvableExprPlan, TableExpr},
+    sql::ast::{
+        AliasedProvableExprPlan, ColumnExpr, DenseFilterExpr, ProvableExpr, ProvableExprPlan,
+        TableExpr,
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, BinaryOperator, Expression, OrderBy, OrderByDirection, Slice,
+        UnaryOperator,
+    },
+    Identifier,
 };
-use proof_of_sql_parser::{intermediate_ast::Expression, Identifier};
 use std::collections::{HashMap, HashSet};
 
 pub struct FilterExprBuilder<C: Commitment> {
     table_expr: Option<TableExpr>,
     where_expr: Option<ProvableExprPlan<C>>,
-    filter_result_expr_list: Vec<ColumnExpr<C>>,
+    where_clause: Option<Box<Expression>>,
+    keeps_all_rows: bool,
+    filter_result_expr_list: Vec<AliasedProvableExprPlan<C>>,
     column_mapping: HashMap<Identifier, ColumnRef>,
 }
 
@@ -22,6 +33,8 @@ impl<C: Commitment> FilterExprBuilder<C> {
         Self {
             table_expr: None,
             where_expr: None,
+            where_clause: None,
+            keeps_all_rows: false,
             filter_result_expr_list: vec![],
             column_mapping,
         }
@@ -36,7 +49,8 @@ impl<C: Commitment> FilterExprBuilder<C> {
         mut self,
         where_expr: Option<Box<Expression>>,
     ) -> Result<Self, ConversionError> {
-        self.where_expr = WhereExprBuilder::new(&self.column_mapping).build(where_expr)?;
+        self.where_expr = WhereExprBuilder::new(&self.column_mapping).build(where_expr.clone())?;
+        self.where_clause = where_expr;
         Ok(self)
     }
 
@@ -47,18 +61,372 @@ impl<C: Commitment> FilterExprBuilder<C> {
 
         columns.into_iter().for_each(|column| {
             let column = *self.column_mapping.get(&column).unwrap();
-            self.filter_result_expr_list.push(ColumnExpr::new(column));
+            self.filter_result_expr_list
+                .push(ColumnExpr::new(column).into());
         });
 
         self
     }
 
+    /// Add the result expressions, returning the expressions that are left for the post-proof select.
+    ///
+    /// A division of a column or literal by another, where either one is a decimal, is proven
+    /// with its quotient truncated to the larger scale of the two, and only divides the rows that
+    /// the where clause selects. A `SIGN` is always proven.
+    /// In the returned expressions, a proven expression is replaced by a reference to its alias.
+    /// The columns that the remaining expressions reference are added as they are,
+    /// before the proven expressions.
+    ///
+    /// A `SELECTED()` is proven as the where clause itself, and the filter then keeps every row
+    /// of the table so that the result shows which of them match.
+    ///
+    /// An expression with a window aggregate, such as `x - AVG(x) OVER ()`, is always proven,
+    /// since the aggregate is over the rows that the where clause selects rather than over the
+    /// rows of the result. Only columns, literals and subtractions may be combined with it.
+    /// A row number is instead computed after the proof from the proven rows of the result.
+    /// These are exactly the selected rows, so it may not be combined with `SELECTED()`.
+    pub fn add_result_exprs(
+        mut self,
+        aliased_exprs: &[AliasedResultExpr],
+    ) -> Result<(Self, Vec<AliasedResultExpr>), ConversionError> {
+        // A `SELECTED()` keeps every row, which the other result expressions must know up front.
+        self.keeps_all_rows |= aliased_exprs
+            .iter()
+            .any(|aliased_expr| matches!(*aliased_expr.expr, Expression::Selected));
+        let mut columns = HashSet::new();
+        let mut proven_exprs = Vec::new();
+        let mut select_exprs = Vec::with_capacity(aliased_exprs.len());
+        for aliased_expr in aliased_exprs {
+            match self.visit_provable_result_expr(&aliased_expr.expr)? {
+                Some(expr) => {
+                    proven_exprs.push(AliasedProvableExprPlan {
+                        expr,
+                        alias: aliased_expr.alias,
+                    });
+                    select_exprs.push(AliasedResultExpr {
+                        expr: Box::new(Expression::Column(aliased_expr.alias)),
+                        alias: aliased_expr.alias,
+                    });
+                }
+                None => {
+                    add_column_identifiers(&aliased_expr.expr, &mut columns);
+                    select_exprs.push(aliased_expr.clone());
+                }
+            }
+        }
+        if let Some(proven_expr) = proven_exprs
+            .iter()
+            .find(|proven_expr| columns.contains(&proven_expr.alias))
+        {
+            return Err(ConversionError::DuplicateResultAlias(
+                proven_expr.alias.to_string(),
+            ));
+        }
+        if self.keeps_all_rows
+            && select_exprs
+                .iter()
+                .any(|aliased_expr| contains_row_number(&aliased_expr.expr))
+        {
+            return Err(ConversionError::InvalidExpression(
+                "row numbers may not be combined with selected".into(),
+            ));
+        }
+        self = self.add_result_column_set(columns);
+        self.filter_result_expr_list.extend(proven_exprs);
+        Ok((self, select_exprs))
+    }
+
+    /// The number of rows that a `LIMIT` with its `OFFSET` needs from the filter, if the filter
+    /// may restrict its where clause to them.
+    ///
+    /// This requires a positive limit and a non-negative offset, and that the result expressions are
+    /// evaluated row by row, since otherwise every selected row contributes to the result.
+    fn provable_limit(
+        &self,
+        aliased_exprs: &[AliasedResultExpr],
+        slice: &Option<Slice>,
+    ) -> Option<u64> {
+        let slice = slice.as_ref()?;
+        let limit = u64::try_from(slice.offset_value)
+            .ok()
+            .filter(|_| slice.number_rows != u64::MAX && slice.number_rows > 0)
+            .and_then(|offset| slice.number_rows.checked_add(offset))?;
+        (!self.keeps_all_rows
+            && !aliased_exprs.iter().any(|aliased_expr| {
+                contains_aggregation(&aliased_expr.expr) || contains_row_number(&aliased_expr.expr)
+            }))
+        .then_some(limit)
+    }
+
+    /// Restrict the where clause to the rows that may be among the rows that a `LIMIT` keeps.
+    ///
+    /// This applies when the first `ORDER BY` is a numeric column of the table and the result
+    /// expressions are evaluated row by row. The filter then only keeps the selected rows that
+    /// are not beyond the `limit + offset`-th one in the order, and every row that ties with it,
+    /// so the post-proof sort and slice give the same rows as with the whole selection.
+    /// See [`ProvableExprPlan::try_new_top_n`].
+    pub fn add_top_n(
+        mut self,
+        aliased_exprs: &[AliasedResultExpr],
+        order_by: &[OrderBy],
+        slice: &Option<Slice>,
+    ) -> Result<Self, ConversionError> {
+        let Some(first_order_by) = order_by.first() else {
+            return Ok(self);
+        };
+        let Some(limit) = self.provable_limit(aliased_exprs, slice) else {
+            return Ok(self);
+        };
+        let order_by_column = aliased_exprs
+            .iter()
+            .find(|aliased_expr| aliased_expr.alias == first_order_by.expr)
+            .and_then(|aliased_expr| match *aliased_expr.expr {
+                Expression::Column(identifier) => self.column_mapping.get(&identifier).copied(),
+                _ => None,
+            });
+        let Some(order_by_column) = order_by_column.filter(|column| {
+            column.column_type().is_numeric() && *column.column_type() != ColumnType::Scalar
+        }) else {
+            return Ok(self);
+        };
+        let selection = self
+            .where_expr
+            .take()
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        self.where_expr = Some(ProvableExprPlan::try_new_top_n(
+            ProvableExprPlan::Column(ColumnExpr::new(order_by_column)),
+            selection,
+            limit,
+            first_order_by.direction == OrderByDirection::Desc,
+        )?);
+        Ok(self)
+    }
+
+    /// Restrict the where clause to `limit + offset` of the selected rows for a `LIMIT` without an `ORDER BY`.
+    ///
+    /// Without an order, SQL leaves open which rows a `LIMIT` returns. The filter keeps the first
+    /// selected rows in the order of the table, but the proof only shows that the kept rows are
+    /// selected and that there are as many of them as the `LIMIT` asks for, so any such rows verify.
+    /// See [`ProvableExprPlan::try_new_limit`].
+    pub fn add_limit(
+        mut self,
+        aliased_exprs: &[AliasedResultExpr],
+        order_by: &[OrderBy],
+        slice: &Option<Slice>,
+    ) -> Result<Self, ConversionError> {
+        if !order_by.is_empty() {
+            return Ok(self);
+        }
+        let Some(limit) = self.provable_limit(aliased_exprs, slice) else {
+            return Ok(self);
+        };
+        let selection = self
+            .where_expr
+            .take()
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        self.where_expr = Some(ProvableExprPlan::try_new_limit(selection, limit)?);
+        Ok(self)
+    }
+
     pub fn build(self) -> DenseFilterExpr<C> {
         DenseFilterExpr::new(
             self.filter_result_expr_list,
             self.table_expr.expect("Table expr is required"),
             self.where_expr
+                .filter(|_| !self.keeps_all_rows)
                 .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true))),
         )
     }
 }
+
+// Private interface
+impl<C: Commitment> FilterExprBuilder<C> {
+    fn visit_provable_result_expr(
+        &mut self,
+        expr: &Expression,
+    ) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
+        match expr {
+            Expression::Selected => {
+                self.keeps_all_rows = true;
+                Ok(Some(
+                    WhereExprBuilder::new(&self.column_mapping)
+                        .build(self.where_clause.clone())?
+                        .unwrap_or_else(|| {
+                            ProvableExprPlan::new_literal(LiteralValue::Boolean(true))
+                        }),
+                ))
+            }
+            Expression::Unary {
+                op: UnaryOperator::Sign,
+                ..
+            } => WhereExprBuilder::new(&self.column_mapping)
+                .build_expr(expr.clone())
+                .map(Some),
+            _ if contains_row_number(expr) => Ok(None),
+            _ if contains_window_aggregation(expr) => self.visit_window_aggregation(expr).map(Some),
+            _ => self.visit_division(expr),
+        }
+    }
+
+    fn visit_window_aggregation(
+        &self,
+        expr: &Expression,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let builder = WhereExprBuilder::new(&self.column_mapping);
+        match expr {
+            Expression::WindowAggregation { op, expr, .. } => {
+                let selection = WhereExprBuilder::new(&self.column_mapping)
+                    .build(self.where_clause.clone())?
+                    .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+                ProvableExprPlan::try_new_window_aggregate(
+                    *op,
+                    builder.build_expr(*expr.clone())?,
+                    selection,
+                )
+            }
+            Expression::Binary {
+                op: BinaryOperator::Subtract,
+                left,
+                right,
+            } => ProvableExprPlan::try_new_subtract(
+                self.visit_window_aggregation(left)?,
+                self.visit_window_aggregation(right)?,
+            ),
+            Expression::Column(_) | Expression::Literal(_) => builder.build_expr(expr.clone()),
+            _ => Err(ConversionError::InvalidExpression(
+                "only columns, literals and subtractions may be combined with window aggregates"
+                    .into(),
+            )),
+        }
+    }
+
+    fn visit_division(
+        &self,
+        expr: &Expression,
+    ) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
+        let Expression::Binary {
+            op: BinaryOperator::Division | BinaryOperator::IntegerDivision,
+            left,
+            right,
+        } = expr
+        else {
+            return Ok(None);
+        };
+        if !matches!(**left, Expression::Column(_) | Expression::Literal(_))
+            || !matches!(**right, Expression::Column(_) | Expression::Literal(_))
+        {
+            return Ok(None);
+        }
+        let builder = WhereExprBuilder::new(&self.column_mapping);
+        let left = builder.build_expr::<C>(*left.clone())?;
+        let right = builder.build_expr::<C>(*right.clone())?;
+        // Only the selected rows are divided, unless the filter keeps every row.
+        let selection = if self.keeps_all_rows {
+            None
+        } else {
+            builder.build(self.where_clause.clone())?
+        };
+        let is_integer = |dtype: ColumnType| {
+            matches!(
+                dtype,
+                ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt
+            )
+        };
+        // An `Int128` quotient is too large to prove, so it is computed after the proof.
+        if is_integer(left.data_type()) && is_integer(right.data_type()) {
+            return ProvableExprPlan::try_new_integer_divide(left, right, selection).map(Some);
+        }
+        let (left_scale, right_scale) = match (left.data_type(), right.data_type()) {
+            (ColumnType::Decimal75(_, left_scale), right_type) => {
+                (left_scale, right_type.scale().unwrap_or(0))
+            }
+            (left_type, ColumnType::Decimal75(_, right_scale)) => {
+                (left_type.scale().unwrap_or(0), right_scale)
+            }
+            _ => return Ok(None),
+        };
+        ProvableExprPlan::try_new_divide(left, right, left_scale.max(right_scale), selection)
+            .map(Some)
+    }
+}
+
+/// Whether `expr` contains an aggregate over the rows of the result, such as `SUM(x)`
+fn contains_aggregation(expr: &Expression) -> bool {
+    match expr {
+        Expression::Aggregation { .. } => true,
+        Expression::Unary { expr, .. }
+        | Expression::InList { expr, .. }
+        | Expression::WindowAggregation { expr, .. } => contains_aggregation(expr),
+        Expression::Binary { left, right, .. } => {
+            contains_aggregation(left) || contains_aggregation(right)
+        }
+        Expression::Tuple(exprs) => exprs.iter().any(|expr| contains_aggregation(expr)),
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::Wildcard
+        | Expression::Selected
+        | Expression::RowNumber { .. } => false,
+    }
+}
+
+/// Whether `expr` contains an aggregate over the selection, such as `AVG(x) OVER ()`
+fn contains_window_aggregation(expr: &Expression) -> bool {
+    contains_window_function_with(expr, &|expr| {
+        matches!(expr, Expression::WindowAggregation { .. })
+    })
+}
+
+/// Whether `expr` contains a row number, which is computed after the proof
+fn contains_row_number(expr: &Expression) -> bool {
+    contains_window_function_with(expr, &|expr| matches!(expr, Expression::RowNumber { .. }))
+}
+
+/// Whether `expr` contains a window aggregate or row number that satisfies `predicate`
+fn contains_window_function_with(
+    expr: &Expression,
+    predicate: &impl Fn(&Expression) -> bool,
+) -> bool {
+    let contains = |expr: &Expression| contains_window_function_with(expr, predicate);
+    match expr {
+        Expression::WindowAggregation { .. } | Expression::RowNumber { .. } => predicate(expr),
+        Expression::Unary { expr, .. } | Expression::InList { expr, .. } => contains(expr),
+        Expression::Aggregation { expr, filter, .. } => {
+            contains(expr) || filter.as_deref().is_some_and(contains)
+        }
+        Expression::Binary { left, right, .. } => contains(left) || contains(right),
+        Expression::Tuple(exprs) => exprs.iter().any(|expr| contains(expr)),
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::Wildcard
+        | Expression::Selected => false,
+    }
+}
+
+/// Add the columns that `expr` references to `columns`
+fn add_column_identifiers(expr: &Expression, columns: &mut HashSet<Identifier>) {
+    match expr {
+        Expression::Column(identifier) => {
+            columns.insert(*identifier);
+        }
+        Expression::Unary { expr, .. }
+        | Expression::InList { expr, .. }
+        | Expression::WindowAggregation { expr, .. } => add_column_identifiers(expr, columns),
+        Expression::RowNumber { order_by } => {
+            columns.extend(order_by.iter().map(|by_expr| by_expr.expr));
+        }
+        Expression::Aggregation { expr, filter, .. } => {
+            add_column_identifiers(expr, columns);
+            if let Some(filter) = filter {
+                add_column_identifiers(filter, columns);
+            }
+        }
+        Expression::Binary { left, right, .. } => {
+            add_column_identifiers(left, columns);
+            add_column_identifiers(right, columns);
+        }
+        Expression::Tuple(exprs) => exprs
+            .iter()
+            .for_each(|expr| add_column_identifiers(expr, columns)),
+        Expression::Literal(_) | Expression::Wildcard | Expression::Selected => {}
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{ColumnField, CommitmentAccessor},
+    },
+    sql::{
+        ast::{sharp_extreme, ColumnExpr, Extreme, MinMaxExpr, TableExpr},
+        parse::{ConversionResult, QueryContext},
+    },
+};
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, Expression};
+
+/// Plans a query of only the minimums and maximums of integer columns without a where clause, such as
+/// ```ignore
+///     SELECT MAX(price) AS high, MIN(price) AS low FROM trades
+/// ```
+/// so that the extremes are checked against the bounds of the columns rather than proven from their values.
+///
+/// Returns `None` if the query has anything else, a where clause or a group by, or if `column_bounds`
+/// has no [`Bounds::Sharp`](crate::base::commitment::Bounds::Sharp) bounds for one of the columns,
+/// in which case the query is planned like any other.
+pub(crate) fn try_build_min_max<C: Commitment>(
+    context: &QueryContext,
+    column_bounds: &dyn CommitmentAccessor<C>,
+) -> ConversionResult<Option<MinMaxExpr<C>>> {
+    let result_exprs = context.get_aliased_result_exprs()?;
+    if context.get_where_expr().is_some() || !context.get_group_by_exprs().is_empty() {
+        return Ok(None);
+    }
+    let column_mapping = context.get_column_mapping();
+    let mut aggregates = Vec::with_capacity(result_exprs.len());
+    for aliased_expr in result_exprs {
+        let Expression::Aggregation {
+            op: op @ (AggregationOperator::Max | AggregationOperator::Min),
+            expr,
+            filter: None,
+        } = &*aliased_expr.expr
+        else {
+            return Ok(None);
+        };
+        let Expression::Column(ident) = **expr else {
+            return Ok(None);
+        };
+        let extreme = match op {
+            AggregationOperator::Max => Extreme::Max,
+            _ => Extreme::Min,
+        };
+        let Some(column_ref) = column_mapping.get(&ident) else {
+            return Ok(None);
+        };
+        let has_sharp_bounds = column_bounds
+            .get_column_bounds(*column_ref)
+            .and_then(|bounds| sharp_extreme(&bounds, extreme))
+            .is_some();
+        if !has_sharp_bounds {
+            return Ok(None);
+        }
+        aggregates.push((
+            ColumnExpr::new(*column_ref),
+            extreme,
+            ColumnField::new(aliased_expr.alias, *column_ref.column_type()),
+        ));
+    }
+    Ok(Some(MinMaxExpr::new(
+        aggregates,
+        TableExpr {
+            table_ref: *context.get_table_ref(),
+        },
+    )))
+}
@@ -8,7 +8,17 @@ pub(crate) use error::ConversionResult;
 mod query_expr_tests;
 
 mod query_expr;
-pub use query_expr::QueryExpr;
+pub use query_expr::{QueryExpr, QueryExprOptions, DEFAULT_MAX_PLAN_DEPTH};
+
+mod plan_cache;
+pub use plan_cache::PlanCache;
+#[cfg(test)]
+mod plan_cache_tests;
+
+mod view;
+pub use view::{View, ViewRegistry};
+#[cfg(test)]
+mod view_tests;
 
 mod result_expr_builder;
 pub(crate) use result_expr_builder::ResultExprBuilder;
@@ -16,11 +26,31 @@ pub(crate) use result_expr_builder::ResultExprBuilder;
 mod filter_expr_builder;
 pub(crate) use filter_expr_builder::FilterExprBuilder;
 
+mod post_aggregation_builder;
+use post_aggregation_builder::try_build_post_aggregation;
+
+mod min_max_builder;
+use min_max_builder::try_build_min_max;
+
 pub(crate) mod query_context;
 pub(crate) use query_context::QueryContext;
 
 mod query_context_builder;
-pub(crate) use query_context_builder::{type_check_binary_operation, QueryContextBuilder};
+pub(crate) use query_context_builder::QueryContextBuilder;
+
+mod access_policy;
+pub use access_policy::AccessPolicy;
+#[cfg(test)]
+mod access_policy_tests;
+
+mod null_literal;
+use null_literal::fold_null_literals;
+
+mod type_system;
+pub(crate) use type_system::type_check_binary_operation;
+pub use type_system::{DefaultTypeSystem, TypeSystem};
+#[cfg(test)]
+mod type_system_tests;
 
 mod where_expr_builder;
 pub(crate) use where_expr_builder::WhereExprBuilder;
@@ -0,0 +1,225 @@
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, Expression, Literal, UnaryOperator};
+
+/// Removes the `NULL` literals from a where clause, following SQL's three-valued logic.
+///
+/// A comparison with `NULL` is unknown for every row, so nothing is proven for it: it is folded
+/// into boolean literals, and a row is selected only if the where clause is true. For example,
+/// `a = NULL` and `NOT (a = NULL)` select no rows, while `a = NULL OR b = 1` is `b = 1`.
+/// Since such a comparison is almost always a mistake for `IS NULL`, a warning is logged for it.
+///
+/// Other `NULL` literals, such as in the result, are left in place and rejected when the query is planned.
+pub(crate) fn fold_null_literals(where_expr: Expression) -> Expression {
+    match fold(where_expr) {
+        Truth::Known(expr) => expr,
+        Truth::Nullable { is_true, .. } => is_true,
+    }
+}
+
+/// A folded boolean expression
+enum Truth {
+    /// An expression without `NULL`, which is never unknown
+    Known(Expression),
+    /// An expression that may be unknown, which is true when `is_true` is and false when `is_false` is
+    Nullable {
+        is_true: Expression,
+        is_false: Expression,
+    },
+}
+
+impl Truth {
+    fn unknown() -> Self {
+        Self::Nullable {
+            is_true: boolean(false),
+            is_false: boolean(false),
+        }
+    }
+
+    fn is_true(&self) -> Expression {
+        match self {
+            Self::Known(expr) => expr.clone(),
+            Self::Nullable { is_true, .. } => is_true.clone(),
+        }
+    }
+
+    fn is_false(&self) -> Expression {
+        match self {
+            Self::Known(expr) => not(expr.clone()),
+            Self::Nullable { is_false, .. } => is_false.clone(),
+        }
+    }
+}
+
+fn fold(expr: Expression) -> Truth {
+    match expr {
+        Expression::Literal(Literal::Null) => Truth::unknown(),
+        Expression::Unary {
+            op: UnaryOperator::Not,
+            expr,
+        } => match fold(*expr) {
+            Truth::Known(expr) => Truth::Known(not(expr)),
+            Truth::Nullable { is_true, is_false } => Truth::Nullable {
+                is_true: is_false,
+                is_false: is_true,
+            },
+        },
+        Expression::Unary {
+            op: op @ (UnaryOperator::IsTrue | UnaryOperator::IsFalse | UnaryOperator::IsUnknown),
+            expr,
+        } => match fold(*expr) {
+            // The operator is kept on an operand without `NULL`, so that its type is still checked.
+            Truth::Known(expr) => Truth::Known(Expression::Unary {
+                op,
+                expr: Box::new(expr),
+            }),
+            truth => Truth::Known(match op {
+                UnaryOperator::IsTrue => truth.is_true(),
+                UnaryOperator::IsFalse => truth.is_false(),
+                _ => not(or(truth.is_true(), truth.is_false())),
+            }),
+        },
+        Expression::Binary {
+            op: op @ (BinaryOperator::And | BinaryOperator::Or),
+            left,
+            right,
+        } => {
+            let (combine, dual): (fn(_, _) -> _, fn(_, _) -> _) = match op {
+                BinaryOperator::And => (and, or),
+                _ => (or, and),
+            };
+            match (fold(*left), fold(*right)) {
+                (Truth::Known(left), Truth::Known(right)) => Truth::Known(combine(left, right)),
+                (left, right) => Truth::Nullable {
+                    is_true: combine(left.is_true(), right.is_true()),
+                    is_false: dual(left.is_false(), right.is_false()),
+                },
+            }
+        }
+        Expression::Binary {
+            op: BinaryOperator::Equal,
+            left,
+            right,
+        } => match (*left, *right) {
+            // A tuple equality is the conjunction of the equalities of its elements.
+            (Expression::Tuple(left), Expression::Tuple(right))
+                if left.len() == right.len()
+                    && left.iter().chain(&right).any(|element| is_null(element)) =>
+            {
+                fold(
+                    left.into_iter()
+                        .zip(right)
+                        .map(|(left, right)| Expression::Binary {
+                            op: BinaryOperator::Equal,
+                            left,
+                            right,
+                        })
+                        .reduce(|conjunction, equality| Expression::Binary {
+                            op: BinaryOperator::And,
+                            left: Box::new(conjunction),
+                            right: Box::new(equality),
+                        })
+                        .expect("a tuple with a NULL element is not empty"),
+                )
+            }
+            (left, right) if is_null(&left) || is_null(&right) => compare_with_null(),
+            (left, right) => Truth::Known(Expression::Binary {
+                op: BinaryOperator::Equal,
+                left: Box::new(left),
+                right: Box::new(right),
+            }),
+        },
+        Expression::Binary {
+            op: BinaryOperator::GreaterThanOrEqual | BinaryOperator::LessThanOrEqual,
+            left,
+            right,
+        } if is_null(&left) || is_null(&right) => compare_with_null(),
+        Expression::InList { expr, .. } if is_null(&expr) => compare_with_null(),
+        Expression::InList { expr, list } if list.contains(&Literal::Null) => {
+            // `x IN (.., NULL)` is true if `x` is in the rest of the list, and unknown otherwise.
+            let list: Vec<_> = list
+                .into_iter()
+                .filter(|literal| *literal != Literal::Null)
+                .collect();
+            Truth::Nullable {
+                is_true: if list.is_empty() {
+                    boolean(false)
+                } else {
+                    Expression::InList { expr, list }
+                },
+                is_false: boolean(false),
+            }
+        }
+        expr => Truth::Known(expr),
+    }
+}
+
+/// A comparison with `NULL`, which is unknown for every row
+fn compare_with_null() -> Truth {
+    tracing::warn!(
+        "a comparison with NULL is never true, so it selects no rows; use IS NULL instead"
+    );
+    Truth::unknown()
+}
+
+/// Whether the value of `expr` is `NULL` for every row
+fn is_null(expr: &Expression) -> bool {
+    match expr {
+        Expression::Literal(Literal::Null) => true,
+        Expression::Binary {
+            op:
+                BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Division
+                | BinaryOperator::IntegerDivision,
+            left,
+            right,
+        } => is_null(left) || is_null(right),
+        Expression::Unary {
+            op: UnaryOperator::Sign | UnaryOperator::Length,
+            expr,
+        } => is_null(expr),
+        _ => false,
+    }
+}
+
+fn boolean(value: bool) -> Expression {
+    Expression::Literal(Literal::Boolean(value))
+}
+
+fn not(expr: Expression) -> Expression {
+    match expr {
+        Expression::Literal(Literal::Boolean(value)) => boolean(!value),
+        expr => Expression::Unary {
+            op: UnaryOperator::Not,
+            expr: Box::new(expr),
+        },
+    }
+}
+
+fn and(left: Expression, right: Expression) -> Expression {
+    match (left, right) {
+        (Expression::Literal(Literal::Boolean(false)), _)
+        | (_, Expression::Literal(Literal::Boolean(false))) => boolean(false),
+        (Expression::Literal(Literal::Boolean(true)), expr)
+        | (expr, Expression::Literal(Literal::Boolean(true))) => expr,
+        (left, right) => Expression::Binary {
+            op: BinaryOperator::And,
+            left: Box::new(left),
+            right: Box::new(right),
+        },
+    }
+}
+
+fn or(left: Expression, right: Expression) -> Expression {
+    match (left, right) {
+        (Expression::Literal(Literal::Boolean(true)), _)
+        | (_, Expression::Literal(Literal::Boolean(true))) => boolean(true),
+        (Expression::Literal(Literal::Boolean(false)), expr)
+        | (expr, Expression::Literal(Literal::Boolean(false))) => expr,
+        (left, right) => Expression::Binary {
+            op: BinaryOperator::Or,
+            left: Box::new(left),
+            right: Box::new(right),
+        },
+    }
+}
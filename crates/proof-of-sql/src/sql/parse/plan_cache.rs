@@ -0,0 +1,127 @@
+use super::{ConversionError, ConversionResult, QueryExpr};
+use crate::base::{
+    commitment::Commitment,
+    database::{ColumnType, SchemaAccessor, TableRef},
+};
+use proof_of_sql_parser::{Identifier, SelectStatement};
+use std::collections::HashMap;
+
+/// A cache of [`QueryExpr`] plans keyed by normalized SQL text and default schema.
+///
+/// Repeated queries that only differ in whitespace or in the case of keywords and identifiers
+/// reuse the cached plan instead of being parsed and planned again.
+/// Each plan remembers the schemas of the tables that it was planned against,
+/// and is evicted and planned again if any of those schemas has changed.
+pub struct PlanCache<C: Commitment> {
+    plans: HashMap<(String, Identifier), CachedPlan<C>>,
+}
+
+struct CachedPlan<C: Commitment> {
+    schemas: Vec<(TableRef, Vec<(Identifier, ColumnType)>)>,
+    plan: QueryExpr<C>,
+}
+
+impl<C: Commitment> Default for PlanCache<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Commitment> PlanCache<C> {
+    /// Creates an empty plan cache.
+    pub fn new() -> Self {
+        Self {
+            plans: HashMap::new(),
+        }
+    }
+
+    /// Returns the plan for `sql`, parsing and planning it only if no up-to-date plan is cached.
+    pub fn get_or_try_new(
+        &mut self,
+        sql: &str,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<&QueryExpr<C>> {
+        let key = (normalize_sql(sql), default_schema);
+        let is_up_to_date = self.plans.get(&key).is_some_and(|cached| {
+            cached
+                .schemas
+                .iter()
+                .all(|(table_ref, schema)| schema_accessor.lookup_schema(*table_ref) == *schema)
+        });
+        if !is_up_to_date {
+            self.plans.remove(&key);
+            let ast: SelectStatement = sql
+                .parse()
+                .map_err(|e| ConversionError::ParseError(format!("{e}")))?;
+            let schemas = ast
+                .get_table_references(default_schema)
+                .into_iter()
+                .map(|resource_id| {
+                    let table_ref = TableRef::new(resource_id);
+                    (table_ref, schema_accessor.lookup_schema(table_ref))
+                })
+                .collect();
+            let plan = QueryExpr::try_new(ast, default_schema, schema_accessor)?;
+            self.plans.insert(key.clone(), CachedPlan { schemas, plan });
+        }
+        Ok(&self.plans[&key].plan)
+    }
+
+    /// Returns the number of cached plans.
+    pub fn len(&self) -> usize {
+        self.plans.len()
+    }
+
+    /// Returns `true` if no plans are cached.
+    pub fn is_empty(&self) -> bool {
+        self.plans.is_empty()
+    }
+
+    /// Removes all cached plans.
+    pub fn clear(&mut self) {
+        self.plans.clear();
+    }
+}
+
+/// Normalizes `sql` so that equivalent spellings of a query share a cache entry.
+///
+/// Outside of string literals and quoted identifiers, runs of whitespace are collapsed to a single space,
+/// whitespace next to punctuation is removed, and letters are lowercased,
+/// since keywords and unquoted identifiers are case insensitive.
+/// String literals (`'...'`) and quoted identifiers (`"..."`) are kept verbatim.
+pub(crate) fn normalize_sql(sql: &str) -> String {
+    // Quoted spans behave like words here.
+    let is_quote = |c: char| c == '\'' || c == '"';
+    let is_punctuation = |c: char| !(c.is_alphanumeric() || c == '_' || is_quote(c));
+    let mut normalized = String::with_capacity(sql.len());
+    let mut quote = None;
+    let mut pending_space = false;
+    for c in sql.trim().chars() {
+        if let Some(q) = quote {
+            normalized.push(c);
+            if c == q {
+                quote = None;
+            }
+        } else if c.is_whitespace() {
+            pending_space = true;
+        } else {
+            if pending_space {
+                let previous = normalized.chars().last().unwrap_or(' ');
+                // Whitespace is only significant between two words or two punctuation marks,
+                // e.g. `a b` or `> =`.
+                if is_punctuation(previous) == is_punctuation(c) {
+                    normalized.push(' ');
+                }
+                pending_space = false;
+            }
+            if is_quote(c) {
+                normalized.push(c);
+                quote = Some(c);
+            } else {
+                normalized.extend(c.to_lowercase());
+            }
+        }
+    }
+    normalized
+}
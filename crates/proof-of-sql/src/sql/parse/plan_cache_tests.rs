@@ -0,0 +1,129 @@
+use super::{plan_cache::normalize_sql, ConversionError, PlanCache, QueryExpr};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+};
+use ark_std::test_rng;
+
+fn accessor_with_table() -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "z"])]),
+        0,
+    );
+    accessor
+}
+
+#[test]
+fn we_can_normalize_whitespace_and_case_outside_of_string_literals() {
+    assert_eq!(
+        normalize_sql("  SELECT  a ,b\n FROM\tT WHERE b = 'Hello  World'  "),
+        "select a,b from t where b='Hello  World'"
+    );
+    assert_eq!(
+        normalize_sql("select a from t where a >= 1"),
+        normalize_sql("select a from t where a>=1")
+    );
+    assert_eq!(
+        normalize_sql("select a from t where b = 'it''s'"),
+        "select a from t where b='it''s'"
+    );
+    assert_ne!(
+        normalize_sql("select a from t where a > = 1"),
+        normalize_sql("select a from t where a >= 1")
+    );
+}
+
+#[test]
+fn we_keep_quoted_identifiers_verbatim_when_normalizing() {
+    assert_eq!(
+        normalize_sql(r#"SELECT "MyCol" ,  "two  words" FROM t"#),
+        r#"select "MyCol","two  words" from t"#
+    );
+    assert_ne!(
+        normalize_sql(r#"select "A" from t"#),
+        normalize_sql(r#"select "a" from t"#)
+    );
+    assert_eq!(
+        normalize_sql(r#"select a from t where b = 'say "HI"'"#),
+        r#"select a from t where b='say "HI"'"#
+    );
+}
+
+#[test]
+fn equivalent_queries_hit_the_same_cached_plan() {
+    let accessor = accessor_with_table();
+    let mut cache = PlanCache::<DoryCommitment>::new();
+    let default_schema = "sxt".parse().unwrap();
+    let first: *const QueryExpr<DoryCommitment> = cache
+        .get_or_try_new("SELECT a FROM t WHERE a = 2", default_schema, &accessor)
+        .unwrap();
+    let second: *const QueryExpr<DoryCommitment> = cache
+        .get_or_try_new("select a\n  from T where a=2 ", default_schema, &accessor)
+        .unwrap();
+    assert_eq!(first, second);
+    assert_eq!(cache.len(), 1);
+
+    cache
+        .get_or_try_new("select a from t where a = 3", default_schema, &accessor)
+        .unwrap();
+    cache
+        .get_or_try_new("select a from t where b = 'X'", default_schema, &accessor)
+        .unwrap();
+    cache
+        .get_or_try_new("select a from t where b = 'x'", default_schema, &accessor)
+        .unwrap();
+    assert_eq!(cache.len(), 4);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn a_schema_change_evicts_the_cached_plan() {
+    let mut accessor = accessor_with_table();
+    let mut cache = PlanCache::<DoryCommitment>::new();
+    let default_schema = "sxt".parse().unwrap();
+    let query = "select * from t where a = 2";
+    let plan = cache
+        .get_or_try_new(query, default_schema, &accessor)
+        .unwrap();
+    let expected = QueryExpr::try_new(query.parse().unwrap(), default_schema, &accessor).unwrap();
+    assert_eq!(*plan, expected);
+
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            varchar("b", ["x", "y", "z"]),
+            int128("c", [4, 5, 6]),
+        ]),
+        0,
+    );
+    let plan = cache
+        .get_or_try_new(query, default_schema, &accessor)
+        .unwrap();
+    let expected_after_change =
+        QueryExpr::try_new(query.parse().unwrap(), default_schema, &accessor).unwrap();
+    assert_eq!(*plan, expected_after_change);
+    assert_ne!(expected, expected_after_change);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn a_query_that_cannot_be_planned_is_not_cached() {
+    let accessor = accessor_with_table();
+    let mut cache = PlanCache::<DoryCommitment>::new();
+    let default_schema = "sxt".parse().unwrap();
+    assert!(matches!(
+        cache.get_or_try_new("select from t", default_schema, &accessor),
+        Err(ConversionError::ParseError(_))
+    ));
+    assert!(matches!(
+        cache.get_or_try_new("select c from t", default_schema, &accessor),
+        Err(ConversionError::MissingColumn(..))
+    ));
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,275 @@
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{ColumnField, ColumnRef, ColumnType, LiteralValue},
+    },
+    sql::{
+        ast::{ColumnExpr, GroupByExpr, PostAggregationExpr, ProvableExprPlan, TableExpr},
+        parse::{ConversionResult, QueryContext, WhereExprBuilder},
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal,
+    },
+    Identifier,
+};
+use std::collections::HashMap;
+
+/// The name of the count column of the group by, which is not in the result unless it is selected
+const COUNT_ALIAS: &str = "__count__";
+
+/// Plans a group by query with arithmetic on aggregates in its result, such as
+/// ```ignore
+///     SELECT region, SUM(revenue) - SUM(cost) AS profit FROM sales GROUP BY region
+/// ```
+/// so that the arithmetic is proven on each group after the aggregation.
+///
+/// Returns the [`GroupByExpr`] of the group keys, the sums and the count that the result refers to,
+/// followed by the arithmetic, along with the expressions that select the result from its columns.
+/// Returns `None` if the result has no arithmetic, or anything but group keys, sums, counts and
+/// additions, subtractions and multiplications of integers, in which case the query is planned
+/// like any other group by query.
+pub(crate) fn try_build_post_aggregation<C: Commitment>(
+    context: &QueryContext,
+) -> ConversionResult<Option<(GroupByExpr<C>, Vec<AliasedResultExpr>)>> {
+    let group_by_exprs = context.get_group_by_exprs();
+    let result_exprs = context.get_aliased_result_exprs()?;
+    if group_by_exprs.is_empty()
+        || !result_exprs
+            .iter()
+            .any(|aliased_expr| arithmetic_operands(&aliased_expr.expr).is_some())
+    {
+        return Ok(None);
+    }
+    let column_mapping = context.get_column_mapping();
+    let mut builder = PostAggregationBuilder {
+        group_by_exprs,
+        column_mapping: &column_mapping,
+        sums: Vec::new(),
+    };
+    for aliased_expr in result_exprs {
+        builder.visit_sums(&aliased_expr.expr);
+    }
+
+    // The hidden columns of the group by may not shadow the result.
+    let count_alias: Identifier = COUNT_ALIAS.parse().expect("the count alias is valid");
+    let sum_aliases = Vec::from_iter(
+        (0..builder.sums.len()).map(|i| format!("__sum_{i}__").parse::<Identifier>().unwrap()),
+    );
+    if result_exprs.iter().any(|aliased_expr| {
+        aliased_expr.alias == count_alias
+            || sum_aliases.contains(&aliased_expr.alias)
+            || (group_by_exprs.contains(&aliased_expr.alias)
+                && arithmetic_operands(&aliased_expr.expr).is_some())
+    }) {
+        return Ok(None);
+    }
+
+    let mut post_aggregation_exprs = Vec::new();
+    let mut select_exprs = Vec::with_capacity(result_exprs.len());
+    for aliased_expr in result_exprs {
+        let column = match &*aliased_expr.expr {
+            expr if arithmetic_operands(expr).is_some() => {
+                let Some((post_aggregation_expr, column_type)) = builder.visit_operand(expr) else {
+                    return Ok(None);
+                };
+                // The arithmetic is at least as wide as a `BIGINT`, like the count.
+                let column_type = match column_type {
+                    ColumnType::Int128 => ColumnType::Int128,
+                    _ => ColumnType::BigInt,
+                };
+                post_aggregation_exprs.push((
+                    post_aggregation_expr,
+                    ColumnField::new(aliased_expr.alias, column_type),
+                ));
+                aliased_expr.alias
+            }
+            expr => match builder.visit_aggregate(expr) {
+                Some(Aggregate::GroupKey(index)) => group_by_exprs[index],
+                Some(Aggregate::Sum(index)) => sum_aliases[index],
+                Some(Aggregate::Count) => count_alias,
+                None => return Ok(None),
+            },
+        };
+        select_exprs.push(AliasedResultExpr {
+            expr: Box::new(Expression::Column(column)),
+            alias: aliased_expr.alias,
+        });
+    }
+
+    let table = TableExpr {
+        table_ref: *context.get_table_ref(),
+    };
+    let where_clause = WhereExprBuilder::new(&column_mapping)
+        .build(context.get_where_expr().clone())?
+        .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+    let group_by_exprs = Vec::from_iter(
+        group_by_exprs
+            .iter()
+            .map(|ident| ColumnExpr::<C>::new(column_mapping[ident])),
+    );
+    let mut sum_expr = Vec::with_capacity(builder.sums.len());
+    let mut sum_filters = Vec::with_capacity(builder.sums.len());
+    for ((ident, filter), alias) in builder.sums.into_iter().zip(sum_aliases) {
+        // For sums the outgoing ColumnType is the same as the incoming ColumnType
+        let column_ref = column_mapping[&ident];
+        sum_expr.push((
+            ColumnExpr::new(column_ref),
+            ColumnField::new(alias, *column_ref.column_type()),
+        ));
+        sum_filters.push(
+            filter
+                .map(|filter| WhereExprBuilder::new(&column_mapping).build_expr(*filter))
+                .transpose()?,
+        );
+    }
+    Ok(Some((
+        GroupByExpr::new_with_post_aggregation_exprs(
+            group_by_exprs,
+            sum_expr,
+            count_alias,
+            table,
+            where_clause,
+            sum_filters,
+            post_aggregation_exprs,
+        ),
+        select_exprs,
+    )))
+}
+
+/// An aggregated column of a group by
+enum Aggregate {
+    GroupKey(usize),
+    Sum(usize),
+    Count,
+}
+
+struct PostAggregationBuilder<'a> {
+    group_by_exprs: &'a [Identifier],
+    column_mapping: &'a HashMap<Identifier, ColumnRef>,
+    /// The distinct sums of the result, with their filters
+    sums: Vec<(Identifier, Option<Box<Expression>>)>,
+}
+
+impl PostAggregationBuilder<'_> {
+    /// Collects the sums of `expr` and of its arithmetic operands
+    fn visit_sums(&mut self, expr: &Expression) {
+        if let Some((left, right)) = arithmetic_operands(expr) {
+            self.visit_sums(left);
+            self.visit_sums(right);
+        } else if let Some(sum) = as_sum(expr) {
+            if !self.sums.contains(&sum) {
+                self.sums.push(sum);
+            }
+        }
+    }
+
+    /// The aggregated column that `expr` is, if any
+    fn visit_aggregate(&self, expr: &Expression) -> Option<Aggregate> {
+        match expr {
+            Expression::Aggregation {
+                op: AggregationOperator::First,
+                expr,
+                filter: None,
+            } => match **expr {
+                Expression::Column(ident) => self
+                    .group_by_exprs
+                    .iter()
+                    .position(|group_by_expr| *group_by_expr == ident)
+                    .map(Aggregate::GroupKey),
+                _ => None,
+            },
+            Expression::Aggregation {
+                op: AggregationOperator::Count,
+                expr,
+                filter: None,
+            } => matches!(**expr, Expression::Column(_)).then_some(Aggregate::Count),
+            expr => {
+                let sum = as_sum(expr)?;
+                self.sums.iter().position(|s| *s == sum).map(Aggregate::Sum)
+            }
+        }
+    }
+
+    /// Converts an operand of arithmetic on aggregates, which must be an integer aggregated column,
+    /// an integer literal or arithmetic on those, and returns it with its type.
+    fn visit_operand(&self, expr: &Expression) -> Option<(PostAggregationExpr, ColumnType)> {
+        if let Some((left, right)) = arithmetic_operands(expr) {
+            let (left, left_type) = self.visit_operand(left)?;
+            let (right, right_type) = self.visit_operand(right)?;
+            let (left, right) = (Box::new(left), Box::new(right));
+            let post_aggregation_expr = match expr {
+                Expression::Binary {
+                    op: BinaryOperator::Add,
+                    ..
+                } => PostAggregationExpr::Add(left, right),
+                Expression::Binary {
+                    op: BinaryOperator::Subtract,
+                    ..
+                } => PostAggregationExpr::Subtract(left, right),
+                _ => PostAggregationExpr::Multiply(left, right),
+            };
+            let column_type = if left_type == ColumnType::Int128 {
+                left_type
+            } else {
+                right_type
+            };
+            return Some((post_aggregation_expr, column_type));
+        }
+        let (index, column_type) = match (expr, self.visit_aggregate(expr)) {
+            (Expression::Literal(Literal::BigInt(value)), _) => {
+                return Some((
+                    PostAggregationExpr::Literal((*value).into()),
+                    ColumnType::BigInt,
+                ));
+            }
+            (Expression::Literal(Literal::Int128(value)), _) => {
+                return Some((PostAggregationExpr::Literal(*value), ColumnType::Int128));
+            }
+            (_, Some(Aggregate::GroupKey(index))) => (
+                index,
+                *self.column_mapping[&self.group_by_exprs[index]].column_type(),
+            ),
+            (_, Some(Aggregate::Sum(index))) => (
+                self.group_by_exprs.len() + index,
+                *self.column_mapping[&self.sums[index].0].column_type(),
+            ),
+            (_, Some(Aggregate::Count)) => (
+                self.group_by_exprs.len() + self.sums.len(),
+                ColumnType::BigInt,
+            ),
+            (_, None) => return None,
+        };
+        column_type
+            .is_integer()
+            .then_some((PostAggregationExpr::Column(index), column_type))
+    }
+}
+
+/// The operands of `expr` if it is an addition, subtraction or multiplication
+fn arithmetic_operands(expr: &Expression) -> Option<(&Expression, &Expression)> {
+    match expr {
+        Expression::Binary {
+            op: BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply,
+            left,
+            right,
+        } => Some((left, right)),
+        _ => None,
+    }
+}
+
+/// The column and the filter of `expr` if it is the sum of a column
+fn as_sum(expr: &Expression) -> Option<(Identifier, Option<Box<Expression>>)> {
+    match expr {
+        Expression::Aggregation {
+            op: AggregationOperator::Sum,
+            expr,
+            filter,
+        } => match **expr {
+            Expression::Column(ident) => Some((ident, filter.clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
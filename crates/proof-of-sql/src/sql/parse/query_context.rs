@@ -4,7 +4,7 @@ use crate::{
         database::{ColumnField, ColumnRef, ColumnType, LiteralValue, TableRef},
     },
     sql::{
-        ast::{ColumnExpr, GroupByExpr, ProvableExprPlan, TableExpr},
+        ast::{ColumnExpr, CountExpr, GroupByExpr, ProvableExprPlan, TableExpr},
         parse::{ConversionError, ConversionResult, WhereExprBuilder},
     },
 };
@@ -31,6 +31,7 @@ pub struct QueryContext {
     res_aliased_exprs: Vec<AliasedResultExpr>,
     column_mapping: HashMap<Identifier, ColumnRef>,
     first_result_col_out_agg_scope: Option<Identifier>,
+    has_selected_result_expr: bool,
 }
 
 impl QueryContext {
@@ -91,7 +92,7 @@ impl QueryContext {
         Ok(())
     }
 
-    fn is_in_agg_scope(&self) -> bool {
+    pub fn is_in_agg_scope(&self) -> bool {
         self.in_agg_scope
     }
 
@@ -134,6 +135,13 @@ impl QueryContext {
         Ok(())
     }
 
+    /// Add a `SELECTED()` result expression, which unlike the others doesn't reference a column.
+    pub fn push_selected_result_expr(&mut self, expr: AliasedResultExpr) {
+        assert!(&self.has_visited_group_by, "Group by must be visited first");
+        self.has_selected_result_expr = true;
+        self.res_aliased_exprs.push(expr);
+    }
+
     pub fn set_group_by_exprs(&mut self, exprs: Vec<Identifier>) {
         self.group_by_exprs = exprs;
 
@@ -192,6 +200,13 @@ impl QueryContext {
             }
         }
 
+        // The selection indicator has one row per row of the table, so it can't be aggregated
+        if self.agg_counter > 0 && self.has_selected_result_expr {
+            return Err(ConversionError::InvalidExpression(
+                "selected is not supported in an aggregation query".to_string(),
+            ));
+        }
+
         // We cannot have column references outside aggregations when there is no group by expressions
         if self.group_by_exprs.is_empty()
             && self.agg_counter > 0
@@ -244,10 +259,6 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
     type Error = ConversionError;
 
     fn try_from(value: &QueryContext) -> Result<Option<GroupByExpr<C>>, Self::Error> {
-        // Currently if there is no where clause, we can't prove the query
-        if value.where_expr.is_none() {
-            return Ok(None);
-        }
         let where_clause = WhereExprBuilder::new(&value.column_mapping)
             .build(value.where_expr.clone())?
             .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
@@ -292,6 +303,7 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
                 if let Expression::Aggregation {
                     op: AggregationOperator::First,
                     expr,
+                    filter: None,
                 } = (*res.expr).clone()
                 {
                     if let Expression::Column(res_ident) = *expr {
@@ -304,39 +316,42 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
                 }
             });
         // Check sums
-        let sum_expr = sum_expr_columns
-            .iter()
-            .map(|res| {
-                if let Expression::Aggregation {
-                    op: AggregationOperator::Sum,
-                    expr,
-                } = (*res.expr).clone()
-                {
-                    if let Expression::Column(ident) = *expr {
-                        // For sums the outgoing ColumnType is the same as the incoming ColumnType
-                        let column_type = *value
-                            .column_mapping
-                            .get(&ident)
-                            .expect("QueryContext should never allow unknown cols to be in sum")
-                            .column_type();
-                        let res_column_field = ColumnField::new(res.alias, column_type);
-                        let column_expr =
-                            ColumnExpr::new(ColumnRef::new(table.table_ref, ident, column_type));
-                        Some((column_expr, res_column_field))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect::<Option<Vec<(ColumnExpr<C>, ColumnField)>>>();
+        let mut sum_expr = Vec::with_capacity(sum_expr_columns.len());
+        let mut sum_filters = Vec::with_capacity(sum_expr_columns.len());
+        for res in sum_expr_columns {
+            let Expression::Aggregation {
+                op: AggregationOperator::Sum,
+                expr,
+                filter,
+            } = (*res.expr).clone()
+            else {
+                return Ok(None);
+            };
+            let Expression::Column(ident) = *expr else {
+                return Ok(None);
+            };
+            // For sums the outgoing ColumnType is the same as the incoming ColumnType
+            let column_type = *value
+                .column_mapping
+                .get(&ident)
+                .expect("QueryContext should never allow unknown cols to be in sum")
+                .column_type();
+            let res_column_field = ColumnField::new(res.alias, column_type);
+            let column_expr = ColumnExpr::new(ColumnRef::new(table.table_ref, ident, column_type));
+            sum_expr.push((column_expr, res_column_field));
+            sum_filters.push(
+                filter
+                    .map(|filter| WhereExprBuilder::new(&value.column_mapping).build_expr(*filter))
+                    .transpose()?,
+            );
+        }
 
         // Check count(*)
         let count_column = &value.res_aliased_exprs[num_result_columns - 1];
         let count_column_compliant = if let Expression::Aggregation {
             op: AggregationOperator::Count,
             expr,
+            filter: None,
         } = (*count_column.expr).clone()
         {
             //TODO: This is due to a workaround related to polars
@@ -344,12 +359,63 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
         } else {
             false
         };
-        if !group_by_compliance || sum_expr.is_none() || !count_column_compliant {
+        if !group_by_compliance || !count_column_compliant {
             return Ok(None);
         }
-        Ok(Some(GroupByExpr::new(
+        Ok(Some(GroupByExpr::new_with_sum_filters(
             group_by_exprs,
-            sum_expr.expect("the none case was just checked"),
+            sum_expr,
+            count_column.alias,
+            table,
+            where_clause,
+            sum_filters,
+        )))
+    }
+}
+
+/// Converts a `QueryContext` into a `Option<CountExpr>`.
+///
+/// We use Some if the query is a lone `COUNT(*)` without a `GROUP BY` and None if it is not
+/// We error out if the query is wrong
+impl<C: Commitment> TryFrom<&QueryContext> for Option<CountExpr<C>> {
+    type Error = ConversionError;
+
+    fn try_from(value: &QueryContext) -> Result<Option<CountExpr<C>>, Self::Error> {
+        let [count_column] = &value.res_aliased_exprs[..] else {
+            return Ok(None);
+        };
+        if !value.group_by_exprs.is_empty() {
+            return Ok(None);
+        }
+        // `count(*)` has already been replaced with the count of a column, which is the same
+        // since columns have no nulls.
+        let Expression::Aggregation {
+            op: AggregationOperator::Count,
+            expr,
+            filter: None,
+        } = (*count_column.expr).clone()
+        else {
+            return Ok(None);
+        };
+        let Expression::Column(ident) = *expr else {
+            return Ok(None);
+        };
+        let table = value.table.map(|table_ref| TableExpr { table_ref }).ok_or(
+            ConversionError::InvalidExpression("QueryContext has no table_ref".to_owned()),
+        )?;
+        let counted_column = value
+            .column_mapping
+            .get(&ident)
+            .ok_or(ConversionError::MissingColumn(
+                Box::new(ident),
+                Box::new(table.table_ref.resource_id()),
+            ))
+            .map(|column_ref| ColumnExpr::<C>::new(*column_ref))?;
+        let where_clause = WhereExprBuilder::new(&value.column_mapping)
+            .build(value.where_expr.clone())?
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        Ok(Some(CountExpr::new(
+            counted_column,
             count_column.alias,
             table,
             where_clause,
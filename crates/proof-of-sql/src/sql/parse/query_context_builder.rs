@@ -1,15 +1,15 @@
-use super::QueryContext;
+use super::{fold_null_literals, QueryContext, TypeSystem};
 use crate::{
     base::{
         database::{ColumnRef, ColumnType, SchemaAccessor, TableRef},
-        math::decimal::Precision,
+        math::decimal::literal_precision_and_scale,
     },
     sql::parse::{ConversionError, ConversionResult},
 };
 use proof_of_sql_parser::{
     intermediate_ast::{
         AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        SelectResultExpr, Slice, TableExpression, UnaryOperator,
+        SelectResultExpr, Slice, TableExpression, UnaryOperator, WindowAggregationOperator,
     },
     Identifier, ResourceId,
 };
@@ -18,14 +18,16 @@ use std::ops::Deref;
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
     schema_accessor: &'a dyn SchemaAccessor,
+    type_system: &'a dyn TypeSystem,
 }
 
 // Public interface
 impl<'a> QueryContextBuilder<'a> {
-    pub fn new(schema_accessor: &'a dyn SchemaAccessor) -> Self {
+    pub fn new(schema_accessor: &'a dyn SchemaAccessor, type_system: &'a dyn TypeSystem) -> Self {
         Self {
             context: QueryContext::default(),
             schema_accessor,
+            type_system,
         }
     }
 
@@ -33,15 +35,22 @@ impl<'a> QueryContextBuilder<'a> {
     pub fn visit_table_expr(
         mut self,
         table_expr: Vec<Box<TableExpression>>,
+        default_catalog: Option<Identifier>,
         default_schema: Identifier,
     ) -> Self {
         assert_eq!(table_expr.len(), 1);
         match *table_expr[0] {
-            TableExpression::Named { table, schema } => {
-                self.context.set_table_ref(TableRef::new(ResourceId::new(
-                    schema.unwrap_or(default_schema),
-                    table,
-                )));
+            TableExpression::Named {
+                table,
+                schema,
+                catalog,
+            } => {
+                let schema = schema.unwrap_or(default_schema);
+                let resource_id = match catalog.or(default_catalog) {
+                    Some(catalog) => ResourceId::new_with_catalog(catalog, schema, table),
+                    None => ResourceId::new(schema, table),
+                };
+                self.context.set_table_ref(TableRef::new(resource_id));
             }
         }
         self
@@ -49,8 +58,9 @@ impl<'a> QueryContextBuilder<'a> {
 
     pub fn visit_where_expr(
         mut self,
-        mut where_expr: Option<Box<Expression>>,
+        where_expr: Option<Box<Expression>>,
     ) -> ConversionResult<Self> {
+        let mut where_expr = where_expr.map(|where_expr| Box::new(fold_null_literals(*where_expr)));
         if let Some(expr) = where_expr.as_deref_mut() {
             self.visit_expr(expr)?;
         }
@@ -118,6 +128,9 @@ impl<'a> QueryContextBuilder<'a> {
     }
 
     fn visit_aliased_expr(&mut self, mut aliased_expr: AliasedResultExpr) -> ConversionResult<()> {
+        if let Expression::Selected = *aliased_expr.expr {
+            return self.visit_selected_result_expr(aliased_expr);
+        }
         self.visit_expr(aliased_expr.expr.as_mut())?;
         self.context.push_aliased_result_expr(aliased_expr)?;
         Ok(())
@@ -134,10 +147,39 @@ impl<'a> QueryContextBuilder<'a> {
             Expression::Column(_) => self.visit_column_expr(expr),
             Expression::Unary { op, expr } => self.visit_unary_expr(op, expr),
             Expression::Binary { op, left, right } => self.visit_binary_expr(op, left, right),
-            Expression::Aggregation { op, expr } => self.visit_agg_expr(op, expr),
+            Expression::Aggregation { op, expr, filter } => {
+                self.visit_agg_expr(op, expr, filter.as_deref_mut())
+            }
+            Expression::Tuple(_) => Err(ConversionError::InvalidExpression(
+                "a tuple may only be compared for equality with another tuple".into(),
+            )),
+            Expression::InList { expr, list } => self.visit_in_list(expr, list),
+            Expression::Selected => Err(ConversionError::InvalidExpression(
+                "selected is only supported as a result column".into(),
+            )),
+            Expression::WindowAggregation {
+                op,
+                expr,
+                partition_by,
+            } => self.visit_window_agg_expr(op, expr, partition_by),
+            Expression::RowNumber { order_by } => self.visit_row_number_expr(order_by),
         }
     }
 
+    /// Visits a `SELECTED()` result column, which is the where clause evaluated on every row.
+    fn visit_selected_result_expr(
+        &mut self,
+        aliased_expr: AliasedResultExpr,
+    ) -> ConversionResult<()> {
+        if !self.context.get_group_by_exprs().is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "selected is not supported in the result of a group by query".into(),
+            ));
+        }
+        self.context.push_selected_result_expr(aliased_expr);
+        Ok(())
+    }
+
     //TODO: Actually support multicolumn expressions
     fn visit_wildcard_expr(&mut self, expr: &mut Expression) -> ConversionResult<ColumnType> {
         let (col_name, col_type) = match self.context.get_any_result_column_ref() {
@@ -177,9 +219,14 @@ impl<'a> QueryContextBuilder<'a> {
         left: &mut Expression,
         right: &mut Expression,
     ) -> ConversionResult<ColumnType> {
+        if let (BinaryOperator::Equal, Expression::Tuple(left), Expression::Tuple(right)) =
+            (op, &mut *left, &mut *right)
+        {
+            return self.visit_tuple_equality(left, right);
+        }
         let left_dtype = self.visit_expr(left)?;
         let right_dtype = self.visit_expr(right)?;
-        check_dtypes(left_dtype, right_dtype, *op)?;
+        self.check_dtypes(left_dtype, right_dtype, *op)?;
         match op {
             BinaryOperator::And
             | BinaryOperator::Or
@@ -188,18 +235,68 @@ impl<'a> QueryContextBuilder<'a> {
             | BinaryOperator::LessThanOrEqual => Ok(ColumnType::Boolean),
             BinaryOperator::Multiply
             | BinaryOperator::Division
+            | BinaryOperator::IntegerDivision
             | BinaryOperator::Subtract
             | BinaryOperator::Add => Ok(left_dtype),
         }
     }
 
+    fn visit_tuple_equality(
+        &mut self,
+        left: &mut [Box<Expression>],
+        right: &mut [Box<Expression>],
+    ) -> ConversionResult<ColumnType> {
+        if left.len() != right.len() {
+            return Err(ConversionError::InvalidExpression(format!(
+                "cannot compare a tuple of {} elements with a tuple of {} elements",
+                left.len(),
+                right.len()
+            )));
+        }
+        for (left, right) in left.iter_mut().zip(right) {
+            let left_dtype = self.visit_expr(left)?;
+            let right_dtype = self.visit_expr(right)?;
+            self.check_dtypes(left_dtype, right_dtype, BinaryOperator::Equal)?;
+        }
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_in_list(
+        &mut self,
+        expr: &mut Expression,
+        list: &[Literal],
+    ) -> ConversionResult<ColumnType> {
+        // A result is computed after the proof, where `IN` is not available.
+        if self.context.is_in_result_scope() {
+            return Err(ConversionError::InvalidExpression(
+                "in is only supported in the where clause".into(),
+            ));
+        }
+        let dtype = self.visit_expr(expr)?;
+        let list_dtypes = list
+            .iter()
+            .map(|literal| self.visit_literal(literal))
+            .collect::<ConversionResult<Vec<_>>>()?;
+        // Every literal must be comparable with every other literal, so that strings and numbers
+        // can't be mixed, as well as with the expression.
+        for (i, list_dtype) in list_dtypes.iter().enumerate() {
+            for other_dtype in list_dtypes[..i].iter().chain([&dtype]) {
+                self.check_dtypes(*other_dtype, *list_dtype, BinaryOperator::Equal)?;
+            }
+        }
+        Ok(ColumnType::Boolean)
+    }
+
     fn visit_unary_expr(
         &mut self,
         op: &UnaryOperator,
         expr: &mut Expression,
     ) -> ConversionResult<ColumnType> {
         match op {
-            UnaryOperator::Not => {
+            UnaryOperator::Not
+            | UnaryOperator::IsTrue
+            | UnaryOperator::IsFalse
+            | UnaryOperator::IsUnknown => {
                 let dtype = self.visit_expr(expr)?;
                 if dtype != ColumnType::Boolean {
                     return Err(ConversionError::InvalidDataType {
@@ -209,6 +306,26 @@ impl<'a> QueryContextBuilder<'a> {
                 }
                 Ok(ColumnType::Boolean)
             }
+            UnaryOperator::Sign => {
+                // A grouped result is computed after the proof, where `SIGN` is not available.
+                if self.context.is_in_result_scope()
+                    && !self.context.get_group_by_exprs().is_empty()
+                {
+                    return Err(ConversionError::InvalidExpression(
+                        "sign is not supported in the result of a group by query".into(),
+                    ));
+                }
+                let dtype = self.visit_expr(expr)?;
+                if !dtype.is_numeric() {
+                    return Err(ConversionError::non_numeric_expr_in_sign(dtype));
+                }
+                Ok(ColumnType::SmallInt)
+            }
+            // Table commitments do not carry the byte lengths of strings, so `LENGTH` cannot be proven.
+            UnaryOperator::Length => Err(ConversionError::Unprovable(
+                "length requires committed byte lengths, which table commitments do not carry"
+                    .into(),
+            )),
         }
     }
 
@@ -216,17 +333,43 @@ impl<'a> QueryContextBuilder<'a> {
         &mut self,
         op: &AggregationOperator,
         expr: &mut Expression,
+        filter: Option<&mut Expression>,
     ) -> ConversionResult<ColumnType> {
         self.context.set_in_agg_scope(true)?;
 
         let expr_dtype = self.visit_expr(expr)?;
 
-        // We only support sum/max/min aggregations on numeric columns.
-        if op != &AggregationOperator::Count && expr_dtype == ColumnType::VarChar {
-            return Err(ConversionError::non_numeric_expr_in_agg(
-                expr_dtype.to_string(),
-                op.to_string(),
-            ));
+        // The filter of an aggregation selects rows just like a WHERE clause.
+        if let Some(filter) = filter {
+            let filter_dtype = self.visit_expr(filter)?;
+            if filter_dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: filter_dtype,
+                });
+            }
+        }
+
+        match op {
+            // Boolean aggregations are only supported on boolean expressions.
+            AggregationOperator::BoolAnd | AggregationOperator::BoolOr => {
+                if expr_dtype != ColumnType::Boolean {
+                    return Err(ConversionError::InvalidDataType {
+                        expected: ColumnType::Boolean,
+                        actual: expr_dtype,
+                    });
+                }
+            }
+            AggregationOperator::Count => {}
+            // We only support sum/max/min aggregations on numeric columns.
+            _ => {
+                if expr_dtype == ColumnType::VarChar {
+                    return Err(ConversionError::non_numeric_expr_in_agg(
+                        expr_dtype.to_string(),
+                        op.to_string(),
+                    ));
+                }
+            }
         }
 
         self.context.set_in_agg_scope(false)?;
@@ -239,6 +382,75 @@ impl<'a> QueryContextBuilder<'a> {
         }
     }
 
+    /// Visits an aggregate over the selection, such as `AVG(x) OVER ()`.
+    ///
+    /// It is evaluated along with the rows of the result, so it may neither be grouped
+    /// nor nested in another aggregation. An aggregate over partitions, such as
+    /// `SUM(x) OVER (PARTITION BY region)`, is rejected: there is no proof of the sum of each
+    /// partition, so it could only be computed after the proof, unlike the aggregate over the
+    /// whole selection, whose sum is proven.
+    fn visit_window_agg_expr(
+        &mut self,
+        op: &WindowAggregationOperator,
+        expr: &mut Expression,
+        partition_by: &[Identifier],
+    ) -> ConversionResult<ColumnType> {
+        if !self.context.is_in_result_scope() {
+            return Err(ConversionError::InvalidExpression(
+                "window aggregates are only supported in the result".into(),
+            ));
+        }
+        if !self.context.get_group_by_exprs().is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "window aggregates are not supported in the result of a group by query".into(),
+            ));
+        }
+        if self.context.is_in_agg_scope() {
+            return Err(ConversionError::InvalidExpression(
+                "nested aggregations are not supported".into(),
+            ));
+        }
+        if !partition_by.is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "window aggregates with partitions cannot be proven".into(),
+            ));
+        }
+        let expr_dtype = self.visit_expr(expr)?;
+        if !expr_dtype.is_numeric() {
+            return Err(ConversionError::non_numeric_expr_in_agg(
+                expr_dtype.to_string(),
+                op.to_string(),
+            ));
+        }
+        Ok(expr_dtype)
+    }
+
+    /// Visits a row number, such as `ROW_NUMBER() OVER (ORDER BY x)`.
+    ///
+    /// It is computed after the proof from the rows of the result, so like a window aggregate it
+    /// may neither be grouped nor nested in an aggregation.
+    fn visit_row_number_expr(&mut self, order_by: &[OrderBy]) -> ConversionResult<ColumnType> {
+        if !self.context.is_in_result_scope() {
+            return Err(ConversionError::InvalidExpression(
+                "row numbers are only supported in the result".into(),
+            ));
+        }
+        if !self.context.get_group_by_exprs().is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "row numbers are not supported in the result of a group by query".into(),
+            ));
+        }
+        if self.context.is_in_agg_scope() {
+            return Err(ConversionError::InvalidExpression(
+                "row numbers may not be aggregated".into(),
+            ));
+        }
+        for by_expr in order_by {
+            self.visit_column_identifier(by_expr.expr)?;
+        }
+        Ok(ColumnType::BigInt)
+    }
+
     fn visit_literal(&self, literal: &Literal) -> Result<ColumnType, ConversionError> {
         match literal {
             Literal::Boolean(_) => Ok(ColumnType::Boolean),
@@ -246,9 +458,10 @@ impl<'a> QueryContextBuilder<'a> {
             Literal::Int128(_) => Ok(ColumnType::Int128),
             Literal::VarChar(_) => Ok(ColumnType::VarChar),
             Literal::Decimal(d) => {
-                let precision = Precision::new(d.precision())?;
-                Ok(ColumnType::Decimal75(precision, d.scale()))
+                let (precision, scale) = literal_precision_and_scale(d)?;
+                Ok(ColumnType::Decimal75(precision, scale))
             }
+            Literal::Null => Err(ConversionError::null_literal()),
         }
     }
 
@@ -266,68 +479,23 @@ impl<'a> QueryContextBuilder<'a> {
 
         Ok(column_type)
     }
-}
 
-pub(crate) fn type_check_binary_operation(
-    left_dtype: &ColumnType,
-    right_dtype: &ColumnType,
-    binary_operator: BinaryOperator,
-) -> bool {
-    match binary_operator {
-        BinaryOperator::And | BinaryOperator::Or => {
-            matches!(
-                (left_dtype, right_dtype),
-                (ColumnType::Boolean, ColumnType::Boolean)
-            )
-        }
-        BinaryOperator::Equal => {
-            matches!(
-                (left_dtype, right_dtype),
-                (ColumnType::VarChar, ColumnType::VarChar)
-                    | (ColumnType::Boolean, ColumnType::Boolean)
-                    | (_, ColumnType::Scalar)
-                    | (ColumnType::Scalar, _)
-            ) || (left_dtype.is_numeric() && right_dtype.is_numeric())
-        }
-        BinaryOperator::GreaterThanOrEqual | BinaryOperator::LessThanOrEqual => {
-            if left_dtype == &ColumnType::VarChar || right_dtype == &ColumnType::VarChar {
-                return false;
-            }
-            // Due to constraints in bitwise_verification we limit the precision of decimal types to 38
-            if let ColumnType::Decimal75(precision, _) = left_dtype {
-                if precision.value() > 38 {
-                    return false;
-                }
-            }
-            if let ColumnType::Decimal75(precision, _) = right_dtype {
-                if precision.value() > 38 {
-                    return false;
-                }
-            }
-            left_dtype.is_numeric() && right_dtype.is_numeric()
-                || matches!(
-                    (left_dtype, right_dtype),
-                    (ColumnType::Boolean, ColumnType::Boolean)
-                )
+    fn check_dtypes(
+        &self,
+        left_dtype: ColumnType,
+        right_dtype: ColumnType,
+        binary_operator: BinaryOperator,
+    ) -> ConversionResult<()> {
+        if self
+            .type_system
+            .type_check_binary_operation(&left_dtype, &right_dtype, binary_operator)
+        {
+            Ok(())
+        } else {
+            Err(ConversionError::DataTypeMismatch(
+                left_dtype.to_string(),
+                right_dtype.to_string(),
+            ))
         }
-        BinaryOperator::Multiply
-        | BinaryOperator::Division
-        | BinaryOperator::Subtract
-        | BinaryOperator::Add => left_dtype.is_numeric() && right_dtype.is_numeric(),
-    }
-}
-
-fn check_dtypes(
-    left_dtype: ColumnType,
-    right_dtype: ColumnType,
-    binary_operator: BinaryOperator,
-) -> ConversionResult<()> {
-    if type_check_binary_operation(&left_dtype, &right_dtype, binary_operator) {
-        Ok(())
-    } else {
-        Err(ConversionError::DataTypeMismatch(
-            left_dtype.to_string(),
-            right_dtype.to_string(),
-        ))
     }
 }
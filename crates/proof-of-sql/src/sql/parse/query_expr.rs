@@ -1,15 +1,160 @@
-use super::{FilterExprBuilder, QueryContextBuilder, ResultExprBuilder};
+use super::{
+    try_build_min_max, try_build_post_aggregation, AccessPolicy, ConversionError,
+    DefaultTypeSystem, FilterExprBuilder, QueryContextBuilder, ResultExprBuilder, TypeSystem,
+    WhereExprBuilder,
+};
 use crate::{
-    base::{commitment::Commitment, database::SchemaAccessor},
+    base::{
+        commitment::Commitment,
+        database::{
+            ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LazyDataAccessor, OwnedTable,
+            SchemaAccessor, TableRef,
+        },
+        proof::{MessageLabel, TranscriptProtocol},
+        scalar::Scalar,
+    },
     sql::{
-        ast::{GroupByExpr, ProofPlan},
+        ast::{
+            ColumnExpr, CountExpr, GroupByExpr, Histogram, HistogramExpr, LookupExpr, ProofPlan,
+            TableExpr,
+        },
         parse::ConversionResult,
-        transform::ResultExpr,
+        proof::{
+            make_empty_table, num_sumcheck_variables, CountBuilder, ProofBuilder, ProofCounts,
+            ProofExpr, ProverEvaluate, ResultBuilder,
+        },
+        transform::{PageCursor, PageExpr, ResultExpr},
     },
 };
-use proof_of_sql_parser::{intermediate_ast::SetExpression, Identifier, SelectStatement};
+use arrow::record_batch::RecordBatch;
+use bumpalo::Bump;
+use merlin::Transcript;
+use num_traits::Zero;
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, BinaryOperator, Expression, Literal, SelectResultExpr, SetExpression,
+    },
+    Identifier, SelectStatement,
+};
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+/// The default maximum depth of the expressions of a query, used by [`QueryExpr::try_new`].
+///
+/// Planning and proving recurse through the expressions, and at this depth they fit within
+/// the 2 MiB stack that Rust gives to spawned threads, even in debug builds.
+pub const DEFAULT_MAX_PLAN_DEPTH: usize = 128;
+
+/// The options of planning a query with [`QueryExpr::try_new_with_options`].
+///
+/// The default options plan a query as [`QueryExpr::try_new`] does, and each `with_` method
+/// changes one of them.
+pub struct QueryExprOptions<'a, C: Commitment> {
+    default_catalog: Option<Identifier>,
+    max_depth: usize,
+    type_system: &'a dyn TypeSystem,
+    access_policy: Option<&'a dyn AccessPolicy>,
+    strict: bool,
+    column_bounds: Option<&'a dyn CommitmentAccessor<C>>,
+    page: Option<(u64, Option<PageCursor>)>,
+}
+
+impl<C: Commitment> Default for QueryExprOptions<'_, C> {
+    fn default() -> Self {
+        Self {
+            default_catalog: None,
+            max_depth: DEFAULT_MAX_PLAN_DEPTH,
+            type_system: &DefaultTypeSystem,
+            access_policy: None,
+            strict: false,
+            column_bounds: None,
+            page: None,
+        }
+    }
+}
+
+impl<'a, C: Commitment> QueryExprOptions<'a, C> {
+    /// Resolve the table of the query in `default_catalog` unless the query names a catalog.
+    ///
+    /// So with a default catalog `cat` and a default schema `sch`, each of `t`, `sch.t` and
+    /// `cat.sch.t` refers to the table `cat.sch.t`. By default, `sch.t` is resolved to
+    /// a table without a catalog instead.
+    pub fn with_default_catalog(mut self, default_catalog: Identifier) -> Self {
+        self.default_catalog = Some(default_catalog);
+        self
+    }
+
+    /// Allow the expressions of the query to be nested up to `max_depth` deep, rather than
+    /// [`DEFAULT_MAX_PLAN_DEPTH`].
+    ///
+    /// The depth counts every operator and operand, so `a = 1 OR a = 2` has depth 3,
+    /// and an `IN` list counts as the balanced tree of `OR`s that it is proven as.
+    /// It is checked before anything recursive is done with the query, so a larger `max_depth`
+    /// should only be used on threads with a correspondingly larger stack.
+    /// Planning errors with [`ConversionError::PlanTooDeep`] if the query is nested deeper.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Check the types of the operands of binary operators with `type_system` rather than
+    /// [`DefaultTypeSystem`].
+    ///
+    /// Planning errors with [`ConversionError::DataTypeMismatch`] if `type_system` rejects
+    /// an operation.
+    pub fn with_type_system(mut self, type_system: &'a dyn TypeSystem) -> Self {
+        self.type_system = type_system;
+        self
+    }
+
+    /// Check that `access_policy` authorizes each column that the query reads.
+    /// See [`QueryExpr::check_access`].
+    pub fn with_access_policy(mut self, access_policy: &'a dyn AccessPolicy) -> Self {
+        self.access_policy = Some(access_policy);
+        self
+    }
+
+    /// Whether to require the rows of the result to be determined by the query rather than
+    /// by the order of the table.
+    ///
+    /// When strict, planning errors with [`ConversionError::NonDeterministicOrder`] if the query
+    /// has a `LIMIT` or an `OFFSET` without an `ORDER BY`. By default, those are allowed and
+    /// select the rows in the order of the table, which clients paginating through a result
+    /// should not rely on.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Answer a query of only `MIN`s and `MAX`s of integer columns without a where clause
+    /// from the bounds of the columns in `column_bounds`.
+    ///
+    /// When `column_bounds` has [`Bounds::Sharp`](crate::base::commitment::Bounds::Sharp) bounds
+    /// for each of the columns, as a [`QueryCommitments`](crate::base::commitment::QueryCommitments)
+    /// computed from the whole table does, the extremes are checked against them instead of being
+    /// proven from the values of the columns, which is much faster for large tables. Any other
+    /// query is planned as usual. The verifier must have the same sharp bounds, or the result of
+    /// the extremes can't be verified.
+    pub fn with_column_bounds(mut self, column_bounds: &'a dyn CommitmentAccessor<C>) -> Self {
+        self.column_bounds = Some(column_bounds);
+        self
+    }
+
+    /// Return the page of `page_size` rows of the sorted result that follows `cursor`,
+    /// rather than the whole result. See [`PageExpr`].
+    ///
+    /// A `None` cursor returns the first page, and [`QueryExpr::next_cursor`] gives the cursor
+    /// of the page after a verified page. Planning errors with
+    /// [`ConversionError::InvalidExpression`] if the query has no `ORDER BY`, or has a `LIMIT`
+    /// or an `OFFSET`, which a page replaces.
+    pub fn with_page(mut self, page_size: u64, cursor: Option<PageCursor>) -> Self {
+        self.page = Some((page_size, cursor));
+        self
+    }
+}
 
 #[derive(PartialEq, Serialize, Deserialize)]
 /// A `QueryExpr` represents a Proof of SQL query that can be executed against a database.
@@ -17,6 +162,7 @@ use std::fmt;
 pub struct QueryExpr<C: Commitment> {
     proof_expr: ProofPlan<C>,
     result: ResultExpr,
+    page: Option<PageExpr>,
 }
 
 // Implements fmt::Debug to aid in debugging QueryExpr.
@@ -34,70 +180,554 @@ impl<C: Commitment> fmt::Debug for QueryExpr<C> {
 impl<C: Commitment> QueryExpr<C> {
     /// Creates a new `QueryExpr` with the given `ProofPlan` and `ResultExpr`.
     pub fn new(proof_expr: ProofPlan<C>, result: ResultExpr) -> Self {
-        Self { proof_expr, result }
+        Self {
+            proof_expr,
+            result,
+            page: None,
+        }
     }
 
     /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`.
+    ///
+    /// Errors with [`ConversionError::PlanTooDeep`] if an expression of the query is nested
+    /// deeper than [`DEFAULT_MAX_PLAN_DEPTH`].
     pub fn try_new(
         ast: SelectStatement,
         default_schema: Identifier,
         schema_accessor: &dyn SchemaAccessor,
     ) -> ConversionResult<Self> {
-        let context = match *ast.expr {
-            SetExpression::Query {
-                result_exprs,
-                from,
-                where_expr,
-                group_by,
-            } => QueryContextBuilder::new(schema_accessor)
-                .visit_table_expr(from, default_schema)
-                .visit_group_by_exprs(group_by)?
-                .visit_result_exprs(result_exprs)?
-                .visit_where_expr(where_expr)?
-                .visit_order_by_exprs(ast.order_by)
-                .visit_slice_expr(ast.slice)
-                .build()?,
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            schema_accessor,
+            &QueryExprOptions::default(),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, resolving its table
+    /// in `default_catalog` unless the query names a catalog.
+    ///
+    /// See [`QueryExprOptions::with_default_catalog`].
+    pub fn try_new_in_catalog(
+        ast: SelectStatement,
+        default_catalog: Identifier,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            schema_accessor,
+            &QueryExprOptions::default().with_default_catalog(default_catalog),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, allowing its expressions
+    /// to be nested up to `max_depth` deep.
+    ///
+    /// See [`QueryExprOptions::with_max_depth`].
+    pub fn try_new_with_max_depth(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        max_depth: usize,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            schema_accessor,
+            &QueryExprOptions::default().with_max_depth(max_depth),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, checking the types of
+    /// the operands of its binary operators with `type_system` rather than [`DefaultTypeSystem`].
+    ///
+    /// See [`QueryExprOptions::with_type_system`].
+    pub fn try_new_with_type_system(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        type_system: &dyn TypeSystem,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            schema_accessor,
+            &QueryExprOptions::default().with_type_system(type_system),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, and check that
+    /// `access_policy` authorizes each column that the query reads.
+    ///
+    /// See [`QueryExprOptions::with_access_policy`].
+    pub fn try_new_with_access_policy(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        access_policy: &dyn AccessPolicy,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            schema_accessor,
+            &QueryExprOptions::default().with_access_policy(access_policy),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, requiring the rows of
+    /// its result to be determined by the query rather than by the order of the table.
+    ///
+    /// See [`QueryExprOptions::with_strict`].
+    pub fn try_new_strict(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            schema_accessor,
+            &QueryExprOptions::default().with_strict(true),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, answering a query of only
+    /// `MIN`s and `MAX`s of integer columns without a where clause from the bounds of the columns.
+    ///
+    /// See [`QueryExprOptions::with_column_bounds`].
+    pub fn try_new_with_column_bounds(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        accessor: &(impl SchemaAccessor + CommitmentAccessor<C>),
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(
+            ast,
+            default_schema,
+            accessor,
+            &QueryExprOptions::default().with_column_bounds(accessor),
+        )
+    }
+
+    /// Plans the histogram of the numeric column `column_id` of `table_ref`, with a bucket
+    /// between each pair of consecutive `boundaries`.
+    ///
+    /// Buckets are left-closed and right-open, so a value on a boundary is counted in the bucket
+    /// that the boundary starts. Read the counts from the verified result with [`Self::histogram`].
+    pub fn try_new_histogram(
+        table_ref: TableRef,
+        column_id: Identifier,
+        boundaries: Vec<i64>,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let column_type = schema_accessor
+            .lookup_column(table_ref, column_id)
+            .ok_or_else(|| {
+                ConversionError::MissingColumn(
+                    Box::new(column_id),
+                    Box::new(table_ref.resource_id()),
+                )
+            })?;
+        let column = ColumnExpr::new(ColumnRef::new(table_ref, column_id, column_type));
+        let histogram = HistogramExpr::try_new(column, boundaries, TableExpr { table_ref })?;
+        Ok(Self {
+            proof_expr: ProofPlan::Histogram(histogram),
+            result: ResultExprBuilder::default().build(),
+            page: None,
+        })
+    }
+
+    /// Plans the lookup of the row of `table_ref` whose column `key_id` is `value`, as for
+    /// ```ignore
+    ///     SELECT * FROM <table_ref> WHERE <key_id> = <value>
+    /// ```
+    /// but with a more compact proof that is meant for attesting a single record.
+    ///
+    /// The verified result has every column of the table, and either the one row with the key
+    /// or no row, which attests that there is no such row. The key should be unique: if it
+    /// is in more than one row, the result can't be verified, and a query planned with
+    /// [`Self::try_new`] is needed instead.
+    pub fn try_new_lookup(
+        table_ref: TableRef,
+        key_id: Identifier,
+        value: Literal,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let column_mapping: HashMap<_, _> = schema_accessor
+            .lookup_schema(table_ref)
+            .into_iter()
+            .map(|(column_id, column_type)| {
+                (column_id, ColumnRef::new(table_ref, column_id, column_type))
+            })
+            .collect();
+        if !column_mapping.contains_key(&key_id) {
+            return Err(ConversionError::MissingColumn(
+                Box::new(key_id),
+                Box::new(table_ref.resource_id()),
+            ));
+        }
+        let where_clause = WhereExprBuilder::new(&column_mapping)
+            .build(Some(Box::new(Expression::Binary {
+                op: BinaryOperator::Equal,
+                left: Box::new(Expression::Column(key_id)),
+                right: Box::new(Expression::Literal(value)),
+            })))?
+            .expect("the where clause is given");
+        let columns = schema_accessor
+            .lookup_schema(table_ref)
+            .into_iter()
+            .map(|(column_id, _)| ColumnExpr::new(column_mapping[&column_id]))
+            .collect();
+        Ok(Self {
+            proof_expr: ProofPlan::Lookup(LookupExpr::new(
+                columns,
+                TableExpr { table_ref },
+                where_clause,
+            )),
+            result: ResultExprBuilder::default().build(),
+            page: None,
+        })
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, as configured by `options`.
+    ///
+    /// With the default options, this is the same as [`QueryExpr::try_new`].
+    pub fn try_new_with_options(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        options: &QueryExprOptions<C>,
+    ) -> ConversionResult<Self> {
+        if options.strict && ast.slice.is_some() && ast.order_by.is_empty() {
+            return Err(ConversionError::NonDeterministicOrder);
+        }
+        let query = Self::try_plan(ast, default_schema, schema_accessor, options)?;
+        if let Some(access_policy) = options.access_policy {
+            query.check_access(access_policy)?;
+        }
+        Ok(query)
+    }
+
+    fn try_plan(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        options: &QueryExprOptions<C>,
+    ) -> ConversionResult<Self> {
+        let SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+        } = *ast.expr;
+        let where_depth = where_expr
+            .as_deref()
+            .map_or(0, |expr| expression_depth(expr, 0));
+        let depth = result_exprs
+            .iter()
+            .filter_map(|result_expr| match result_expr {
+                SelectResultExpr::ALL => None,
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => Some(&aliased_expr.expr),
+            })
+            .map(|expr| expression_depth(expr, where_depth))
+            .chain([where_depth])
+            .max()
+            .unwrap_or(0);
+        if depth > options.max_depth {
+            return Err(ConversionError::PlanTooDeep {
+                depth,
+                max: options.max_depth,
+            });
+        }
+        let context = QueryContextBuilder::new(schema_accessor, options.type_system)
+            .visit_table_expr(from, options.default_catalog, default_schema)
+            .visit_group_by_exprs(group_by)?
+            .visit_result_exprs(result_exprs)?
+            .visit_where_expr(where_expr)?
+            .visit_order_by_exprs(ast.order_by)
+            .visit_slice_expr(ast.slice)
+            .build()?;
+        // A page is taken from the sorted result in place of a `LIMIT` and an `OFFSET`.
+        let page = match &options.page {
+            Some(_) if context.get_slice_expr().is_some() => {
+                return Err(ConversionError::InvalidExpression(
+                    "a page of a result cannot be combined with LIMIT or OFFSET".into(),
+                ));
+            }
+            Some((page_size, cursor)) => Some(PageExpr::try_new(
+                context.get_order_by_exprs()?,
+                *page_size,
+                cursor.clone(),
+            )?),
+            None => None,
         };
         let result_aliased_exprs = context.get_aliased_result_exprs()?;
         let group_by = context.get_group_by_exprs();
-        if !group_by.is_empty() {
-            if let Some(group_by_expr) = Option::<GroupByExpr<C>>::try_from(&context)? {
-                return Ok(Self {
-                    proof_expr: ProofPlan::GroupBy(group_by_expr),
-                    result: ResultExprBuilder::default()
-                        .add_select_exprs(result_aliased_exprs)
-                        .add_order_by_exprs(context.get_order_by_exprs()?)
-                        .add_slice_expr(context.get_slice_expr())
-                        .build(),
-                });
-            }
+        let min_max_expr = match options.column_bounds {
+            Some(column_bounds) => try_build_min_max(&context, column_bounds)?,
+            None => None,
+        };
+        let aggregate_expr = if let Some(min_max_expr) = min_max_expr {
+            Some(ProofPlan::MinMax(min_max_expr))
+        } else if group_by.is_empty() {
+            Option::<CountExpr<C>>::try_from(&context)?.map(ProofPlan::Count)
+        } else {
+            Option::<GroupByExpr<C>>::try_from(&context)?.map(ProofPlan::GroupBy)
+        };
+        if let Some(aggregate_expr) = aggregate_expr {
+            // The proof already has one row per group, or the single row of a count or of extremes,
+            // so its columns are only renamed.
+            let select_exprs = aggregate_expr
+                .get_column_result_fields()
+                .iter()
+                .zip(result_aliased_exprs)
+                .map(|(field, aliased_expr)| AliasedResultExpr {
+                    expr: Box::new(Expression::Column(field.name())),
+                    alias: aliased_expr.alias,
+                })
+                .collect::<Vec<_>>();
+            return Ok(Self {
+                proof_expr: aggregate_expr,
+                result: ResultExprBuilder::default()
+                    .add_select_exprs(&select_exprs)
+                    .add_order_by_exprs(context.get_order_by_exprs()?)
+                    .add_slice_expr(context.get_slice_expr())
+                    .add_page_expr(page.clone())
+                    .build(),
+                page,
+            });
+        }
+        // Arithmetic on aggregates is proven on the groups, and only the selected columns are returned.
+        if let Some((group_by_expr, select_exprs)) = try_build_post_aggregation(&context)? {
+            return Ok(Self {
+                proof_expr: ProofPlan::GroupBy(group_by_expr),
+                result: ResultExprBuilder::default()
+                    .add_select_exprs(&select_exprs)
+                    .add_order_by_exprs(context.get_order_by_exprs()?)
+                    .add_slice_expr(context.get_slice_expr())
+                    .add_page_expr(page.clone())
+                    .build(),
+                page,
+            });
         }
 
-        let filter = FilterExprBuilder::new(context.get_column_mapping())
+        let filter_builder = FilterExprBuilder::new(context.get_column_mapping())
             .add_table_expr(*context.get_table_ref())
-            .add_where_expr(context.get_where_expr().clone())?
-            .add_result_column_set(context.get_result_column_set())
-            .build();
+            .add_where_expr(context.get_where_expr().clone())?;
+        // Without a GROUP BY, the result expressions are evaluated row by row, so some can be proven.
+        let (filter_builder, select_exprs) = if group_by.is_empty() {
+            let (filter_builder, select_exprs) =
+                filter_builder.add_result_exprs(result_aliased_exprs)?;
+            let order_by = context.get_order_by_exprs()?;
+            let filter_builder = filter_builder
+                .add_top_n(result_aliased_exprs, &order_by, context.get_slice_expr())?
+                .add_limit(result_aliased_exprs, &order_by, context.get_slice_expr())?;
+            (filter_builder, select_exprs)
+        } else {
+            (
+                filter_builder.add_result_column_set(context.get_result_column_set()),
+                result_aliased_exprs.to_vec(),
+            )
+        };
+        let filter = filter_builder.build();
         let result = ResultExprBuilder::default()
             .add_group_by_exprs(context.get_group_by_exprs(), result_aliased_exprs)
-            .add_select_exprs(result_aliased_exprs)
+            .add_select_exprs(&select_exprs)
             .add_order_by_exprs(context.get_order_by_exprs()?)
             .add_slice_expr(context.get_slice_expr())
+            .add_page_expr(page.clone())
             .build();
 
         Ok(Self {
             proof_expr: ProofPlan::DenseFilter(filter),
             result,
+            page,
         })
     }
 
+    /// Returns the cursor of the page following `page`, the verified and transformed result of
+    /// a query planned with [`QueryExprOptions::with_page`].
+    ///
+    /// Returns `None` if the query is not paged or `page` is empty.
+    pub fn next_cursor(&self, page: &RecordBatch) -> Option<PageCursor> {
+        self.page.as_ref()?.next_cursor(page)
+    }
+
     /// Immutable access to this query's provable filter expression.
     pub fn proof_expr(&self) -> &ProofPlan<C> {
         &self.proof_expr
     }
 
+    /// Returns the tables that the query reads, with the columns that it reads from each of them
+    /// sorted by name.
+    ///
+    /// These are the only columns that proving the query fetches, so a server can check them
+    /// against the columns that a client may query before proving anything.
+    pub fn referenced_schema(&self) -> BTreeMap<TableRef, Vec<(Identifier, ColumnType)>> {
+        let mut schema = BTreeMap::<_, Vec<_>>::new();
+        for column in self.proof_expr.get_column_references() {
+            schema
+                .entry(column.table_ref())
+                .or_default()
+                .push((column.column_id(), *column.column_type()));
+        }
+        for columns in schema.values_mut() {
+            columns.sort_by_key(|(column_id, _)| *column_id);
+        }
+        schema
+    }
+
+    /// Checks that `access_policy` authorizes each column of [`Self::referenced_schema`].
+    ///
+    /// Errors with [`ConversionError::AccessDenied`] for the first column, in the order of
+    /// the referenced schema, that `access_policy` does not authorize.
+    pub fn check_access(&self, access_policy: &dyn AccessPolicy) -> ConversionResult<()> {
+        let referenced_schema = self.referenced_schema();
+        for (table_ref, columns) in &referenced_schema {
+            for &(column_id, column_type) in columns {
+                let column = ColumnRef::new(*table_ref, column_id, column_type);
+                if !access_policy.authorize(&column, &referenced_schema) {
+                    return Err(ConversionError::AccessDenied {
+                        column: Box::new(column),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the histogram from the verified result of a query planned with [`Self::try_new_histogram`].
+    ///
+    /// Returns `None` for other queries, or if `result` does not have the count of each bucket.
+    pub fn histogram<S: Scalar>(&self, result: &OwnedTable<S>) -> Option<Histogram> {
+        match &self.proof_expr {
+            ProofPlan::Histogram(histogram) => histogram.histogram(result),
+            _ => None,
+        }
+    }
+
     /// Immutable access to this query's post-proof result transform expression.
     pub fn result(&self) -> &ResultExpr {
         &self.result
     }
+
+    /// Computes the result of this query over `table` without proving it.
+    ///
+    /// This runs the prover's evaluation of the result and the post-proof transform, but no commitments
+    /// or proof, so it is the result that a proof of the query over `table` verifies to. `table` stands in
+    /// for the table that the query reads, and must have the columns that the query references.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table` lacks a column that the query references, if the query divides by zero,
+    /// or if the result can't be transformed, as proving the query would.
+    pub fn evaluate_owned(&self, table: &OwnedTable<C::Scalar>) -> OwnedTable<C::Scalar> {
+        let mut accessor = LazyDataAccessor::new(|column: ColumnRef| {
+            table.inner_table()[&column.column_id()].clone()
+        });
+        let schema: Vec<_> = table
+            .inner_table()
+            .iter()
+            .map(|(column_id, column)| (*column_id, column.column_type()))
+            .collect();
+        for table_ref in self.referenced_schema().into_keys() {
+            accessor.add_table(table_ref, schema.clone(), table.num_rows(), 0);
+        }
+
+        let result_fields = self.proof_expr.get_column_result_fields();
+        let result: OwnedTable<C::Scalar> = if self.proof_expr.is_empty(&accessor) {
+            make_empty_table(&result_fields).expect("an empty result is a valid table")
+        } else {
+            let alloc = Bump::new();
+            let mut result_builder = ResultBuilder::new(self.proof_expr.get_length(&accessor));
+            self.proof_expr
+                .result_evaluate(&mut result_builder, &alloc, &accessor);
+            result_builder
+                .make_provable_query_result()
+                .to_owned_table(&result_fields)
+                .expect("the result of the prover's evaluation is a valid table")
+        };
+        let batch = self
+            .result
+            .transform_results(RecordBatch::try_from(result).expect("the result converts to arrow"))
+            .expect("the result could not be transformed");
+        OwnedTable::try_from(batch).expect("the transformed result converts from arrow")
+    }
+
+    /// Count the terms that a proof of this query would contain when run against `accessor`.
+    ///
+    /// Some counts depend on the data (e.g. the bit distributions used by inequalities),
+    /// so this runs the prover's evaluation pass, but skips the commitments and sumcheck.
+    pub fn proof_counts(&self, accessor: &impl DataAccessor<C::Scalar>) -> ProofCounts {
+        let table_length = self.proof_expr.get_length(accessor);
+        let num_sumcheck_variables = num_sumcheck_variables(table_length);
+
+        let alloc = Bump::new();
+        let mut result_builder = ResultBuilder::new(table_length);
+        self.proof_expr
+            .result_evaluate(&mut result_builder, &alloc, accessor);
+
+        // The counts do not depend on the values of the challenges.
+        let mut transcript = Transcript::new(MessageLabel::QueryProof.as_bytes());
+        let mut post_result_challenges =
+            vec![Zero::zero(); result_builder.num_post_result_challenges()];
+        transcript.challenge_scalars(
+            &mut post_result_challenges,
+            MessageLabel::PostResultChallenges,
+        );
+        let mut proof_builder =
+            ProofBuilder::new(table_length, num_sumcheck_variables, post_result_challenges);
+        self.proof_expr
+            .prover_evaluate(&mut proof_builder, &alloc, accessor);
+
+        let mut count_builder = CountBuilder::new(proof_builder.bit_distributions());
+        self.proof_expr
+            .count(&mut count_builder, accessor)
+            .and_then(|()| count_builder.counts())
+            .expect("the prover's bit distributions should always match the count")
+    }
+}
+
+/// The depth of `expr`, computed without recursion so that it cannot overflow the stack itself.
+///
+/// A window aggregate is planned with a copy of the where clause, whose depth is `where_depth`.
+fn expression_depth(expr: &Expression, where_depth: usize) -> usize {
+    let mut max_depth = 0;
+    let mut stack = vec![(expr, 1)];
+    while let Some((expr, depth)) = stack.pop() {
+        max_depth = max_depth.max(depth);
+        match expr {
+            Expression::Literal(_)
+            | Expression::Column(_)
+            | Expression::Wildcard
+            | Expression::Selected
+            | Expression::RowNumber { .. } => {}
+            // The aggregate is over the rows that the where clause selects, so its plan
+            // contains a copy of the where clause.
+            Expression::WindowAggregation { expr, .. } => {
+                stack.push((expr, depth + 1));
+                max_depth = max_depth.max(depth + where_depth);
+            }
+            Expression::Unary { expr, .. } => stack.push((expr, depth + 1)),
+            Expression::Binary { left, right, .. } => {
+                stack.push((left, depth + 1));
+                stack.push((right, depth + 1));
+            }
+            Expression::Aggregation { expr, filter, .. } => {
+                stack.push((expr, depth + 1));
+                stack.extend(filter.iter().map(|filter| (&**filter, depth + 1)));
+            }
+            Expression::Tuple(exprs) => {
+                stack.extend(exprs.iter().map(|expr| (&**expr, depth + 1)));
+            }
+            // `a IN (l_1, ..., l_n)` is proven as a balanced tree of `ceil(log2(n))` levels of `OR`
+            // over the equalities `a = l_i`.
+            Expression::InList { expr, list } => {
+                let or_depth = list.len().max(1).next_power_of_two().trailing_zeros() as usize;
+                stack.push((expr, depth + or_depth + 1));
+            }
+        }
+    }
+    max_depth
 }
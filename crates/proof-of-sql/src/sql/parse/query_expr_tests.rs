@@ -5,6 +5,7 @@ use crate::{
     sql::{
         ast::{test_utility::*, ProofPlan},
         parse::QueryExpr,
+        proof::ProofCounts,
         transform::test_utility::{col as pc, *},
     },
 };
@@ -126,8 +127,8 @@ macro_rules! expected_query {
         orderby_macro!($($order_by)?, $($order_dirs)?);
 
         macro_rules! filter_macro {
-            () => {dense_filter(cols_expr(t, &$result_columns, &accessor), tab(t), const_bool(true))};
-            ($expr:expr) => { dense_filter(cols_expr(t, &$result_columns, &accessor), tab(t), $expr) };
+            () => {dense_filter(cols_expr_plan(t, &$result_columns, &accessor), tab(t), const_bool(true))};
+            ($expr:expr) => { dense_filter(cols_expr_plan(t, &$result_columns, &accessor), tab(t), $expr) };
         }
         let filter = filter_macro!($($filter)?);
 
@@ -148,7 +149,7 @@ fn we_can_convert_an_ast_with_one_column() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
@@ -170,7 +171,7 @@ fn we_can_convert_an_ast_with_one_column_and_i128_data() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3_i64)),
         ),
@@ -192,7 +193,7 @@ fn we_can_convert_an_ast_with_one_column_and_a_filter_by_a_string_literal() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where a = 'abc'", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_varchar("abc")),
         ),
@@ -243,7 +244,7 @@ fn we_dont_have_duplicate_filter_result_expressions() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
@@ -267,7 +268,7 @@ fn we_can_convert_an_ast_with_two_columns() {
     let ast = query_to_provable_ast(t, "select a,  b from sxt_tab where c = 123", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "b"], &accessor),
             tab(t),
             equal(column(t, "c", &accessor), const_bigint(123)),
         ),
@@ -290,7 +291,7 @@ fn we_can_parse_all_result_columns_with_select_star() {
     let ast = query_to_provable_ast(t, "select * from sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "b"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
@@ -313,7 +314,7 @@ fn we_can_convert_an_ast_with_one_positive_cond() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where b = +4", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "b", &accessor), const_bigint(4)),
         ),
@@ -336,7 +337,7 @@ fn we_can_convert_an_ast_with_one_not_equals_cond() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where b <> +4", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             not(equal(column(t, "b", &accessor), const_bigint(4))),
         ),
@@ -359,7 +360,7 @@ fn we_can_convert_an_ast_with_one_negative_cond() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where b <= -4", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             lte(column(t, "b", &accessor), const_bigint(-4)),
         ),
@@ -387,7 +388,7 @@ fn we_can_convert_an_ast_with_cond_and() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             and(
                 equal(column(t, "b", &accessor), const_bigint(3)),
@@ -418,7 +419,7 @@ fn we_can_convert_an_ast_with_cond_or() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             or(
                 equal(column(t, "b", &accessor), const_bigint(3)),
@@ -449,7 +450,7 @@ fn we_can_convert_an_ast_with_conds_or_not() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             or(
                 lte(column(t, "b", &accessor), const_bigint(3)),
@@ -481,7 +482,7 @@ fn we_can_convert_an_ast_with_conds_not_and_or() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             not(and(
                 or(
@@ -513,7 +514,7 @@ fn we_can_convert_an_ast_with_the_min_i128_filter_value() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_int128(i128::MIN)),
         ),
@@ -539,7 +540,7 @@ fn we_can_convert_an_ast_with_the_max_i128_filter_value() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_int128(i128::MAX)),
         ),
@@ -566,7 +567,7 @@ fn we_can_convert_an_ast_using_an_aliased_column() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            vec![col_expr(t, "a", &accessor)],
+            vec![col_expr_plan(t, "a", &accessor)],
             tab(t),
             gte(column(t, "b", &accessor), const_bigint(4)),
         ),
@@ -614,7 +615,7 @@ fn we_can_convert_an_ast_with_a_schema() {
     let ast = query_to_provable_ast(t, "select a from eth.sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
@@ -634,7 +635,11 @@ fn we_can_convert_an_ast_without_any_dense_filter() {
         0,
     );
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
         result(&[("a", "a")]),
     );
     let queries = ["select * from eth.sxt_tab", "select a from eth.sxt_tab"];
@@ -661,7 +666,7 @@ fn we_can_parse_order_by_with_a_single_column() {
     let ast = query_to_provable_ast(t, "select * from sxt_tab where a = 3 order by b", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "b"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
@@ -691,7 +696,7 @@ fn we_can_parse_order_by_with_multiple_columns() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "b"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
@@ -723,8 +728,8 @@ fn we_can_parse_order_by_referencing_an_alias_associated_with_column_b_but_with_
     let expected_ast = QueryExpr::new(
         dense_filter(
             vec![
-                col_expr(t, "name", &accessor),
-                col_expr(t, "salary", &accessor),
+                col_expr_plan(t, "name", &accessor),
+                col_expr_plan(t, "salary", &accessor),
             ],
             tab(t),
             equal(column(t, "salary", &accessor), const_bigint(5)),
@@ -836,8 +841,8 @@ fn we_can_parse_order_by_queries_with_the_same_column_name_appearing_more_than_o
         let expected_ast = QueryExpr::new(
             dense_filter(
                 vec![
-                    col_expr(t, "name", &accessor),
-                    col_expr(t, "salary", &accessor),
+                    col_expr_plan(t, "name", &accessor),
+                    col_expr_plan(t, "salary", &accessor),
                 ],
                 tab(t),
                 const_bool(true),
@@ -872,7 +877,11 @@ fn we_can_parse_a_query_having_a_simple_limit_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab limit 3", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            limit(const_bool(true), 3),
+        ),
         composite_result(vec![select(&[pc("a").alias("a")]), slice(3, 0)]),
     );
     assert_eq!(ast, expected_ast);
@@ -891,7 +900,11 @@ fn no_slice_is_applied_when_limit_is_u64_max_and_offset_is_zero() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset 0", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
         composite_result(vec![select(&[pc("a").alias("a")])]),
     );
     assert_eq!(ast, expected_ast);
@@ -910,7 +923,11 @@ fn we_can_parse_a_query_having_a_simple_positive_offset_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset 7", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
         composite_result(vec![select(&[pc("a").alias("a")]), slice(u64::MAX, 7)]),
     );
     assert_eq!(ast, expected_ast);
@@ -929,7 +946,11 @@ fn we_can_parse_a_query_having_a_negative_offset_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset -7", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
         composite_result(vec![select(&[pc("a").alias("a")]), slice(u64::MAX, -7)]),
     );
     assert_eq!(ast, expected_ast);
@@ -948,7 +969,11 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab limit 55 offset 3", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            limit(const_bool(true), 58),
+        ),
         composite_result(vec![select(&[pc("a").alias("a")]), slice(55, 3)]),
     );
     assert_eq!(ast, expected_ast);
@@ -976,9 +1001,14 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_wher
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
-            equal(column(t, "a", &accessor), const_bigint(-3)),
+            top_n(
+                column(t, "a", &accessor),
+                equal(column(t, "a", &accessor), const_bigint(-3)),
+                58,
+                true,
+            ),
         ),
         composite_result(vec![
             select(&[pc("a").alias("a")]),
@@ -992,7 +1022,6 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_wher
 ///////////////////////////
 // Group By Expressions - Prover
 ///////////////////////////
-#[ignore]
 #[test]
 fn we_can_do_provable_group_by() {
     let t = "sxt.employees".parse().unwrap();
@@ -1025,14 +1054,13 @@ fn we_can_do_provable_group_by() {
             const_bool(true),
         ),
         composite_result(vec![select(&[
-            pc("department").first().alias("department"),
-            pc("salary").sum().alias("total_salary"),
-            pc("department").count().alias("num_employee"),
+            pc("department").alias("department"),
+            pc("total_salary").alias("total_salary"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
 }
-#[ignore]
 #[test]
 fn we_can_do_provable_group_by_without_sum() {
     let t = "sxt.employees".parse().unwrap();
@@ -1059,13 +1087,12 @@ fn we_can_do_provable_group_by_without_sum() {
             const_bool(true),
         ),
         composite_result(vec![select(&[
-            pc("department").first().alias("department"),
-            pc("department").count().alias("num_employee"),
+            pc("department").alias("department"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
 }
-#[ignore]
 #[test]
 fn we_can_do_provable_group_by_with_two_group_by_columns() {
     let t = "sxt.employees".parse().unwrap();
@@ -1099,10 +1126,10 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
             const_bool(true),
         ),
         composite_result(vec![select(&[
-            pc("state").first().alias("state"),
-            pc("department").first().alias("department"),
-            pc("salary").sum().alias("total_salary"),
-            pc("department").count().alias("num_employee"),
+            pc("state").alias("state"),
+            pc("department").alias("department"),
+            pc("total_salary").alias("total_salary"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
@@ -1141,14 +1168,74 @@ fn we_can_do_provable_group_by_with_two_sums_and_dense_filter() {
             lte(column(t, "tax", &accessor), const_bigint(1)),
         ),
         composite_result(vec![select(&[
-            pc("department").first().alias("department"),
-            pc("salary").sum().alias("total_salary"),
-            pc("tax").sum().alias("total_tax"),
-            pc("department").count().alias("num_employee"),
+            pc("department").alias("department"),
+            pc("total_salary").alias("total_salary"),
+            pc("total_tax").alias("total_tax"),
+            pc("num_employee").alias("num_employee"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_do_provable_group_by_with_a_filtered_sum() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "tax" => [1_i64, 2, 1, 1, 1, 1, 2],
+            "salary" => [4_i64, 7, 2, 3, 4, 5, 7],
+            "department" => [5_i64, 5, 2, 5, 2, 5, 2],
+        ),
+        0,
+    );
+
+    let ast = query_to_provable_ast(
+        t,
+        "select department, sum(salary) filter (where salary >= 4) as high_salary, count(*) as num_employee from employees where tax <= 1 group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_sum_filters(
+            cols_expr(t, &["department"], &accessor),
+            sums_expr(
+                t,
+                &["salary"],
+                &["high_salary"],
+                &[ColumnType::BigInt],
+                &accessor,
+            ),
+            "num_employee",
+            tab(t),
+            lte(column(t, "tax", &accessor), const_bigint(1)),
+            vec![Some(gte(column(t, "salary", &accessor), const_bigint(4)))],
+        ),
+        composite_result(vec![select(&[
+            pc("department").alias("department"),
+            pc("high_salary").alias("high_salary"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
 }
+
+#[test]
+fn we_cannot_filter_an_aggregation_with_a_non_boolean_expression() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "salary" => [4_i64, 7, 2],
+            "department" => [5_i64, 5, 2],
+        ),
+        0,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, sum(salary) filter (where salary) as s, count(*) as c from employees where salary >= 0 group by department",
+        &accessor,
+    );
+}
 ///////////////////////////
 // Group By Expressions - Polars
 ///////////////////////////
@@ -1171,7 +1258,7 @@ fn we_can_group_by_without_using_aggregate_functions() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1296,19 +1383,39 @@ fn group_by_column_cannot_be_a_column_result_alias() {
 #[test]
 fn we_can_have_aggregate_functions_without_a_group_by_clause() {
     let ast = query!(
-        select: ["count(s)"],
+        select: ["count(s)", "max(i)"],
     );
     let expected_ast = expected_query!(
         select: [
-            cols = ["s"],
+            cols = ["i", "s"],
             exprs = [
                 pc("s").count().alias("__count__"),
+                pc("i").max().alias("__max__"),
             ]
         ]
     );
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_prove_a_count_without_a_group_by_clause() {
+    let (t, accessor) = get_test_accessor();
+    let ast = query!(
+        select: ["count(i) as n"],
+        filter: "i >= 2",
+    );
+    let expected_ast = QueryExpr::new(
+        count_all(
+            col_expr(t, "i", &accessor),
+            "n",
+            tab(t),
+            gte(column(t, "i", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![select(&[pc("n").alias("n")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
     let t = "sxt.employees".parse().unwrap();
@@ -1328,7 +1435,7 @@ fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_ex
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["bonus", "department"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1362,7 +1469,7 @@ fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["bonus", "department"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1400,7 +1507,7 @@ fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             equal(column(t, "salary", &accessor), const_bigint(4)),
         ),
@@ -1454,7 +1561,7 @@ fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_wi
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1516,7 +1623,7 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b", "f", "h"], &accessor),
+            cols_expr_plan(t, &["a", "b", "f", "h"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1533,6 +1640,181 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_prove_a_decimal_division_in_the_result_expr() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+            "b" => [-7_i64],
+        ),
+        0,
+    );
+    let ast = query_to_provable_ast(t, "select a / 2.5 as r, b from employees", &accessor);
+    // `a` is only referenced by the division, so it is not a result of the filter.
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                col_expr_plan(t, "b", &accessor),
+                aliased_plan(
+                    div(column(t, "a", &accessor), const_decimal75(2, 1, 25), 1),
+                    "r",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![select(&[pc("r").alias("r"), pc("b").alias("b")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_an_integer_division_in_the_result_expr() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+            "b" => [-7_i64],
+        ),
+        0,
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select a / 3 as q, b div a as r from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                aliased_plan(int_div(column(t, "a", &accessor), const_bigint(3)), "q"),
+                aliased_plan(
+                    int_div(column(t, "b", &accessor), column(t, "a", &accessor)),
+                    "r",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![select(&[pc("q").alias("q"), pc("r").alias("r")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_integer_divide_decimals() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+        ),
+        0,
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select a div 2.5 as q from employees")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
+
+#[test]
+fn we_cannot_prove_a_decimal_division_whose_alias_is_a_referenced_column() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+            "b" => [-7_i64],
+        ),
+        0,
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select a / 2.5 as b, b + 1 as c from employees")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::DuplicateResultAlias(_))
+    ));
+}
+
+#[test]
+fn we_can_prove_a_sign_in_the_result_expr_and_the_where_clause() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+            "b" => [-7_i64],
+        ),
+        0,
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select sign(a) as s, b from employees where sign(b) = -1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                col_expr_plan(t, "b", &accessor),
+                aliased_plan(sign(column(t, "a", &accessor)), "s"),
+            ],
+            tab(t),
+            equal(sign(column(t, "b", &accessor)), const_bigint(-1)),
+        ),
+        composite_result(vec![select(&[pc("s").alias("s"), pc("b").alias("b")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_a_sign_in_the_result_expr_of_a_group_by_query() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+            "b" => [-7_i64],
+        ),
+        0,
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select sign(a) as s from employees group by a")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+}
+
+#[test]
+fn we_cannot_prove_the_length_of_a_string() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [4_i64],
+            "name" => ["abc"],
+        ),
+        0,
+    );
+    for sql in [
+        "select length(name) as l from employees",
+        "select a from employees where length(name) = 3",
+    ] {
+        let intermediate_ast = SelectStatementParser::new().parse(sql).unwrap();
+        assert!(matches!(
+            QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+            Err(ConversionError::Unprovable(_))
+        ));
+    }
+}
+
 #[test]
 fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
 ) {
@@ -1554,7 +1836,7 @@ fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_preceden
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["c", "f", "g", "h"], &accessor),
+            cols_expr_plan(t, &["c", "f", "g", "h"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1587,7 +1869,7 @@ fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["c", "f"], &accessor),
+            cols_expr_plan(t, &["c", "f"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1697,6 +1979,21 @@ fn varchar_column_is_not_compatible_with_integer_column() {
     );
 }
 
+#[test]
+fn int128_column_can_be_compared_with_a_decimal_literal_only_if_scaling_cannot_overflow() {
+    query!(select: ["d"], filter: "d = 1.5");
+    query!(select: ["i"], filter: "i >= 1.5");
+    query!(select: ["d"], filter: "d >= 1");
+    assert!(matches!(
+        query!(select: ["d"], filter: "d >= 1.5", should_err: true),
+        ConversionError::DataTypeMismatch(_, _)
+    ));
+    assert!(matches!(
+        query!(select: ["d"], filter: "-0.01 <= d", should_err: true),
+        ConversionError::DataTypeMismatch(_, _)
+    ));
+}
+
 #[test]
 fn arithmetic_operations_are_not_allowed_with_varchar_column() {
     assert_eq!(
@@ -1937,3 +2234,88 @@ fn we_can_serialize_list_of_filters_from_query_expr() {
     assert_eq!(filter_exprs.len(), deserialized_as_ref.len());
     assert_eq!(filter_exprs[0], deserialized_as_ref[0]);
 }
+
+#[test]
+fn we_can_get_the_proof_counts_of_an_and_of_two_equalities() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [1_i64, 2, 3, 1],
+            "b" => ["x", "y", "x", "z"],
+        ),
+        0,
+    );
+    let query = query_to_provable_ast(
+        t,
+        "select a, b from sxt_tab where a = 1 and b = 'x'",
+        &accessor,
+    );
+    // each equality: 1 anchored column, 2 intermediate mles, 2 subpolynomials
+    // the and: 1 intermediate mle, 1 subpolynomial
+    // the dense filter: 2 anchored result columns, 2 intermediate mles, 3 subpolynomials
+    assert_eq!(
+        query.proof_counts(&accessor),
+        ProofCounts {
+            sumcheck_max_multiplicands: 3,
+            result_columns: 2,
+            anchored_mles: 4,
+            intermediate_mles: 7,
+            sumcheck_subpolynomials: 8,
+            post_result_challenges: 2,
+            window_aggregates: 0,
+        }
+    );
+}
+
+#[test]
+fn strict_planning_rejects_a_limit_or_an_offset_without_an_order_by() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [5_i64],
+        ),
+        0,
+    );
+    let plan_strict = |query: &str| {
+        QueryExpr::<RistrettoPoint>::try_new_strict(
+            SelectStatementParser::new().parse(query).unwrap(),
+            t.schema_id(),
+            &accessor,
+        )
+    };
+    for query in [
+        "select a from sxt_tab limit 5",
+        "select a from sxt_tab limit 5 offset 3",
+        "select a from sxt_tab where a = 5 offset 3",
+    ] {
+        assert!(
+            matches!(
+                plan_strict(query),
+                Err(ConversionError::NonDeterministicOrder)
+            ),
+            "{query}"
+        );
+        assert!(
+            QueryExpr::<RistrettoPoint>::try_new(
+                SelectStatementParser::new().parse(query).unwrap(),
+                t.schema_id(),
+                &accessor,
+            )
+            .is_ok(),
+            "{query}"
+        );
+    }
+    for query in [
+        "select a from sxt_tab order by a limit 5",
+        "select a from sxt_tab order by a desc limit 5 offset 3",
+        "select a from sxt_tab",
+    ] {
+        assert_eq!(
+            plan_strict(query).unwrap(),
+            query_to_provable_ast(t, query, &accessor),
+            "{query}"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::sql::transform::{CompositionExpr, GroupByExpr, OrderByExprs, SelectExpr, SliceExpr};
+use crate::sql::transform::{
+    CompositionExpr, GroupByExpr, OrderByExprs, PageExpr, SelectExpr, SliceExpr,
+};
 use proof_of_sql_parser::{
     intermediate_ast::{AliasedResultExpr, Expression, OrderBy, Slice},
     Identifier,
@@ -74,6 +76,14 @@ impl ResultExprBuilder {
         self
     }
 
+    /// Chain a new `PageExpr` to the current `ResultExpr`.
+    pub fn add_page_expr(mut self, page: Option<PageExpr>) -> Self {
+        if let Some(page) = page {
+            self.composition.add(Box::new(page));
+        }
+        self
+    }
+
     /// Build a `ResultExpr` from the current state of the builder.
     pub fn build(self) -> crate::sql::transform::ResultExpr {
         crate::sql::transform::ResultExpr::new(Box::new(self.composition))
@@ -0,0 +1,136 @@
+use crate::base::{database::ColumnType, math::decimal::MAX_SUPPORTED_PRECISION};
+use proof_of_sql_parser::intermediate_ast::BinaryOperator;
+
+/// The rules for which column types the operands of a binary operator may have,
+/// e.g. whether an integer may be compared with a decimal, which promotes the integer to a decimal.
+///
+/// Queries are planned with [`DefaultTypeSystem`] unless another type system is passed to
+/// [`QueryExpr::try_new_with_type_system`](super::QueryExpr::try_new_with_type_system),
+/// such as one that forbids implicit promotions that an organization does not want.
+///
+/// Note: the provable expressions still check their operands with the default rules,
+/// so a custom type system can only reject operations that the default accepts.
+/// An operation that only the custom type system accepts fails to plan nonetheless.
+pub trait TypeSystem {
+    /// Whether `binary_operator` may be applied to operands of types `left_dtype` and `right_dtype`.
+    ///
+    /// Defaults to the rules of [`DefaultTypeSystem`].
+    fn type_check_binary_operation(
+        &self,
+        left_dtype: &ColumnType,
+        right_dtype: &ColumnType,
+        binary_operator: BinaryOperator,
+    ) -> bool {
+        type_check_binary_operation(left_dtype, right_dtype, binary_operator)
+    }
+}
+
+/// The type system that queries are planned with by default.
+///
+/// It promotes integers and decimals to a common scale, as long as the promoted
+/// type still fits in the precision that the operation supports.
+///
+/// Integers of different widths are compared without narrowing either side. So a literal outside
+/// the range of a column's type, as in `smallint_column = 100000`, compares like any other:
+/// the equality holds for no row and `smallint_column < 100000` holds for every row.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultTypeSystem;
+
+impl TypeSystem for DefaultTypeSystem {}
+
+pub(crate) fn type_check_binary_operation(
+    left_dtype: &ColumnType,
+    right_dtype: &ColumnType,
+    binary_operator: BinaryOperator,
+) -> bool {
+    match binary_operator {
+        BinaryOperator::And | BinaryOperator::Or => {
+            matches!(
+                (left_dtype, right_dtype),
+                (ColumnType::Boolean, ColumnType::Boolean)
+            )
+        }
+        BinaryOperator::Equal => {
+            matches!(
+                (left_dtype, right_dtype),
+                (ColumnType::Binary(left_length), ColumnType::Binary(right_length))
+                    if left_length == right_length
+            ) || matches!(
+                (left_dtype, right_dtype),
+                (ColumnType::VarChar, ColumnType::VarChar)
+                    | (ColumnType::Boolean, ColumnType::Boolean)
+                    | (_, ColumnType::Scalar)
+                    | (ColumnType::Scalar, _)
+            ) || (left_dtype.is_numeric()
+                && right_dtype.is_numeric()
+                // The side with the smaller scale is scaled up before comparing.
+                && fits_in_precision_after_scaling(left_dtype, right_dtype, MAX_SUPPORTED_PRECISION)
+                && fits_in_precision_after_scaling(right_dtype, left_dtype, MAX_SUPPORTED_PRECISION))
+        }
+        BinaryOperator::GreaterThanOrEqual | BinaryOperator::LessThanOrEqual => {
+            if left_dtype == &ColumnType::VarChar || right_dtype == &ColumnType::VarChar {
+                return false;
+            }
+            // Due to constraints in bitwise_verification we limit the precision of decimal types to 38
+            if let ColumnType::Decimal75(precision, _) = left_dtype {
+                if precision.value() > 38 {
+                    return false;
+                }
+            }
+            if let ColumnType::Decimal75(precision, _) = right_dtype {
+                if precision.value() > 38 {
+                    return false;
+                }
+            }
+            // The side with the smaller scale is scaled up before comparing,
+            // so it must also fit within 38 digits once scaled.
+            // For example, comparing an `Int128` column with the literal `1.5` is not supported.
+            if !fits_in_precision_after_scaling(left_dtype, right_dtype, 38)
+                || !fits_in_precision_after_scaling(right_dtype, left_dtype, 38)
+            {
+                return false;
+            }
+            left_dtype.is_numeric() && right_dtype.is_numeric()
+                || matches!(
+                    (left_dtype, right_dtype),
+                    (ColumnType::Boolean, ColumnType::Boolean)
+                )
+        }
+        BinaryOperator::Multiply
+        | BinaryOperator::Division
+        | BinaryOperator::Subtract
+        | BinaryOperator::Add => left_dtype.is_numeric() && right_dtype.is_numeric(),
+        BinaryOperator::IntegerDivision => {
+            let is_integer = |dtype: &ColumnType| {
+                matches!(
+                    dtype,
+                    ColumnType::SmallInt
+                        | ColumnType::Int
+                        | ColumnType::BigInt
+                        | ColumnType::Int128
+                )
+            };
+            is_integer(left_dtype) && is_integer(right_dtype)
+        }
+    }
+}
+
+/// Returns false if `dtype` needs to be scaled up to the scale of `other_dtype`
+/// and its precision would then exceed `max_precision`.
+fn fits_in_precision_after_scaling(
+    dtype: &ColumnType,
+    other_dtype: &ColumnType,
+    max_precision: u8,
+) -> bool {
+    match (
+        dtype.precision_value(),
+        dtype.scale().unwrap_or(0),
+        other_dtype.scale().unwrap_or(0),
+    ) {
+        (Some(precision), scale, other_scale) if scale < other_scale => {
+            i16::from(precision) + i16::from(other_scale) - i16::from(scale)
+                <= i16::from(max_precision)
+        }
+        _ => true,
+    }
+}
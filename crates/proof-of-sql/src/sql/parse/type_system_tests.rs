@@ -0,0 +1,91 @@
+use super::{ConversionError, DefaultTypeSystem, QueryExpr, TypeSystem};
+use crate::{
+    base::database::{owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::intermediate_ast::BinaryOperator;
+
+/// A type system that never promotes an integer to a decimal
+struct NoIntegerToDecimalPromotion;
+
+impl TypeSystem for NoIntegerToDecimalPromotion {
+    fn type_check_binary_operation(
+        &self,
+        left_dtype: &ColumnType,
+        right_dtype: &ColumnType,
+        binary_operator: BinaryOperator,
+    ) -> bool {
+        let is_decimal = |dtype: &ColumnType| matches!(dtype, ColumnType::Decimal75(_, _));
+        is_decimal(left_dtype) == is_decimal(right_dtype)
+            && DefaultTypeSystem.type_check_binary_operation(
+                left_dtype,
+                right_dtype,
+                binary_operator,
+            )
+    }
+}
+
+fn accessor_with_table() -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            decimal75("d", 10, 2, [100, 250, 300]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    type_system: &dyn TypeSystem,
+) -> Result<QueryExpr<DoryCommitment>, ConversionError> {
+    QueryExpr::try_new_with_type_system(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor_with_table(),
+        type_system,
+    )
+}
+
+#[test]
+fn the_default_type_system_plans_like_try_new() {
+    let accessor = accessor_with_table();
+    for sql in [
+        "SELECT a FROM t WHERE a = 2.5",
+        "SELECT a + d AS s FROM t WHERE d >= 1",
+    ] {
+        assert_eq!(
+            plan(sql, &DefaultTypeSystem).unwrap(),
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap()
+        );
+    }
+}
+
+#[test]
+fn a_custom_type_system_can_reject_an_operation_that_the_default_accepts() {
+    for sql in [
+        "SELECT a FROM t WHERE a = 2.5",
+        "SELECT a + d AS s FROM t",
+        "SELECT a FROM t WHERE d IN (1, 2)",
+    ] {
+        assert!(plan(sql, &DefaultTypeSystem).is_ok(), "{sql}");
+        assert!(
+            matches!(
+                plan(sql, &NoIntegerToDecimalPromotion),
+                Err(ConversionError::DataTypeMismatch(_, _))
+            ),
+            "{sql}"
+        );
+    }
+    // Operations without a promotion are still accepted.
+    assert!(plan(
+        "SELECT a + 1 AS b FROM t WHERE d >= 1.5",
+        &NoIntegerToDecimalPromotion
+    )
+    .is_ok());
+}
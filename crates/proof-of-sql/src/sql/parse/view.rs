@@ -0,0 +1,402 @@
+use super::{ConversionError, ConversionResult, QueryExpr};
+use crate::base::{
+    commitment::Commitment,
+    database::{SchemaAccessor, TableRef},
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, BinaryOperator, Expression, OrderBy, SelectResultExpr, SetExpression,
+        TableExpression,
+    },
+    Identifier, ResourceId, SelectStatement,
+};
+use std::collections::HashMap;
+
+/// A saved query that other queries can select from by name, as if it were a table.
+///
+/// A view is not materialized. A query of a view is planned as a query of the view's table instead,
+/// with the view's `WHERE` clause added to its own and its columns replaced by the view's expressions,
+/// so the proof is over the view's table and is verified against the commitment to that table.
+///
+/// This is why the definition can only filter and compute expressions row by row: it cannot have a
+/// `GROUP BY`, `ORDER BY` or `LIMIT`, nor aggregations, window functions or `SELECTED()`.
+/// For the same reason, a query of a view cannot select `SELECTED()`: it would show every row of the
+/// view's table, including the rows that the view's `WHERE` clause hides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct View {
+    definition: SelectStatement,
+}
+
+impl View {
+    /// Creates a view that selects `definition`.
+    ///
+    /// Errors with [`ConversionError::InvalidView`] if `definition` cannot be inlined into other queries.
+    pub fn try_new(definition: SelectStatement) -> ConversionResult<Self> {
+        let SetExpression::Query {
+            result_exprs,
+            group_by,
+            ..
+        } = definition.expr.as_ref();
+        if !group_by.is_empty() {
+            return Err(invalid_view("a view cannot have a GROUP BY"));
+        }
+        if !definition.order_by.is_empty() || definition.slice.is_some() {
+            return Err(invalid_view("a view cannot have an ORDER BY or a LIMIT"));
+        }
+        let is_row_wise = result_exprs.iter().all(|result_expr| match result_expr {
+            SelectResultExpr::ALL => true,
+            SelectResultExpr::AliasedResultExpr(aliased_expr) => is_row_wise(&aliased_expr.expr),
+        });
+        if !is_row_wise {
+            return Err(invalid_view(
+                "the columns of a view must be computed row by row",
+            ));
+        }
+        Ok(Self { definition })
+    }
+
+    /// The query that the view selects.
+    pub fn definition(&self) -> &SelectStatement {
+        &self.definition
+    }
+}
+
+impl std::str::FromStr for View {
+    type Err = ConversionError;
+
+    fn from_str(sql: &str) -> Result<Self, Self::Err> {
+        let definition: SelectStatement = sql
+            .parse()
+            .map_err(|e| ConversionError::ParseError(format!("{e}")))?;
+        Self::try_new(definition)
+    }
+}
+
+/// The views that queries can select from, keyed by their names.
+///
+/// Views may select from other views, and a view is resolved by name each time it is used,
+/// so redefining a view also changes the views that select from it.
+/// A view hides any table that has the same name.
+#[derive(Debug, Clone, Default)]
+pub struct ViewRegistry {
+    views: HashMap<TableRef, View>,
+}
+
+impl ViewRegistry {
+    /// Creates a registry without any views.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `view` under `name`, replacing any view that already has that name,
+    /// and returns the plan of the view.
+    ///
+    /// The tables that the view names without a schema are looked up in the schema of `name`.
+    /// This errors with [`ConversionError::RecursiveView`] if the view would select from itself,
+    /// directly or through other views, and with any other [`ConversionError`] if it cannot be planned.
+    /// The registry is unchanged if this errors.
+    pub fn try_register<C: Commitment>(
+        &mut self,
+        name: TableRef,
+        view: View,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<QueryExpr<C>> {
+        let definition =
+            self.inline_views(view.definition.clone(), name.schema_id(), &mut vec![name])?;
+        let plan = QueryExpr::try_new(definition, name.schema_id(), schema_accessor)?;
+        self.views.insert(name, view);
+        Ok(plan)
+    }
+
+    /// Returns the view named `name`, if there is one.
+    pub fn get(&self, name: TableRef) -> Option<&View> {
+        self.views.get(&name)
+    }
+
+    /// Removes the view named `name`, returning it if there was one.
+    pub fn remove(&mut self, name: TableRef) -> Option<View> {
+        self.views.remove(&name)
+    }
+
+    /// Returns the number of views.
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    /// Returns `true` if there are no views.
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// Parse an intermediate AST `SelectStatement`, which may select from a view, into a `QueryExpr`.
+    ///
+    /// This is [`QueryExpr::try_new`] applied to the query with its views inlined.
+    pub fn try_plan<C: Commitment>(
+        &self,
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<QueryExpr<C>> {
+        let ast = self.try_inline(ast, default_schema)?;
+        QueryExpr::try_new(ast, default_schema, schema_accessor)
+    }
+
+    /// Rewrites `ast` so that it selects from the table of the view that it selects from, if any.
+    ///
+    /// Errors with [`ConversionError::MissingColumn`] if `ast` uses a column that the view does not have.
+    pub fn try_inline(
+        &self,
+        ast: SelectStatement,
+        default_schema: Identifier,
+    ) -> ConversionResult<SelectStatement> {
+        self.inline_views(ast, default_schema, &mut Vec::new())
+    }
+
+    /// Inlines the views of `ast`, where `expanding` holds the views whose definitions are being inlined.
+    fn inline_views(
+        &self,
+        ast: SelectStatement,
+        default_schema: Identifier,
+        expanding: &mut Vec<TableRef>,
+    ) -> ConversionResult<SelectStatement> {
+        let SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+        } = *ast.expr;
+        let name = match from.as_slice() {
+            [table_expr] => match table_expr.as_ref() {
+                TableExpression::Named {
+                    table,
+                    schema,
+                    catalog: None,
+                } => Some(TableRef::new(ResourceId::new(
+                    schema.unwrap_or(default_schema),
+                    *table,
+                ))),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(name) = name.filter(|name| expanding.contains(name)) {
+            return Err(ConversionError::RecursiveView(Box::new(name.resource_id())));
+        }
+        let Some((name, view)) = name.and_then(|name| Some((name, self.views.get(&name)?))) else {
+            return Ok(SelectStatement {
+                expr: Box::new(SetExpression::Query {
+                    result_exprs,
+                    from,
+                    where_expr,
+                    group_by,
+                }),
+                order_by: ast.order_by,
+                slice: ast.slice,
+            });
+        };
+
+        expanding.push(name);
+        let definition = self.inline_views(view.definition.clone(), name.schema_id(), expanding)?;
+        expanding.pop();
+        let SetExpression::Query {
+            result_exprs: view_exprs,
+            from: view_from,
+            where_expr: view_where,
+            ..
+        } = *definition.expr;
+        let columns = ViewColumns::new(name, &view_exprs);
+
+        let result_exprs = result_exprs
+            .into_iter()
+            .map(|result_expr| match result_expr {
+                SelectResultExpr::ALL => Ok(view_exprs.clone()),
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                    Ok(vec![SelectResultExpr::AliasedResultExpr(
+                        AliasedResultExpr::new(
+                            columns.substitute(&aliased_expr.expr)?,
+                            aliased_expr.alias,
+                        ),
+                    )])
+                }
+            })
+            .collect::<ConversionResult<Vec<_>>>()?
+            .concat();
+        let where_expr = match (
+            view_where,
+            where_expr
+                .map(|expr| columns.substitute(&expr).map(Box::new))
+                .transpose()?,
+        ) {
+            (Some(left), Some(right)) => Some(Box::new(Expression::Binary {
+                op: BinaryOperator::And,
+                left,
+                right,
+            })),
+            (view_where, where_expr) => view_where.or(where_expr),
+        };
+        let group_by = group_by
+            .into_iter()
+            .map(|id| columns.column(id))
+            .collect::<ConversionResult<_>>()?;
+        // The tables of the view are resolved in the schema of the view rather than the default schema.
+        let from = view_from
+            .into_iter()
+            .map(|table_expr| match *table_expr {
+                TableExpression::Named {
+                    table,
+                    schema,
+                    catalog,
+                } => Box::new(TableExpression::Named {
+                    table,
+                    schema: schema.or(Some(name.schema_id())),
+                    catalog,
+                }),
+            })
+            .collect();
+        Ok(SelectStatement {
+            expr: Box::new(SetExpression::Query {
+                result_exprs,
+                from,
+                where_expr,
+                group_by,
+            }),
+            order_by: ast.order_by,
+            slice: ast.slice,
+        })
+    }
+}
+
+/// The columns of a view, as expressions of the columns of the view's table.
+struct ViewColumns<'a> {
+    name: TableRef,
+    expressions: HashMap<Identifier, &'a Expression>,
+    /// Whether the view selects `*`, so that every column of its table is a column of the view.
+    selects_all: bool,
+}
+
+impl<'a> ViewColumns<'a> {
+    fn new(name: TableRef, view_exprs: &'a [SelectResultExpr]) -> Self {
+        Self {
+            name,
+            expressions: view_exprs
+                .iter()
+                .filter_map(|result_expr| match result_expr {
+                    SelectResultExpr::ALL => None,
+                    SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                        Some((aliased_expr.alias, aliased_expr.expr.as_ref()))
+                    }
+                })
+                .collect(),
+            selects_all: view_exprs.contains(&SelectResultExpr::ALL),
+        }
+    }
+
+    /// The expression that the column `id` of the view is computed by.
+    fn expression(&self, id: Identifier) -> ConversionResult<Expression> {
+        match self.expressions.get(&id) {
+            Some(expr) => Ok((*expr).clone()),
+            None if self.selects_all => Ok(Expression::Column(id)),
+            None => Err(ConversionError::MissingColumn(
+                Box::new(id),
+                Box::new(self.name.resource_id()),
+            )),
+        }
+    }
+
+    /// The column of the view's table that the column `id` of the view is.
+    ///
+    /// Errors if the column of the view is computed by any other expression,
+    /// since only columns can be grouped, partitioned or sorted by.
+    fn column(&self, id: Identifier) -> ConversionResult<Identifier> {
+        match self.expression(id)? {
+            Expression::Column(column) => Ok(column),
+            _ => Err(ConversionError::InvalidExpression(format!(
+                "column '{id}' of view '{}' is not a column of its table, so it cannot be grouped or sorted by",
+                self.name
+            ))),
+        }
+    }
+
+    /// Replaces the columns of the view in `expr` with the expressions that they are computed by.
+    ///
+    /// Errors with [`ConversionError::InvalidView`] on `SELECTED()`, which keeps every row of the
+    /// view's table rather than only the rows of the view.
+    fn substitute(&self, expr: &Expression) -> ConversionResult<Expression> {
+        let substitute = |expr: &Expression| self.substitute(expr).map(Box::new);
+        Ok(match expr {
+            Expression::Literal(_) | Expression::Wildcard => expr.clone(),
+            Expression::Selected => {
+                return Err(invalid_view(
+                    "a query of a view cannot select SELECTED(), which shows the rows that the view hides",
+                ))
+            }
+            Expression::Column(id) => self.expression(*id)?,
+            Expression::Unary { op, expr } => Expression::Unary {
+                op: *op,
+                expr: substitute(expr)?,
+            },
+            Expression::Binary { op, left, right } => Expression::Binary {
+                op: *op,
+                left: substitute(left)?,
+                right: substitute(right)?,
+            },
+            Expression::Aggregation { op, expr, filter } => Expression::Aggregation {
+                op: op.clone(),
+                expr: substitute(expr)?,
+                filter: filter.as_deref().map(substitute).transpose()?,
+            },
+            Expression::Tuple(exprs) => Expression::Tuple(
+                exprs
+                    .iter()
+                    .map(|expr| substitute(expr))
+                    .collect::<ConversionResult<_>>()?,
+            ),
+            Expression::InList { expr, list } => Expression::InList {
+                expr: substitute(expr)?,
+                list: list.clone(),
+            },
+            Expression::WindowAggregation {
+                op,
+                expr,
+                partition_by,
+            } => Expression::WindowAggregation {
+                op: *op,
+                expr: substitute(expr)?,
+                partition_by: partition_by
+                    .iter()
+                    .map(|id| self.column(*id))
+                    .collect::<ConversionResult<_>>()?,
+            },
+            Expression::RowNumber { order_by } => Expression::RowNumber {
+                order_by: order_by
+                    .iter()
+                    .map(|order_by| {
+                        Ok(OrderBy {
+                            expr: self.column(order_by.expr)?,
+                            direction: order_by.direction,
+                        })
+                    })
+                    .collect::<ConversionResult<_>>()?,
+            },
+        })
+    }
+}
+
+/// Whether `expr` is computed from each row alone, rather than from the rows that match the `WHERE` clause.
+fn is_row_wise(expr: &Expression) -> bool {
+    match expr {
+        Expression::Literal(_) | Expression::Column(_) => true,
+        Expression::Unary { expr, .. } | Expression::InList { expr, .. } => is_row_wise(expr),
+        Expression::Binary { left, right, .. } => is_row_wise(left) && is_row_wise(right),
+        Expression::Tuple(exprs) => exprs.iter().all(|expr| is_row_wise(expr)),
+        Expression::Wildcard
+        | Expression::Aggregation { .. }
+        | Expression::Selected
+        | Expression::WindowAggregation { .. }
+        | Expression::RowNumber { .. } => false,
+    }
+}
+
+fn invalid_view(reason: &str) -> ConversionError {
+    ConversionError::InvalidView(reason.to_string())
+}
@@ -0,0 +1,224 @@
+use super::{ConversionError, QueryExpr, View, ViewRegistry};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    },
+    sql::proof::VerifiableQueryResult,
+};
+use ark_std::test_rng;
+use arrow::record_batch::RecordBatch;
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 5, 2, 7, 3]),
+            varchar("b", ["one", "five", "two", "seven", "three"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryExpr<DoryCommitment> {
+    QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), accessor).unwrap()
+}
+
+fn register(
+    views: &mut ViewRegistry,
+    name: &str,
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> Result<QueryExpr<DoryCommitment>, ConversionError> {
+    views.try_register(name.parse().unwrap(), sql.parse()?, accessor)
+}
+
+#[test]
+fn we_can_query_a_filtered_view_and_verify_the_composed_result() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let mut views = ViewRegistry::new();
+    register(
+        &mut views,
+        "sxt.big",
+        "SELECT a, b AS name FROM t WHERE a >= 2",
+        &accessor,
+    )
+    .unwrap();
+
+    let query = views
+        .try_plan::<DoryCommitment>(
+            "SELECT name FROM big WHERE a <= 5".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+    assert_eq!(
+        query,
+        plan("SELECT b AS name FROM t WHERE a >= 2 AND a <= 5", &accessor)
+    );
+
+    let result =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    let table = result
+        .verify(query.proof_expr(), &accessor, &(&setup).into())
+        .unwrap()
+        .table;
+    let table = query
+        .result()
+        .transform_results(RecordBatch::try_from(table).unwrap())
+        .unwrap();
+    assert_eq!(
+        OwnedTable::<DoryScalar>::try_from(table).unwrap(),
+        owned_table([varchar("name", ["five", "two", "three"])])
+    );
+}
+
+#[test]
+fn we_can_query_a_view_of_a_view_with_computed_columns() {
+    let accessor = accessor_with_table(DoryProverPublicSetup::rand(4, 3, &mut test_rng()));
+    let mut views = ViewRegistry::new();
+    register(
+        &mut views,
+        "sxt.small",
+        "SELECT a + 1 AS c, b FROM t WHERE a <= 5",
+        &accessor,
+    )
+    .unwrap();
+    register(
+        &mut views,
+        "sxt.mid",
+        "SELECT * FROM small WHERE b = 'five' OR b = 'three'",
+        &accessor,
+    )
+    .unwrap();
+    assert_eq!(views.len(), 2);
+
+    let query = views
+        .try_plan::<DoryCommitment>(
+            "SELECT b, c FROM mid".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+    assert_eq!(
+        query,
+        plan(
+            "SELECT b, a + 1 AS c FROM t WHERE a <= 5 AND (b = 'five' OR b = 'three')",
+            &accessor
+        )
+    );
+
+    // Queries that do not select from a view are planned as they are.
+    let query = views
+        .try_plan::<DoryCommitment>(
+            "SELECT b FROM t WHERE a = 7".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+    assert_eq!(query, plan("SELECT b FROM t WHERE a = 7", &accessor));
+}
+
+#[test]
+fn we_cannot_register_a_recursive_view() {
+    let accessor = accessor_with_table(DoryProverPublicSetup::rand(4, 3, &mut test_rng()));
+    let mut views = ViewRegistry::new();
+    assert!(matches!(
+        register(&mut views, "sxt.v", "SELECT * FROM v", &accessor),
+        Err(ConversionError::RecursiveView(_))
+    ));
+    assert!(views.is_empty());
+
+    register(
+        &mut views,
+        "sxt.v1",
+        "SELECT * FROM t WHERE a > 1",
+        &accessor,
+    )
+    .unwrap();
+    register(
+        &mut views,
+        "sxt.v2",
+        "SELECT * FROM v1 WHERE a < 7",
+        &accessor,
+    )
+    .unwrap();
+    assert!(matches!(
+        register(&mut views, "sxt.v1", "SELECT * FROM v2", &accessor),
+        Err(ConversionError::RecursiveView(_))
+    ));
+    // The failed registration leaves the existing view in place.
+    assert_eq!(
+        views.get("sxt.v1".parse().unwrap()),
+        Some(&"SELECT * FROM t WHERE a > 1".parse().unwrap())
+    );
+}
+
+#[test]
+fn we_cannot_use_a_view_that_cannot_be_inlined_or_a_column_that_it_does_not_have() {
+    let accessor = accessor_with_table(DoryProverPublicSetup::rand(4, 3, &mut test_rng()));
+    for sql in [
+        "SELECT a FROM t GROUP BY a",
+        "SELECT a FROM t ORDER BY a",
+        "SELECT a FROM t LIMIT 2",
+        "SELECT COUNT(*) AS n FROM t",
+        "SELECT SUM(a) OVER () AS s FROM t",
+    ] {
+        assert!(
+            matches!(sql.parse::<View>(), Err(ConversionError::InvalidView(_))),
+            "{sql}"
+        );
+    }
+
+    let mut views = ViewRegistry::new();
+    register(&mut views, "sxt.v", "SELECT a FROM t", &accessor).unwrap();
+    assert!(matches!(
+        views.try_plan::<DoryCommitment>(
+            "SELECT b FROM v".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        ),
+        Err(ConversionError::MissingColumn(..))
+    ));
+}
+
+#[test]
+fn we_cannot_show_the_rows_that_a_view_hides_with_selected() {
+    let accessor = accessor_with_table(DoryProverPublicSetup::rand(4, 3, &mut test_rng()));
+    let mut views = ViewRegistry::new();
+    register(
+        &mut views,
+        "sxt.big",
+        "SELECT * FROM t WHERE a >= 2",
+        &accessor,
+    )
+    .unwrap();
+    for sql in [
+        "SELECT *, SELECTED() AS s FROM big",
+        "SELECT a, SELECTED() AS s FROM big WHERE a <= 5",
+        "SELECT SELECTED() AS s FROM big ORDER BY a LIMIT 2",
+    ] {
+        assert!(
+            matches!(
+                views.try_plan::<DoryCommitment>(
+                    sql.parse().unwrap(),
+                    "sxt".parse().unwrap(),
+                    &accessor,
+                ),
+                Err(ConversionError::InvalidView(_))
+            ),
+            "{sql}"
+        );
+    }
+    // The same query of the table is fine.
+    plan("SELECT *, SELECTED() AS s FROM t WHERE a >= 2", &accessor);
+}
@@ -1,14 +1,16 @@
-use super::ConversionError;
+use super::{type_check_binary_operation, ConversionError};
 use crate::{
     base::{
         commitment::Commitment,
         database::{ColumnRef, ColumnType, LiteralValue},
-        math::decimal::{try_into_to_scalar, Precision},
+        math::decimal::{literal_precision_and_scale, try_into_to_scalar, Precision},
+        scalar::Scalar,
     },
     sql::ast::{ColumnExpr, ProvableExpr, ProvableExprPlan},
 };
 use proof_of_sql_parser::{
     intermediate_ast::{BinaryOperator, Expression, Literal, UnaryOperator},
+    intermediate_decimal::IntermediateDecimal,
     Identifier,
 };
 use std::collections::HashMap;
@@ -42,6 +44,15 @@ impl<'a> WhereExprBuilder<'a> {
             })
             .transpose()
     }
+
+    /// Builds a `proof_of_sql::sql::ast::ProvableExprPlan` from an `Expression` of any type,
+    /// such as an operand of a provable division in the result.
+    pub fn build_expr<C: Commitment>(
+        &self,
+        expr: Expression,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        self.visit_expr(expr)
+    }
 }
 
 // Private interface
@@ -55,6 +66,7 @@ impl WhereExprBuilder<'_> {
             Expression::Literal(lit) => self.visit_literal(lit),
             Expression::Binary { op, left, right } => self.visit_binary_expr(op, *left, *right),
             Expression::Unary { op, expr } => self.visit_unary_expr(op, *expr),
+            Expression::InList { expr, list } => self.visit_in_list(*expr, list),
             _ => panic!("The parser must ensure that the expression is a boolean expression"),
         }
     }
@@ -79,9 +91,7 @@ impl WhereExprBuilder<'_> {
             Literal::BigInt(i) => Ok(ProvableExprPlan::new_literal(LiteralValue::BigInt(i))),
             Literal::Int128(i) => Ok(ProvableExprPlan::new_literal(LiteralValue::Int128(i))),
             Literal::Decimal(d) => {
-                let scale = d.scale();
-                let precision = Precision::new(d.precision())
-                    .map_err(|_| ConversionError::InvalidPrecision(d.precision()))?;
+                let (precision, scale) = literal_precision_and_scale(&d)?;
                 Ok(ProvableExprPlan::new_literal(LiteralValue::Decimal75(
                     precision,
                     scale,
@@ -92,9 +102,55 @@ impl WhereExprBuilder<'_> {
                 s.clone(),
                 s.into(),
             )))),
+            Literal::Null => Err(ConversionError::null_literal()),
         }
     }
 
+    /// Builds `expr IN (list)` as a disjunction of equalities of `expr` with the literals of the list.
+    ///
+    /// The literals must be comparable with each other, so a list can't mix strings and numbers.
+    /// When the list mixes integer and decimal literals, all of them are promoted to a common
+    /// `Decimal75` type first. See [`promote_in_list_literals`].
+    fn visit_in_list<C: Commitment>(
+        &self,
+        expr: Expression,
+        list: Vec<Literal>,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let list: Vec<ProvableExprPlan<C>> = match promote_in_list_literals(&list)? {
+            Some(values) => values
+                .into_iter()
+                .map(ProvableExprPlan::new_literal)
+                .collect(),
+            None => list
+                .into_iter()
+                .map(|literal| self.visit_literal(literal))
+                .collect::<Result<_, _>>()?,
+        };
+        for (i, element) in list.iter().enumerate() {
+            let datatype = element.data_type();
+            for other_datatype in list[..i].iter().map(ProvableExpr::<C>::data_type) {
+                if !type_check_binary_operation(&other_datatype, &datatype, BinaryOperator::Equal) {
+                    return Err(ConversionError::DataTypeMismatch(
+                        other_datatype.to_string(),
+                        datatype.to_string(),
+                    ));
+                }
+            }
+        }
+        if list.is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "an in list must not be empty".into(),
+            ));
+        }
+        let equalities = list
+            .into_iter()
+            .map(|element| {
+                ProvableExprPlan::try_new_equals(self.visit_expr(expr.clone())?, element)
+            })
+            .collect::<Result<_, _>>()?;
+        ProvableExprPlan::try_new_or_any(equalities)
+    }
+
     fn visit_unary_expr<C: Commitment>(
         &self,
         op: UnaryOperator,
@@ -103,6 +159,14 @@ impl WhereExprBuilder<'_> {
         let expr = self.visit_expr(expr);
         match op {
             UnaryOperator::Not => ProvableExprPlan::try_new_not(expr?),
+            UnaryOperator::Sign => ProvableExprPlan::try_new_sign(expr?),
+            UnaryOperator::Length => Err(ConversionError::Unprovable(
+                "length requires committed byte lengths, which table commitments do not carry"
+                    .into(),
+            )),
+            UnaryOperator::IsTrue => ProvableExprPlan::try_new_is_true(expr?),
+            UnaryOperator::IsFalse => ProvableExprPlan::try_new_is_false(expr?),
+            UnaryOperator::IsUnknown => ProvableExprPlan::try_new_is_unknown(expr?),
         }
     }
 
@@ -123,11 +187,21 @@ impl WhereExprBuilder<'_> {
                 let right = self.visit_expr(right);
                 ProvableExprPlan::try_new_or(left?, right?)
             }
-            BinaryOperator::Equal => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                ProvableExprPlan::try_new_equals(left?, right?)
-            }
+            BinaryOperator::Equal => match (left, right) {
+                (Expression::Tuple(left), Expression::Tuple(right)) => {
+                    let left = left.into_iter().map(|expr| self.visit_expr(*expr));
+                    let right = right.into_iter().map(|expr| self.visit_expr(*expr));
+                    ProvableExprPlan::try_new_tuple_equals(
+                        left.collect::<Result<_, _>>()?,
+                        right.collect::<Result<_, _>>()?,
+                    )
+                }
+                (left, right) => {
+                    let left = self.visit_expr(left);
+                    let right = self.visit_expr(right);
+                    ProvableExprPlan::try_new_equals(left?, right?)
+                }
+            },
             BinaryOperator::GreaterThanOrEqual => {
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
@@ -141,10 +215,68 @@ impl WhereExprBuilder<'_> {
             BinaryOperator::Add
             | BinaryOperator::Subtract
             | BinaryOperator::Multiply
-            | BinaryOperator::Division => Err(ConversionError::Unprovable(format!(
+            | BinaryOperator::Division
+            | BinaryOperator::IntegerDivision => Err(ConversionError::Unprovable(format!(
                 "Binary operator {:?} is not supported in the where clause",
                 op
             ))),
         }
     }
 }
+
+/// Unifies the literals of an `IN` list that mixes integers and decimals to a common `Decimal75` type.
+///
+/// The common type has the largest scale of the literals, and enough precision for
+/// the largest number of integral digits of the literals at that scale.
+/// Returns `None` for a list without decimals, or with any non-numeric literal,
+/// since it either needs no promotion or is rejected when the list is type checked.
+fn promote_in_list_literals<S: Scalar>(
+    list: &[Literal],
+) -> Result<Option<Vec<LiteralValue<S>>>, ConversionError> {
+    if !list
+        .iter()
+        .any(|literal| matches!(literal, Literal::Decimal(_)))
+    {
+        return Ok(None);
+    }
+    let Some(decimals) = list
+        .iter()
+        .map(|literal| match literal {
+            Literal::BigInt(i) => Some(IntermediateDecimal::from(*i)),
+            Literal::Int128(i) => Some(IntermediateDecimal::from(*i)),
+            Literal::Decimal(d) => Some(d.clone()),
+            Literal::Boolean(_) | Literal::VarChar(_) | Literal::Null => None,
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+    let precisions_and_scales = decimals
+        .iter()
+        .map(literal_precision_and_scale)
+        .collect::<Result<Vec<_>, _>>()?;
+    let scale = precisions_and_scales
+        .iter()
+        .map(|&(_, scale)| scale)
+        .max()
+        .unwrap_or(0);
+    let integral_digits = precisions_and_scales
+        .iter()
+        .map(|&(precision, scale)| precision.value() as i16 - scale as i16)
+        .max()
+        .unwrap_or(0);
+    let precision = (integral_digits + scale as i16).clamp(1, u8::MAX as i16) as u8;
+    let precision =
+        Precision::new(precision).map_err(|_| ConversionError::InvalidPrecision(precision))?;
+    decimals
+        .iter()
+        .map(|d| {
+            Ok(LiteralValue::Decimal75(
+                precision,
+                scale,
+                try_into_to_scalar(d, precision, scale)?,
+            ))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
@@ -4,6 +4,7 @@ mod tests {
         base::{
             database::{ColumnRef, ColumnType, LiteralValue},
             math::decimal::Precision,
+            scalar::Curve25519Scalar,
         },
         record_batch,
         sql::{
@@ -16,7 +17,7 @@ mod tests {
     };
     use curve25519_dalek::RistrettoPoint;
     use proof_of_sql_parser::{
-        intermediate_ast::{BinaryOperator, Expression, Literal},
+        intermediate_ast::{BinaryOperator, Expression, Literal, UnaryOperator},
         intermediate_decimal::IntermediateDecimal,
         Identifier, SelectStatement,
     };
@@ -95,6 +96,48 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn we_can_check_whether_a_boolean_column_is_true_false_or_unknown() {
+        let column_mapping = get_column_mappings_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let is = |op, column: &str| {
+            builder.build_expr::<RistrettoPoint>(Expression::Unary {
+                op,
+                expr: Box::new(Expression::Column(Identifier::try_new(column).unwrap())),
+            })
+        };
+        let column = || {
+            ProvableExprPlan::Column(ColumnExpr::new(ColumnRef::new(
+                "sxt.sxt_tab".parse().unwrap(),
+                Identifier::try_new("boolean_column").unwrap(),
+                ColumnType::Boolean,
+            )))
+        };
+        // Since columns are never NULL, these reduce to the plain boolean tests.
+        assert_eq!(
+            is(UnaryOperator::IsTrue, "boolean_column").unwrap(),
+            column()
+        );
+        assert_eq!(
+            is(UnaryOperator::IsFalse, "boolean_column").unwrap(),
+            ProvableExprPlan::try_new_not(column()).unwrap()
+        );
+        assert_eq!(
+            is(UnaryOperator::IsUnknown, "boolean_column").unwrap(),
+            ProvableExprPlan::Literal(LiteralExpr::new(LiteralValue::Boolean(false)))
+        );
+        for op in [
+            UnaryOperator::IsTrue,
+            UnaryOperator::IsFalse,
+            UnaryOperator::IsUnknown,
+        ] {
+            assert!(matches!(
+                is(op, "varchar_column"),
+                Err(ConversionError::InvalidDataType { .. })
+            ));
+        }
+    }
+
     #[test]
     fn we_can_directly_check_nested_eq() {
         let column_mapping = get_column_mappings_for_testing();
@@ -240,6 +283,82 @@ mod tests {
         run_test_case(&column_mapping, expr_integer_to_integer);
     }
 
+    #[test]
+    fn we_promote_a_mixed_in_list_of_integers_and_decimals_to_a_common_decimal_type() {
+        let column_mapping = get_column_mappings_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let expr_in_list = Expression::InList {
+            expr: Box::new(Expression::Column(
+                Identifier::try_new("decimal_column").unwrap(),
+            )),
+            list: vec![
+                Literal::BigInt(10),
+                Literal::Decimal(IntermediateDecimal::try_from("2.5").unwrap()),
+            ],
+        };
+        let actual = builder
+            .build::<RistrettoPoint>(Some(Box::new(expr_in_list)))
+            .unwrap()
+            .unwrap();
+        let column = || {
+            ProvableExprPlan::Column(ColumnExpr::new(ColumnRef::new(
+                "sxt.sxt_tab".parse().unwrap(),
+                Identifier::try_new("decimal_column").unwrap(),
+                ColumnType::Decimal75(Precision::new(7).unwrap(), 2),
+            )))
+        };
+        let decimal = |value: i64| {
+            ProvableExprPlan::Literal(LiteralExpr::new(LiteralValue::Decimal75(
+                Precision::new(3).unwrap(),
+                1,
+                Curve25519Scalar::from(value),
+            )))
+        };
+        let expected = ProvableExprPlan::try_new_or(
+            ProvableExprPlan::try_new_equals(column(), decimal(100)).unwrap(),
+            ProvableExprPlan::try_new_equals(column(), decimal(25)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn we_cannot_mix_strings_and_numbers_in_an_in_list() {
+        let column_mapping = get_column_mappings_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let expr_in_list = Expression::InList {
+            expr: Box::new(Expression::Column(
+                Identifier::try_new("varchar_column").unwrap(),
+            )),
+            list: vec![Literal::VarChar("x".into()), Literal::BigInt(1)],
+        };
+        assert!(matches!(
+            builder.build::<RistrettoPoint>(Some(Box::new(expr_in_list))),
+            Err(ConversionError::DataTypeMismatch(..))
+        ));
+    }
+
+    #[test]
+    fn we_can_directly_check_whether_a_tuple_of_columns_eq_a_tuple_of_literals() {
+        let column_mapping = get_column_mappings_for_testing();
+        let expr_tuple_to_tuple = Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: Box::new(Expression::Tuple(vec![
+                Box::new(Expression::Column(
+                    Identifier::try_new("bigint_column").unwrap(),
+                )),
+                Box::new(Expression::Column(
+                    Identifier::try_new("varchar_column").unwrap(),
+                )),
+            ])),
+            right: Box::new(Expression::Tuple(vec![
+                Box::new(Expression::Literal(Literal::BigInt(1))),
+                Box::new(Expression::Literal(Literal::VarChar("x".into()))),
+            ])),
+        };
+        run_test_case(&column_mapping, expr_tuple_to_tuple);
+    }
+
     #[test]
     fn we_can_check_scaled_integers_eq_correctly() {
         let column_mapping = get_column_mappings_for_testing();
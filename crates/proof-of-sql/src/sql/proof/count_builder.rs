@@ -1,13 +1,14 @@
 use crate::{
     base::{bit::BitDistribution, proof::ProofError},
-    sql::proof::ProofCounts,
+    sql::proof::{ProofCounts, SharedSubexpressions},
 };
-use std::cmp::max;
+use std::{any::Any, cmp::max, hash::Hash};
 
 /// Track the number of components expected for in a query's proof
 pub struct CountBuilder<'a> {
     bit_distributions: &'a [BitDistribution],
     counts: ProofCounts,
+    shared_subexpressions: SharedSubexpressions<()>,
 }
 
 impl<'a> CountBuilder<'a> {
@@ -15,6 +16,7 @@ impl<'a> CountBuilder<'a> {
         Self {
             bit_distributions,
             counts: Default::default(),
+            shared_subexpressions: SharedSubexpressions::default(),
         }
     }
 
@@ -34,6 +36,13 @@ impl<'a> CountBuilder<'a> {
         }
     }
 
+    /// Returns `false` if an identical subexpression was already counted.
+    ///
+    /// The proof of such a subexpression is shared with the first copy, so it must not be counted again.
+    pub fn count_shared_subexpression<K: Any + Eq + Hash + Clone>(&mut self, key: &K) -> bool {
+        self.shared_subexpressions.insert(key, ())
+    }
+
     pub fn count_result_columns(&mut self, cnt: usize) {
         self.counts.result_columns += cnt;
     }
@@ -71,4 +80,9 @@ impl<'a> CountBuilder<'a> {
     pub fn count_post_result_challenges(&mut self, cnt: usize) {
         self.counts.post_result_challenges += cnt;
     }
+
+    /// Adds `cnt` to the number of window aggregates that the prover sends.
+    pub fn count_window_aggregates(&mut self, cnt: usize) {
+        self.counts.window_aggregates += cnt;
+    }
 }
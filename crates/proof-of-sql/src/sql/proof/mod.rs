@@ -13,13 +13,31 @@ pub(crate) use composite_polynomial_builder::CompositePolynomialBuilder;
 mod composite_polynomial_builder_test;
 
 mod proof_counts;
-pub(crate) use proof_counts::ProofCounts;
+pub use proof_counts::ProofCounts;
+
+mod prover_config;
+pub use prover_config::ProverConfig;
+
+mod prover_report;
+pub use prover_report::{NodeReport, ProverReport};
+#[cfg(test)]
+mod prover_report_test;
+
+mod verification_report;
+pub use verification_report::VerificationReport;
+#[cfg(test)]
+mod verification_report_test;
 
 mod verification_builder;
 pub(crate) use verification_builder::VerificationBuilder;
 #[cfg(test)]
 mod verification_builder_test;
 
+mod shared_subexpressions;
+pub(crate) use shared_subexpressions::SharedSubexpressions;
+#[cfg(test)]
+mod shared_subexpressions_test;
+
 mod provable_result_column;
 pub(crate) use provable_result_column::ProvableResultColumn;
 
@@ -43,14 +61,18 @@ pub(crate) use proof_exprs::{HonestProver, ProverEvaluate, ProverHonestyMarker};
 mod query_proof;
 #[cfg(test)]
 pub(crate) use query_proof::make_transcript;
+pub(crate) use query_proof::num_sumcheck_variables;
 #[cfg(not(feature = "test"))]
 pub(crate) use query_proof::QueryProof;
 #[cfg(feature = "test")]
 pub use query_proof::QueryProof;
+pub use query_proof::{plan_hash, PROOF_VERSION};
 #[cfg(all(test, feature = "blitzar"))]
 mod query_proof_test;
 
 mod query_result;
+/// The error returned when a proof could not be created or verified
+pub use crate::base::proof::ProofError;
 pub use query_result::{QueryData, QueryError, QueryResult};
 
 mod sumcheck_subpolynomial;
@@ -58,7 +80,13 @@ pub(crate) use sumcheck_subpolynomial::{
     SumcheckSubpolynomial, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
 };
 
+#[cfg(feature = "inspect-subpolynomials")]
+mod subpolynomial_record;
+#[cfg(feature = "inspect-subpolynomials")]
+pub use subpolynomial_record::SubpolynomialRecord;
+
 mod verifiable_query_result;
+pub(crate) use verifiable_query_result::make_empty_table;
 pub use verifiable_query_result::VerifiableQueryResult;
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test;
@@ -1,20 +1,34 @@
+#[cfg(feature = "inspect-subpolynomials")]
+use super::SubpolynomialRecord;
 use super::{
-    CompositePolynomialBuilder, SumcheckRandomScalars, SumcheckSubpolynomial,
-    SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+    CompositePolynomialBuilder, ProverReport, SharedSubexpressions, SumcheckRandomScalars,
+    SumcheckSubpolynomial, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
 };
 use crate::base::{
     bit::BitDistribution,
     commitment::{CommittableColumn, VecCommitmentExt},
+    database::Column,
     polynomial::{CompositePolynomial, MultilinearExtension},
+    proof::ProofError,
     scalar::Scalar,
 };
+use bumpalo::Bump;
 use num_traits::Zero;
+use std::{
+    any::Any,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 /// Track components used to form a query's proof
 pub struct ProofBuilder<'a, S: Scalar> {
     table_length: usize,
     num_sumcheck_variables: usize,
     bit_distributions: Vec<BitDistribution>,
+    window_aggregates: Vec<S>,
     commitment_descriptor: Vec<CommittableColumn<'a>>,
     pre_result_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
     sumcheck_subpolynomials: Vec<SumcheckSubpolynomial<'a, S>>,
@@ -26,6 +40,15 @@ pub struct ProofBuilder<'a, S: Scalar> {
     /// Note: this vector is treated as a stack and the first
     /// challenge is the last entry in the vector.
     post_result_challenges: Vec<S>,
+    report: Option<ProverReport>,
+    cancellation_token: Option<Arc<AtomicBool>>,
+    error: Option<ProofError>,
+    filter_compaction: bool,
+    msm_window_bits: Option<usize>,
+    /// The columns of the subexpressions that have already been proven, keyed by their plan
+    shared_subexpressions: SharedSubexpressions<Column<'a, S>>,
+    #[cfg(feature = "inspect-subpolynomials")]
+    subpolynomial_records: Option<Vec<SubpolynomialRecord<S>>>,
 }
 
 impl<'a, S: Scalar> ProofBuilder<'a, S> {
@@ -38,13 +61,129 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
             table_length,
             num_sumcheck_variables,
             bit_distributions: Vec::new(),
+            window_aggregates: Vec::new(),
             commitment_descriptor: Vec::new(),
             pre_result_mles: Vec::new(),
             sumcheck_subpolynomials: Vec::new(),
             post_result_challenges,
+            report: None,
+            cancellation_token: None,
+            error: None,
+            filter_compaction: true,
+            msm_window_bits: None,
+            shared_subexpressions: SharedSubexpressions::default(),
+            #[cfg(feature = "inspect-subpolynomials")]
+            subpolynomial_records: None,
+        }
+    }
+
+    /// Commit the witnesses of filters at their full table length,
+    /// rather than only the rows that the filter selects. See [`Self::is_filter_compaction_enabled`].
+    pub fn disable_filter_compaction(&mut self) {
+        self.filter_compaction = false;
+    }
+
+    /// Whether filters should only commit the selected rows of the witnesses that are zero
+    /// outside of the selection.
+    ///
+    /// The commitment to a column does not change when its trailing zeros are dropped,
+    /// so this only reduces the work of the prover and does not change the proof.
+    pub fn is_filter_compaction_enabled(&self) -> bool {
+        self.filter_compaction
+    }
+
+    /// Commit the intermediate MLEs with MSMs over windows of `msm_window_bits` bits,
+    /// rather than the window that the commitment scheme chooses from the number of rows.
+    pub fn set_msm_window_bits(&mut self, msm_window_bits: usize) {
+        self.msm_window_bits = Some(msm_window_bits);
+    }
+
+    /// Poll `token` in [`Self::is_cancelled`] so that the remaining nodes can be skipped once it is set.
+    pub fn set_cancellation_token(&mut self, token: Arc<AtomicBool>) {
+        self.cancellation_token = Some(token);
+    }
+
+    /// Whether the cancellation token has been set, in which case the proof will be abandoned.
+    ///
+    /// Nodes should check this before evaluating and return a placeholder if it is set.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token
+            .as_ref()
+            .is_some_and(|token| token.load(Ordering::Relaxed))
+    }
+
+    /// Abandon the proof because the data cannot satisfy the constraints of a node, e.g. a zero divisor.
+    ///
+    /// Only the first error is kept. Nodes should still return a column of the right shape.
+    pub fn fail_with(&mut self, error: ProofError) {
+        self.error.get_or_insert(error);
+    }
+
+    /// Take the error passed to [`Self::fail_with`], if any.
+    pub fn take_error(&mut self) -> Option<ProofError> {
+        self.error.take()
+    }
+
+    /// Start collecting a [`ProverReport`] for the nodes that are evaluated with [`Self::record_node`].
+    pub fn collect_report(&mut self) {
+        self.report = Some(ProverReport::default());
+    }
+
+    /// Take the [`ProverReport`] collected so far, if [`Self::collect_report`] was called.
+    pub fn take_report(&mut self) -> Option<ProverReport> {
+        self.report.take()
+    }
+
+    /// Run `evaluate`, recording its time and allocations as `node_type` if a report is being collected.
+    pub fn record_node<T>(
+        &mut self,
+        node_type: &'static str,
+        alloc: &Bump,
+        evaluate: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        match self
+            .report
+            .as_mut()
+            .map(|report| report.enter_node(node_type, alloc))
+        {
+            None => evaluate(self),
+            Some(frame) => {
+                let result = evaluate(self);
+                if let Some(report) = self.report.as_mut() {
+                    report.exit_node(node_type, alloc, frame);
+                }
+                result
+            }
         }
     }
 
+    /// Start recording the subpolynomials passed to [`Self::produce_sumcheck_subpolynomial`].
+    #[cfg(feature = "inspect-subpolynomials")]
+    pub fn record_subpolynomials(&mut self) {
+        self.subpolynomial_records = Some(Vec::new());
+    }
+
+    /// Take the subpolynomials recorded so far, in the order they were produced,
+    /// if [`Self::record_subpolynomials`] was called.
+    #[cfg(feature = "inspect-subpolynomials")]
+    pub fn take_subpolynomial_records(&mut self) -> Option<Vec<SubpolynomialRecord<S>>> {
+        self.subpolynomial_records.take()
+    }
+
+    /// The column of an identical subexpression that was already proven, if any.
+    pub fn get_shared_subexpression<K: Any + Eq + Hash>(&self, key: &K) -> Option<Column<'a, S>> {
+        self.shared_subexpressions.get(key).cloned()
+    }
+
+    /// Record the column of a proven subexpression, so that later copies of it can reuse it.
+    pub fn insert_shared_subexpression<K: Any + Eq + Hash + Clone>(
+        &mut self,
+        key: &K,
+        column: Column<'a, S>,
+    ) {
+        self.shared_subexpressions.insert(key, column);
+    }
+
     pub fn table_length(&self) -> usize {
         self.table_length
     }
@@ -63,6 +202,12 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         self.bit_distributions.push(dist);
     }
 
+    /// Produce the value of an aggregate over the whole selection, such as `SUM(x) OVER ()`,
+    /// which is sent to the verifier
+    pub fn produce_window_aggregate(&mut self, value: S) {
+        self.window_aggregates.push(value);
+    }
+
     /// Produce an anchored MLE that we can reference in sumcheck.
     ///
     /// An anchored MLE is an MLE where the verifier has access to the commitment.
@@ -89,6 +234,22 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         subpolynomial_type: SumcheckSubpolynomialType,
         terms: Vec<SumcheckSubpolynomialTerm<'a, S>>,
     ) {
+        #[cfg(feature = "inspect-subpolynomials")]
+        if let Some(records) = self.subpolynomial_records.as_mut() {
+            records.push(SubpolynomialRecord {
+                subpolynomial_type,
+                terms: terms
+                    .iter()
+                    .map(|(coefficient, mles)| {
+                        let mles = mles
+                            .iter()
+                            .map(|mle| mle.to_sumcheck_term(self.num_sumcheck_variables).to_vec())
+                            .collect();
+                        (*coefficient, mles)
+                    })
+                    .collect(),
+            });
+        }
         self.sumcheck_subpolynomials
             .push(SumcheckSubpolynomial::new(subpolynomial_type, terms));
     }
@@ -104,10 +265,11 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         offset_generators: usize,
         setup: &V::CommitmentPublicSetup,
     ) -> V {
-        V::from_commitable_columns_with_offset(
+        V::from_commitable_columns_with_offset_and_msm_window(
             &self.commitment_descriptor,
             offset_generators,
             setup,
+            self.msm_window_bits,
         )
     }
 
@@ -167,6 +329,10 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         &self.bit_distributions
     }
 
+    pub fn window_aggregates(&self) -> &[S] {
+        &self.window_aggregates
+    }
+
     /// Pops a challenge off the stack of post-result challenges.
     ///
     /// These challenges are used in creation of the constraints in the proof.
@@ -14,7 +14,10 @@ use arrow::{
 };
 use curve25519_dalek::RistrettoPoint;
 use num_traits::{One, Zero};
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 #[test]
 fn we_can_compute_commitments_for_intermediate_mles_using_a_zero_offset() {
@@ -198,3 +201,14 @@ fn we_can_consume_post_result_challenges_in_proof_builder() {
         builder.consume_post_result_challenge()
     );
 }
+
+#[test]
+fn we_can_observe_a_cancellation_token_being_set() {
+    let mut builder = ProofBuilder::<Curve25519Scalar>::new(2, 1, Vec::new());
+    assert!(!builder.is_cancelled());
+    let token = Arc::new(AtomicBool::new(false));
+    builder.set_cancellation_token(token.clone());
+    assert!(!builder.is_cancelled());
+    token.store(true, Ordering::Relaxed);
+    assert!(builder.is_cancelled());
+}
@@ -2,12 +2,17 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 /// Counters for different terms used within a proof
-#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProofCounts {
+    /// The maximum degree of any sumcheck subpolynomial.
     pub sumcheck_max_multiplicands: usize,
+    /// The number of columns in the query result.
     pub result_columns: usize,
+    /// The number of MLEs whose commitments are known to the verifier, e.g. table columns.
     pub anchored_mles: usize,
+    /// The number of MLEs that the prover commits to as part of the proof.
     pub intermediate_mles: usize,
+    /// The number of subpolynomials that are combined into the sumcheck polynomial.
     pub sumcheck_subpolynomials: usize,
 
     /// The number of challenges used in the proof.
@@ -15,9 +20,14 @@ pub struct ProofCounts {
     /// the prover after the prover sends the result, but before the prover
     /// send commitments to the intermediate witness columns.
     pub post_result_challenges: usize,
+
+    /// The number of aggregates over the whole selection, such as `SUM(x) OVER ()`,
+    /// whose values the prover sends.
+    pub window_aggregates: usize,
 }
 
 impl ProofCounts {
+    /// Log each of the counts to the current tracing span.
     #[tracing::instrument(name = "ProofCounts::annotate_trace", level = "debug", skip_all)]
     pub fn annotate_trace(&self) {
         tracing::info!(
@@ -32,5 +42,6 @@ impl ProofCounts {
             self.sumcheck_subpolynomials
         );
         tracing::info!("post_result_challenges = {:?}", self.post_result_challenges);
+        tracing::info!("window_aggregates = {:?}", self.window_aggregates);
     }
 }
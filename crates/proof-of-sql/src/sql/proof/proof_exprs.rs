@@ -1,7 +1,9 @@
 use super::{CountBuilder, ProofBuilder, ResultBuilder, VerificationBuilder};
 use crate::base::{
     commitment::Commitment,
-    database::{ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor},
+    database::{
+        ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor, TableRef,
+    },
     proof::ProofError,
     scalar::Scalar,
 };
@@ -40,6 +42,9 @@ pub trait ProofExpr<C: Commitment>: Debug + Send + Sync + ProverEvaluate<C::Scal
 
     /// Return all the columns referenced in the Query
     fn get_column_references(&self) -> HashSet<ColumnRef>;
+
+    /// Return all the tables read by the Query, including those none of whose columns are referenced
+    fn get_table_references(&self) -> HashSet<TableRef>;
 }
 
 pub trait ProverEvaluate<S: Scalar> {
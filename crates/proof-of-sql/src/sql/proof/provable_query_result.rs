@@ -1,10 +1,11 @@
 use super::{
-    decode_and_convert, decode_multiple_elements, Indexes, ProvableResultColumn, QueryError,
+    decode_and_convert, decode_multiple_elements, Indexes, ProvableResultColumn,
+    ProvableResultElement, QueryError,
 };
 use crate::base::{
     database::{ColumnField, ColumnType, OwnedColumn, OwnedTable},
     polynomial::compute_evaluation_vector,
-    scalar::Scalar,
+    scalar::{Scalar, VarCharHash},
 };
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
@@ -83,6 +84,22 @@ impl ProvableQueryResult {
         evaluation_point: &[S],
         table_length: usize,
         column_result_fields: &[ColumnField],
+    ) -> Option<Vec<S>> {
+        self.evaluate_with_varchar_hash(
+            evaluation_point,
+            table_length,
+            column_result_fields,
+            VarCharHash::default(),
+        )
+    }
+
+    /// Like [`Self::evaluate`], but hashes the values of `VarChar` and `Binary` result columns with `varchar_hash`
+    pub fn evaluate_with_varchar_hash<S: Scalar>(
+        &self,
+        evaluation_point: &[S],
+        table_length: usize,
+        column_result_fields: &[ColumnField],
+        varchar_hash: VarCharHash,
     ) -> Option<Vec<S>> {
         assert_eq!(self.num_columns as usize, column_result_fields.len());
 
@@ -114,7 +131,11 @@ impl ProvableQueryResult {
                     ColumnType::Decimal75(_, _) => decode_and_convert::<S, S>(&self.data[offset..]),
 
                     ColumnType::Scalar => decode_and_convert::<S, S>(&self.data[offset..]),
-                    ColumnType::VarChar => decode_and_convert::<&str, S>(&self.data[offset..]),
+                    ColumnType::VarChar => <&str>::decode(&self.data[offset..])
+                        .map(|(x, sz)| (varchar_hash.hash_to_scalar(x), sz)),
+                    ColumnType::Binary(length) => <&[u8]>::decode(&self.data[offset..])
+                        .filter(|(x, _)| x.len() == length)
+                        .map(|(x, sz)| (varchar_hash.hash_bytes_to_scalar(x), sz)),
                 }?;
 
                 val += evaluation_vec[index as usize] * x;
@@ -182,6 +203,20 @@ impl ProvableQueryResult {
                         offset += num_read;
                         Ok((field.name(), OwnedColumn::VarChar(col)))
                     }
+                    ColumnType::Binary(length) => {
+                        let (col, num_read) =
+                            decode_multiple_elements::<&[u8]>(&self.data[offset..], n)
+                                .filter(|(col, _)| col.iter().all(|bytes| bytes.len() == length))
+                                .ok_or(QueryError::Overflow)?;
+                        offset += num_read;
+                        Ok((
+                            field.name(),
+                            OwnedColumn::Binary(
+                                length,
+                                col.into_iter().map(<[u8]>::to_vec).collect(),
+                            ),
+                        ))
+                    }
                     ColumnType::Scalar => {
                         let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)
                             .ok_or(QueryError::Overflow)?;
@@ -0,0 +1,42 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+/// Options that control how a proof is created.
+#[derive(Default, Debug, Clone)]
+pub struct ProverConfig {
+    /// Whether to collect a [`ProverReport`](super::ProverReport) while proving.
+    ///
+    /// This adds a small amount of overhead for every node of the query plan.
+    pub collect_report: bool,
+    /// A flag that can be set from another thread to abandon the proof.
+    ///
+    /// The flag is polled between the nodes of the query plan. Once it is set, the remaining
+    /// nodes are skipped and proving fails with [`ProofError::Cancelled`](super::ProofError::Cancelled).
+    pub cancellation_token: Option<Arc<AtomicBool>>,
+    /// Whether filters should commit their witnesses at the full length of the table.
+    ///
+    /// By default, a filter that selects `m` of `n` rows commits to one of its two witnesses
+    /// over just the `m` selected rows, since the rest of that witness is zero.
+    /// This lowers the commitment work of the filter from `2n` to `n + m` rows,
+    /// so it helps at any selectivity below 100%, most of all for highly selective filters.
+    /// The proof and the verified result are the same either way.
+    pub disable_filter_compaction: bool,
+    /// Bytes that identify the deployment that the proof is created for, such as the name of a network.
+    ///
+    /// They are absorbed into the Fiat-Shamir transcript, so a proof only verifies with
+    /// [`VerifiableQueryResult::verify_with_domain_separator`](super::VerifiableQueryResult::verify_with_domain_separator)
+    /// (or `verify_with_report` and `verify_with_roots`) given the same bytes, and cannot be replayed in a deployment with a different separator, even over identical data.
+    /// The default is empty, which adds nothing to the transcript, and is what [`VerifiableQueryResult::verify`](super::VerifiableQueryResult::verify) expects.
+    pub domain_separator: Vec<u8>,
+    /// The number of bits in each window of the multi-scalar multiplications (MSMs)
+    /// that commit to the intermediate columns of the proof.
+    ///
+    /// Pippenger's method sums the generators of the rows into `2^bits` buckets per window,
+    /// so wider windows do fewer additions over the rows but more over the buckets, and the best
+    /// window depends on the hardware as well as the number of rows. The default of `None` chooses
+    /// the window from the number of rows, and windows are clamped to at most 16 bits.
+    /// The commitments and the proof are the same for any window.
+    ///
+    /// This only applies to Dory commitments computed on the CPU, i.e. without the `blitzar` feature.
+    /// `blitzar` chooses its own windows and offers no way to set them, so it ignores this.
+    pub msm_window_bits: Option<usize>,
+}
@@ -0,0 +1,89 @@
+use bumpalo::Bump;
+use indexmap::IndexMap;
+use std::time::{Duration, Instant};
+
+/// The resources spent on all nodes of a single type while proving a query.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeReport {
+    /// The number of nodes of this type that were evaluated.
+    pub num_evaluations: usize,
+    /// The wall-clock time spent in `prover_evaluate`, excluding the time spent in child nodes.
+    pub time: Duration,
+    /// The number of bytes allocated in `prover_evaluate`, excluding the bytes allocated by child nodes.
+    pub allocated_bytes: usize,
+}
+
+/// A report of the resources spent by each node type while proving a query.
+///
+/// This is only collected if [`ProverConfig::collect_report`](super::ProverConfig::collect_report) is set.
+#[derive(Default, Debug, Clone)]
+pub struct ProverReport {
+    nodes: IndexMap<&'static str, NodeReport>,
+    total_time: Duration,
+    /// The time and bytes spent in the children of the node that is currently being evaluated.
+    nested: (Duration, usize),
+}
+
+impl ProverReport {
+    /// Returns the reports for each node type, in the order in which they were first evaluated.
+    pub fn nodes(&self) -> &IndexMap<&'static str, NodeReport> {
+        &self.nodes
+    }
+
+    /// Returns the wall-clock time spent creating the whole proof.
+    ///
+    /// This includes the time spent outside of the nodes, e.g. for committing to intermediate MLEs and sumcheck.
+    pub fn total_time(&self) -> Duration {
+        self.total_time
+    }
+
+    /// Returns the wall-clock time spent in all nodes.
+    pub fn node_time(&self) -> Duration {
+        self.nodes.values().map(|node| node.time).sum()
+    }
+
+    pub(super) fn set_total_time(&mut self, total_time: Duration) {
+        self.total_time = total_time;
+    }
+
+    /// Start recording a node, returning the state that must be passed to [`Self::exit_node`].
+    pub(super) fn enter_node(&mut self, node_type: &'static str, alloc: &Bump) -> NodeFrame {
+        self.nodes.entry(node_type).or_default();
+        NodeFrame {
+            outer_nested: std::mem::take(&mut self.nested),
+            start_time: Instant::now(),
+            start_bytes: used_bytes(alloc),
+        }
+    }
+
+    /// Finish recording a node, attributing the time and allocations that were not spent in nested nodes to `node_type`.
+    pub(super) fn exit_node(&mut self, node_type: &'static str, alloc: &Bump, frame: NodeFrame) {
+        let time = frame.start_time.elapsed();
+        let allocated_bytes = used_bytes(alloc).saturating_sub(frame.start_bytes);
+        let (nested_time, nested_bytes) = std::mem::replace(
+            &mut self.nested,
+            (
+                frame.outer_nested.0 + time,
+                frame.outer_nested.1 + allocated_bytes,
+            ),
+        );
+        let node = self.nodes.entry(node_type).or_default();
+        node.num_evaluations += 1;
+        node.time += time.saturating_sub(nested_time);
+        node.allocated_bytes += allocated_bytes.saturating_sub(nested_bytes);
+    }
+}
+
+/// The state of a node that is currently being recorded by a [`ProverReport`].
+pub(super) struct NodeFrame {
+    outer_nested: (Duration, usize),
+    start_time: Instant,
+    start_bytes: usize,
+}
+
+/// The number of bytes that have been handed out by `alloc`, including any space wasted at the end of old chunks.
+fn used_bytes(alloc: &Bump) -> usize {
+    alloc
+        .allocated_bytes()
+        .saturating_sub(alloc.chunk_capacity())
+}
@@ -0,0 +1,40 @@
+use super::ProofBuilder;
+use crate::base::scalar::Curve25519Scalar;
+use bumpalo::Bump;
+
+#[test]
+fn no_report_is_collected_by_default() {
+    let alloc = Bump::new();
+    let mut builder = ProofBuilder::<Curve25519Scalar>::new(1, 1, Vec::new());
+    let result = builder.record_node("Node", &alloc, |_| 5);
+    assert_eq!(result, 5);
+    assert!(builder.take_report().is_none());
+}
+
+#[test]
+fn we_can_attribute_time_and_allocations_to_nested_nodes() {
+    let alloc = Bump::with_capacity(1 << 16);
+    let mut builder = ProofBuilder::<Curve25519Scalar>::new(1, 1, Vec::new());
+    builder.collect_report();
+    builder.record_node("Parent", &alloc, |builder| {
+        alloc.alloc_slice_fill_copy(100, 0u8);
+        for _ in 0..2 {
+            builder.record_node("Child", &alloc, |_| {
+                alloc.alloc_slice_fill_copy(1000, 0u8);
+            });
+        }
+    });
+    let report = builder.take_report().unwrap();
+
+    assert_eq!(
+        report.nodes().keys().copied().collect::<Vec<_>>(),
+        ["Parent", "Child"]
+    );
+    let parent = report.nodes()["Parent"];
+    let child = report.nodes()["Child"];
+    assert_eq!(parent.num_evaluations, 1);
+    assert_eq!(parent.allocated_bytes, 100);
+    assert_eq!(child.num_evaluations, 2);
+    assert_eq!(child.allocated_bytes, 2000);
+    assert_eq!(report.node_time(), parent.time + child.time);
+}
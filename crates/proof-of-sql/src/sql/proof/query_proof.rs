@@ -1,6 +1,7 @@
 use super::{
-    CountBuilder, ProofBuilder, ProofCounts, ProofExpr, ProvableQueryResult, QueryResult,
-    SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilder,
+    CountBuilder, ProofBuilder, ProofCounts, ProofExpr, ProvableQueryResult, ProverConfig,
+    ProverReport, QueryError, QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars,
+    VerificationBuilder, VerificationReport,
 };
 use crate::{
     base::{
@@ -10,6 +11,7 @@ use crate::{
         math::log2_up,
         polynomial::{compute_evaluation_vector, CompositePolynomialInfo},
         proof::{MessageLabel, ProofError, TranscriptProtocol},
+        scalar::Scalar,
     },
     proof_primitive::sumcheck::SumcheckProof,
     sql::proof::{QueryData, ResultBuilder},
@@ -18,17 +20,36 @@ use bumpalo::Bump;
 use merlin::Transcript;
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
-use std::cmp;
+use std::{cmp, sync::atomic::Ordering, time::Instant};
+
+/// The version of the serialized proof format.
+///
+/// This must be incremented whenever a change to the crate makes proofs incompatible,
+/// such as a change to the provable AST or to how proofs are constructed.
+pub const PROOF_VERSION: u32 = 5;
 
 /// The proof for a query.
 ///
+/// The proof is tagged with the [`PROOF_VERSION`] it was created with, and a proof with
+/// a different version is rejected with [`ProofError::VersionMismatch`] before it is verified.
+/// It is also tagged with the [`plan_hash`] of the plan that it proves. The plan is already bound to the
+/// proof through the transcript, so the tag only serves as an early check: a proof verified against any
+/// other plan is rejected with [`ProofError::PlanMismatch`] rather than a failed sumcheck.
+///
 /// Note: Because the class is deserialized from untrusted data, it
 /// cannot maintain any invariant on its data members; hence, they are
 /// all public so as to allow for easy manipulation for testing.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct QueryProof<CP: CommitmentEvaluationProof> {
+    /// The version of the proof format. See [`PROOF_VERSION`].
+    pub version: u32,
+    /// The hash of the plan that the proof was created for, used only to reject a mismatched plan early.
+    /// See [`plan_hash`].
+    pub plan_hash: [u8; 32],
     /// Bit distributions
     pub bit_distributions: Vec<BitDistribution>,
+    /// Values of the aggregates over the whole selection, such as `SUM(x) OVER ()`
+    pub window_aggregates: Vec<CP::Scalar>,
     /// Commitments
     pub commitments: Vec<CP::Commitment>,
     /// Sumcheck Proof
@@ -41,14 +62,46 @@ pub struct QueryProof<CP: CommitmentEvaluationProof> {
 
 impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
     /// Create a new `QueryProof`.
+    ///
+    /// This errors like [`Self::new_with_config`], except that it cannot be cancelled.
     #[tracing::instrument(name = "QueryProof::new", level = "debug", skip_all)]
     pub fn new(
         expr: &(impl ProofExpr<CP::Commitment> + Serialize),
         accessor: &impl DataAccessor<CP::Scalar>,
         setup: &CP::ProverPublicSetup,
-    ) -> (Self, ProvableQueryResult) {
+    ) -> Result<(Self, ProvableQueryResult), ProofError> {
+        let (proof, provable_result, _) =
+            Self::new_with_config(expr, accessor, setup, &ProverConfig::default())?;
+        Ok((proof, provable_result))
+    }
+
+    /// Create a new `QueryProof`, along with a [`ProverReport`] if [`ProverConfig::collect_report`] is set.
+    ///
+    /// Returns [`ProofError::Cancelled`] if [`ProverConfig::cancellation_token`] is set before the
+    /// proof is complete, [`ProofError::DivisionByZero`] if a provable division has a zero divisor,
+    /// [`ProofError::DivisionOverflow`] if a provable integer division has a quotient that does not fit in its type,
+    /// [`ProofError::UnknownColumn`] if the query references a column that the accessor does not have,
+    /// and [`ProofError::TypeMismatch`] if such a column does not have the type that the query expects.
+    #[tracing::instrument(name = "QueryProof::new_with_config", level = "debug", skip_all, err)]
+    pub fn new_with_config(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup,
+        config: &ProverConfig,
+    ) -> Result<(Self, ProvableQueryResult, Option<ProverReport>), ProofError> {
+        let start_time = Instant::now();
+        let is_cancelled = || {
+            config
+                .cancellation_token
+                .as_ref()
+                .is_some_and(|token| token.load(Ordering::Relaxed))
+        };
+        if is_cancelled() {
+            return Err(ProofError::Cancelled);
+        }
+        check_column_references(expr, accessor)?;
         let table_length = expr.get_length(accessor);
-        let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
+        let num_sumcheck_variables = num_sumcheck_variables(table_length);
         let generator_offset = expr.get_offset(accessor);
         assert!(num_sumcheck_variables > 0);
 
@@ -56,10 +109,18 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let mut result_builder = ResultBuilder::new(table_length);
         expr.result_evaluate(&mut result_builder, &alloc, accessor);
         let provable_result = result_builder.make_provable_query_result();
+        if is_cancelled() {
+            return Err(ProofError::Cancelled);
+        }
 
         // construct a transcript for the proof
-        let mut transcript: Transcript =
-            make_transcript(expr, &provable_result, table_length, generator_offset);
+        let mut transcript: Transcript = make_transcript(
+            expr,
+            &provable_result,
+            table_length,
+            generator_offset,
+            &config.domain_separator,
+        );
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -75,14 +136,43 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         let mut builder =
             ProofBuilder::new(table_length, num_sumcheck_variables, post_result_challenges);
+        if config.collect_report {
+            builder.collect_report();
+        }
+        if config.disable_filter_compaction {
+            builder.disable_filter_compaction();
+        }
+        if let Some(msm_window_bits) = config.msm_window_bits {
+            builder.set_msm_window_bits(msm_window_bits);
+        }
+        if let Some(token) = &config.cancellation_token {
+            builder.set_cancellation_token(token.clone());
+        }
         expr.prover_evaluate(&mut builder, &alloc, accessor);
+        if builder.is_cancelled() {
+            return Err(ProofError::Cancelled);
+        }
+        if let Some(error) = builder.take_error() {
+            return Err(error);
+        }
+        let mut report = builder.take_report();
 
-        let proof = QueryProof::new_from_builder(builder, generator_offset, transcript, setup);
-        (proof, provable_result)
+        let proof = QueryProof::new_from_builder(
+            builder,
+            plan_hash(expr),
+            generator_offset,
+            transcript,
+            setup,
+        );
+        if let Some(report) = report.as_mut() {
+            report.set_total_time(start_time.elapsed());
+        }
+        Ok((proof, provable_result, report))
     }
 
     pub(crate) fn new_from_builder(
         builder: ProofBuilder<CP::Scalar>,
+        plan_hash: [u8; 32],
         generator_offset: usize,
         mut transcript: Transcript,
         setup: &CP::ProverPublicSetup,
@@ -93,8 +183,13 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         // commit to any intermediate MLEs
         let commitments = builder.commit_intermediate_mles(generator_offset, setup);
 
-        // add the commitments and bit distributions to the proof
-        extend_transcript(&mut transcript, &commitments, builder.bit_distributions());
+        // add the commitments, bit distributions and window aggregates to the proof
+        extend_transcript(
+            &mut transcript,
+            &commitments,
+            builder.bit_distributions(),
+            builder.window_aggregates(),
+        );
 
         // construct the sumcheck polynomial
         let num_random_scalars = num_sumcheck_variables + builder.num_sumcheck_subpolynomials();
@@ -140,7 +235,10 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         );
 
         let proof = Self {
+            version: PROOF_VERSION,
+            plan_hash,
             bit_distributions: builder.bit_distributions().to_vec(),
+            window_aggregates: builder.window_aggregates().to_vec(),
             commitments,
             sumcheck_proof,
             pre_result_mle_evaluations,
@@ -149,7 +247,6 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         proof
     }
 
-    #[tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)]
     /// Verify a `QueryProof`. Note: This does NOT transform the result!
     pub fn verify(
         &self,
@@ -158,9 +255,73 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         result: &ProvableQueryResult,
         setup: &CP::VerifierPublicSetup,
     ) -> QueryResult<CP::Scalar> {
+        self.verify_impl(expr, accessor, result, setup, &[], None)
+    }
+
+    /// Verify a `QueryProof` that was created with [`ProverConfig::domain_separator`] set to `domain_separator`.
+    ///
+    /// [`Self::verify`] is the same as this with an empty `domain_separator`.
+    pub fn verify_with_domain_separator(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup,
+        domain_separator: &[u8],
+    ) -> QueryResult<CP::Scalar> {
+        self.verify_impl(expr, accessor, result, setup, domain_separator, None)
+    }
+
+    /// Verify a `QueryProof` like [`Self::verify_with_domain_separator`], but go on past the constraints,
+    /// the sumcheck evaluation check and the check of the commitments when they fail, and report every failure.
+    ///
+    /// See [`VerificationReport`] for which failures are reported individually.
+    pub fn verify_with_report(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup,
+        domain_separator: &[u8],
+    ) -> VerificationReport {
+        let mut failures = Vec::new();
+        if let Err(error) = self.verify_impl(
+            expr,
+            accessor,
+            result,
+            setup,
+            domain_separator,
+            Some(&mut failures),
+        ) {
+            failures.push(error);
+        }
+        VerificationReport::new(failures)
+    }
+
+    /// Verify a `QueryProof`, collecting the failures that verification can go on past
+    /// into `failures` if it is given.
+    #[tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)]
+    fn verify_impl(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup,
+        domain_separator: &[u8],
+        mut failures: Option<&mut Vec<QueryError>>,
+    ) -> QueryResult<CP::Scalar> {
+        if self.version != PROOF_VERSION {
+            Err(ProofError::VersionMismatch {
+                expected: PROOF_VERSION,
+                found: self.version,
+            })?;
+        }
+        if self.plan_hash != plan_hash(expr) {
+            Err(ProofError::PlanMismatch)?;
+        }
         let table_length = expr.get_length(accessor);
         let generator_offset = expr.get_offset(accessor);
-        let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
+        let num_sumcheck_variables = num_sumcheck_variables(table_length);
         assert!(num_sumcheck_variables > 0);
 
         // validate bit decompositions
@@ -190,7 +351,13 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 ))?;
 
         // construct a transcript for the proof
-        let mut transcript = make_transcript(expr, result, table_length, generator_offset);
+        let mut transcript = make_transcript(
+            expr,
+            result,
+            table_length,
+            generator_offset,
+            domain_separator,
+        );
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -203,8 +370,13 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             MessageLabel::PostResultChallenges,
         );
 
-        // add the commitments and bit disctibutions to the proof
-        extend_transcript(&mut transcript, &self.commitments, &self.bit_distributions);
+        // add the commitments, bit disctibutions and window aggregates to the proof
+        extend_transcript(
+            &mut transcript,
+            &self.commitments,
+            &self.bit_distributions,
+            &self.window_aggregates,
+        );
 
         // draw the random scalars for sumcheck
         let num_random_scalars = num_sumcheck_variables + counts.sumcheck_subpolynomials;
@@ -242,10 +414,11 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let column_result_fields = expr.get_column_result_fields();
 
         // compute the evaluation of the result MLEs
-        let result_evaluations = match result.evaluate(
+        let result_evaluations = match result.evaluate_with_varchar_hash(
             &subclaim.evaluation_point,
             table_length,
             &column_result_fields[..],
+            accessor.varchar_hash(),
         ) {
             Some(evaluations) => evaluations,
             _ => Err(ProofError::VerificationError(
@@ -266,23 +439,32 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             generator_offset,
             sumcheck_evaluations,
             &self.bit_distributions,
+            &self.window_aggregates,
             &commitments,
             sumcheck_random_scalars.subpolynomial_multipliers,
             &evaluation_random_scalars,
             post_result_challenges,
         );
+        if failures.is_some() {
+            builder.collect_constraint_failures();
+        }
         expr.verifier_evaluate(&mut builder, accessor)?;
+        for error in builder.take_constraint_failures() {
+            fail_or_collect(&mut failures, error)?;
+        }
 
         // perform the evaluation check of the sumcheck polynomial
         if builder.sumcheck_evaluation() != subclaim.expected_evaluation {
-            Err(ProofError::VerificationError(
-                "sumcheck evaluation check failed",
-            ))?;
+            fail_or_collect(
+                &mut failures,
+                ProofError::SumcheckFailed("sumcheck evaluation check failed"),
+            )?;
         }
 
         // finally, check the MLE evaluations with the inner product proof
         let product = builder.folded_pre_result_evaluation();
-        self.evaluation_proof
+        if self
+            .evaluation_proof
             .verify_batched_proof(
                 &mut transcript,
                 builder.pre_result_commitments(),
@@ -293,9 +475,13 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 table_length,
                 setup,
             )
-            .map_err(|_e| {
-                ProofError::VerificationError("Inner product proof of MLE evaluations failed")
-            })?;
+            .is_err()
+        {
+            fail_or_collect(
+                &mut failures,
+                ProofError::CommitmentMismatch("Inner product proof of MLE evaluations failed"),
+            )?;
+        }
 
         let mut verification_hash = [0u8; 32];
         transcript.challenge_bytes(
@@ -315,9 +501,65 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             && self.commitments.num_commitments() == counts.intermediate_mles
             && self.pre_result_mle_evaluations.len()
                 == counts.intermediate_mles + counts.anchored_mles
+            && self.window_aggregates.len() == counts.window_aggregates
     }
 }
 
+/// Return `error`, unless failures are being collected into `failures`, in which case it is collected instead.
+fn fail_or_collect(
+    failures: &mut Option<&mut Vec<QueryError>>,
+    error: ProofError,
+) -> Result<(), ProofError> {
+    match failures {
+        Some(failures) => {
+            failures.push(error.into());
+            Ok(())
+        }
+        None => Err(error),
+    }
+}
+
+/// The number of sumcheck variables for a table with `table_length` rows.
+///
+/// This is at least one, so that empty and single row tables are proven like any other.
+pub(crate) fn num_sumcheck_variables(table_length: usize) -> usize {
+    log2_up(cmp::max(table_length, 2))
+}
+
+/// Checks that the accessor has every column that `expr` references, with the type that `expr` expects,
+/// without fetching any data.
+pub(super) fn check_column_references<C: Commitment, S: Scalar>(
+    expr: &impl ProofExpr<C>,
+    accessor: &impl DataAccessor<S>,
+) -> Result<(), ProofError> {
+    expr.get_column_references()
+        .into_iter()
+        .try_for_each(|column| {
+            match accessor.column_type(column.table_ref(), column.column_id()) {
+                None => Err(ProofError::UnknownColumn {
+                    column: Box::new(column.column_id()),
+                    table: Box::new(column.table_ref().resource_id()),
+                }),
+                // The nodes of the plan were type checked against the declared types, so a column
+                // of another type would break their invariants while proving.
+                Some(actual) if actual != *column.column_type() => Err(ProofError::TypeMismatch {
+                    column: Box::new(column.column_id()),
+                    expected: *column.column_type(),
+                    actual,
+                }),
+                Some(_) => Ok(()),
+            }
+        })
+}
+
+/// The hash of the canonical serialization of a plan, which binds the plan to a [`QueryProof`] transcript.
+///
+/// Plans that differ in any way, such as in a literal, have different hashes, so comparing hashes
+/// reports a [`ProofError::PlanMismatch`] before any verification work is done.
+pub fn plan_hash(expr: &impl Serialize) -> [u8; 32] {
+    blake3::hash(&postcard::to_allocvec(expr).expect("a plan can always be serialized")).into()
+}
+
 /// Creates a transcript using the Merlin library.
 ///
 /// This function is used to produce a transcript for a proof expression
@@ -337,6 +579,8 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 ///
 /// * `generator_offset` - The offset of the generator used in the proof, as a `usize`.
 ///
+/// * `domain_separator` - The bytes that identify the deployment, which are only added if they are not empty.
+///
 /// # Returns
 /// This function returns a `merlin::Transcript`. The transcript is a record
 /// of all the operations and data involved in creating a proof.
@@ -346,20 +590,27 @@ pub fn make_transcript<C: Commitment>(
     result: &ProvableQueryResult,
     table_length: usize,
     generator_offset: usize,
+    domain_separator: &[u8],
 ) -> merlin::Transcript {
     let mut transcript = Transcript::new(MessageLabel::QueryProof.as_bytes());
+    // Proofs without a domain separator have the same transcript as before separators existed.
+    if !domain_separator.is_empty() {
+        transcript.append_auto(MessageLabel::DomainSeparator, domain_separator);
+    }
     transcript.append_auto(MessageLabel::QueryResultData, result);
-    transcript.append_auto(MessageLabel::ProofExpr, expr);
+    transcript.append_auto(MessageLabel::PlanHash, &plan_hash(expr));
     transcript.append_auto(MessageLabel::TableLength, &table_length);
     transcript.append_auto(MessageLabel::GeneratorOffset, &generator_offset);
     transcript
 }
 
-fn extend_transcript<C: serde::Serialize>(
+fn extend_transcript<C: serde::Serialize, S: Scalar>(
     transcript: &mut Transcript,
     commitments: &C,
     bit_distributions: &[BitDistribution],
+    window_aggregates: &[S],
 ) {
     transcript.append_auto(MessageLabel::QueryCommit, commitments);
     transcript.append_auto(MessageLabel::QueryBitDistributions, bit_distributions);
+    transcript.append_canonical_serialize(MessageLabel::QueryWindowAggregates, window_aggregates);
 }
@@ -63,7 +63,7 @@ fn verify_a_trivial_query_proof_with_given_offset(n: usize, offset_generators: u
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let QueryData {
         verification_hash,
         table,
@@ -141,7 +141,7 @@ fn verify_fails_if_the_summation_in_sumcheck_isnt_zero() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -193,7 +193,7 @@ fn verify_fails_if_the_sumcheck_evaluation_isnt_correct() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -244,7 +244,7 @@ fn veriy_fails_if_result_mle_evaluation_fails() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, mut result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, mut result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     match result.indexes_mut() {
         Indexes::Sparse(ref mut indexes) => {
             indexes.pop();
@@ -302,7 +302,7 @@ fn verify_fails_if_counts_dont_match() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -361,7 +361,7 @@ fn verify_a_proof_with_an_anchored_commitment_and_given_offset(offset_generators
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let QueryData {
         verification_hash,
         table,
@@ -379,7 +379,7 @@ fn verify_a_proof_with_an_anchored_commitment_and_given_offset(offset_generators
     assert_eq!(result, expected_result);
 
     // invalid offset will fail to verify
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let expr = TestQueryExpr {
         table_length: 2,
         offset_generators: offset_generators + 1,
@@ -459,7 +459,7 @@ fn verify_fails_if_the_result_doesnt_satisfy_an_anchored_equation() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -519,7 +519,7 @@ fn verify_fails_if_the_anchored_commitment_doesnt_match() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -539,6 +539,7 @@ fn verify_a_proof_with_an_intermediate_commitment_and_given_offset(offset_genera
         anchored_mles: 1,
         intermediate_mles: 1,
         post_result_challenges: 0,
+        window_aggregates: 0,
     };
     fn result_eval<'a>(
         builder: &mut ResultBuilder<'a>,
@@ -600,7 +601,7 @@ fn verify_a_proof_with_an_intermediate_commitment_and_given_offset(offset_genera
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let QueryData {
         verification_hash,
         table,
@@ -618,7 +619,7 @@ fn verify_a_proof_with_an_intermediate_commitment_and_given_offset(offset_genera
     assert_eq!(result, expected_result);
 
     // invalid offset will fail to verify
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     // let invalid_counts = {
     //     let mut counts = counts;
     //     counts.offset_generators += 1;
@@ -662,6 +663,7 @@ fn verify_fails_if_an_intermediate_commitment_doesnt_match() {
         anchored_mles: 1,
         intermediate_mles: 1,
         post_result_challenges: 0,
+        window_aggregates: 0,
     };
     fn result_eval<'a>(
         builder: &mut ResultBuilder<'a>,
@@ -723,7 +725,7 @@ fn verify_fails_if_an_intermediate_commitment_doesnt_match() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (mut proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (mut proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     proof.commitments[0] = proof.commitments[0] * Curve25519Scalar::from(2u64);
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
@@ -747,6 +749,7 @@ fn verify_fails_if_an_intermediate_equation_isnt_satified() {
         anchored_mles: 1,
         intermediate_mles: 1,
         post_result_challenges: 0,
+        window_aggregates: 0,
     };
     fn result_eval<'a>(
         builder: &mut ResultBuilder<'a>,
@@ -808,7 +811,7 @@ fn verify_fails_if_an_intermediate_equation_isnt_satified() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -831,6 +834,7 @@ fn verify_fails_the_result_doesnt_satisfy_an_intermediate_equation() {
         anchored_mles: 1,
         intermediate_mles: 1,
         post_result_challenges: 0,
+        window_aggregates: 0,
     };
     fn result_eval<'a>(
         builder: &mut ResultBuilder<'a>,
@@ -892,7 +896,7 @@ fn verify_fails_the_result_doesnt_satisfy_an_intermediate_equation() {
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     assert!(proof.verify(&expr, &accessor, &result, &()).is_err());
 }
 
@@ -959,7 +963,7 @@ fn verify_a_proof_with_a_post_result_challenge_and_given_offset(offset_generator
         verifier_fn: Some(Box::new(verifier_eval)),
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let QueryData {
         verification_hash,
         table,
@@ -977,7 +981,7 @@ fn verify_a_proof_with_a_post_result_challenge_and_given_offset(offset_generator
     assert_eq!(result, expected_result);
 
     // invalid offset will fail to verify
-    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let expr = TestQueryExpr {
         table_length: 2,
         offset_generators: offset_generators + 1,
@@ -1,7 +1,10 @@
-use crate::base::{
-    database::{OwnedTable, OwnedTableError},
-    proof::ProofError,
-    scalar::Scalar,
+use crate::{
+    base::{
+        database::{OwnedTable, OwnedTableError},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::parse::ConversionError,
 };
 use arrow::{error::ArrowError, record_batch::RecordBatch};
 use thiserror::Error;
@@ -23,6 +26,12 @@ pub enum QueryError {
     /// The table data was invalid. This should never happen because this should get caught by the verifier before reaching this point.
     #[error(transparent)]
     InvalidTable(#[from] OwnedTableError),
+    /// The query that the proof was verified against could not be parsed or planned.
+    #[error(transparent)]
+    InvalidQuery(#[from] ConversionError),
+    /// The verified result could not be transformed into the final result of the query.
+    #[error("The verified result could not be transformed: {0}")]
+    TransformError(String),
 }
 
 /// The verified results of a query along with metadata produced by verification
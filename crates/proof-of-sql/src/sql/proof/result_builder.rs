@@ -48,7 +48,7 @@ impl<'a> ResultBuilder<'a> {
     /// Specifically, these are the challenges that the verifier sends to
     /// the prover after the prover sends the result, but before the prover
     /// send commitments to the intermediate witness columns.
-    pub(super) fn num_post_result_challenges(&self) -> usize {
+    pub(crate) fn num_post_result_challenges(&self) -> usize {
         self.num_post_result_challenges
     }
 
@@ -0,0 +1,57 @@
+use std::{
+    any::Any,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// A subexpression, type-erased so that plans of different types share one map, and its value.
+type KeyAndValue<V> = (Box<dyn Any>, V);
+
+/// The values of the subexpressions of a plan that were already counted, proven or verified,
+/// keyed by the subexpression itself.
+///
+/// The keys are grouped by their hash, but a lookup only returns the value of a key that is
+/// equal to the given one, so two different subexpressions never share a value, even if their
+/// hashes collide.
+pub struct SharedSubexpressions<V> {
+    values: HashMap<u64, Vec<KeyAndValue<V>>>,
+}
+
+impl<V> Default for SharedSubexpressions<V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<V> SharedSubexpressions<V> {
+    /// The value of a subexpression equal to `key`, if any.
+    pub fn get<K: Any + Eq + Hash>(&self, key: &K) -> Option<&V> {
+        self.values
+            .get(&hash_key(key))?
+            .iter()
+            .find(|(other, _)| other.downcast_ref::<K>() == Some(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Record the value of the subexpression `key`.
+    ///
+    /// Returns `false`, and keeps the old value, if a subexpression equal to `key` already has a value.
+    pub fn insert<K: Any + Eq + Hash + Clone>(&mut self, key: &K, value: V) -> bool {
+        if self.get(key).is_some() {
+            return false;
+        }
+        self.values
+            .entry(hash_key(key))
+            .or_default()
+            .push((Box::new(key.clone()), value));
+        true
+    }
+}
+
+fn hash_key(key: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,44 @@
+use super::SharedSubexpressions;
+use std::hash::{Hash, Hasher};
+
+/// A key whose hash does not depend on its value, so all keys collide.
+#[derive(Clone, PartialEq, Eq)]
+struct CollidingKey(u8);
+
+impl Hash for CollidingKey {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+#[test]
+fn we_can_get_the_value_of_an_equal_key() {
+    let mut shared = SharedSubexpressions::default();
+    assert_eq!(shared.get(&"a = 1".to_string()), None);
+    assert!(shared.insert(&"a = 1".to_string(), 1));
+    assert_eq!(shared.get(&"a = 1".to_string()), Some(&1));
+    assert_eq!(shared.get(&"a = 2".to_string()), None);
+}
+
+#[test]
+fn inserting_an_equal_key_again_keeps_the_first_value() {
+    let mut shared = SharedSubexpressions::default();
+    assert!(shared.insert(&CollidingKey(1), 1));
+    assert!(!shared.insert(&CollidingKey(1), 2));
+    assert_eq!(shared.get(&CollidingKey(1)), Some(&1));
+}
+
+#[test]
+fn different_keys_with_the_same_hash_do_not_share_a_value() {
+    let mut shared = SharedSubexpressions::default();
+    assert!(shared.insert(&CollidingKey(1), 1));
+    assert_eq!(shared.get(&CollidingKey(2)), None);
+    assert!(shared.insert(&CollidingKey(2), 2));
+    assert_eq!(shared.get(&CollidingKey(1)), Some(&1));
+    assert_eq!(shared.get(&CollidingKey(2)), Some(&2));
+}
+
+#[test]
+fn keys_of_different_types_do_not_share_a_value() {
+    let mut shared = SharedSubexpressions::default();
+    assert!(shared.insert(&CollidingKey(1), 1));
+    assert_eq!(shared.get(&1u8), None);
+}
@@ -0,0 +1,48 @@
+use super::SumcheckSubpolynomialType;
+use crate::base::scalar::Scalar;
+use num_traits::Zero;
+
+/// A sumcheck subpolynomial as it was produced with
+/// [`ProofBuilder::produce_sumcheck_subpolynomial`](super::ProofBuilder::produce_sumcheck_subpolynomial),
+/// for checking the constraints of a new node without proving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubpolynomialRecord<S: Scalar> {
+    /// Whether the subpolynomial should be zero at every row or sum to zero
+    pub subpolynomial_type: SumcheckSubpolynomialType,
+    /// Each term as its coefficient and the values of the MLEs that it is the product of,
+    /// padded with zeros to the rows of the sumcheck
+    pub terms: Vec<(S, Vec<Vec<S>>)>,
+}
+
+impl<S: Scalar> SubpolynomialRecord<S> {
+    /// The value of the subpolynomial at each row.
+    pub fn row_values(&self) -> Vec<S> {
+        let num_rows = self
+            .terms
+            .iter()
+            .flat_map(|(_, mles)| mles.iter().map(Vec::len))
+            .max()
+            .unwrap_or(0);
+        (0..num_rows)
+            .map(|row| {
+                self.terms
+                    .iter()
+                    .map(|(coefficient, mles)| {
+                        mles.iter()
+                            .fold(*coefficient, |product, mle| product * mle[row])
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Whether the subpolynomial is zero at every row, for an identity,
+    /// or sums to zero across the rows, for a zero sum.
+    pub fn is_satisfied(&self) -> bool {
+        let row_values = self.row_values();
+        match self.subpolynomial_type {
+            SumcheckSubpolynomialType::Identity => row_values.iter().all(Zero::is_zero),
+            SumcheckSubpolynomialType::ZeroSum => row_values.into_iter().sum::<S>().is_zero(),
+        }
+    }
+}
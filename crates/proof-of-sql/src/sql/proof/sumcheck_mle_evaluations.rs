@@ -17,6 +17,10 @@ pub struct SumcheckMleEvaluations<'a, S: Scalar> {
     ///     x_i = 1, if i < table_length;
     ///         = 0, otherwise
     pub one_evaluation: S,
+    /// The evaluation (at the random point generated by sumcheck) of an MLE {x_i} where
+    ///     x_i = 1, if i == 0;
+    ///         = 0, otherwise
+    pub first_row_evaluation: S,
 
     /// The evaluation (at the random point generated by sumcheck) of the MLE that is 1 at the result indexes and 0 elsewhere.
     /// This is only computed if the result indexes are dense, and is None otherwise.
@@ -61,6 +65,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             sumcheck_random_scalars.entrywise_point,
         );
         let one_evaluation = compute_truncated_lagrange_basis_sum(table_length, evaluation_point);
+        let first_row_evaluation = compute_truncated_lagrange_basis_sum(1, evaluation_point);
 
         let result_indexes_evaluation = result_indexes.evaluate_at_point(evaluation_point);
 
@@ -68,6 +73,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             table_length,
             num_sumcheck_variables: evaluation_point.len(),
             one_evaluation,
+            first_row_evaluation,
             random_evaluation,
             pre_result_evaluations,
             result_evaluations,
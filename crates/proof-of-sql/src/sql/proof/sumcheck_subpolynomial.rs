@@ -2,6 +2,7 @@ use super::CompositePolynomialBuilder;
 use crate::base::{polynomial::MultilinearExtension, scalar::Scalar};
 
 /// The type of a sumcheck subpolynomial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SumcheckSubpolynomialType {
     /// The subpolynomial should be zero at every entry/row
     Identity,
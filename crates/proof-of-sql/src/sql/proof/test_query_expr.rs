@@ -5,6 +5,7 @@ use super::{
 use crate::base::{
     database::{
         ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
+        TableRef,
     },
     proof::ProofError,
     scalar::Curve25519Scalar,
@@ -62,6 +63,7 @@ impl ProofExpr<RistrettoPoint> for TestQueryExpr {
         builder.count_intermediate_mles(self.counts.intermediate_mles);
         builder.count_subpolynomials(self.counts.sumcheck_subpolynomials);
         builder.count_post_result_challenges(self.counts.post_result_challenges);
+        builder.count_window_aggregates(self.counts.window_aggregates);
         Ok(())
     }
 
@@ -101,6 +103,10 @@ impl ProofExpr<RistrettoPoint> for TestQueryExpr {
     fn get_column_references(&self) -> HashSet<ColumnRef> {
         unimplemented!("no real usage for this function yet")
     }
+
+    fn get_table_references(&self) -> HashSet<TableRef> {
+        unimplemented!("no real usage for this function yet")
+    }
 }
 
 impl ProverEvaluate<Curve25519Scalar> for TestQueryExpr {
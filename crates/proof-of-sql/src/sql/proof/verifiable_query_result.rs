@@ -1,13 +1,18 @@
-use super::{ProofExpr, ProvableQueryResult, QueryData, QueryProof, QueryResult};
+use super::{
+    query_proof::check_column_references, ProofExpr, ProvableQueryResult, ProverConfig,
+    ProverReport, QueryData, QueryProof, QueryResult, VerificationReport,
+};
 use crate::base::{
-    commitment::CommitmentEvaluationProof,
+    commitment::{CommitmentEvaluationProof, QueryCommitments},
     database::{
         ColumnField, ColumnType, CommitmentAccessor, DataAccessor, OwnedColumn, OwnedTable,
+        OwnedTableError, TableRef,
     },
     proof::ProofError,
     scalar::Scalar,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// The result of an sql query along with a proof that the query is valid. The
 /// result and proof can be verified using commitments to database columns.
@@ -78,27 +83,50 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
     ///
     /// This function both computes the result of a query and constructs a proof of the results
     /// validity.
+    ///
+    /// This errors like [`Self::new_with_config`], except that it cannot be cancelled.
     pub fn new(
         expr: &(impl ProofExpr<CP::Commitment> + Serialize),
         accessor: &impl DataAccessor<CP::Scalar>,
         setup: &CP::ProverPublicSetup,
-    ) -> Self {
+    ) -> Result<Self, ProofError> {
+        Self::new_with_config(expr, accessor, setup, &ProverConfig::default())
+            .map(|(result, _)| result)
+    }
+
+    /// Form a `VerifiableQueryResult` from a query expression, along with a [`ProverReport`]
+    /// if [`ProverConfig::collect_report`] is set.
+    ///
+    /// Returns [`ProofError::Cancelled`] if [`ProverConfig::cancellation_token`] is set before the
+    /// proof is complete, [`ProofError::DivisionByZero`] if a provable division has a zero divisor,
+    /// [`ProofError::DivisionOverflow`] if a provable integer division has a quotient that does not fit in its type,
+    /// [`ProofError::UnknownColumn`] if the query references a column that the accessor does not have,
+    /// and [`ProofError::TypeMismatch`] if such a column does not have the type that the query expects.
+    pub fn new_with_config(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup,
+        config: &ProverConfig,
+    ) -> Result<(Self, Option<ProverReport>), ProofError> {
         // a query must have at least one result column; if not, it should
         // have been rejected at the parsing stage.
+        check_column_references(expr, accessor)?;
 
         // handle the empty case
         if expr.is_empty(accessor) {
-            return VerifiableQueryResult {
+            let result = VerifiableQueryResult {
                 provable_result: None,
                 proof: None,
             };
+            return Ok((result, config.collect_report.then(ProverReport::default)));
         }
 
-        let (proof, res) = QueryProof::new(expr, accessor, setup);
-        Self {
+        let (proof, res, report) = QueryProof::new_with_config(expr, accessor, setup, config)?;
+        let result = Self {
             provable_result: Some(res),
             proof: Some(proof),
-        }
+        };
+        Ok((result, report))
     }
 
     /// Verify a `VerifiableQueryResult`. Upon success, this function returns the finalized form of
@@ -108,11 +136,26 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
     /// error.
     ///
     /// Note: This does NOT transform the result!
+    #[tracing::instrument(name = "VerifiableQueryResult::verify", level = "debug", skip_all, err)]
     pub fn verify(
         &self,
         expr: &(impl ProofExpr<CP::Commitment> + Serialize),
         accessor: &impl CommitmentAccessor<CP::Commitment>,
         setup: &CP::VerifierPublicSetup,
+    ) -> QueryResult<CP::Scalar> {
+        self.verify_with_domain_separator(expr, accessor, setup, &[])
+    }
+
+    /// Verify a `VerifiableQueryResult` that was created with [`ProverConfig::domain_separator`]
+    /// set to `domain_separator`, like [`Self::verify`].
+    ///
+    /// A proof created for a deployment with a different separator fails to verify.
+    pub fn verify_with_domain_separator(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup,
+        domain_separator: &[u8],
     ) -> QueryResult<CP::Scalar> {
         // a query must have at least one result column; if not, it should
         // have been rejected at the parsing stage.
@@ -136,17 +179,85 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
             ))?;
         }
 
-        self.proof.as_ref().unwrap().verify(
+        self.proof.as_ref().unwrap().verify_with_domain_separator(
             expr,
             accessor,
             self.provable_result.as_ref().unwrap(),
             setup,
+            domain_separator,
         )
     }
+
+    /// Verify a `VerifiableQueryResult` like [`Self::verify_with_domain_separator`], but rather than
+    /// stopping at the first constraint that fails, go on and return every failure in a [`VerificationReport`].
+    ///
+    /// This is slower than [`Self::verify`] for a proof that does not verify, and is meant for finding out why.
+    /// The failing sumcheck subpolynomials are not told apart, see [`VerificationReport`].
+    /// Pass an empty `domain_separator` for a proof created without [`ProverConfig::domain_separator`].
+    pub fn verify_with_report(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup,
+        domain_separator: &[u8],
+    ) -> VerificationReport {
+        match (&self.proof, &self.provable_result) {
+            (Some(proof), Some(result)) if !expr.is_empty(accessor) => {
+                proof.verify_with_report(expr, accessor, result, setup, domain_separator)
+            }
+            // Without a proof to check, verification fails or succeeds as a whole.
+            _ => VerificationReport::new(
+                self.verify_with_domain_separator(expr, accessor, setup, domain_separator)
+                    .err()
+                    .into_iter()
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Verify a `VerifiableQueryResult` against table commitments that a client trusts via their published roots.
+    ///
+    /// Before proceeding with [`Self::verify_with_domain_separator`], this checks that every table `expr` reads has a
+    /// commitment in `commitments` whose [`TableCommitment::root`](crate::base::commitment::TableCommitment::root)
+    /// matches the trusted root in `roots`. This prevents a prover from substituting a different table,
+    /// or a different version of a table, than the one the client trusts.
+    /// A table is checked even if `expr` references none of its columns, since its length still enters the proof.
+    ///
+    /// Note: since the root binds every column of a table commitment, `commitments` should hold the same
+    /// table commitments that the roots were published for, rather than commitments to only the queried columns.
+    /// Pass an empty `domain_separator` for a proof created without [`ProverConfig::domain_separator`].
+    pub fn verify_with_roots(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        commitments: &QueryCommitments<CP::Commitment>,
+        roots: &HashMap<TableRef, [u8; 32]>,
+        setup: &CP::VerifierPublicSetup,
+        domain_separator: &[u8],
+    ) -> QueryResult<CP::Scalar> {
+        for table_ref in expr.get_table_references() {
+            match (commitments.get(&table_ref), roots.get(&table_ref)) {
+                (Some(table_commitment), Some(root)) if table_commitment.root() == *root => {}
+                _ => Err(ProofError::CommitmentMismatch(
+                    "table commitment does not match the trusted root",
+                ))?,
+            }
+        }
+        self.verify_with_domain_separator(expr, commitments, setup, domain_separator)
+    }
 }
 
 fn make_empty_query_result<S: Scalar>(result_fields: Vec<ColumnField>) -> QueryResult<S> {
-    let table = OwnedTable::try_new(
+    Ok(QueryData {
+        table: make_empty_table(&result_fields)?,
+        verification_hash: Default::default(),
+    })
+}
+
+/// The table with the columns of `result_fields` and no rows.
+pub(crate) fn make_empty_table<S: Scalar>(
+    result_fields: &[ColumnField],
+) -> Result<OwnedTable<S>, OwnedTableError> {
+    OwnedTable::try_new(
         result_fields
             .iter()
             .map(|field| {
@@ -155,7 +266,7 @@ fn make_empty_query_result<S: Scalar>(result_fields: Vec<ColumnField>) -> QueryR
                     match field.data_type() {
                         ColumnType::Boolean => OwnedColumn::Boolean(vec![]),
                         ColumnType::SmallInt => OwnedColumn::SmallInt(vec![]),
-                        ColumnType::Int => OwnedColumn::SmallInt(vec![]),
+                        ColumnType::Int => OwnedColumn::Int(vec![]),
                         ColumnType::BigInt => OwnedColumn::BigInt(vec![]),
                         ColumnType::Int128 => OwnedColumn::Int128(vec![]),
                         ColumnType::Decimal75(precision, scale) => {
@@ -163,13 +274,10 @@ fn make_empty_query_result<S: Scalar>(result_fields: Vec<ColumnField>) -> QueryR
                         }
                         ColumnType::Scalar => OwnedColumn::Scalar(vec![]),
                         ColumnType::VarChar => OwnedColumn::VarChar(vec![]),
+                        ColumnType::Binary(length) => OwnedColumn::Binary(length, vec![]),
                     },
                 )
             })
             .collect(),
-    )?;
-    Ok(QueryData {
-        table,
-        verification_hash: Default::default(),
-    })
+    )
 }
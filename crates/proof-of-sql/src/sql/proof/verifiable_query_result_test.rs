@@ -19,7 +19,7 @@ fn we_can_verify_queries_on_an_empty_table() {
         ..Default::default()
     };
     let accessor = RecordBatchTestAccessor::new_empty();
-    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &()).unwrap();
     let res = res
         .verify(&expr, &accessor, &())
         .unwrap()
@@ -91,7 +91,7 @@ fn tamper_no_result(
         ..Default::default()
     };
     let accessor_p = RecordBatchTestAccessor::new_empty();
-    let (proof, _result) = QueryProof::new(&expr_p, &accessor_p, &());
+    let (proof, _result) = QueryProof::new(&expr_p, &accessor_p, &()).unwrap();
     res_p.proof = Some(proof);
     assert!(res_p.verify(expr, accessor, &()).is_err());
 }
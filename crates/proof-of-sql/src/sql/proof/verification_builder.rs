@@ -1,6 +1,7 @@
-use super::SumcheckMleEvaluations;
-use crate::base::{bit::BitDistribution, commitment::Commitment};
+use super::{SharedSubexpressions, SumcheckMleEvaluations};
+use crate::base::{bit::BitDistribution, commitment::Commitment, proof::ProofError};
 use num_traits::Zero;
+use std::{any::Any, hash::Hash};
 
 /// Track components used to verify a query's proof
 pub struct VerificationBuilder<'a, C: Commitment> {
@@ -11,6 +12,7 @@ pub struct VerificationBuilder<'a, C: Commitment> {
     inner_product_multipliers: &'a [C::Scalar],
     sumcheck_evaluation: C::Scalar,
     bit_distributions: &'a [BitDistribution],
+    window_aggregates: &'a [C::Scalar],
     pre_result_commitments: Vec<C>,
     folded_pre_result_evaluation: C::Scalar,
     consumed_result_mles: usize,
@@ -25,13 +27,19 @@ pub struct VerificationBuilder<'a, C: Commitment> {
     /// Note: this vector is treated as a stack and the first
     /// challenge is the last entry in the vector.
     post_result_challenges: Vec<C::Scalar>,
+    /// The evaluations of the subexpressions that have already been verified, keyed by their plan
+    shared_subexpressions: SharedSubexpressions<C::Scalar>,
+    /// The failed constraints, if they are collected rather than returned. See [`Self::check_constraint`].
+    constraint_failures: Option<Vec<ProofError>>,
 }
 
 impl<'a, C: Commitment> VerificationBuilder<'a, C> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         generator_offset: usize,
         mle_evaluations: SumcheckMleEvaluations<'a, C::Scalar>,
         bit_distributions: &'a [BitDistribution],
+        window_aggregates: &'a [C::Scalar],
         intermediate_commitments: &'a [C],
         subpolynomial_multipliers: &'a [C::Scalar],
         inner_product_multipliers: &'a [C::Scalar],
@@ -45,6 +53,7 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
             mle_evaluations,
             generator_offset,
             bit_distributions,
+            window_aggregates,
             intermediate_commitments,
             subpolynomial_multipliers,
             inner_product_multipliers,
@@ -56,6 +65,8 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
             consumed_intermediate_mles: 0,
             produced_subpolynomials: 0,
             post_result_challenges,
+            shared_subexpressions: SharedSubexpressions::default(),
+            constraint_failures: None,
         }
     }
 
@@ -63,6 +74,45 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         self.mle_evaluations.table_length
     }
 
+    /// The evaluation of an identical subexpression that was already verified, if any.
+    pub fn get_shared_subexpression<K: Any + Eq + Hash>(&self, key: &K) -> Option<C::Scalar> {
+        self.shared_subexpressions.get(key).copied()
+    }
+
+    /// Record the evaluation of a verified subexpression, so that later copies of it can reuse it.
+    pub fn insert_shared_subexpression<K: Any + Eq + Hash + Clone>(
+        &mut self,
+        key: &K,
+        eval: C::Scalar,
+    ) {
+        self.shared_subexpressions.insert(key, eval);
+    }
+
+    /// Collect the constraints that fail in [`Self::check_constraint`] rather than returning them,
+    /// so that verification goes on past a failed constraint.
+    pub fn collect_constraint_failures(&mut self) {
+        self.constraint_failures = Some(Vec::new());
+    }
+
+    /// Take the constraints that failed since [`Self::collect_constraint_failures`] was called.
+    pub fn take_constraint_failures(&mut self) -> Vec<ProofError> {
+        self.constraint_failures.take().unwrap_or_default()
+    }
+
+    /// Check a constraint that the verifier checks directly on the values of the proof.
+    ///
+    /// This returns the error of `result`, unless failed constraints are being collected,
+    /// in which case the error is collected and the node should go on as if the constraint held.
+    pub fn check_constraint(&mut self, result: Result<(), ProofError>) -> Result<(), ProofError> {
+        match (result, self.constraint_failures.as_mut()) {
+            (Err(error), Some(failures)) => {
+                failures.push(error);
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+
     pub fn generator_offset(&self) -> usize {
         self.generator_offset
     }
@@ -88,6 +138,14 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         res
     }
 
+    /// Consume the value of an aggregate over the whole selection, such as `SUM(x) OVER ()`,
+    /// that the prover sent
+    pub fn consume_window_aggregate(&mut self) -> C::Scalar {
+        let res = self.window_aggregates[0];
+        self.window_aggregates = &self.window_aggregates[1..];
+        res
+    }
+
     /// Consume the evaluation of an intermediate MLE used in sumcheck
     ///
     /// An interemdiate MLE is one where the verifier doesn't have access to its commitment
@@ -139,6 +197,7 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
     /// Check that the verification builder is completely built up
     fn completed(&self) -> bool {
         self.bit_distributions.is_empty()
+            && self.window_aggregates.is_empty()
             && self.produced_subpolynomials == self.subpolynomial_multipliers.len()
             && self.consumed_intermediate_mles == self.intermediate_commitments.len()
             && self.consumed_pre_result_mles == self.mle_evaluations.pre_result_evaluations.len()
@@ -18,6 +18,7 @@ fn an_empty_sumcheck_polynomial_evaluates_to_zero() {
         &[][..],
         &[][..],
         &[][..],
+        &[][..],
         Vec::new(),
     );
     assert_eq!(builder.sumcheck_evaluation(), Curve25519Scalar::zero());
@@ -41,6 +42,7 @@ fn we_build_up_a_sumcheck_polynomial_evaluation_from_subpolynomial_evaluations()
         mle_evaluations,
         &[][..],
         &[][..],
+        &[][..],
         &subpolynomial_multipliers,
         &[][..],
         Vec::new(),
@@ -76,6 +78,7 @@ fn we_build_up_the_folded_pre_result_commitment() {
         0,
         mle_evaluations,
         &[][..],
+        &[][..],
         &intermediate_commitments,
         &[][..],
         &inner_product_multipliers,
@@ -118,6 +121,7 @@ fn we_can_consume_result_evaluations() {
         &[][..],
         &[][..],
         &[][..],
+        &[][..],
         Vec::new(),
     );
     assert_eq!(builder.consume_result_mle(), Curve25519Scalar::from(123u64));
@@ -133,6 +137,7 @@ fn we_can_consume_post_result_challenges_in_proof_builder() {
         &[][..],
         &[][..],
         &[][..],
+        &[][..],
         vec![
             Curve25519Scalar::from(123),
             Curve25519Scalar::from(456),
@@ -0,0 +1,36 @@
+use super::QueryError;
+
+/// Every check that failed while verifying a proof, for finding out why a proof does not verify.
+///
+/// This is returned by [`VerifiableQueryResult::verify_with_report`](super::VerifiableQueryResult::verify_with_report),
+/// which goes on verifying past a failed constraint, rather than stopping at the first one like
+/// [`VerifiableQueryResult::verify`](super::VerifiableQueryResult::verify).
+/// A check that cannot be made because of an earlier failure still stops verification,
+/// so the failures are only all of them up to the last one.
+///
+/// The constraints that the verifier checks directly, such as bitwise decompositions, are reported one by one.
+/// The sumcheck subpolynomials are not: the verifier only evaluates their random linear combination
+/// at a random point, so a failure of any of them is a single [`ProofError::SumcheckFailed`](crate::base::proof::ProofError::SumcheckFailed),
+/// and a commitment that does not open to its evaluation is likewise a single
+/// [`ProofError::CommitmentMismatch`](crate::base::proof::ProofError::CommitmentMismatch),
+/// without telling which subpolynomial or column is at fault.
+#[derive(Default, Debug)]
+pub struct VerificationReport {
+    failures: Vec<QueryError>,
+}
+
+impl VerificationReport {
+    pub(super) fn new(failures: Vec<QueryError>) -> Self {
+        Self { failures }
+    }
+
+    /// Returns the failed checks, in the order in which they were made.
+    pub fn failures(&self) -> &[QueryError] {
+        &self.failures
+    }
+
+    /// Returns `true` if no check failed, so the proof verified.
+    pub fn is_verified(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
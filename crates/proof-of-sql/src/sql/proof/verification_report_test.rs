@@ -0,0 +1,52 @@
+use super::{QueryError, VerifiableQueryResult};
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+        proof::ProofError,
+    },
+    proof_primitive::dory::{DoryEvaluationProof, DoryProverPublicSetup, DoryScalar},
+    sql::parse::QueryExpr,
+};
+use ark_std::test_rng;
+use num_traits::One;
+
+#[test]
+fn we_can_report_every_failed_check_of_a_proof_that_does_not_verify() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 1, 3]), bigint("b", [4, 5, 6, 7])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT b FROM t WHERE a = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let res =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+            .unwrap();
+    let report = res.verify_with_report(query.proof_expr(), &accessor, &(&setup).into(), &[]);
+    assert!(report.is_verified());
+    assert!(report.failures().is_empty());
+
+    // An evaluation that differs from the committed column fails both the sumcheck and the commitment check.
+    let mut tampered = res.clone();
+    tampered.proof.as_mut().unwrap().pre_result_mle_evaluations[0] += DoryScalar::one();
+    assert!(matches!(
+        tampered.verify(query.proof_expr(), &accessor, &(&setup).into()),
+        Err(QueryError::ProofError(ProofError::SumcheckFailed(_)))
+    ));
+    let report = tampered.verify_with_report(query.proof_expr(), &accessor, &(&setup).into(), &[]);
+    assert!(!report.is_verified());
+    assert!(matches!(
+        report.failures(),
+        [
+            QueryError::ProofError(ProofError::SumcheckFailed(_)),
+            QueryError::ProofError(ProofError::CommitmentMismatch(_)),
+        ]
+    ));
+}
@@ -0,0 +1,221 @@
+use super::{
+    parse::{ConversionError, QueryExpr},
+    proof::{
+        make_empty_table, plan_hash, ProofError, ProofExpr, ProverConfig, QueryError,
+        VerifiableQueryResult,
+    },
+};
+use crate::base::{
+    commitment::{Commitment, CommitmentEvaluationProof, QueryCommitments},
+    database::{DataAccessor, OwnedTable, SchemaAccessor},
+    scalar::Scalar,
+};
+use arrow::record_batch::RecordBatch;
+use proof_of_sql_parser::{Identifier, SelectStatement};
+use serde::{Deserialize, Serialize};
+
+/// A [`VerifiableQueryResult`] serialized to be sent from the prover to the verifier.
+///
+/// Along with the result and its proof, this holds the [`plan_hash`] of the plan that the proof was created for,
+/// so that [`verify_query`] reports a [`ProofError::PlanMismatch`] early, even when the table is empty and there is no proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedProof(Vec<u8>);
+
+impl SerializedProof {
+    /// The bytes of the serialized proof.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consume the serialized proof, returning its bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for SerializedProof {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedProofContents<CP: CommitmentEvaluationProof> {
+    plan_hash: [u8; 32],
+    result: VerifiableQueryResult<CP>,
+}
+
+/// Parse, plan and prove `sql`, returning the serialized proof along with the result of the query.
+///
+/// Tables that `sql` does not qualify with a schema are looked up in `default_schema`.
+/// This errors with [`ConversionError::ParseError`] if `sql` cannot be parsed,
+/// with [`ConversionError::ProofCreationError`] if the planned query cannot be proven or its result cannot be computed,
+/// and with any other [`ConversionError`] if the parsed query cannot be planned.
+///
+/// ```
+/// # #[cfg(feature = "test")]
+/// # {
+/// use ark_std::test_rng;
+/// use proof_of_sql::{
+///     base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+///     proof_primitive::dory::{DoryEvaluationProof, DoryProverPublicSetup},
+///     sql::prove_query,
+/// };
+///
+/// let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+/// let mut accessor =
+///     OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+/// accessor.add_table(
+///     "sxt.t".parse().unwrap(),
+///     owned_table([
+///         bigint("a", [1, 2, 3, 2]),
+///         varchar("b", ["hi", "hello", "there", "world"]),
+///     ]),
+///     0,
+/// );
+/// let (proof, result) = prove_query::<DoryEvaluationProof>(
+///     "SELECT b FROM t WHERE a = 2",
+///     "sxt".parse().unwrap(),
+///     &accessor,
+///     &setup,
+/// )
+/// .unwrap();
+/// assert_eq!(result, owned_table([varchar("b", ["hello", "world"])]));
+/// assert!(!proof.as_bytes().is_empty());
+/// # }
+/// ```
+pub fn prove_query<CP: CommitmentEvaluationProof + Serialize>(
+    sql: &str,
+    default_schema: Identifier,
+    accessor: &(impl DataAccessor<CP::Scalar> + SchemaAccessor),
+    setup: &CP::ProverPublicSetup,
+) -> Result<(SerializedProof, OwnedTable<CP::Scalar>), ConversionError> {
+    let query = plan_query::<CP::Commitment>(sql, default_schema, accessor)?;
+    let (result, _) = VerifiableQueryResult::<CP>::new_with_config(
+        query.proof_expr(),
+        accessor,
+        setup,
+        &ProverConfig::default(),
+    )
+    .map_err(proof_creation_error)?;
+    let result_fields = query.proof_expr().get_column_result_fields();
+    let table: OwnedTable<CP::Scalar> = match &result.provable_result {
+        Some(provable_result) => provable_result.to_owned_table(&result_fields),
+        None => make_empty_table(&result_fields).map_err(QueryError::from),
+    }
+    .map_err(proof_creation_error)?;
+    let table = transform_result(&query, table).map_err(proof_creation_error)?;
+    let bytes = postcard::to_allocvec(&SerializedProofContents {
+        plan_hash: plan_hash(query.proof_expr()),
+        result,
+    })
+    .map_err(proof_creation_error)?;
+    Ok((SerializedProof(bytes), table))
+}
+
+/// Parse and plan `sql`, and verify that `serialized_proof` proves its result, returning the result of the query.
+///
+/// This is the verifier's half of [`prove_query`], and `sql` and `default_schema` must be those that the proof
+/// was created with. A proof is only accepted for the plan that it was created for, so the result is known
+/// to answer `sql` rather than any other query of the same tables.
+/// This errors with [`QueryError::InvalidQuery`] if `sql` cannot be parsed or planned against `commitments`,
+/// and with [`QueryError::ProofError`] if the proof is malformed, was created for a different plan or does not verify.
+///
+/// ```
+/// # #[cfg(feature = "test")]
+/// # {
+/// use ark_std::test_rng;
+/// use proof_of_sql::{
+///     base::{
+///         commitment::{QueryCommitments, TableCommitment},
+///         database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+///     },
+///     proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+///     sql::{prove_query, verify_query},
+/// };
+///
+/// // The prover has the table, while the verifier only has a commitment to it.
+/// let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+/// let table = owned_table([
+///     bigint("a", [1, 2, 3, 2]),
+///     varchar("b", ["hi", "hello", "there", "world"]),
+/// ]);
+/// let commitments: QueryCommitments<DoryCommitment> = QueryCommitments::from_iter([(
+///     "sxt.t".parse().unwrap(),
+///     TableCommitment::from_owned_table_with_offset(&table, 0, &setup),
+/// )]);
+/// let mut accessor =
+///     OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+/// accessor.add_table("sxt.t".parse().unwrap(), table, 0);
+///
+/// let sql = "SELECT b FROM t WHERE a = 2";
+/// let (proof, _) =
+///     prove_query::<DoryEvaluationProof>(sql, "sxt".parse().unwrap(), &accessor, &setup).unwrap();
+/// let result = verify_query::<DoryEvaluationProof>(
+///     sql,
+///     "sxt".parse().unwrap(),
+///     &proof,
+///     &commitments,
+///     &(&setup).into(),
+/// )
+/// .unwrap();
+/// assert_eq!(result, owned_table([varchar("b", ["hello", "world"])]));
+///
+/// // The proof does not answer any other query.
+/// assert!(verify_query::<DoryEvaluationProof>(
+///     "SELECT b FROM t WHERE a = 3",
+///     "sxt".parse().unwrap(),
+///     &proof,
+///     &commitments,
+///     &(&setup).into(),
+/// )
+/// .is_err());
+/// # }
+/// ```
+pub fn verify_query<CP: CommitmentEvaluationProof + for<'a> Deserialize<'a>>(
+    sql: &str,
+    default_schema: Identifier,
+    serialized_proof: &SerializedProof,
+    commitments: &QueryCommitments<CP::Commitment>,
+    setup: &CP::VerifierPublicSetup,
+) -> Result<OwnedTable<CP::Scalar>, QueryError> {
+    let query = plan_query::<CP::Commitment>(sql, default_schema, commitments)?;
+    let contents: SerializedProofContents<CP> =
+        postcard::from_bytes(serialized_proof.as_bytes())
+            .map_err(|_| ProofError::VerificationError("the serialized proof is malformed"))?;
+    if contents.plan_hash != plan_hash(query.proof_expr()) {
+        Err(ProofError::PlanMismatch)?;
+    }
+    let table = contents
+        .result
+        .verify(query.proof_expr(), commitments, setup)?
+        .table;
+    transform_result(&query, table).map_err(QueryError::TransformError)
+}
+
+fn plan_query<C: Commitment>(
+    sql: &str,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> Result<QueryExpr<C>, ConversionError> {
+    let ast: SelectStatement = sql
+        .parse()
+        .map_err(|e| ConversionError::ParseError(format!("{e}")))?;
+    QueryExpr::try_new(ast, default_schema, schema_accessor)
+}
+
+/// Apply the post-proof transformation of `query` to its proven result `table`.
+fn transform_result<C: Commitment, S: Scalar>(
+    query: &QueryExpr<C>,
+    table: OwnedTable<S>,
+) -> Result<OwnedTable<S>, String> {
+    let batch = query
+        .result()
+        .transform_results(RecordBatch::try_from(table).map_err(|e| e.to_string())?)
+        .ok_or("the result could not be transformed")?;
+    OwnedTable::try_from(batch).map_err(|e| e.to_string())
+}
+
+fn proof_creation_error(error: impl ToString) -> ConversionError {
+    ConversionError::ProofCreationError(error.to_string())
+}
@@ -0,0 +1,237 @@
+use super::{
+    parse::ConversionError,
+    proof::{ProofError, QueryError},
+    prove_query, verify_query, SerializedProof,
+};
+use crate::{
+    base::{
+        commitment::{QueryCommitments, QueryCommitmentsExt},
+        database::{
+            owned_table_utility::*, ColumnRef, LiteralValue, OwnedTable, OwnedTableTestAccessor,
+            SchemaAccessor, TestAccessor,
+        },
+    },
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
+};
+use ark_std::test_rng;
+use arrow::{
+    array::{ArrayRef, Int64Array},
+    record_batch::RecordBatch,
+};
+use indexmap::IndexMap;
+use std::sync::Arc;
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [3, 1, 2, 1]),
+            bigint("zero", [0; 4]),
+            varchar("b", ["x", "y", "z", "w"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn commitments(
+    accessor: &OwnedTableTestAccessor<DoryEvaluationProof>,
+) -> QueryCommitments<DoryCommitment> {
+    let table_ref = "sxt.t".parse().unwrap();
+    QueryCommitments::from_accessor_with_max_bounds(
+        accessor
+            .lookup_schema(table_ref)
+            .into_iter()
+            .map(|(column_id, column_type)| ColumnRef::new(table_ref, column_id, column_type)),
+        accessor,
+    )
+}
+
+#[test]
+fn we_can_prove_a_query_and_verify_its_serialized_proof() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let sql = "SELECT b, a AS x FROM t WHERE a < 3 ORDER BY x DESC, b";
+    let (proof, table) =
+        prove_query::<DoryEvaluationProof>(sql, "sxt".parse().unwrap(), &accessor, &setup).unwrap();
+    assert_eq!(
+        table,
+        owned_table([varchar("b", ["z", "w", "y"]), bigint("x", [2, 1, 1])])
+    );
+
+    assert_eq!(
+        verify_query::<DoryEvaluationProof>(
+            sql,
+            "sxt".parse().unwrap(),
+            &proof,
+            &commitments(&accessor),
+            &(&setup).into()
+        )
+        .unwrap(),
+        table
+    );
+}
+
+#[test]
+fn we_can_prove_a_query_of_an_empty_table() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([bigint("a", [0; 0])]),
+        0,
+    );
+    let (_, table) = prove_query::<DoryEvaluationProof>(
+        "SELECT a FROM t",
+        "sxt".parse().unwrap(),
+        &accessor,
+        &setup,
+    )
+    .unwrap();
+    assert_eq!(table, owned_table([bigint("a", [0; 0])]));
+}
+
+#[test]
+fn we_can_prove_and_verify_a_query_of_columns_with_quoted_identifiers() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint(r#""select""#, [3, 1, 2]),
+            varchar(r#""first name""#, ["x", "y", "z"]),
+        ]),
+        0,
+    );
+    let sql = r#"SELECT "first name" AS "name on file", "select" FROM t WHERE "select" >= 2"#;
+    let (proof, table) =
+        prove_query::<DoryEvaluationProof>(sql, "sxt".parse().unwrap(), &accessor, &setup).unwrap();
+    let expected = owned_table([
+        varchar(r#""name on file""#, ["x", "z"]),
+        bigint(r#""select""#, [3, 2]),
+    ]);
+    assert_eq!(table, expected);
+    assert_eq!(
+        verify_query::<DoryEvaluationProof>(
+            sql,
+            "sxt".parse().unwrap(),
+            &proof,
+            &commitments(&accessor),
+            &(&setup).into()
+        )
+        .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_a_query_of_values_filled_by_ingestion_defaults() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+    let sparse_batch = RecordBatch::try_from_iter([(
+        "a",
+        Arc::new(Int64Array::from(vec![Some(3), None, Some(2), None])) as ArrayRef,
+    )])
+    .unwrap();
+    let defaults = IndexMap::from_iter([
+        ("a".parse().unwrap(), LiteralValue::BigInt(0)),
+        (
+            "b".parse().unwrap(),
+            LiteralValue::VarChar(("missing".to_string(), "missing".into())),
+        ),
+    ]);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        OwnedTable::try_from_record_batch_with_defaults(sparse_batch, &defaults).unwrap(),
+        0,
+    );
+    let sql = "SELECT a, b FROM t WHERE a = 0";
+    let (proof, table) =
+        prove_query::<DoryEvaluationProof>(sql, "sxt".parse().unwrap(), &accessor, &setup).unwrap();
+    let expected = owned_table([bigint("a", [0, 0]), varchar("b", ["missing", "missing"])]);
+    assert_eq!(table, expected);
+    assert_eq!(
+        verify_query::<DoryEvaluationProof>(
+            sql,
+            "sxt".parse().unwrap(),
+            &proof,
+            &commitments(&accessor),
+            &(&setup).into()
+        )
+        .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn parse_plan_and_proof_errors_are_distinct() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let prove =
+        |sql| prove_query::<DoryEvaluationProof>(sql, "sxt".parse().unwrap(), &accessor, &setup);
+    assert!(matches!(
+        prove("SELECT a FRM t"),
+        Err(ConversionError::ParseError(_))
+    ));
+    assert!(matches!(
+        prove("SELECT c FROM t"),
+        Err(ConversionError::MissingColumn(..))
+    ));
+    assert!(matches!(
+        prove("SELECT a / zero AS q FROM t"),
+        Err(ConversionError::ProofCreationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_against_a_different_or_invalid_query() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let commitments = commitments(&accessor);
+    let (proof, _) = prove_query::<DoryEvaluationProof>(
+        "SELECT b FROM t WHERE a = 1",
+        "sxt".parse().unwrap(),
+        &accessor,
+        &setup,
+    )
+    .unwrap();
+    let verify = |sql, proof| {
+        verify_query::<DoryEvaluationProof>(
+            sql,
+            "sxt".parse().unwrap(),
+            proof,
+            &commitments,
+            &(&setup).into(),
+        )
+    };
+    assert!(verify("SELECT b FROM t WHERE a = 1", &proof).is_ok());
+    for sql in [
+        "SELECT b FROM t WHERE a = 2",
+        "SELECT a FROM t WHERE a = 1",
+        "SELECT b FROM t WHERE a = 1 OR a = 1",
+    ] {
+        assert!(
+            matches!(
+                verify(sql, &proof),
+                Err(QueryError::ProofError(ProofError::PlanMismatch))
+            ),
+            "{sql}"
+        );
+    }
+    assert!(matches!(
+        verify("SELECT c FROM t", &proof),
+        Err(QueryError::InvalidQuery(ConversionError::MissingColumn(..)))
+    ));
+    let mut bytes = proof.as_bytes().to_vec();
+    bytes.truncate(bytes.len() / 2);
+    assert!(matches!(
+        verify("SELECT b FROM t WHERE a = 1", &SerializedProof::from(bytes)),
+        Err(QueryError::ProofError(ProofError::VerificationError(_)))
+    ));
+}
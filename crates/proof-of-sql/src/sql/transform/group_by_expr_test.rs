@@ -136,6 +136,25 @@ fn we_can_transform_batch_using_simple_group_by_with_count_aggregation() {
     assert_eq!(data, expected_data);
 }
 
+#[test]
+fn we_can_transform_batch_using_simple_group_by_with_boolean_aggregations() {
+    let data = record_batch!("batch" => [1_i64, 2, 1, 3, 2, 3], "is_valid" => [true, true, false, false, true, false]);
+    let by_exprs = vec![col("batch")];
+    let agg_exprs = vec![
+        col("batch").first().alias("batch"),
+        col("is_valid").bool_and().alias("all_valid"),
+        col("is_valid").bool_or().alias("any_valid"),
+    ];
+    let result_expr = composite_result(vec![groupby(by_exprs, agg_exprs)]);
+    let data = result_expr.transform_results(data).unwrap();
+    let expected_data = record_batch!(
+        "batch" => [1_i64, 2, 3],
+        "all_valid" => [false, true, false],
+        "any_valid" => [true, true, false]
+    );
+    assert_eq!(data, expected_data);
+}
+
 #[test]
 fn we_can_transform_batch_using_simple_group_by_with_first_aggregation() {
     let data = record_batch!("a" => ["a", "d", "b", "a", "b"]);
@@ -33,6 +33,12 @@ pub use slice_expr::SliceExpr;
 #[cfg(test)]
 mod slice_expr_test;
 
+mod page_expr;
+pub use page_expr::{PageCursor, PageExpr};
+
+#[cfg(test)]
+mod page_expr_test;
+
 mod select_expr;
 pub use select_expr::SelectExpr;
 
@@ -78,7 +78,7 @@ pub(crate) fn order_by_map_i128_to_utf8(v: i128) -> String {
 
 // Polars doesn't support Decimal columns inside order by.
 // So we need to remap them to the supported UTF8 type.
-fn order_by_map_to_utf8_if_decimal(expr: Expr) -> Expr {
+pub(super) fn order_by_map_to_utf8_if_decimal(expr: Expr) -> Expr {
     expr.map(
         |series| match series.dtype().clone() {
             DataType::Decimal(Some(INT128_PRECISION), Some(INT128_SCALE)) => {
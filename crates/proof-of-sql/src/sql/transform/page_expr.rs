@@ -0,0 +1,146 @@
+use crate::sql::{
+    parse::{ConversionError, ConversionResult},
+    transform::RecordBatchExpr,
+};
+use arrow::{
+    array::{ArrayRef, UInt32Array},
+    compute::{take, SortOptions},
+    record_batch::RecordBatch,
+    row::{RowConverter, Rows, SortField},
+};
+use dyn_partial_eq::DynPartialEq;
+use proof_of_sql_parser::intermediate_ast::{OrderBy, OrderByDirection};
+use serde::{Deserialize, Serialize};
+
+/// An opaque pagination cursor pointing at the last row returned by a [`PageExpr`].
+///
+/// The cursor should only be passed back to a `PageExpr` with the same `by_exprs`
+/// that produced it, since the encoded key depends on the sort order and column types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// the row-encoded sort key of the last row returned
+    key: Vec<u8>,
+    /// the number of rows with exactly this key that have already been returned
+    ///
+    /// This allows rows with duplicate keys to be split across pages without gaps or overlaps.
+    num_ties: u64,
+}
+
+/// A `PageExpr` returns the page of a sorted result that starts immediately after a cursor.
+///
+/// Unlike `OFFSET`, the page boundary is defined by the sort key of the last row seen,
+/// rather than by a row count. So, rows inserted before the cursor do not shift the next page.
+/// The rows are sorted by the `by_exprs` (stable with regard to the input order)
+/// before the page is taken, so no `OrderByExprs` is needed beforehand.
+#[derive(Debug, Clone, DynPartialEq, PartialEq, Serialize, Deserialize)]
+pub struct PageExpr {
+    by_exprs: Vec<OrderBy>,
+    page_size: u64,
+    cursor: Option<PageCursor>,
+}
+
+impl PageExpr {
+    /// Create a new `PageExpr` node.
+    ///
+    /// A `None` cursor returns the first page.
+    /// Errors with [`ConversionError::InvalidExpression`] if there are no `by_exprs`,
+    /// since the rows of a page would then depend on the order of the table.
+    pub fn try_new(
+        by_exprs: Vec<OrderBy>,
+        page_size: u64,
+        cursor: Option<PageCursor>,
+    ) -> ConversionResult<Self> {
+        if by_exprs.is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "a page of a result requires an ORDER BY".into(),
+            ));
+        }
+        Ok(Self {
+            by_exprs,
+            page_size,
+            cursor,
+        })
+    }
+
+    /// Returns the cursor for the page following `page`, where `page` is the output of this `PageExpr`.
+    ///
+    /// Returns `None` if `page` is empty, since there can be no following rows.
+    pub fn next_cursor(&self, page: &RecordBatch) -> Option<PageCursor> {
+        let last = page.num_rows().checked_sub(1)?;
+        let rows = self.sort_rows(page)?;
+        let key = rows.row(last);
+        let page_ties =
+            u64::try_from((0..=last).rev().take_while(|&i| rows.row(i) == key).count()).ok()?;
+        let num_ties = match &self.cursor {
+            Some(cursor) if cursor.key.as_slice() == key.as_ref() => cursor.num_ties + page_ties,
+            _ => page_ties,
+        };
+        Some(PageCursor {
+            key: key.as_ref().to_vec(),
+            num_ties,
+        })
+    }
+
+    fn sort_rows(&self, record_batch: &RecordBatch) -> Option<Rows> {
+        let columns = self
+            .by_exprs
+            .iter()
+            .map(|by_expr| record_batch.column_by_name(by_expr.expr.name()).cloned())
+            .collect::<Option<Vec<ArrayRef>>>()?;
+        let fields = self
+            .by_exprs
+            .iter()
+            .zip(&columns)
+            .map(|(by_expr, column)| {
+                SortField::new_with_options(
+                    column.data_type().clone(),
+                    SortOptions {
+                        descending: by_expr.direction == OrderByDirection::Desc,
+                        nulls_first: false,
+                    },
+                )
+            })
+            .collect();
+        let mut converter = RowConverter::new(fields).ok()?;
+        converter.convert_columns(&columns).ok()
+    }
+}
+
+#[typetag::serde]
+impl RecordBatchExpr for PageExpr {
+    /// Sort the `RecordBatch` and return the `page_size` rows following the cursor.
+    fn apply_transformation(&self, record_batch: RecordBatch) -> Option<RecordBatch> {
+        let rows = self.sort_rows(&record_batch)?;
+        let mut indexes: Vec<usize> = (0..record_batch.num_rows()).collect();
+        indexes.sort_by(|&a, &b| rows.row(a).cmp(&rows.row(b)));
+
+        let start = match &self.cursor {
+            None => 0,
+            Some(cursor) => {
+                // The row format guarantees that comparing the encoded bytes is equivalent to
+                // comparing the rows themselves.
+                let key = cursor.key.as_slice();
+                let num_before = indexes.partition_point(|&i| rows.row(i).as_ref() < key);
+                let num_equal = indexes[num_before..]
+                    .iter()
+                    .take_while(|&&i| rows.row(i).as_ref() == key)
+                    .count();
+                num_before + num_equal.min(cursor.num_ties.try_into().ok()?)
+            }
+        };
+        let page_indexes: Vec<u32> = indexes
+            .into_iter()
+            .skip(start)
+            .take(self.page_size.try_into().unwrap_or(usize::MAX))
+            .map(|i| u32::try_from(i).ok())
+            .collect::<Option<_>>()?;
+        let page_indexes = UInt32Array::from(page_indexes);
+        let columns = record_batch
+            .columns()
+            .iter()
+            .map(|column| take(column, &page_indexes, None))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        RecordBatch::try_new(record_batch.schema(), columns).ok()
+    }
+}
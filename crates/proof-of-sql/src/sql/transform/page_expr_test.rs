@@ -0,0 +1,130 @@
+use crate::{
+    record_batch,
+    sql::{
+        parse::ConversionError,
+        transform::{test_utility::page, PageExpr, RecordBatchExpr},
+    },
+};
+use arrow::compute::concat_batches;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::{Asc, Desc};
+
+#[test]
+fn we_can_page_through_a_sorted_column_in_two_pages_without_gaps_or_overlaps() {
+    let data = record_batch!(
+        "a" => [5_i64, -3, 8, 1, 0, 12, 7],
+        "b" => ["e", "c", "h", "a", "z", "l", "g"]
+    );
+
+    let first_page_expr = page(&["a"], &[Asc], 4, None);
+    let first_page = first_page_expr.apply_transformation(data.clone()).unwrap();
+    assert_eq!(
+        first_page,
+        record_batch!("a" => [-3_i64, 0, 1, 5], "b" => ["c", "z", "a", "e"])
+    );
+    let cursor = first_page_expr.next_cursor(&first_page);
+
+    let second_page_expr = page(&["a"], &[Asc], 4, cursor);
+    let second_page = second_page_expr.apply_transformation(data.clone()).unwrap();
+    assert_eq!(
+        second_page,
+        record_batch!("a" => [7_i64, 8, 12], "b" => ["g", "h", "l"])
+    );
+
+    let full_result = concat_batches(&data.schema(), &[first_page, second_page]).unwrap();
+    assert_eq!(
+        full_result,
+        record_batch!(
+            "a" => [-3_i64, 0, 1, 5, 7, 8, 12],
+            "b" => ["c", "z", "a", "e", "g", "h", "l"]
+        )
+    );
+}
+
+#[test]
+fn we_can_page_through_duplicate_keys_split_across_pages() {
+    let data = record_batch!(
+        "a" => [2_i64, 1, 2, 2, 3],
+        "b" => ["x", "y", "z", "w", "v"]
+    );
+
+    let first_page_expr = page(&["a"], &[Asc], 2, None);
+    let first_page = first_page_expr.apply_transformation(data.clone()).unwrap();
+    assert_eq!(
+        first_page,
+        record_batch!("a" => [1_i64, 2], "b" => ["y", "x"])
+    );
+
+    let second_page_expr = page(&["a"], &[Asc], 2, first_page_expr.next_cursor(&first_page));
+    let second_page = second_page_expr.apply_transformation(data.clone()).unwrap();
+    assert_eq!(
+        second_page,
+        record_batch!("a" => [2_i64, 2], "b" => ["z", "w"])
+    );
+
+    let third_page_expr = page(
+        &["a"],
+        &[Asc],
+        2,
+        second_page_expr.next_cursor(&second_page),
+    );
+    let third_page = third_page_expr.apply_transformation(data).unwrap();
+    assert_eq!(third_page, record_batch!("a" => [3_i64], "b" => ["v"]));
+}
+
+#[test]
+fn the_next_page_starts_after_the_cursor_even_if_rows_are_added_before_it() {
+    let data = record_batch!("a" => [30_i128, 10, 20], "b" => ["c", "a", "b"]);
+    let first_page_expr = page(&["a"], &[Desc], 1, None);
+    let first_page = first_page_expr.apply_transformation(data).unwrap();
+    assert_eq!(first_page, record_batch!("a" => [30_i128], "b" => ["c"]));
+
+    let changed_data = record_batch!("a" => [30_i128, 10, 20, 40], "b" => ["c", "a", "b", "d"]);
+    let second_page_expr = page(&["a"], &[Desc], 5, first_page_expr.next_cursor(&first_page));
+    let second_page = second_page_expr.apply_transformation(changed_data).unwrap();
+    assert_eq!(
+        second_page,
+        record_batch!("a" => [20_i128, 10], "b" => ["b", "a"])
+    );
+}
+
+#[test]
+fn we_can_page_using_multiple_order_by_columns() {
+    let data = record_batch!(
+        "a" => [1_i64, 1, 2, 2],
+        "b" => ["b", "a", "a", "b"]
+    );
+    let first_page_expr = page(&["a", "b"], &[Desc, Asc], 3, None);
+    let first_page = first_page_expr.apply_transformation(data.clone()).unwrap();
+    assert_eq!(
+        first_page,
+        record_batch!("a" => [2_i64, 2, 1], "b" => ["a", "b", "a"])
+    );
+    let second_page_expr = page(
+        &["a", "b"],
+        &[Desc, Asc],
+        3,
+        first_page_expr.next_cursor(&first_page),
+    );
+    let second_page = second_page_expr.apply_transformation(data).unwrap();
+    assert_eq!(second_page, record_batch!("a" => [1_i64], "b" => ["b"]));
+    assert!(second_page_expr.next_cursor(&second_page).is_some());
+}
+
+#[test]
+fn there_is_no_next_cursor_after_an_empty_page() {
+    let data = record_batch!("a" => [1_i64], "b" => ["a"]);
+    let first_page_expr = page(&["a"], &[Asc], 1, None);
+    let first_page = first_page_expr.apply_transformation(data.clone()).unwrap();
+    let second_page_expr = page(&["a"], &[Asc], 1, first_page_expr.next_cursor(&first_page));
+    let second_page = second_page_expr.apply_transformation(data).unwrap();
+    assert_eq!(second_page.num_rows(), 0);
+    assert_eq!(second_page_expr.next_cursor(&second_page), None);
+}
+
+#[test]
+fn we_cannot_create_a_page_expr_without_an_order_by() {
+    assert!(matches!(
+        PageExpr::try_new(vec![], 1, None),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+}
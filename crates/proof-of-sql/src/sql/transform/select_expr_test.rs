@@ -137,3 +137,30 @@ fn using_max_with_an_empty_batch_will_return_empty_even_with_count_or_sum_in_the
     let expected_data = empty_data;
     assert_eq!(data, expected_data);
 }
+
+#[test]
+fn we_can_use_row_numbers_with_select_expression() {
+    use proof_of_sql_parser::intermediate_ast::OrderByDirection::{Asc, Desc};
+    let data = record_batch!(
+        "x" => [3_i64, -1, 3, 0, 3],
+        "r" => ["b", "a", "a", "c", "b"],
+        "k" => [1_i128, -2, 5, 0, 1]
+    );
+    let result_expr = ResultExpr::new(select(&[
+        row_number(&["x"], &[Asc]).alias("x_asc"),
+        row_number(&["x"], &[Desc]).alias("x_desc"),
+        row_number(&["x", "r"], &[Desc, Asc]).alias("x_desc_r_asc"),
+        row_number(&["k"], &[Asc]).alias("k_asc"),
+        (row_number(&["r"], &[Desc]) - lit_i64(1)).alias("r_desc_from_zero"),
+    ]));
+    let data = result_expr.transform_results(data).unwrap();
+    // Rows that tie keep the order that they have in the batch
+    let expected_data = record_batch!(
+        "x_asc" => [3_i64, 1, 4, 2, 5],
+        "x_desc" => [1_i64, 5, 2, 4, 3],
+        "x_desc_r_asc" => [2_i64, 5, 1, 4, 3],
+        "k_asc" => [3_i64, 1, 5, 2, 4],
+        "r_desc_from_zero" => [1_i64, 3, 4, 0, 2]
+    );
+    assert_eq!(data, expected_data);
+}
@@ -20,6 +20,19 @@ pub fn col(name: &str) -> Box<Expression> {
     Box::new(Expression::Column(name.parse().unwrap()))
 }
 
+pub fn row_number(cols: &[&str], directions: &[OrderByDirection]) -> Box<Expression> {
+    Box::new(Expression::RowNumber {
+        order_by: cols
+            .iter()
+            .zip(directions)
+            .map(|(col, direction)| OrderBy {
+                expr: col.parse().unwrap(),
+                direction: *direction,
+            })
+            .collect(),
+    })
+}
+
 pub(crate) fn select(result_schema: &[impl ToPolarsExpr]) -> Box<dyn RecordBatchExpr> {
     #[allow(deprecated)]
     Box::new(SelectExpr::new(result_schema))
@@ -67,6 +80,24 @@ pub fn orders(cols: &[&str], directions: &[OrderByDirection]) -> Box<dyn RecordB
     Box::new(OrderByExprs::new(by_exprs))
 }
 
+pub fn page(
+    cols: &[&str],
+    directions: &[OrderByDirection],
+    page_size: u64,
+    cursor: Option<PageCursor>,
+) -> PageExpr {
+    let by_exprs = cols
+        .iter()
+        .zip(directions.iter())
+        .map(|(col, direction)| OrderBy {
+            expr: col.parse().unwrap(),
+            direction: *direction,
+        })
+        .collect();
+
+    PageExpr::try_new(by_exprs, page_size, cursor).unwrap()
+}
+
 pub fn groupby<
     T: IntoIterator<Item = Box<Expression>>,
     A: IntoIterator<Item = AliasedResultExpr>,
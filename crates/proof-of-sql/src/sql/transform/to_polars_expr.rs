@@ -1,5 +1,8 @@
-use super::{polars_arithmetic::SafeDivision, polars_conversions::LiteralConversion};
-use polars::prelude::{col, Expr};
+use super::{
+    order_by_exprs::order_by_map_to_utf8_if_decimal, polars_arithmetic::SafeDivision,
+    polars_conversions::LiteralConversion,
+};
+use polars::prelude::{col, lit, DataType, Expr, Null, SortOptions};
 use proof_of_sql_parser::intermediate_ast::*;
 pub(crate) trait ToPolarsExpr {
     fn to_polars_expr(&self) -> Expr;
@@ -30,6 +33,7 @@ impl ToPolarsExpr for Expression {
                 Literal::Int128(value) => value.to_lit(),
                 Literal::VarChar(_) => panic!("Expression not supported"),
                 Literal::Decimal(_) => todo!(),
+                Literal::Null => lit(Null {}),
             },
             Expression::Column(identifier) => col(identifier.as_str()),
             Expression::Binary { op, left, right } => {
@@ -39,20 +43,71 @@ impl ToPolarsExpr for Expression {
                     BinaryOperator::Add => left + right,
                     BinaryOperator::Subtract => left - right,
                     BinaryOperator::Multiply => left * right,
-                    BinaryOperator::Division => left.checked_div(right),
-                    _ => panic!("Operation not supported yet"),
+                    BinaryOperator::Division | BinaryOperator::IntegerDivision => {
+                        left.checked_div(right)
+                    }
+                    BinaryOperator::And => left.and(right),
+                    BinaryOperator::Or => left.or(right),
+                    BinaryOperator::Equal => left.eq(right),
+                    BinaryOperator::GreaterThanOrEqual => left.gt_eq(right),
+                    BinaryOperator::LessThanOrEqual => left.lt_eq(right),
                 }
             }
-            Expression::Aggregation { op, expr } => {
-                let expr = expr.to_polars_expr();
+            Expression::Unary {
+                op: UnaryOperator::Not | UnaryOperator::IsFalse,
+                expr,
+            } => expr.to_polars_expr().not(),
+            Expression::Unary {
+                op: UnaryOperator::IsTrue,
+                expr,
+            } => expr.to_polars_expr(),
+            Expression::Unary {
+                op: UnaryOperator::IsUnknown,
+                expr,
+            } => expr.to_polars_expr().is_null(),
+            Expression::Aggregation { op, expr, filter } => {
+                let expr = match filter {
+                    Some(filter) => expr.to_polars_expr().filter(filter.to_polars_expr()),
+                    None => expr.to_polars_expr(),
+                };
                 match op {
                     AggregationOperator::Count => expr.count(),
                     AggregationOperator::Sum => expr.sum(),
                     AggregationOperator::Min => expr.min(),
                     AggregationOperator::Max => expr.max(),
                     AggregationOperator::First => expr.first(),
+                    AggregationOperator::BoolAnd => expr.all(true),
+                    AggregationOperator::BoolOr => expr.any(true),
                 }
             }
+            Expression::RowNumber { order_by } => {
+                let sort_options = |descending| SortOptions {
+                    descending,
+                    maintain_order: true,
+                    ..Default::default()
+                };
+                // Stably sorting by each column, from the last to the first, sorts by all of them
+                // with the remaining ties in the order of the rows.
+                let permutation = order_by
+                    .iter()
+                    .rev()
+                    .fold(None, |permutation: Option<Expr>, by_expr| {
+                        let key = order_by_map_to_utf8_if_decimal(col(by_expr.expr.as_str()));
+                        let options = sort_options(by_expr.direction == OrderByDirection::Desc);
+                        Some(match permutation {
+                            None => key.arg_sort(options),
+                            Some(permutation) => permutation
+                                .clone()
+                                .take(key.take(permutation).arg_sort(options)),
+                        })
+                    })
+                    .expect("the parser requires at least one order by column");
+                // The inverse of the permutation is the position of each row in the sorted order
+                permutation
+                    .arg_sort(sort_options(false))
+                    .cast(DataType::Int64)
+                    + lit(1_i64)
+            }
             _ => panic!("Operation not supported"),
         }
     }
@@ -0,0 +1,3 @@
+//! Utilities for building randomized fixtures in tests of code that uses Proof of SQL.
+mod random_test_accessor;
+pub use random_test_accessor::{make_random_test_accessor_data, RandomTestAccessorDescriptor};
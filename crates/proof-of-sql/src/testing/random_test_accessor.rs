@@ -1,19 +1,20 @@
 use crate::base::database::ColumnType;
 use arrow::{
     array::{
-        Array, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array, Int64Array,
-        StringArray,
+        Array, BooleanArray, Decimal128Array, Decimal256Array, FixedSizeBinaryArray, Int16Array,
+        Int32Array, Int64Array, StringArray,
     },
     datatypes::{i256, DataType, Field, Schema},
     record_batch::RecordBatch,
 };
 use rand::{
     distributions::{Distribution, Uniform},
-    rngs::StdRng,
+    Rng,
 };
 use std::sync::Arc;
 
-/// Specify what form a randomly generated TestAccessor can take
+/// Specify the size and the range of values of a table generated by [`make_random_test_accessor_data`]
+#[derive(Clone, Debug)]
 pub struct RandomTestAccessorDescriptor {
     /// The minimum number of rows in the generated RecordBatch
     pub min_rows: usize,
@@ -36,17 +37,76 @@ impl Default for RandomTestAccessorDescriptor {
     }
 }
 
-/// Generate a DataFrame with random data
+/// Generate a [`RecordBatch`] of random data with the columns `cols`, to be added to a test accessor.
+///
+/// The number of rows and the values are drawn uniformly from the ranges of `descriptor`.
+/// Integer values are clamped to the range of their column type, booleans are whether the value is odd,
+/// decimals have the value as their unscaled value, `VarChar`s are the value prefixed with `s`,
+/// and `Binary`s are the little-endian bytes of the value, repeated to the length of the column.
+///
+/// # Panics
+///
+/// Panics if any column is a `Scalar` column, which has no arrow representation.
+///
+/// ```
+/// # #[cfg(feature = "test")]
+/// # {
+/// use ark_std::test_rng;
+/// use arrow::array::Int64Array;
+/// use proof_of_sql::{
+///     base::database::{ColumnType, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+///     proof_primitive::dory::{DoryEvaluationProof, DoryProverPublicSetup, DoryScalar},
+///     sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+///     testing::{make_random_test_accessor_data, RandomTestAccessorDescriptor},
+/// };
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let descriptor = RandomTestAccessorDescriptor {
+///     min_rows: 1,
+///     max_rows: 20,
+///     ..Default::default()
+/// };
+/// let mut rng = StdRng::from_seed([0; 32]);
+/// let data = make_random_test_accessor_data(
+///     &mut rng,
+///     &[("a", ColumnType::BigInt), ("b", ColumnType::VarChar)],
+///     &descriptor,
+/// );
+/// let a = data.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+/// let expected_rows = a.values().iter().filter(|&&a| a >= 2).count();
+///
+/// let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+/// let mut accessor =
+///     OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup.clone());
+/// accessor.add_table(
+///     "sxt.t".parse().unwrap(),
+///     OwnedTable::<DoryScalar>::try_from(data).unwrap(),
+///     0,
+/// );
+/// let query = QueryExpr::try_new(
+///     "SELECT b FROM t WHERE a >= 2".parse().unwrap(),
+///     "sxt".parse().unwrap(),
+///     &accessor,
+/// )
+/// .unwrap();
+/// let result = VerifiableQueryResult::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &setup)
+///     .unwrap()
+///     .verify(query.proof_expr(), &accessor, &(&setup).into())
+///     .unwrap()
+///     .table;
+/// assert_eq!(result.num_rows(), expected_rows);
+/// # }
+/// ```
 pub fn make_random_test_accessor_data(
-    rng: &mut StdRng,
+    rng: &mut impl Rng,
     cols: &[(&str, ColumnType)],
     descriptor: &RandomTestAccessorDescriptor,
 ) -> RecordBatch {
     let n = Uniform::new(descriptor.min_rows, descriptor.max_rows + 1).sample(rng);
     let dist = Uniform::new(descriptor.min_value, descriptor.max_value + 1);
 
-    let mut columns: Vec<Arc<dyn Array>> = Vec::with_capacity(n);
-    let mut column_fields: Vec<_> = Vec::with_capacity(n);
+    let mut columns: Vec<Arc<dyn Array>> = Vec::with_capacity(cols.len());
+    let mut column_fields: Vec<_> = Vec::with_capacity(cols.len());
 
     for (col_name, col_type) in cols {
         let values: Vec<i64> = dist.sample_iter(&mut *rng).take(n).collect();
@@ -62,7 +122,7 @@ pub fn make_random_test_accessor_data(
                 column_fields.push(Field::new(*col_name, DataType::Int16, false));
                 let values: Vec<i16> = values
                     .iter()
-                    .map(|x| ((*x >> 48) as i16)) // Shift right to align the lower 16 bits
+                    .map(|x| (*x).clamp(i16::MIN.into(), i16::MAX.into()) as i16)
                     .collect();
                 columns.push(Arc::new(Int16Array::from(values)));
             }
@@ -70,7 +130,7 @@ pub fn make_random_test_accessor_data(
                 column_fields.push(Field::new(*col_name, DataType::Int32, false));
                 let values: Vec<i32> = values
                     .iter()
-                    .map(|x| ((*x >> 32) as i32)) // Shift right to align the lower 32 bits
+                    .map(|x| (*x).clamp(i32::MIN.into(), i32::MAX.into()) as i32)
                     .collect();
                 columns.push(Arc::new(Int32Array::from(values)));
             }
@@ -114,6 +174,23 @@ pub fn make_random_test_accessor_data(
 
                 columns.push(Arc::new(StringArray::from(col)));
             }
+            ColumnType::Binary(length) => {
+                let length = *length;
+                let byte_length = i32::try_from(length).expect("binary length overflow");
+                column_fields.push(Field::new(
+                    *col_name,
+                    DataType::FixedSizeBinary(byte_length),
+                    false,
+                ));
+
+                let col = values.iter().map(|v| {
+                    let bytes = v.to_le_bytes();
+                    Some((0..length).map(|i| bytes[i % 8]).collect::<Vec<u8>>())
+                });
+                columns.push(Arc::new(
+                    FixedSizeBinaryArray::try_from_sparse_iter_with_size(col, byte_length).unwrap(),
+                ));
+            }
             ColumnType::Scalar => unimplemented!("Scalar columns are not supported by arrow"),
         }
     }
@@ -125,7 +202,8 @@ pub fn make_random_test_accessor_data(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::record_batch;
+    use crate::{base::math::decimal::Precision, record_batch};
+    use rand::rngs::StdRng;
     use rand_core::SeedableRng;
 
     #[test]
@@ -166,4 +244,48 @@ mod tests {
             record_batch!("b" => [-2_i64], "a" => ["s-2"], "c" => [-2_i128])
         );
     }
+
+    #[test]
+    fn we_can_construct_random_test_data_of_every_arrow_compatible_column_type() {
+        let descriptor = RandomTestAccessorDescriptor {
+            min_rows: 1,
+            max_rows: 1,
+            min_value: i64::MIN,
+            max_value: i64::MIN,
+        };
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let cols = [
+            ("a", ColumnType::Boolean),
+            ("b", ColumnType::SmallInt),
+            ("c", ColumnType::Int),
+            ("d", ColumnType::BigInt),
+            ("e", ColumnType::Int128),
+            ("f", ColumnType::Decimal75(Precision::new(75).unwrap(), 2)),
+            ("g", ColumnType::VarChar),
+        ];
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descriptor);
+
+        let expected = record_batch!(
+            "a" => [false],
+            "b" => [i16::MIN],
+            "c" => [i32::MIN],
+            "d" => [i64::MIN],
+            "e" => [i64::MIN as i128],
+        );
+        for (i, column) in expected.columns().iter().enumerate() {
+            assert_eq!(data.column(i), column);
+        }
+        assert_eq!(
+            data.column(5).as_any().downcast_ref::<Decimal256Array>(),
+            Some(
+                &Decimal256Array::from(vec![i256::from(i64::MIN)])
+                    .with_precision_and_scale(75, 2)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            data.column(6).as_any().downcast_ref::<StringArray>(),
+            Some(&StringArray::from(vec![format!("s{}", i64::MIN)]))
+        );
+    }
 }
@@ -0,0 +1,99 @@
+#![cfg(all(feature = "datafusion", feature = "test"))]
+use ark_std::test_rng;
+use arrow::{
+    array::{Int64Array, StringArray},
+    compute::concat_batches,
+    record_batch::RecordBatch,
+};
+use datafusion::prelude::SessionContext;
+use proof_of_sql::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{DoryEvaluationProof, DoryProverPublicSetup},
+    sql::datafusion::ProvableTableProvider,
+};
+use std::sync::Arc;
+
+async fn run(ctx: &SessionContext, sql: &str) -> RecordBatch {
+    let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    concat_batches(&batches[0].schema(), &batches).unwrap()
+}
+
+fn accessor_with_table(
+    setup: DoryProverPublicSetup,
+) -> OwnedTableTestAccessor<DoryEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 5, 2, 7, 3]),
+            varchar("b", ["one", "five", "two", "seven", "three"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+#[tokio::test]
+async fn we_can_run_a_proven_filtered_scan_through_datafusion_and_verify_it() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let accessor = accessor_with_table(setup.clone());
+    let provider = Arc::new(ProvableTableProvider::<DoryEvaluationProof, _>::new(
+        accessor.clone(),
+        "sxt.t".parse().unwrap(),
+        setup.clone(),
+    ));
+    let ctx = SessionContext::new();
+    ctx.register_table("t", provider.clone()).unwrap();
+
+    let batch = run(&ctx, "SELECT b FROM t WHERE a > 2 ORDER BY b").await;
+    assert_eq!(
+        batch.column(0).as_any().downcast_ref::<StringArray>(),
+        Some(&StringArray::from(vec!["five", "seven", "three"]))
+    );
+
+    // The filter is pushed down, so the proven scan has only the rows where it holds.
+    let proven_scans = provider.take_proven_scans();
+    assert_eq!(proven_scans.len(), 1);
+    let verified = proven_scans[0]
+        .verify(&accessor, &(&setup).into())
+        .unwrap()
+        .table;
+    assert_eq!(
+        verified
+            .select_columns(&["a".parse().unwrap(), "b".parse().unwrap()])
+            .unwrap(),
+        owned_table([
+            bigint("a", [5, 7, 3]),
+            varchar("b", ["five", "seven", "three"]),
+        ])
+    );
+    assert!(provider.take_proven_scans().is_empty());
+}
+
+#[tokio::test]
+async fn filters_that_cannot_be_proven_are_applied_by_datafusion() {
+    let setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let provider = Arc::new(ProvableTableProvider::<DoryEvaluationProof, _>::new(
+        accessor_with_table(setup.clone()),
+        "sxt.t".parse().unwrap(),
+        setup,
+    ));
+    let ctx = SessionContext::new();
+    ctx.register_table("t", provider.clone()).unwrap();
+
+    let batch = run(
+        &ctx,
+        "SELECT a FROM t WHERE a >= 2 AND b LIKE 't%' ORDER BY a",
+    )
+    .await;
+    assert_eq!(
+        batch.column(0).as_any().downcast_ref::<Int64Array>(),
+        Some(&Int64Array::from(vec![2, 3]))
+    );
+    let batch = run(&ctx, "SELECT COUNT(*) AS n FROM t WHERE a < 5").await;
+    assert_eq!(
+        batch.column(0).as_any().downcast_ref::<Int64Array>(),
+        Some(&Int64Array::from(vec![3]))
+    );
+    assert_eq!(provider.take_proven_scans().len(), 2);
+}
@@ -33,7 +33,8 @@ fn run_query(
         &accessor,
     )
     .unwrap();
-    let proof = VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let proof = VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &())
+        .unwrap();
     let owned_table_result = proof
         .verify(query.proof_expr(), &accessor, &())
         .unwrap()
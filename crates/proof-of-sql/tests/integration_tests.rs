@@ -6,15 +6,40 @@ use curve25519_dalek::RistrettoPoint;
 use proof_of_sql::base::commitment::InnerProductProof;
 use proof_of_sql::{
     base::{
-        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
-        scalar::Curve25519Scalar,
+        commitment::{QueryCommitments, QueryCommitmentsExt, TableCommitment},
+        database::{
+            owned_table_utility::*, ColumnRef, ColumnType, CommitmentAccessor, OwnedTable,
+            OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+        scalar::{Curve25519Scalar, VarCharHash},
+    },
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
     },
-    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup},
     record_batch,
     sql::{
-        parse::{ConversionError, QueryExpr},
-        proof::QueryProof,
+        parse::{ConversionError, QueryExpr, QueryExprOptions, DEFAULT_MAX_PLAN_DEPTH},
+        proof::{
+            plan_hash, ProofError, ProverConfig, QueryError, QueryProof, VerifiableQueryResult,
+            PROOF_VERSION,
+        },
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
     },
+    time::{Duration, Instant},
+};
+use tracing::{
+    span::{Attributes, Id},
+    Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    Layer,
 };
 
 #[test]
@@ -33,7 +58,7 @@ fn we_can_prove_a_minimal_filter_query_with_curve25519() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     let owned_table_result = proof
         .verify(query.proof_expr(), &accessor, &serialized_result, &())
         .unwrap()
@@ -62,7 +87,8 @@ fn we_can_prove_a_minimal_filter_query_with_dory() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
     let owned_table_result = proof
         .verify(
             query.proof_expr(),
@@ -76,6 +102,66 @@ fn we_can_prove_a_minimal_filter_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_query_on_an_empty_table_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE a = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_on_an_empty_table_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE a = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_basic_equality_query_with_curve25519() {
@@ -92,7 +178,7 @@ fn we_can_prove_a_basic_equality_query_with_curve25519() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     let owned_table_result = proof
         .verify(query.proof_expr(), &accessor, &serialized_result, &())
         .unwrap()
@@ -121,7 +207,8 @@ fn we_can_prove_a_basic_equality_query_with_dory() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
     let owned_table_result = proof
         .verify(
             query.proof_expr(),
@@ -135,6 +222,46 @@ fn we_can_prove_a_basic_equality_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_an_equality_query_with_the_maximum_i128_literal_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            int128("big_col", [i128::MAX, 0, i128::MIN]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE big_col = 170141183460469231731687303715884105727"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1]), int128("big_col", [i128::MAX])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_basic_inequality_query_with_curve25519() {
@@ -151,7 +278,7 @@ fn we_can_prove_a_basic_inequality_query_with_curve25519() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     let owned_table_result = proof
         .verify(query.proof_expr(), &accessor, &serialized_result, &())
         .unwrap()
@@ -218,7 +345,7 @@ fn we_can_prove_a_basic_equality_with_out_of_order_results_with_curve25519() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     let owned_table_result = proof
         .verify(query.proof_expr(), &accessor, &serialized_result, &())
         .unwrap()
@@ -253,7 +380,8 @@ fn we_can_prove_a_basic_inequality_query_with_dory() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
     let owned_table_result = proof
         .verify(
             query.proof_expr(),
@@ -292,7 +420,7 @@ fn we_can_prove_a_complex_query_with_curve25519() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     let owned_table_result = proof
         .verify(query.proof_expr(), &accessor, &serialized_result, &())
         .unwrap()
@@ -337,7 +465,8 @@ fn we_can_prove_a_complex_query_with_dory() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
     let owned_table_result = proof
         .verify(
             query.proof_expr(),
@@ -377,7 +506,7 @@ fn we_can_prove_a_minimal_group_by_query_with_curve25519() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &()).unwrap();
     let owned_table_result: OwnedTable<Curve25519Scalar> = proof
         .verify(query.proof_expr(), &accessor, &serialized_result, &())
         .unwrap()
@@ -416,7 +545,8 @@ fn we_can_prove_a_basic_group_by_query_with_dory() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
     let owned_table_result = proof
         .verify(
             query.proof_expr(),
@@ -433,3 +563,2779 @@ fn we_can_prove_a_basic_group_by_query_with_dory() {
     ]);
     assert_eq!(owned_table_result, expected_result);
 }
+
+#[test]
+fn the_referenced_schema_of_a_query_lists_only_the_columns_it_reads() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    let table_ref: TableRef = "sxt.table".parse().unwrap();
+    accessor.add_table(
+        table_ref,
+        owned_table([
+            bigint("a", [1, 2]),
+            varchar("b", ["x", "y"]),
+            bigint("c", [3, 4]),
+            bigint("secret", [5, 6]),
+        ]),
+        0,
+    );
+    let referenced_schema = |sql: &str| {
+        QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap()
+        .referenced_schema()
+    };
+    let expected_schema = |columns: &[(&str, ColumnType)]| {
+        BTreeMap::from([(
+            table_ref,
+            columns
+                .iter()
+                .map(|(name, column_type)| (name.parse().unwrap(), *column_type))
+                .collect::<Vec<_>>(),
+        )])
+    };
+    assert_eq!(
+        referenced_schema("SELECT c, b FROM table WHERE a = 1"),
+        expected_schema(&[
+            ("a", ColumnType::BigInt),
+            ("b", ColumnType::VarChar),
+            ("c", ColumnType::BigInt)
+        ])
+    );
+    assert_eq!(
+        referenced_schema("SELECT a, sum(c) AS s FROM table GROUP BY a"),
+        expected_schema(&[("a", ColumnType::BigInt), ("c", ColumnType::BigInt)])
+    );
+}
+
+#[test]
+fn we_can_prove_a_query_on_a_renamed_column_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut table = owned_table([bigint("a", [1, 2, 3]), bigint("external_b", [1, 0, 1])]);
+    table
+        .rename_column(&"external_b".parse().unwrap(), "b".parse().unwrap())
+        .unwrap();
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table("sxt.table".parse().unwrap(), table, 0);
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE b = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1, 3]), bigint("b", [1, 1])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_on_an_appended_table_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let first_table = owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "x"])]);
+    let second_table = owned_table([bigint("a", [4, 5]), varchar("b", ["y", "x"])]);
+    let mut table = first_table.clone();
+    table.append(&second_table).unwrap();
+
+    let prove_and_verify = |table| {
+        let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+            dory_prover_setup.clone(),
+        );
+        accessor.add_table("sxt.table".parse().unwrap(), table, 0);
+        let query = QueryExpr::try_new(
+            "SELECT * FROM table WHERE b = 'x'".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap();
+        proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table
+    };
+    let mut expected_result = prove_and_verify(first_table);
+    expected_result
+        .append(&prove_and_verify(second_table))
+        .unwrap();
+    let owned_table_result = prove_and_verify(table);
+    assert_eq!(
+        owned_table_result,
+        owned_table([bigint("a", [1, 3, 5]), varchar("b", ["x", "x", "x"])])
+    );
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_verify_a_query_on_an_appended_table_against_the_base_and_delta_commitments_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let table_ref: TableRef = "sxt.table".parse().unwrap();
+    let base = owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "x"])]);
+    let delta = owned_table([bigint("a", [4, 5]), varchar("b", ["x", "y"])]);
+    let mut table = base.clone();
+    table.append(&delta).unwrap();
+
+    // The verifier only commits to the appended rows, at the offset where the base commitment ends.
+    let base_commitment = TableCommitment::<DoryCommitment>::from_owned_table_with_offset(
+        &base,
+        0,
+        &dory_prover_setup,
+    );
+    let delta_commitment = TableCommitment::from_owned_table_with_offset(
+        &delta,
+        base_commitment.range().end,
+        &dory_prover_setup,
+    );
+    let mut commitments = QueryCommitments::from_iter([(table_ref, base_commitment)]);
+    commitments
+        .try_append_delta(table_ref, delta_commitment)
+        .unwrap();
+    assert_eq!(
+        commitments[&table_ref],
+        TableCommitment::from_owned_table_with_offset(&table, 0, &dory_prover_setup)
+    );
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(table_ref, table, 0);
+    // The selected rows are on both sides of the boundary between the base and the delta.
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE b = 'x'".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap()
+    .verify(query.proof_expr(), &commitments, &dory_verifier_setup)
+    .unwrap()
+    .table;
+    assert_eq!(
+        owned_table_result,
+        owned_table([bigint("a", [1, 3, 4]), varchar("b", ["x", "x", "x"])])
+    );
+}
+
+#[test]
+fn we_can_only_verify_a_query_against_the_root_of_the_table_it_was_proven_over_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let table_ref: TableRef = "sxt.table".parse().unwrap();
+    let table_a = owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]);
+    let table_b = owned_table::<DoryScalar>([bigint("a", [1, 2, 4]), bigint("b", [1, 0, 1])]);
+    let commitment_a =
+        TableCommitment::from_owned_table_with_offset(&table_a, 0, &dory_prover_setup);
+    let commitment_b =
+        TableCommitment::from_owned_table_with_offset(&table_b, 0, &dory_prover_setup);
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(table_ref, table_a, 0);
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE b = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+
+    let commitments_a = QueryCommitments::from_iter([(table_ref, commitment_a.clone())]);
+    let owned_table_result = verifiable_result
+        .verify_with_roots(
+            query.proof_expr(),
+            &commitments_a,
+            &HashMap::from_iter([(table_ref, commitment_a.root())]),
+            &dory_verifier_setup,
+            &[],
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1, 3]), bigint("b", [1, 1])]);
+    assert_eq!(owned_table_result, expected_result);
+
+    // the trusted root is for table b, so the commitments to table a are rejected
+    assert!(verifiable_result
+        .verify_with_roots(
+            query.proof_expr(),
+            &commitments_a,
+            &HashMap::from_iter([(table_ref, commitment_b.root())]),
+            &dory_verifier_setup,
+            &[],
+        )
+        .is_err());
+
+    // the commitments match the trusted root of table b, but the proof is over table a
+    assert!(verifiable_result
+        .verify_with_roots(
+            query.proof_expr(),
+            &QueryCommitments::from_iter([(table_ref, commitment_b.clone())]),
+            &HashMap::from_iter([(table_ref, commitment_b.root())]),
+            &dory_verifier_setup,
+            &[],
+        )
+        .is_err());
+
+    // a missing root is rejected
+    assert!(verifiable_result
+        .verify_with_roots(
+            query.proof_expr(),
+            &commitments_a,
+            &HashMap::new(),
+            &dory_verifier_setup,
+            &[],
+        )
+        .is_err());
+}
+
+#[test]
+fn each_way_of_tampering_with_a_query_result_is_reported_as_its_own_proof_error_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let table_ref = "sxt.table".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        table_ref,
+        owned_table([bigint("a", [1, 2, 3, 4]), bigint("b", [1, 0, 1, 0])]),
+        0,
+    );
+    let mut tampered_accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    tampered_accessor.add_table(
+        table_ref,
+        owned_table([bigint("a", [1, 2, 3, 5]), bigint("b", [1, 0, 1, 0])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table WHERE b = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let verify = |verifiable_result: &VerifiableQueryResult<DoryEvaluationProof>,
+                  accessor: &OwnedTableTestAccessor<DoryEvaluationProof>| {
+        match verifiable_result.verify(query.proof_expr(), accessor, &dory_verifier_setup) {
+            Err(QueryError::ProofError(error)) => error,
+            Err(error) => panic!("expected a proof error, got {error}"),
+            Ok(_) => panic!("expected a proof error, but the result verified"),
+        }
+    };
+    assert!(verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .is_ok());
+
+    // the commitment to column `a` differs from the data the proof was created from
+    assert!(matches!(
+        verify(&verifiable_result, &tampered_accessor),
+        ProofError::CommitmentMismatch(_)
+    ));
+
+    // a claimed evaluation no longer satisfies the constraints of the query
+    let mut tampered_result = verifiable_result.clone();
+    tampered_result
+        .proof
+        .as_mut()
+        .unwrap()
+        .pre_result_mle_evaluations[0] += DoryScalar::from(1);
+    assert!(matches!(
+        verify(&tampered_result, &accessor),
+        ProofError::SumcheckFailed(_)
+    ));
+
+    // the proof has the terms of a different query
+    let mut tampered_result = verifiable_result.clone();
+    tampered_result
+        .proof
+        .as_mut()
+        .unwrap()
+        .pre_result_mle_evaluations
+        .push(DoryScalar::from(1));
+    assert!(matches!(
+        verify(&tampered_result, &accessor),
+        ProofError::VerificationError(_)
+    ));
+}
+
+/// A tracing layer that records the names of all spans that are created.
+#[derive(Clone, Default)]
+struct SpanNameRecorder(Arc<Mutex<Vec<&'static str>>>);
+
+impl<S: Subscriber> Layer<S> for SpanNameRecorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        self.0.lock().unwrap().push(attrs.metadata().name());
+    }
+}
+
+#[test]
+fn we_can_trace_the_verification_of_an_or_query_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE a = 2 OR b = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+
+    let recorder = SpanNameRecorder::default();
+    let subscriber = tracing_subscriber::registry().with(recorder.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap();
+    });
+
+    let span_names = recorder.0.lock().unwrap();
+    for expected_name in [
+        "VerifiableQueryResult::verify",
+        "QueryProof::verify",
+        "OrExpr::verifier_evaluate",
+        "EqualsExpr::verifier_evaluate",
+    ] {
+        assert!(span_names.contains(&expected_name));
+    }
+}
+
+#[test]
+fn we_can_collect_a_prover_report_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE a >= 3 OR NOT b = 1"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (verifiable_result, report) =
+        VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+            &ProverConfig {
+                collect_report: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [2, 3]), bigint("b", [0, 1])]);
+    assert_eq!(owned_table_result, expected_result);
+
+    let report = report.unwrap();
+    for node_type in [
+        "DenseFilterExpr",
+        "OrExpr",
+        "NotExpr",
+        "EqualsExpr",
+        "InequalityExpr",
+        "ColumnExpr",
+    ] {
+        assert!(report.nodes()[node_type].num_evaluations > 0);
+    }
+    assert!(report.node_time() > Duration::ZERO);
+    assert!(report.node_time() <= report.total_time());
+
+    // no report is collected by default
+    let (_, report) = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &ProverConfig::default(),
+    )
+    .unwrap();
+    assert!(report.is_none());
+}
+
+#[test]
+fn we_can_prove_comparisons_on_smallint_and_int_columns_with_literals_of_any_width_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            smallint("s", [1_i16, -2, 3, i16::MAX]),
+            int("i", [1_i32, -2, 3, i32::MIN]),
+        ]),
+        0,
+    );
+    for (where_clause, expected_s, expected_i) in [
+        ("s = 3", vec![3], vec![3]),
+        ("i = -2", vec![-2], vec![-2]),
+        ("s >= 3", vec![3, i16::MAX], vec![3, i32::MIN]),
+        ("i <= 1", vec![1, -2, i16::MAX], vec![1, -2, i32::MIN]),
+        ("s < 1", vec![-2], vec![-2]),
+        ("i > -2", vec![1, 3], vec![1, 3]),
+        ("s <> 1", vec![-2, 3, i16::MAX], vec![-2, 3, i32::MIN]),
+        // literals wider than the column type
+        ("s = 32767", vec![i16::MAX], vec![i32::MIN]),
+        ("s >= 100000", vec![], vec![]),
+        ("i = -2147483648", vec![i16::MAX], vec![i32::MIN]),
+        ("i <= -3000000000", vec![], vec![]),
+        (
+            "s = 170141183460469231731687303715884105727",
+            vec![],
+            vec![],
+        ),
+        ("i >= 2.5", vec![3], vec![3]),
+        ("s = 3.0", vec![3], vec![3]),
+        // comparisons between the two column types
+        ("s = i", vec![1, -2, 3], vec![1, -2, 3]),
+        ("s > i", vec![i16::MAX], vec![i32::MIN]),
+    ] {
+        let query = QueryExpr::try_new(
+            format!("SELECT s, i FROM table WHERE {where_clause}")
+                .parse()
+                .unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap();
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table
+            // the result columns are not necessarily in the order of the select clause
+            .select_columns(&["s".parse().unwrap(), "i".parse().unwrap()])
+            .unwrap();
+        let expected_result = owned_table([smallint("s", expected_s), int("i", expected_i)]);
+        assert_eq!(owned_table_result, expected_result, "{where_clause}");
+    }
+}
+
+#[test]
+fn we_can_prove_a_query_on_an_empty_table_with_smallint_and_int_columns_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            smallint("s", Vec::<i16>::new()),
+            int("i", Vec::<i32>::new()),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT s, i FROM table WHERE s = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table
+        // the result columns are not necessarily in the order of the select clause
+        .select_columns(&["s".parse().unwrap(), "i".parse().unwrap()])
+        .unwrap();
+    let expected_result = owned_table([
+        smallint("s", Vec::<i16>::new()),
+        int("i", Vec::<i32>::new()),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_tuple_equality_query_with_fewer_intermediate_mles_than_the_and_query_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 1, 3]),
+            varchar("b", ["x", "y", "x", "x", "x"]),
+            bigint("c", [10, 20, 30, 40, 50]),
+        ]),
+        0,
+    );
+    let mut results = Vec::new();
+    for where_clause in ["(a, b) = (1, 'x')", "a = 1 AND b = 'x'"] {
+        let query = QueryExpr::try_new(
+            format!("SELECT c FROM table WHERE {where_clause}")
+                .parse()
+                .unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap();
+        let num_intermediate_mles = proof.commitments.len();
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        results.push((owned_table_result, num_intermediate_mles));
+    }
+    let expected_result = owned_table([bigint("c", [10, 40])]);
+    assert_eq!(results[0].0, expected_result);
+    assert_eq!(results[1].0, expected_result);
+    assert!(results[0].1 < results[1].1);
+}
+
+#[test]
+fn we_cannot_compare_tuples_of_different_lengths_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1]), bigint("b", [2])]),
+        0,
+    );
+    let res = QueryExpr::<DoryCommitment>::try_new(
+        "SELECT a FROM table WHERE (a, b) = (1, 2, 3)"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    );
+    assert!(matches!(res, Err(ConversionError::InvalidExpression(_))));
+}
+
+#[test]
+fn we_can_cancel_proving_a_multi_node_query_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7, 8]),
+            bigint("b", [1, 0, 1, 0, 1, 0, 1, 0]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE a >= 3 AND NOT b = 1 OR a = 1"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let token = Arc::new(AtomicBool::new(false));
+    let config = ProverConfig {
+        cancellation_token: Some(token.clone()),
+        ..Default::default()
+    };
+
+    // an unset token does not affect the proof
+    let start_time = Instant::now();
+    let (verifiable_result, _) = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &config,
+    )
+    .unwrap();
+    let proof_time = start_time.elapsed();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1, 4, 6, 8]), bigint("b", [1, 0, 0, 0])]);
+    assert_eq!(owned_table_result, expected_result);
+
+    // a set token aborts the proof before any of the nodes are proven
+    token.store(true, Ordering::Relaxed);
+    let start_time = Instant::now();
+    let res = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &config,
+    );
+    assert!(matches!(res, Err(ProofError::Cancelled)));
+    assert!(start_time.elapsed() < proof_time);
+}
+
+#[test]
+fn a_proof_only_verifies_with_the_domain_separator_it_was_created_with() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let table_ref: TableRef = "sxt.table".parse().unwrap();
+    let table = owned_table::<DoryScalar>([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]);
+    let table_commitment =
+        TableCommitment::from_owned_table_with_offset(&table, 0, &dory_prover_setup);
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(table_ref, table, 0);
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table WHERE b = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let config = ProverConfig {
+        domain_separator: b"deployment-a".to_vec(),
+        ..Default::default()
+    };
+    let (verifiable_result, _) = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &config,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify_with_domain_separator(
+            query.proof_expr(),
+            &accessor,
+            &dory_verifier_setup,
+            b"deployment-a",
+        )
+        .unwrap()
+        .table;
+    assert_eq!(owned_table_result, owned_table([bigint("a", [1, 3])]));
+
+    // The same proof cannot be replayed in a deployment with another separator, or without one.
+    assert!(verifiable_result
+        .verify_with_domain_separator(
+            query.proof_expr(),
+            &accessor,
+            &dory_verifier_setup,
+            b"deployment-b",
+        )
+        .is_err());
+    assert!(verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .is_err());
+
+    // Every other way of verifying takes the separator as well.
+    assert!(verifiable_result
+        .verify_with_report(
+            query.proof_expr(),
+            &accessor,
+            &dory_verifier_setup,
+            b"deployment-a",
+        )
+        .is_verified());
+    assert!(!verifiable_result
+        .verify_with_report(query.proof_expr(), &accessor, &dory_verifier_setup, &[])
+        .is_verified());
+    let commitments = QueryCommitments::from_iter([(table_ref, table_commitment.clone())]);
+    let roots = HashMap::from_iter([(table_ref, table_commitment.root())]);
+    assert!(verifiable_result
+        .verify_with_roots(
+            query.proof_expr(),
+            &commitments,
+            &roots,
+            &dory_verifier_setup,
+            b"deployment-a",
+        )
+        .is_ok());
+    assert!(verifiable_result
+        .verify_with_roots(
+            query.proof_expr(),
+            &commitments,
+            &roots,
+            &dory_verifier_setup,
+            &[],
+        )
+        .is_err());
+}
+
+#[test]
+fn we_can_prove_a_decimal_division_in_the_result_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3]),
+            decimal75("total", 10, 2, [1000, 100, -200]),
+            decimal75("count_dec", 10, 2, [200, 300, 300]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT id, total / count_dec AS ratio FROM table WHERE id >= 1"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let owned_table_result: OwnedTable<DoryScalar> = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    // 10.00 / 2.00 = 5.00, and 1.00 / 3.00 and -2.00 / 3.00 are truncated to the larger scale
+    let expected_result = owned_table([
+        bigint("id", [1, 2, 3]),
+        decimal75("ratio", 12, 2, [500, 33, -66]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_cannot_prove_a_decimal_division_by_zero_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            decimal75("total", 10, 2, [1000, 100]),
+            decimal75("count_dec", 10, 2, [200, 0]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT total / count_dec AS ratio FROM table"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let res = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &ProverConfig::default(),
+    );
+    assert!(matches!(res, Err(ProofError::DivisionByZero)));
+}
+
+#[test]
+fn we_can_guard_a_division_by_zero_with_the_where_clause_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            decimal75("total", 10, 2, [1000, 100, 300]),
+            decimal75("count_dec", 10, 2, [200, 0, -300]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )?;
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        let owned_table_result: OwnedTable<DoryScalar> = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        Ok::<_, ProofError>(owned_table_result)
+    };
+
+    assert_eq!(
+        prove_and_verify("SELECT total / count_dec AS ratio FROM table WHERE count_dec <> 0")
+            .unwrap(),
+        owned_table([decimal75("ratio", 12, 2, [500, -100])])
+    );
+    assert!(matches!(
+        prove_and_verify("SELECT total / count_dec AS ratio FROM table"),
+        Err(ProofError::DivisionByZero)
+    ));
+    // `SELECTED()` keeps every row, so every row is divided.
+    assert!(matches!(
+        prove_and_verify(
+            "SELECT total / count_dec AS ratio, SELECTED() AS s FROM table WHERE count_dec <> 0"
+        ),
+        Err(ProofError::DivisionByZero)
+    ));
+}
+
+#[test]
+fn we_can_guard_an_integer_division_by_zero_with_the_where_clause_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            smallint("a", [i16::MIN, 7, -7]),
+            smallint("b", [-1_i16, 0, 2]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )?;
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        let owned_table_result: OwnedTable<DoryScalar> = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        Ok::<_, ProofError>(owned_table_result)
+    };
+
+    // Rows that are not selected can neither divide by zero nor overflow.
+    assert_eq!(
+        prove_and_verify("SELECT a / b AS q FROM table WHERE b > 0").unwrap(),
+        owned_table([smallint("q", [-3_i16])])
+    );
+    assert!(matches!(
+        prove_and_verify("SELECT a DIV b AS q FROM table"),
+        Err(ProofError::DivisionByZero)
+    ));
+    assert!(matches!(
+        prove_and_verify("SELECT a / b AS q FROM table WHERE b <> 0"),
+        Err(ProofError::DivisionOverflow)
+    ));
+    // `SELECTED()` keeps every row, so every row is divided.
+    assert!(matches!(
+        prove_and_verify("SELECT a / b AS q, SELECTED() AS s FROM table WHERE b > 0"),
+        Err(ProofError::DivisionByZero)
+    ));
+}
+
+#[test]
+fn we_can_prove_an_integer_division_in_the_result_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("id", [1, 2, 3]), bigint("total", [-7, 7, -8])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT total / 3 AS third, total DIV id AS q FROM table"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let owned_table_result: OwnedTable<DoryScalar> = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    // Integer quotients are truncated towards zero, e.g. -7 / 3 = -2 and -8 DIV 3 = -2
+    let expected_result = owned_table([bigint("third", [-2, 2, -2]), bigint("q", [-7, 3, -2])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_the_sign_of_a_column_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            decimal75("balance", 10, 2, [-1050, 0, 25, 0]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT id, SIGN(balance) AS s FROM table WHERE id <= 3"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let owned_table_result: OwnedTable<DoryScalar> = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let expected_result = owned_table([bigint("id", [1, 2, 3]), smallint("s", [-1_i16, 0, 1])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_in_list_of_mixed_integers_and_decimals_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 5]),
+            decimal75("price", 10, 2, [100, 250, 251, 300, -300]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT id FROM table WHERE price IN (1, 2.5, 3, -2.999)"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("id", [1, 2, 4])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_equality_with_a_literal_like_an_equality_with_a_constant_column_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            varchar("name", ["x", "y", "x", "z"]),
+            decimal75("price", 10, 2, [250, 100, 250, -250]),
+            bigint("two", [2; 4]),
+            varchar("x", ["x"; 4]),
+            decimal75("two_and_a_half", 12, 4, [25000; 4]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table
+    };
+    for (with_literal, with_column, ids) in [
+        ("id = 2", "id = two", vec![2]),
+        ("2 = id", "two = id", vec![2]),
+        ("name = 'x'", "name = x", vec![1, 3]),
+        ("price = 2.5", "price = two_and_a_half", vec![1, 3]),
+        ("2.5 = price", "two_and_a_half = price", vec![1, 3]),
+        ("price <> 2.5", "price <> two_and_a_half", vec![2, 4]),
+    ] {
+        let result = prove_and_verify(&format!("SELECT id FROM table WHERE {with_literal}"));
+        assert_eq!(result, owned_table([bigint("id", ids)]), "{with_literal}");
+        assert_eq!(
+            result,
+            prove_and_verify(&format!("SELECT id FROM table WHERE {with_column}")),
+            "{with_literal}"
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_a_query_on_a_table_in_a_catalog_by_its_full_and_partial_paths_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "cat.sch.t".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3])]),
+        0,
+    );
+    accessor.add_table(
+        "other.sch.t".parse().unwrap(),
+        owned_table([bigint("a", [4, 5])]),
+        0,
+    );
+    accessor.add_table("sch.t".parse().unwrap(), owned_table([bigint("a", [6])]), 0);
+    let prove_and_verify = |query: QueryExpr<DoryCommitment>| {
+        VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table
+    };
+    let in_catalog = |sql: &str| {
+        QueryExpr::try_new_in_catalog(
+            sql.parse().unwrap(),
+            "cat".parse().unwrap(),
+            "sch".parse().unwrap(),
+            &accessor,
+        )
+    };
+    let without_catalog =
+        |sql: &str| QueryExpr::try_new(sql.parse().unwrap(), "sch".parse().unwrap(), &accessor);
+
+    for sql in [
+        "SELECT a FROM t",
+        "SELECT a FROM sch.t",
+        "SELECT a FROM cat.sch.t",
+    ] {
+        assert_eq!(
+            prove_and_verify(in_catalog(sql).unwrap()),
+            owned_table([bigint("a", [1, 2, 3])]),
+            "{sql}"
+        );
+    }
+    assert_eq!(
+        prove_and_verify(without_catalog("SELECT a FROM cat.sch.t").unwrap()),
+        owned_table([bigint("a", [1, 2, 3])])
+    );
+    assert_eq!(
+        prove_and_verify(in_catalog("SELECT a FROM other.sch.t").unwrap()),
+        owned_table([bigint("a", [4, 5])])
+    );
+    for sql in ["SELECT a FROM t", "SELECT a FROM sch.t"] {
+        assert_eq!(
+            prove_and_verify(without_catalog(sql).unwrap()),
+            owned_table([bigint("a", [6])]),
+            "{sql}"
+        );
+    }
+
+    let error = in_catalog("SELECT b FROM t").unwrap_err();
+    assert!(matches!(error, ConversionError::MissingColumn(..)));
+    assert_eq!(
+        error.to_string(),
+        "Column 'b' was not found in table 'cat.sch.t'"
+    );
+    assert_eq!(
+        in_catalog("SELECT a FROM missing.t")
+            .unwrap_err()
+            .to_string(),
+        "Column 'a' was not found in table 'cat.missing.t'"
+    );
+}
+
+#[test]
+fn we_can_prove_a_not_in_list_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 5]),
+            varchar("name", ["a", "b", "c", "a", "d"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT id FROM table WHERE name NOT IN ('a', 'c') AND id NOT IN (5)"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("id", [2])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_comparisons_with_decimal_literals_of_a_large_scale_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            decimal75("rate", 20, 18, [1, 0, 1_000_000_000_000_000_000_i64, -1]),
+        ]),
+        0,
+    );
+    for (sql, ids) in [
+        (
+            "SELECT id FROM table WHERE rate = 0.000000000000000001",
+            vec![1],
+        ),
+        (
+            "SELECT id FROM table WHERE rate = -0.000000000000000001",
+            vec![4],
+        ),
+        (
+            "SELECT id FROM table WHERE rate = 1.000000000000000000",
+            vec![3],
+        ),
+        (
+            "SELECT id FROM table WHERE rate < 0.000000000000000001",
+            vec![2, 4],
+        ),
+        (
+            "SELECT id FROM table WHERE rate > 0.0000000000000000005",
+            vec![1, 3],
+        ),
+        (
+            "SELECT id FROM table WHERE rate = 0.0000000000000000005",
+            vec![],
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+        assert_eq!(
+            owned_table_result,
+            owned_table([bigint("id", ids)]),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_the_deviation_from_the_mean_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 5]),
+            decimal75("price", 10, 2, [1000, 1250, 900, 5000, 1150]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT price - AVG(price) OVER () AS deviation, SUM(price) OVER () AS total, id FROM table WHERE price < 50"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap()
+    .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+    .unwrap()
+    .table;
+    let owned_table_result: OwnedTable<DoryScalar> = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    // The mean of 10.00, 12.50, 9.00 and 11.50 is 10.75.
+    let expected_result = owned_table([
+        decimal75("deviation", 11, 2, [-75, 175, -175, 75]),
+        decimal75("total", 75, 2, [4300; 4]),
+        bigint("id", [1, 2, 3, 5]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_row_numbers_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 5, 6]),
+            bigint("score", [5, 9, 5, -1, 2, 9]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT id, ROW_NUMBER() OVER (ORDER BY score DESC) AS rn FROM table \
+            WHERE score > 0 ORDER BY rn"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap()
+    .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+    .unwrap()
+    .table;
+    let owned_table_result: OwnedTable<DoryScalar> = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    // The ties on 9 and on 5 keep the order of the table
+    let expected_result =
+        owned_table([bigint("id", [2, 6, 1, 3, 5]), bigint("rn", [1, 2, 3, 4, 5])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_cannot_use_a_decimal_literal_with_a_scale_beyond_the_maximum() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            decimal75("rate", 20, 18, [1]),
+            decimal75("tiny", 75, 75, [1]),
+        ]),
+        0,
+    );
+    let literal_of_scale = |scale: usize| format!("0.{}1", "0".repeat(scale - 1));
+    assert!(QueryExpr::<DoryCommitment>::try_new(
+        format!(
+            "SELECT rate FROM table WHERE tiny = {}",
+            literal_of_scale(75)
+        )
+        .parse()
+        .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .is_ok());
+    for scale in [76, 200, 300] {
+        assert!(
+            matches!(
+                QueryExpr::<DoryCommitment>::try_new(
+                    format!(
+                        "SELECT rate FROM table WHERE rate < {}",
+                        literal_of_scale(scale)
+                    )
+                    .parse()
+                    .unwrap(),
+                    "sxt".parse().unwrap(),
+                    &accessor,
+                ),
+                Err(ConversionError::InvalidScale(_))
+            ),
+            "{scale}"
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_comparisons_between_decimal_columns_of_different_scales_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 5]),
+            decimal75("price", 10, 2, [100, 250, -300, 0, 199]),
+            decimal75("cost", 12, 4, [10000, 25001, -30000, -1, 20000]),
+        ]),
+        0,
+    );
+    for (sql, ids) in [
+        ("SELECT id FROM table WHERE price = cost", vec![1, 3]),
+        ("SELECT id FROM table WHERE cost = price", vec![1, 3]),
+        ("SELECT id FROM table WHERE price < cost", vec![2, 5]),
+        ("SELECT id FROM table WHERE price >= cost", vec![1, 3, 4]),
+        ("SELECT id FROM table WHERE cost > price", vec![2, 5]),
+        ("SELECT id FROM table WHERE cost <= price", vec![1, 3, 4]),
+        (
+            "SELECT id FROM table WHERE price < 2.5001",
+            vec![1, 2, 3, 4, 5],
+        ),
+        ("SELECT id FROM table WHERE price > 2.4999", vec![2]),
+        ("SELECT id FROM table WHERE price <= -2.99999", vec![3]),
+        ("SELECT id FROM table WHERE cost = 2.5001", vec![2]),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap();
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        assert_eq!(
+            owned_table_result,
+            owned_table([bigint("id", ids)]),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_compare_decimal_columns_whose_rescaled_precision_exceeds_the_maximum() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            decimal75("price", 10, 2, [100]),
+            decimal75("total", 75, 0, [1]),
+        ]),
+        0,
+    );
+    for sql in [
+        "SELECT price FROM table WHERE price = total",
+        "SELECT price FROM table WHERE total = price",
+    ] {
+        let result = QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        );
+        assert!(matches!(result, Err(ConversionError::DataTypeMismatch(..))));
+    }
+}
+
+fn or_chain(num_terms: usize) -> String {
+    (0..num_terms)
+        .map(|i| format!("a = {i}"))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+#[test]
+fn we_can_prove_an_or_chain_of_the_maximum_default_depth_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 200, 3])]),
+        0,
+    );
+    // `n` equalities chained with `OR` have depth `n + 1`.
+    let sql = format!(
+        "SELECT a FROM table WHERE {}",
+        or_chain(DEFAULT_MAX_PLAN_DEPTH - 1)
+    );
+    let query =
+        QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    assert_eq!(owned_table_result, owned_table([bigint("a", [1, 3])]));
+}
+
+#[test]
+fn we_cannot_plan_a_query_nested_deeper_than_the_maximum_depth() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3])]),
+        0,
+    );
+    let sql = format!("SELECT a FROM table WHERE {}", or_chain(10_000));
+    let result = QueryExpr::<DoryCommitment>::try_new(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        ConversionError::PlanTooDeep {
+            depth: 10_001,
+            max: DEFAULT_MAX_PLAN_DEPTH
+        }
+    );
+
+    // An IN list of 8 literals is proven as 3 levels of `OR` over the equalities.
+    let sql = "SELECT a FROM table WHERE a IN (0, 1, 2, 3, 4, 5, 6, 7)";
+    let result = QueryExpr::<DoryCommitment>::try_new_with_max_depth(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+        4,
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        ConversionError::PlanTooDeep { depth: 5, max: 4 }
+    );
+    let query = QueryExpr::<DoryCommitment>::try_new_with_max_depth(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+        5,
+    );
+    assert!(query.is_ok());
+
+    let in_list = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+    let sql = format!("SELECT a FROM table WHERE a IN ({})", in_list.join(", "));
+    let query = QueryExpr::<DoryCommitment>::try_new(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    );
+    assert!(query.is_ok());
+
+    let sql = format!(
+        "SELECT a FROM table WHERE {}",
+        or_chain(DEFAULT_MAX_PLAN_DEPTH)
+    );
+    let query = QueryExpr::<DoryCommitment>::try_new_with_max_depth(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+        DEFAULT_MAX_PLAN_DEPTH + 1,
+    );
+    assert!(query.is_ok());
+}
+
+#[test]
+fn we_cannot_use_an_in_list_that_mixes_strings_and_numbers() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("id", [1, 2, 3]), varchar("name", ["a", "1", "c"])]),
+        0,
+    );
+    for sql in [
+        "SELECT id FROM table WHERE id IN (1, '2')",
+        "SELECT id FROM table WHERE name IN ('a', 1)",
+    ] {
+        let result = QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        );
+        assert!(matches!(result, Err(ConversionError::DataTypeMismatch(..))));
+    }
+}
+
+#[test]
+fn we_can_prove_the_selection_indicator_of_a_filter_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [3, -1, 0, 7, -5]),
+            varchar("b", ["v", "w", "x", "y", "z"]),
+        ]),
+        0,
+    );
+    for (sql, expected) in [
+        (
+            "SELECT SELECTED() FROM table WHERE a > 0",
+            owned_table([boolean("__selected__", [true, false, false, true, false])]),
+        ),
+        (
+            "SELECT b, SELECTED() AS s FROM table WHERE a > 0 AND b <> 'v' ORDER BY b DESC",
+            owned_table([
+                varchar("b", ["z", "y", "x", "w", "v"]),
+                boolean("s", [false, true, false, false, false]),
+            ]),
+        ),
+        (
+            "SELECT SELECTED() AS s FROM table",
+            owned_table([boolean("s", [true; 5])]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap();
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        let owned_table_result: OwnedTable<DoryScalar> = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(owned_table_result, expected);
+    }
+}
+
+#[test]
+fn we_cannot_use_the_selection_indicator_outside_of_a_plain_result_column() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "z"])]),
+        0,
+    );
+    for sql in [
+        "SELECT a FROM table WHERE SELECTED()",
+        "SELECT NOT SELECTED() AS s FROM table WHERE a > 1",
+        "SELECT a, SELECTED() AS s FROM table WHERE a > 1 GROUP BY a",
+        "SELECT COUNT(a) AS c, SELECTED() AS s FROM table WHERE a > 1",
+    ] {
+        let result = QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        );
+        assert!(
+            matches!(result, Err(ConversionError::InvalidExpression(_))),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_a_filtered_sum_without_a_group_by_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    let x = [3_i64, -5, 0, 7, -1, 2];
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("x", x)]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT SUM(x) FILTER (WHERE x > 0) AS pos, SUM(x) AS total FROM table"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let owned_table_result: OwnedTable<DoryScalar> = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let expected_pos: i64 = x.iter().filter(|&&v| v > 0).sum();
+    let expected_total: i64 = x.iter().sum();
+    let expected_result = owned_table([
+        bigint("pos", [expected_pos]),
+        bigint("total", [expected_total]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_group_by_query_with_a_filtered_sum_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("g", [1, 2, 1, 2, 1, 3]),
+            bigint("x", [3, -5, -4, 7, 2, -1]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT g, SUM(x) FILTER (WHERE x > 0) AS pos, COUNT(*) AS c FROM table WHERE x >= -100 GROUP BY g"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup)
+            .unwrap();
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("g", [1, 2, 3]),
+        bigint("pos", [5, 7, 0]),
+        bigint("c", [3, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_cannot_prove_a_query_on_a_column_that_the_prover_does_not_have_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+
+    let mut planning_accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    planning_accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table WHERE b = 5".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &planning_accessor,
+    )
+    .unwrap();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3])]),
+        0,
+    );
+    let result = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &ProverConfig::default(),
+    );
+    match result {
+        Err(ProofError::UnknownColumn { column, table }) => {
+            assert_eq!(column.as_str(), "b");
+            assert_eq!(*table, "sxt.table".parse().unwrap());
+        }
+        _ => panic!("expected an unknown column error"),
+    }
+}
+
+#[test]
+fn we_cannot_prove_a_query_on_a_column_whose_type_the_prover_does_not_match_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+
+    let mut planning_accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    planning_accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), boolean("b", [true, false, true])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table WHERE b OR a = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &planning_accessor,
+    )
+    .unwrap();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]),
+        0,
+    );
+    let result = VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        &ProverConfig::default(),
+    );
+    match result {
+        Err(ProofError::TypeMismatch {
+            column,
+            expected: ColumnType::Boolean,
+            actual: ColumnType::BigInt,
+        }) => assert_eq!(column.as_str(), "b"),
+        _ => panic!("expected a type mismatch error"),
+    }
+}
+
+#[test]
+fn we_can_prove_boolean_aggregations_with_a_group_by_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("batch", [1, 2, 1, 3, 2, 3]),
+            boolean("is_valid", [true, true, true, false, true, false]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT batch, BOOL_AND(is_valid) AS all_valid, BOOL_OR(is_valid) AS any_valid FROM table GROUP BY batch"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "batch" => [1_i64, 2, 3],
+        "all_valid" => [true, true, false],
+        "any_valid" => [true, true, false]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn boolean_aggregations_over_no_rows_are_true_for_bool_and_and_false_for_bool_or_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("batch", [1, 2, 1]),
+            boolean("is_valid", [true, false, true]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT BOOL_AND(is_valid) AS all_valid, BOOL_OR(is_valid) AS any_valid FROM table WHERE batch = 99"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!("all_valid" => [true], "any_valid" => [false]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_cannot_use_a_boolean_aggregation_on_a_non_boolean_column() {
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        DoryProverPublicSetup::rand(4, 3, &mut test_rng()),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("batch", [1, 2, 1])]),
+        0,
+    );
+    let result = QueryExpr::<DoryCommitment>::try_new(
+        "SELECT BOOL_OR(batch) AS any_batch FROM table"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    );
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+}
+
+#[test]
+fn we_can_prove_a_varchar_equality_with_a_domain_separated_varchar_hash_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let table = owned_table([
+        bigint("id", [1, 2, 3]),
+        varchar("name", ["alice", "bob", "carol"]),
+    ]);
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup_and_varchar_hash(
+            dory_prover_setup.clone(),
+            VarCharHash::DomainSeparatedBlake3,
+        );
+    accessor.add_table("sxt.table".parse().unwrap(), table.clone(), 0);
+    let mut default_accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    default_accessor.add_table("sxt.table".parse().unwrap(), table, 0);
+    let name_ref = ColumnRef::new(
+        "sxt.table".parse().unwrap(),
+        "name".parse().unwrap(),
+        ColumnType::VarChar,
+    );
+    assert_ne!(
+        accessor.get_commitment(name_ref),
+        default_accessor.get_commitment(name_ref)
+    );
+
+    let query = QueryExpr::try_new(
+        "SELECT id, name FROM table WHERE name = 'bob'"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("id", [2]), varchar("name", ["bob"])]);
+    assert_eq!(owned_table_result, expected_result);
+
+    // A verifier with a different hash does not accept the proof.
+    assert!(verifiable_result
+        .verify(query.proof_expr(), &default_accessor, &dory_verifier_setup)
+        .is_err());
+}
+
+#[test]
+fn we_cannot_verify_a_proof_with_a_different_version_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table WHERE b = 1".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    assert_eq!(
+        verifiable_result.proof.as_ref().unwrap().version,
+        PROOF_VERSION
+    );
+    let is_version_mismatch = |result: &VerifiableQueryResult<DoryEvaluationProof>| {
+        matches!(
+            result.verify(query.proof_expr(), &accessor, &dory_verifier_setup),
+            Err(QueryError::ProofError(ProofError::VersionMismatch { expected, found }))
+                if expected == PROOF_VERSION && found == PROOF_VERSION + 1
+        )
+    };
+
+    // The version is part of the compact serialization.
+    let mut compact_result: VerifiableQueryResult<DoryEvaluationProof> =
+        postcard::from_bytes(&postcard::to_allocvec(&verifiable_result).unwrap()).unwrap();
+    assert!(compact_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .is_ok());
+    compact_result.proof.as_mut().unwrap().version += 1;
+    let compact_result: VerifiableQueryResult<DoryEvaluationProof> =
+        postcard::from_bytes(&postcard::to_allocvec(&compact_result).unwrap()).unwrap();
+    assert!(is_version_mismatch(&compact_result));
+
+    // The version is part of the JSON serialization.
+    let mut json = serde_json::to_value(&verifiable_result).unwrap();
+    assert_eq!(json["proof"]["version"], PROOF_VERSION);
+    json["proof"]["version"] = (PROOF_VERSION + 1).into();
+    let json_result: VerifiableQueryResult<DoryEvaluationProof> =
+        serde_json::from_value(json).unwrap();
+    assert!(is_version_mismatch(&json_result));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_against_a_different_plan_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]),
+        0,
+    );
+    let plan = |sql: &str| {
+        QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap()
+    };
+    let query = plan("SELECT a FROM table WHERE b = 1");
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    )
+    .unwrap();
+    assert_eq!(
+        verifiable_result.proof.as_ref().unwrap().plan_hash,
+        plan_hash(query.proof_expr())
+    );
+    assert!(verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .is_ok());
+
+    // A proof of `b = 1` is not a proof of `b = 0`, even though both select one column of the same table.
+    let other_query = plan("SELECT a FROM table WHERE b = 0");
+    assert!(matches!(
+        verifiable_result.verify(other_query.proof_expr(), &accessor, &dory_verifier_setup),
+        Err(QueryError::ProofError(ProofError::PlanMismatch))
+    ));
+
+    // Relabeling the proof with the hash of the other plan does not help, since the transcript commits to the hash.
+    let mut relabeled_result = verifiable_result.clone();
+    relabeled_result.proof.as_mut().unwrap().plan_hash = plan_hash(other_query.proof_expr());
+    assert!(relabeled_result
+        .verify(other_query.proof_expr(), &accessor, &dory_verifier_setup)
+        .is_err());
+}
+
+#[test]
+fn a_compacted_filter_proves_the_same_result_as_a_full_length_filter_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", (0..64).map(|i| i % 50)),
+            varchar("b", (0..64).map(|i| format!("row {i}"))),
+        ]),
+        0,
+    );
+    for (sql, expected_rows) in [
+        ("SELECT a, b FROM table WHERE a = 49", 1),
+        ("SELECT a, b FROM table WHERE a = 50", 0),
+        ("SELECT a, b FROM table WHERE a <= 9", 20),
+        ("SELECT a, b FROM table", 64),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let prove = |disable_filter_compaction| {
+            VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+                query.proof_expr(),
+                &accessor,
+                &dory_prover_setup,
+                &ProverConfig {
+                    disable_filter_compaction,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .0
+        };
+        let compacted = prove(false);
+        let full_length = prove(true);
+        assert_eq!(
+            postcard::to_allocvec(&compacted).unwrap(),
+            postcard::to_allocvec(&full_length).unwrap()
+        );
+        let compacted_table = compacted
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        let full_length_table = full_length
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        assert_eq!(compacted_table, full_length_table);
+        assert_eq!(compacted_table.num_rows(), expected_rows);
+    }
+}
+
+#[test]
+fn proofs_with_any_msm_window_are_identical_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", (0..40).map(|i| i * i - 300)),
+            varchar("b", (0..40).map(|i| format!("row {i}"))),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, b FROM table WHERE a >= 0".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let prove = |msm_window_bits| {
+        VerifiableQueryResult::<DoryEvaluationProof>::new_with_config(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+            &ProverConfig {
+                msm_window_bits,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .0
+    };
+    let expected = prove(None);
+    for msm_window_bits in [1, 4, 8] {
+        let res = prove(Some(msm_window_bits));
+        assert_eq!(
+            postcard::to_allocvec(&res).unwrap(),
+            postcard::to_allocvec(&expected).unwrap()
+        );
+        let owned_table_result = res
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        assert_eq!(owned_table_result.num_rows(), 22);
+    }
+}
+
+#[test]
+fn we_can_prove_equal_and_or_queries_at_offsets_near_a_power_of_two_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(6, 4, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+    let table = owned_table([bigint("a", [1, 2, 3, 2, 5]), bigint("b", [2, 2, 0, 1, 5])]);
+    let queries = [
+        (
+            "SELECT * FROM table WHERE a = 2",
+            owned_table([bigint("a", [2, 2]), bigint("b", [2, 1])]),
+        ),
+        (
+            "SELECT * FROM table WHERE a = 2 AND b = 2",
+            owned_table([bigint("a", [2]), bigint("b", [2])]),
+        ),
+        (
+            "SELECT * FROM table WHERE a = 2 OR b = 5",
+            owned_table([bigint("a", [2, 2, 5]), bigint("b", [2, 1, 5])]),
+        ),
+    ];
+    // Dory supports offsets that are multiples of the evaluation vector's size, which is 8 here.
+    for offset in [0, 504, 512, 1016] {
+        let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+            dory_prover_setup.clone(),
+        );
+        accessor.add_table("sxt.table".parse().unwrap(), table.clone(), offset);
+        for (sql, expected) in &queries {
+            let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor)
+                .unwrap();
+            let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+                query.proof_expr(),
+                &accessor,
+                &dory_prover_setup,
+            )
+            .unwrap()
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+            assert_eq!(&owned_table_result, expected, "{sql} at offset {offset}");
+        }
+    }
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table("sxt.table".parse().unwrap(), table, 509);
+    let query = QueryExpr::try_new(
+        queries[0].0.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(matches!(
+        VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup),
+        Err(QueryError::ProofError(ProofError::CommitmentMismatch(_)))
+    ));
+}
+
+#[test]
+fn we_can_prove_is_true_is_false_and_is_unknown_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            boolean("flag", [true, false, false, true]),
+        ]),
+        0,
+    );
+    // Columns are never NULL, so `IS TRUE` and `IS FALSE` keep the true and false rows,
+    // and `IS UNKNOWN` keeps no rows.
+    for (sql, expected) in [
+        (
+            "SELECT a FROM table WHERE flag IS TRUE",
+            owned_table([bigint("a", [1, 4])]),
+        ),
+        (
+            "SELECT a FROM table WHERE flag IS FALSE",
+            owned_table([bigint("a", [2, 3])]),
+        ),
+        (
+            "SELECT a FROM table WHERE a >= 2 IS TRUE AND NOT flag IS TRUE",
+            owned_table([bigint("a", [2, 3])]),
+        ),
+        (
+            "SELECT a FROM table WHERE flag IS UNKNOWN",
+            owned_table([bigint("a", [0; 0])]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+        assert_eq!(owned_table_result, expected, "{sql}");
+    }
+
+    assert!(matches!(
+        QueryExpr::<DoryCommitment>::try_new(
+            "SELECT a FROM table WHERE a IS TRUE".parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+}
+
+#[test]
+fn we_can_prove_queries_with_hex_and_binary_integer_literals_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            bigint("flags", [0x04, 0x0F, 0x14, 0xFF]),
+        ]),
+        0,
+    );
+    for (sql, expected) in [
+        (
+            "SELECT a FROM table WHERE flags = 0x04",
+            owned_table([bigint("a", [1])]),
+        ),
+        (
+            "SELECT a FROM table WHERE flags = 0b1111 OR flags = 0xff",
+            owned_table([bigint("a", [2, 4])]),
+        ),
+        (
+            "SELECT a FROM table WHERE flags >= 0x10",
+            owned_table([bigint("a", [3, 4])]),
+        ),
+        (
+            "SELECT a FROM table WHERE flags IN (0x14, 0b100)",
+            owned_table([bigint("a", [1, 3])]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+        assert_eq!(owned_table_result, expected, "{sql}");
+    }
+
+    assert!(
+        "SELECT a FROM table WHERE flags = 0x123456789ABCDEF0123456789ABCDEF01"
+            .parse::<proof_of_sql_parser::SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_compare_integer_columns_with_literals_just_outside_of_their_range_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            smallint("s", [i16::MIN, -1, 0, i16::MAX]),
+            int("i", [i32::MIN, -1, 0, i32::MAX]),
+            bigint("b", [i64::MIN, -1, 0, i64::MAX]),
+            int128("c", [i128::MIN, -1, 0, i128::MAX]),
+        ]),
+        0,
+    );
+    // The literals are not narrowed to the type of the column, so the comparisons are exact.
+    let all_rows = || owned_table([bigint("a", [1, 2, 3, 4])]);
+    let no_rows = || owned_table([bigint("a", [0; 0])]);
+    for (column, above, below) in [
+        ("s", "32768", "-32769"),
+        ("i", "2147483648", "-2147483649"),
+        ("b", "9223372036854775808", "-9223372036854775809"),
+        (
+            "c",
+            "170141183460469231731687303715884105728.0",
+            "-170141183460469231731687303715884105729.0",
+        ),
+    ] {
+        let mut queries = vec![
+            (
+                format!("SELECT a FROM table WHERE {column} = {above}"),
+                no_rows(),
+            ),
+            (
+                format!("SELECT a FROM table WHERE {column} = {below}"),
+                no_rows(),
+            ),
+            (
+                format!("SELECT a FROM table WHERE {column} != {above}"),
+                all_rows(),
+            ),
+        ];
+        // Decimals beyond 38 digits can't be used in inequalities.
+        if column != "c" {
+            queries.extend([
+                (
+                    format!("SELECT a FROM table WHERE {column} < {above}"),
+                    all_rows(),
+                ),
+                (
+                    format!("SELECT a FROM table WHERE {column} >= {above}"),
+                    no_rows(),
+                ),
+                (
+                    format!("SELECT a FROM table WHERE {column} > {below}"),
+                    all_rows(),
+                ),
+                (
+                    format!("SELECT a FROM table WHERE {column} <= {below}"),
+                    no_rows(),
+                ),
+            ]);
+        }
+        for (sql, expected) in queries {
+            let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor)
+                .unwrap();
+            let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+                query.proof_expr(),
+                &accessor,
+                &dory_prover_setup,
+            )
+            .unwrap()
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+            assert_eq!(owned_table_result, expected, "{sql}");
+        }
+    }
+
+    // An integer literal outside of the range of `Int128` is not a literal at all.
+    assert!(
+        "SELECT a FROM table WHERE c = 170141183460469231731687303715884105728"
+            .parse::<proof_of_sql_parser::SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn comparisons_with_null_select_no_rows_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            varchar("b", ["x", "y", "x", "z"]),
+        ]),
+        0,
+    );
+    for (sql, expected) in [
+        ("SELECT a FROM table WHERE a = NULL", vec![]),
+        ("SELECT a FROM table WHERE NOT (a = NULL)", vec![]),
+        ("SELECT a FROM table WHERE b <> NULL", vec![]),
+        ("SELECT a FROM table WHERE a + NULL >= 2", vec![]),
+        ("SELECT a FROM table WHERE a IN (NULL)", vec![]),
+        ("SELECT a FROM table WHERE a = NULL OR b = 'x'", vec![1, 3]),
+        (
+            "SELECT a FROM table WHERE NOT (a = NULL AND b = 'x')",
+            vec![2, 4],
+        ),
+        ("SELECT a FROM table WHERE a IN (2, NULL)", vec![2]),
+        ("SELECT a FROM table WHERE NOT a IN (2, NULL)", vec![]),
+        (
+            "SELECT a FROM table WHERE (a = NULL) IS UNKNOWN",
+            vec![1, 2, 3, 4],
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let owned_table_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+        assert_eq!(
+            owned_table_result,
+            owned_table([bigint("a", expected)]),
+            "{sql}"
+        );
+    }
+
+    // NULL is only supported where a comparison with it is folded away.
+    assert!(QueryExpr::<DoryCommitment>::try_new(
+        "SELECT a = NULL AS n FROM table".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .is_err());
+}
+
+#[test]
+fn evaluating_a_query_without_a_proof_matches_the_verified_result_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let table = owned_table([
+        bigint("a", [5, 3, 9, -2, 9, 3, 0, 7]),
+        bigint("b", [1, 2, 3, 4, 5, 6, 7, 8]),
+        varchar("s", ["x", "y", "x", "z", "y", "x", "z", "x"]),
+        boolean("f", [true, false, true, true, false, false, true, true]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table("sxt.table".parse().unwrap(), table.clone(), 0);
+    for sql in [
+        "SELECT * FROM table",
+        "SELECT a, s FROM table WHERE a >= 3 AND f",
+        "SELECT a + b AS c, a * 2 AS d FROM table WHERE s = 'x'",
+        "SELECT COUNT(*) AS n FROM table",
+        "SELECT COUNT(*) AS n FROM table WHERE b <= 4",
+        "SELECT s, SUM(a) AS total, COUNT(*) AS n FROM table GROUP BY s",
+        "SELECT a, b FROM table WHERE a = 9 ORDER BY b DESC",
+        "SELECT a, b FROM table ORDER BY a, b LIMIT 3 OFFSET 1",
+        "SELECT b FROM table WHERE a = 4",
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let verified_table = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+        let verified_table: OwnedTable<DoryScalar> = query
+            .result()
+            .transform_results(verified_table.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(query.evaluate_owned(&table), verified_table, "{sql}");
+    }
+}
+
+#[test]
+fn we_can_page_through_a_verified_sorted_result_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [5, -3, 8, 1, 0, 12, 7]),
+            varchar("b", ["e", "c", "h", "a", "z", "l", "g"]),
+        ]),
+        0,
+    );
+    let plan_page = |sql: &str, cursor| {
+        QueryExpr::try_new_with_options(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+            &QueryExprOptions::default().with_page(4, cursor),
+        )
+    };
+    let prove_page = |query: &QueryExpr<DoryCommitment>| {
+        let verified_table = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        )
+        .unwrap()
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+        query
+            .result()
+            .transform_results(verified_table.try_into().unwrap())
+            .unwrap()
+    };
+    let sql = "SELECT a, b FROM table WHERE a <> 1 ORDER BY a";
+
+    let first_query = plan_page(sql, None).unwrap();
+    let first_page = prove_page(&first_query);
+    assert_eq!(
+        first_page,
+        record_batch!("a" => [-3_i64, 0, 5, 7], "b" => ["c", "z", "e", "g"])
+    );
+    let second_query = plan_page(sql, first_query.next_cursor(&first_page)).unwrap();
+    let second_page = prove_page(&second_query);
+    assert_eq!(
+        second_page,
+        record_batch!("a" => [8_i64, 12], "b" => ["h", "l"])
+    );
+
+    for sql in [
+        "SELECT a, b FROM table",
+        "SELECT a, b FROM table ORDER BY a LIMIT 2",
+    ] {
+        assert!(
+            matches!(
+                plan_page(sql, None),
+                Err(ConversionError::InvalidExpression(_))
+            ),
+            "{sql}"
+        );
+    }
+}